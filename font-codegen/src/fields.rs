@@ -105,6 +105,7 @@ impl Fields {
                     arg_tokens: arg,
                     is_offset: fld.is_offset_or_array_of_offsets(),
                     is_array: fld.is_array(),
+                    is_boxed: fld.is_boxed(),
                     manual_compile_type: fld.attrs.compile_type.is_some(),
                 })
         })
@@ -269,6 +270,7 @@ pub(crate) struct FieldConstructorInfo {
     pub(crate) arg_tokens: TokenStream,
     pub(crate) is_offset: bool,
     pub(crate) is_array: bool,
+    pub(crate) is_boxed: bool,
     pub(crate) manual_compile_type: bool,
 }
 
@@ -308,7 +310,7 @@ fn traversal_arm_for_field(
                     #name_str,
                     traversal::FieldType::offset_to_array_of_records(
                         self.#name()#maybe_unwrap,
-                        self.#getter(#pass_data)#maybe_unwrap,
+                        self.#getter(#pass_data),
                         stringify!(#typ),
                         #offset_data,
                     )
@@ -320,7 +322,7 @@ fn traversal_arm_for_field(
                 OffsetTarget::Array(_) => quote!(offset_to_array_of_scalars),
             };
             let getter = fld.offset_getter_name();
-            quote!(Field::new(#name_str, FieldType::#constructor_name(self.#name()#maybe_unwrap, self.#getter(#pass_data)#maybe_unwrap)))
+            quote!(Field::new(#name_str, FieldType::#constructor_name(self.#name()#maybe_unwrap, self.#getter(#pass_data))))
         }
         FieldType::Scalar { .. } => quote!(Field::new(#name_str, self.#name()#maybe_unwrap)),
 
@@ -429,6 +431,10 @@ fn check_resolution(phase: Phase, field_type: &FieldType) -> syn::Result<()> {
     Ok(())
 }
 
+fn is_byte_sized(typ: &FieldType) -> bool {
+    matches!(typ, FieldType::Scalar { typ } if typ == "u8" || typ == "i8")
+}
+
 impl Field {
     pub(crate) fn type_for_record(&self) -> TokenStream {
         match &self.typ {
@@ -557,6 +563,18 @@ impl Field {
             }
         }
 
+        if let Some(boxed) = &self.attrs.compile_boxed {
+            if !matches!(self.typ, FieldType::Offset { .. })
+                || self.is_nullable()
+                || self.attrs.compile_type.is_some()
+            {
+                return Err(logged_syn_error(
+                    boxed.span(),
+                    "'compile_boxed' is only valid on non-nullable offsets without a 'compile_type'",
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -564,6 +582,10 @@ impl Field {
         self.attrs.nullable.is_some()
     }
 
+    fn is_boxed(&self) -> bool {
+        self.attrs.compile_boxed.is_some()
+    }
+
     fn is_computed(&self) -> bool {
         self.attrs.format.is_some() || self.attrs.compile.is_some()
     }
@@ -667,7 +689,9 @@ impl Field {
         if let Some(typ) = &self.attrs.compile_type {
             return typ.into_token_stream();
         }
-        let type_tokens = self.typ.compile_type_tokens(self.is_nullable());
+        let type_tokens = self
+            .typ
+            .compile_type_tokens(self.is_nullable(), self.is_boxed());
 
         // if this is versioned, we wrap in `Option``
         if self.is_version_dependent() {
@@ -679,7 +703,11 @@ impl Field {
         type_tokens
     }
 
-    pub(crate) fn table_getter(&self, generic: Option<&syn::Ident>) -> Option<TokenStream> {
+    pub(crate) fn table_getter(
+        &self,
+        table_name: &syn::Ident,
+        generic: Option<&syn::Ident>,
+    ) -> Option<TokenStream> {
         if !self.has_getter() {
             return None;
         }
@@ -710,7 +738,7 @@ impl Field {
         }
 
         let docs = &self.attrs.docs;
-        let offset_getter = self.typed_offset_field_getter(generic, None);
+        let offset_getter = self.typed_offset_field_getter(table_name, generic, None);
 
         Some(quote! {
             #( #docs )*
@@ -756,7 +784,7 @@ impl Field {
             }
         };
 
-        let offset_getter = self.typed_offset_field_getter(None, Some(record));
+        let offset_getter = self.typed_offset_field_getter(&record.name, None, Some(record));
         Some(quote! {
             #(#docs)*
             pub fn #name(&self) -> #add_borrow_just_for_record #return_type {
@@ -775,6 +803,7 @@ impl Field {
 
     fn typed_offset_field_getter(
         &self,
+        type_name: &syn::Ident,
         generic: Option<&syn::Ident>,
         record: Option<&Record>,
     ) -> Option<TokenStream> {
@@ -805,9 +834,17 @@ impl Field {
             }
         }
 
+        // the field being resolved, for error context
+        let type_str = type_name.to_string();
+        let field_str = raw_name.to_string();
+        let site = if self.is_array() {
+            quote!(FieldSite::new(#type_str, #field_str).at(i))
+        } else {
+            quote!(FieldSite::new(#type_str, #field_str))
+        };
         let resolve = match self.attrs.read_offset_args.as_deref() {
-            None => quote!(resolve(data)),
-            Some(_) => quote!(resolve_with_args(data, &args)),
+            None => quote!(resolve_field(data, #site)),
+            Some(_) => quote!(resolve_field_with_args(data, &args, #site)),
         };
 
         let args_if_needed = self.attrs.read_offset_args.as_ref().map(|args| {
@@ -827,7 +864,7 @@ impl Field {
         let (base_method, convert_impl) = if self.is_array() {
             (
                 &self.name,
-                quote!( .iter().map(move |off| off.get().#resolve) ),
+                quote!( .iter().enumerate().map(move |(i, off)| off.get().#resolve) ),
             )
         } else {
             (raw_name, quote!( .#resolve))
@@ -978,7 +1015,14 @@ impl Field {
             panic!("Should have resolved {self:?}")
         }
         let len_expr = match self.attrs.count.as_deref() {
-            Some(Count::All(_)) => quote!(cursor.remaining_bytes()),
+            // ignore any trailing bytes that don't make up a whole item
+            Some(Count::All(_)) => match &self.typ {
+                FieldType::Array { inner_typ } if !is_byte_sized(inner_typ) => {
+                    let inner_typ = inner_typ.cooked_type_tokens();
+                    quote!(cursor.remaining_bytes() / #inner_typ::RAW_BYTE_LEN * #inner_typ::RAW_BYTE_LEN)
+                }
+                _ => quote!(cursor.remaining_bytes()),
+            },
             Some(other) => {
                 let count_expr = other.count_expr();
                 let size_expr = match &self.typ {
@@ -1294,8 +1338,8 @@ impl FieldType {
     /// The tokens used to represent this type in `write-fonts`
     ///
     /// This does not include the `Option` that may be present if this is versioned.
-    fn compile_type_tokens(&self, nullable: bool) -> TokenStream {
-        self.compile_type_impl(nullable, false)
+    fn compile_type_tokens(&self, nullable: bool, boxed: bool) -> TokenStream {
+        self.compile_type_impl(nullable, boxed, false)
     }
 
     /// The type for a constructor argument for this field.
@@ -1303,11 +1347,11 @@ impl FieldType {
     /// Specifically, this does not wrap offset-types in offset markers
     /// (these conversions occur in the constructor).
     fn compile_type_for_constructor(&self, nullable: bool) -> TokenStream {
-        self.compile_type_impl(nullable, true)
+        self.compile_type_impl(nullable, false, true)
     }
 
     // impl code reused for the two calls above
-    fn compile_type_impl(&self, nullable: bool, for_constructor: bool) -> TokenStream {
+    fn compile_type_impl(&self, nullable: bool, boxed: bool, for_constructor: bool) -> TokenStream {
         match self {
             FieldType::Scalar { typ } => typ.into_token_stream(),
             FieldType::Struct { typ } => typ.into_token_stream(),
@@ -1321,6 +1365,7 @@ impl FieldType {
                     }
                 }
                 let width = width_for_offset(typ);
+                let target = if boxed { quote!(Box<#target>) } else { target };
                 if nullable {
                     // we don't bother wrapping this in an Option if versioned,
                     // since it already acts like an Option
@@ -1334,7 +1379,7 @@ impl FieldType {
                     panic!("nesting arrays is not supported");
                 }
 
-                let inner_tokens = inner_typ.compile_type_impl(nullable, for_constructor);
+                let inner_tokens = inner_typ.compile_type_impl(nullable, boxed, for_constructor);
                 quote!( Vec<#inner_tokens> )
             }
             FieldType::ComputedArray(array) | FieldType::VarLenArray(array) => array.compile_type(),
@@ -21,6 +21,8 @@ pub(crate) fn generate_flags(raw: &BitFlags) -> proc_macro2::TokenStream {
 
     let all_names = raw.variants.iter().map(|var| var.name.to_string());
     let all_values = raw.variants.iter().map(|var| &var.name).collect::<Vec<_>>();
+    let flag_names = all_names.clone();
+    let flag_values = raw.variants.iter().map(|var| &var.value);
 
     quote! {
         #( #docs )*
@@ -295,7 +297,10 @@ pub(crate) fn generate_flags(raw: &BitFlags) -> proc_macro2::TokenStream {
         #[cfg(feature = "traversal")]
         impl<'a> From<#name> for FieldType<'a> {
             fn from(src: #name) -> FieldType<'a> {
-                src.bits().into()
+                FieldType::Flags(FlagsValue {
+                    bits: src.bits().into(),
+                    flags: &[#( (#flag_names, #flag_values), )*],
+                })
             }
         }
     }
@@ -330,6 +335,9 @@ pub(crate) fn generate_raw_enum(raw: &RawEnum) -> TokenStream {
         }
     });
 
+    let variant_names = raw.variants.iter().map(|variant| &variant.name);
+    let variant_strs = raw.variants.iter().map(|variant| variant.name.to_string());
+
     let variant_inits = raw.variants.iter().map(|variant| {
         let name = &variant.name;
         let value = &variant.value;
@@ -374,7 +382,14 @@ pub(crate) fn generate_raw_enum(raw: &RawEnum) -> TokenStream {
         #[cfg(feature = "traversal")]
         impl<'a> From<#name> for FieldType<'a> {
             fn from(src: #name) -> FieldType<'a> {
-                (src as #typ).into()
+                let name = match src {
+                    #( #name::#variant_names => Some(#variant_strs), )*
+                    #name::Unknown => None,
+                };
+                FieldType::Enum(EnumValue {
+                    value: (src as #typ).into(),
+                    name,
+                })
             }
         }
     }
@@ -54,14 +54,66 @@ pub fn generate_code(code_str: &str, mode: Mode) -> Result<String, syn::Error> {
     // 4. Touchup
     debug!("Touchup (mode {:?})", mode);
     let source_str = formatting::format(tables)?;
+    Ok(add_header(source_str))
+}
+
+/// Generate the lookup of traversable top-level tables by tag.
+///
+/// The inputs are the sources of every 'parse' step in the codegen plan; each
+/// table in them with a `#[tag]` attribute gets an entry.
+pub fn generate_traversal_dispatch<'a>(
+    inputs: impl IntoIterator<Item = &'a str>,
+) -> Result<String, syn::Error> {
+    let mut arms = Vec::new();
+    for code_str in inputs {
+        let items: Items = syn::parse_str(code_str)?;
+        for item in items.iter() {
+            if let Item::Table(item) = item {
+                arms.extend(table::generate_traversal_dispatch_arm(
+                    item,
+                    &items.parse_module_path,
+                ));
+            }
+        }
+    }
+    // sort by table path, so the output doesn't depend on the plan's order
+    arms.sort_by_cached_key(|arm| arm.to_string());
+
+    let code = quote! {
+        #[cfg(feature = "traversal")]
+        impl<'a> FontRef<'a> {
+            /// Returns the table with the given tag as a [`SomeTable`], or `None`
+            /// if the tag is not a generated top-level table.
+            ///
+            /// [`SomeTable`]: crate::traversal::SomeTable
+            pub(crate) fn generated_traversable_table(
+                &self,
+                tag: Tag,
+            ) -> Option<Result<Box<dyn crate::traversal::SomeTable<'a> + 'a>, ReadError>> {
+                fn boxed<'a, T: crate::traversal::SomeTable<'a> + 'a>(
+                    table: Result<T, ReadError>,
+                ) -> Option<Result<Box<dyn crate::traversal::SomeTable<'a> + 'a>, ReadError>> {
+                    Some(table.map(|table| Box::new(table) as _))
+                }
+                match tag {
+                    #( #arms )*
+                    _ => None,
+                }
+            }
+        }
+    };
+    let source_str = formatting::format(code)?;
+    Ok(add_header(source_str))
+}
 
-    Ok(format!(
+fn add_header(source_str: String) -> String {
+    format!(
         "\
     // THIS FILE IS AUTOGENERATED.\n\
     // Any changes to this file will be overwritten.\n\
     // For more information about how codegen works, see font-codegen/README.md\n\n\
     {source_str}",
-    ))
+    )
 }
 
 pub(crate) fn generate_parse_module(items: &Items) -> Result<proc_macro2::TokenStream, syn::Error> {
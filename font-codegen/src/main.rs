@@ -66,6 +66,21 @@ fn run_plan(path: &Path) -> miette::Result<()> {
         std::fs::write(&op.target, generated)
             .map_err(|e| miette!("error writing '{}': {}", op.target.display(), e))?;
     }
+
+    if let Some(target) = &plan.traversal_dispatch {
+        let sources = plan
+            .generate
+            .iter()
+            .filter(|op| matches!(op.mode, Mode::Parse))
+            .map(|op| read_contents(&op.source))
+            .collect::<Result<Vec<_>, _>>()?;
+        let generated =
+            font_codegen::generate_traversal_dispatch(sources.iter().map(String::as_str))
+                .map_err(|e| miette!("failed to generate traversal dispatch: {e}"))?;
+        debug!("writing {} bytes to {}", generated.len(), target.display());
+        std::fs::write(target, generated)
+            .map_err(|e| miette!("error writing '{}': {}", target.display(), e))?;
+    }
     Ok(())
 }
 
@@ -82,6 +97,10 @@ fn ensure_correct_working_directory() -> miette::Result<()> {
 struct CodegenPlan {
     generate: Vec<CodegenOp>,
     clean: Vec<PathBuf>,
+    /// Where to write the lookup of traversable tables, which is built from
+    /// the inputs of all the 'parse' steps.
+    #[serde(default)]
+    traversal_dispatch: Option<PathBuf>,
 }
 
 #[derive(Deserialize)]
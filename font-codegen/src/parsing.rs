@@ -207,6 +207,10 @@ pub(crate) struct FieldAttrs {
     pub(crate) compile_with: Option<Attr<syn::Ident>>,
     pub(crate) default: Option<Attr<syn::Expr>>,
     pub(crate) compile_type: Option<Attr<syn::Type>>,
+    /// If present, the target of this offset is boxed in `write-fonts`.
+    ///
+    /// This is required for tables that (indirectly) contain themselves.
+    pub(crate) compile_boxed: Option<syn::Path>,
     pub(crate) read_with_args: Option<Attr<FieldReadArgs>>,
     pub(crate) read_offset_args: Option<Attr<FieldReadArgs>>,
     /// If present, a custom method that returns a FieldType for this field,
@@ -953,6 +957,7 @@ static OFFSET_ADJUSTMENT: &str = "offset_adjustment";
 static COMPILE: &str = "compile";
 static COMPILE_WITH: &str = "compile_with";
 static COMPILE_TYPE: &str = "compile_type";
+static COMPILE_BOXED: &str = "compile_boxed";
 static DEFAULT: &str = "default";
 static READ_WITH: &str = "read_with";
 static READ_OFFSET_WITH: &str = "read_offset_with";
@@ -992,6 +997,8 @@ impl Parse for FieldAttrs {
                 this.compile_with = Some(Attr::new(ident.clone(), attr.parse_args()?));
             } else if ident == COMPILE_TYPE {
                 this.compile_type = Some(Attr::new(ident.clone(), attr.parse_args()?));
+            } else if ident == COMPILE_BOXED {
+                this.compile_boxed = Some(attr.path);
             } else if ident == DEFAULT {
                 this.default = Some(Attr::new(ident.clone(), attr.parse_args()?));
             } else if ident == VALIDATE {
@@ -266,10 +266,13 @@ pub(crate) fn generate_compile_impl(
              name,
              is_offset,
              is_array,
+             is_boxed,
              ..
          }| {
             if *is_array {
                 quote!(#name: #name.into_iter().map(Into::into).collect())
+            } else if *is_boxed {
+                quote!( #name: Box::new(#name).into())
             } else if *is_offset {
                 quote!( #name: #name.into())
             } else {
@@ -6,7 +6,9 @@ use quote::{quote, ToTokens};
 
 use crate::parsing::{Attr, GenericGroup, Item, Items, Phase};
 
-use super::parsing::{Field, ReferencedFields, Table, TableFormat, TableReadArg, TableReadArgs};
+use super::parsing::{
+    Count, Field, ReferencedFields, Table, TableFormat, TableReadArg, TableReadArgs,
+};
 
 pub(crate) fn generate(item: &Table) -> syn::Result<TokenStream> {
     let docs = &item.attrs.docs;
@@ -240,6 +242,10 @@ pub(crate) fn generate_group(item: &GenericGroup) -> syn::Result<TokenStream> {
             fn type_name(&self) -> &str {
                 self.as_some_table().type_name()
             }
+
+            fn is_open_ended(&self) -> bool {
+                self.as_some_table().is_open_ended()
+            }
         }
     })
 }
@@ -256,6 +262,18 @@ fn generate_debug(item: &Table) -> syn::Result<TokenStream> {
         quote!(let version = self.#name();)
     });
     let field_arms = item.fields.iter_field_traversal_match_arms(false);
+    let open_ended = item
+        .fields
+        .iter()
+        .last()
+        .filter(|fld| fld.attrs.count.as_deref().map(Count::all).unwrap_or(false))
+        .map(|_| {
+            quote! {
+                fn is_open_ended(&self) -> bool {
+                    true
+                }
+            }
+        });
     let attrs = item.fields.fields.is_empty().then(|| {
         quote! {
             #[allow(unused_variables)]
@@ -278,6 +296,8 @@ fn generate_debug(item: &Table) -> syn::Result<TokenStream> {
                     _ => None,
                 }
             }
+
+            #open_ended
         }
 
         #[cfg(feature = "traversal")]
@@ -317,6 +337,28 @@ pub(crate) fn generate_compile(item: &Table, parse_module: &syn::Path) -> syn::R
     })
 }
 
+/// Generate the match arm that returns this table from
+/// `FontRef::traversable_table`, if it is a top-level table.
+pub(crate) fn generate_traversal_dispatch_arm(
+    item: &Table,
+    parse_module: &syn::Path,
+) -> Option<TokenStream> {
+    item.attrs.tag.as_ref()?;
+    // the parse module path starts with 'read_fonts', and we're in read-fonts
+    let module = parse_module.segments.iter().skip(1);
+    let module_name = &parse_module.segments.last()?.ident;
+    let name = item.raw_name();
+    let path = quote!(crate:: #( #module:: )* #name);
+    // tables with arguments are read by the TableProvider method that
+    // knows where to find them
+    let read_table = if item.attrs.read_args.is_some() {
+        quote!(self.#module_name())
+    } else {
+        quote!(self.expect_table::<#path>())
+    };
+    Some(quote!( #path::TAG => boxed(#read_table), ))
+}
+
 fn generate_to_owned_impl(item: &Table, parse_module: &syn::Path) -> syn::Result<TokenStream> {
     let name = item.raw_name();
     let field_to_owned_stmts = item.fields.iter_from_obj_ref_stmts(false);
@@ -467,6 +509,11 @@ pub(crate) fn generate_format_compile(
     });
 
     let default_variant = &item.variants.first().unwrap().name;
+    let maybe_extra_traits = item
+        .attrs
+        .capabilities
+        .as_ref()
+        .map(|cap| cap.extra_traits());
 
     let write_arms = item.variants.iter().map(|variant| {
         let var_name = &variant.name;
@@ -494,7 +541,7 @@ pub(crate) fn generate_format_compile(
     let constructors = generate_format_constructors(item, items)?;
     Ok(quote! {
         #( #docs )*
-        #[derive(Clone, Debug)]
+        #[derive(Clone, Debug, #maybe_extra_traits)]
         pub enum #name {
             #( #variants ),*
         }
@@ -684,6 +731,10 @@ pub(crate) fn generate_format_group(item: &TableFormat) -> syn::Result<TokenStre
             fn get_field(&self, idx: usize) -> Option<Field<'a>> {
                 self.dyn_inner().get_field(idx)
             }
+
+            fn is_open_ended(&self) -> bool {
+                self.dyn_inner().is_open_ended()
+            }
         }
     })
 }
@@ -787,7 +838,7 @@ impl Table {
         let generic = self.attrs.generic_offset.as_ref().map(|attr| &attr.attr);
         self.fields
             .iter()
-            .filter_map(move |fld| fld.table_getter(generic))
+            .filter_map(move |fld| fld.table_getter(self.raw_name(), generic))
             .chain(
                 self.attrs
                     .read_args
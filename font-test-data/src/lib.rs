@@ -23,6 +23,8 @@ pub static VAZIRMATN_VAR_GLYPHS: &str =
 
 pub static SIMPLE_GLYF: &[u8] = include_bytes!("../test_data/ttf/simple_glyf.ttf");
 
+pub static LAYOUT_FEATURES: &[u8] = include_bytes!("../test_data/ttf/layout_features.ttf");
+
 pub mod post {
 
     #[rustfmt::skip]
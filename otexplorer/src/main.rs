@@ -1,12 +1,13 @@
 //! Print the contents of font tables.
 //!
 //! This accepts command line arguments similar to what is present in ttx,
-//! although it does not produce xml output.
+//! although it does not produce xml output. With `--json`, the tables are
+//! instead printed as a JSON object keyed by tag.
 
 use std::{collections::HashSet, str::FromStr};
 
 use font_types::Tag;
-use read_fonts::{traversal::SomeTable, FileRef, FontRef, ReadError, TableProvider, TopLevelTable};
+use read_fonts::{traversal::SomeTable, FileRef, FontRef};
 
 mod print;
 mod query;
@@ -33,7 +34,11 @@ fn main() -> Result<(), Error> {
     }
 
     let filter = TableFilter::from_args(&args)?;
-    print_tables(&font, &filter);
+    if args.json {
+        print_json(&font, &filter);
+    } else {
+        print_tables(&font, &filter);
+    }
     Ok(())
 }
 
@@ -75,6 +80,25 @@ fn print_tables(font: &FontRef, filter: &TableFilter) {
     }
 }
 
+fn print_json(font: &FontRef, filter: &TableFilter) {
+    let mut tables = serde_json::Map::new();
+    for tag in font
+        .table_directory
+        .table_records()
+        .iter()
+        .map(|rec| rec.tag())
+        .filter(|tag| filter.should_print(*tag))
+    {
+        let value = match font.table_as_traversable(tag) {
+            Ok(table) => serde_json::to_value(&*table)
+                .unwrap_or_else(|err| serde_json::json!({ "error": err.to_string() })),
+            Err(err) => serde_json::json!({ "error": err.to_string() }),
+        };
+        tables.insert(tag.to_string(), value);
+    }
+    println!("{}", serde_json::to_string_pretty(&tables).unwrap());
+}
+
 fn get_offset_width(font: &FontRef) -> usize {
     // pick how much padding we use for offsets based on the max offset in directory
     let max_off = font
@@ -96,37 +120,8 @@ fn hex_width(val: u32) -> usize {
     }
 }
 
-/// Given a font and a tag, return the appropriate table as a [`dyn SomeTable`][SomeTable].
-fn get_some_table<'a>(
-    font: &FontRef<'a>,
-    tag: Tag,
-) -> Result<Box<dyn SomeTable<'a> + 'a>, ReadError> {
-    use read_fonts::tables;
-    match tag {
-        tables::gpos::Gpos::TAG => font.gpos().map(|x| Box::new(x) as _),
-        tables::gsub::Gsub::TAG => font.gsub().map(|x| Box::new(x) as _),
-        tables::cmap::Cmap::TAG => font.cmap().map(|x| Box::new(x) as _),
-        tables::fvar::Fvar::TAG => font.fvar().map(|x| Box::new(x) as _),
-        tables::avar::Avar::TAG => font.avar().map(|x| Box::new(x) as _),
-        tables::gdef::Gdef::TAG => font.gdef().map(|x| Box::new(x) as _),
-        tables::glyf::Glyf::TAG => font.glyf().map(|x| Box::new(x) as _),
-        tables::head::Head::TAG => font.head().map(|x| Box::new(x) as _),
-        tables::hhea::Hhea::TAG => font.hhea().map(|x| Box::new(x) as _),
-        tables::hmtx::Hmtx::TAG => font.hmtx().map(|x| Box::new(x) as _),
-        tables::loca::Loca::TAG => font.loca(None).map(|x| Box::new(x) as _),
-        tables::maxp::Maxp::TAG => font.maxp().map(|x| Box::new(x) as _),
-        tables::name::Name::TAG => font.name().map(|x| Box::new(x) as _),
-        tables::post::Post::TAG => font.post().map(|x| Box::new(x) as _),
-        tables::colr::Colr::TAG => font.colr().map(|x| Box::new(x) as _),
-        tables::stat::Stat::TAG => font.stat().map(|x| Box::new(x) as _),
-        tables::vhea::Vhea::TAG => font.vhea().map(|x| Box::new(x) as _),
-        tables::vmtx::Vmtx::TAG => font.vmtx().map(|x| Box::new(x) as _),
-        _ => Err(ReadError::TableIsMissing(tag)),
-    }
-}
-
 fn print_table(font: &FontRef, tag: Tag) {
-    match get_some_table(font, tag) {
+    match font.table_as_traversable(tag) {
         Ok(table) => fancy_print_table(&table).unwrap(),
         Err(err) => println!("{tag}: Error '{err}'"),
     }
@@ -211,6 +206,7 @@ mod flags {
                 optional -q, --query query: Query
                 optional -t, --tables include: String
                 optional -x, --exclude exclude: String
+                optional --json
             }
 
     }
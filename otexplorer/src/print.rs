@@ -179,6 +179,7 @@ impl<'a> PrettyPrinter<'a> {
                 write!(this, "{}", val.to_u16())
             })?,
             FieldType::NameId(val) => write!(self, "{val:?}")?,
+            FieldType::Enum(_) | FieldType::Flags(_) => write!(self, "{field:?}")?,
             FieldType::ResolvedOffset(ResolvedOffset { offset, target }) => {
                 match target {
                     Ok(table) => {
@@ -248,6 +249,8 @@ impl<'a> PrettyPrinter<'a> {
             FieldType::Fixed(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::LongDateTime(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::GlyphId(val) => self.print_hex(&val.to_be_bytes())?,
+            FieldType::Enum(val) => self.print_hex(&val.value.to_be_bytes())?,
+            FieldType::Flags(val) => self.print_hex(&val.bits.to_be_bytes())?,
             FieldType::BareOffset(offset) => self.print_offset_hex(*offset)?,
             _ => (),
         }
@@ -356,5 +359,7 @@ fn is_scalar(field_type: &FieldType) -> bool {
             | FieldType::Fixed(_)
             | FieldType::LongDateTime(_)
             | FieldType::GlyphId(_)
+            | FieldType::Enum(_)
+            | FieldType::Flags(_)
     )
 }
@@ -21,7 +21,7 @@ pub enum QueryElement {
 }
 
 pub fn print_query(font: &FontRef, query: &Query) -> Result<(), String> {
-    let table = match super::get_some_table(font, query.tag) {
+    let table = match font.table_as_traversable(query.tag) {
         Ok(table) => table,
         Err(err) => return Err(err.to_string()),
     };
@@ -173,6 +173,8 @@ fn field_type_name(field_type: &FieldType) -> Cow<'static, str> {
         FieldType::LongDateTime(_) => "LongDateTime".into(),
         FieldType::GlyphId(_) => "GlyphId".into(),
         FieldType::NameId(_) => "NameId".into(),
+        FieldType::Enum(_) => "enum".into(),
+        FieldType::Flags(_) => "flags".into(),
         FieldType::Array(arr) => format!("[{}]", arr.type_name()).into(),
         FieldType::Record(record) => record.type_name().to_string().into(),
         FieldType::ResolvedOffset(ResolvedOffset {
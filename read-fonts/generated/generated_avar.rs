@@ -94,6 +94,9 @@ impl<'a> SomeTable<'a> for Avar<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -74,7 +74,8 @@ impl<'a> Base<'a> {
     /// Attempt to resolve [`horiz_axis_offset`][Self::horiz_axis_offset].
     pub fn horiz_axis(&self) -> Option<Result<Axis<'a>, ReadError>> {
         let data = self.data;
-        self.horiz_axis_offset().resolve(data)
+        self.horiz_axis_offset()
+            .resolve_field(data, FieldSite::new("Base", "horiz_axis_offset"))
     }
 
     /// Offset to vertical Axis table, from beginning of BASE table (may be NULL)
@@ -86,7 +87,8 @@ impl<'a> Base<'a> {
     /// Attempt to resolve [`vert_axis_offset`][Self::vert_axis_offset].
     pub fn vert_axis(&self) -> Option<Result<Axis<'a>, ReadError>> {
         let data = self.data;
-        self.vert_axis_offset().resolve(data)
+        self.vert_axis_offset()
+            .resolve_field(data, FieldSite::new("Base", "vert_axis_offset"))
     }
 
     /// Offset to Item Variation Store table, from beginning of BASE table (may be null)
@@ -98,7 +100,8 @@ impl<'a> Base<'a> {
     /// Attempt to resolve [`item_var_store_offset`][Self::item_var_store_offset].
     pub fn item_var_store(&self) -> Option<Result<ItemVariationStore<'a>, ReadError>> {
         let data = self.data;
-        self.item_var_store_offset().map(|x| x.resolve(data))?
+        self.item_var_store_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Base", "item_var_store_offset")))?
     }
 }
 
@@ -121,10 +124,7 @@ impl<'a> SomeTable<'a> for Base<'a> {
             )),
             3usize if version.compatible((1, 1)) => Some(Field::new(
                 "item_var_store_offset",
-                FieldType::offset(
-                    self.item_var_store_offset().unwrap(),
-                    self.item_var_store().unwrap(),
-                ),
+                FieldType::offset(self.item_var_store_offset().unwrap(), self.item_var_store()),
             )),
             _ => None,
         }
@@ -177,7 +177,8 @@ impl<'a> Axis<'a> {
     /// Attempt to resolve [`base_tag_list_offset`][Self::base_tag_list_offset].
     pub fn base_tag_list(&self) -> Option<Result<BaseTagList<'a>, ReadError>> {
         let data = self.data;
-        self.base_tag_list_offset().resolve(data)
+        self.base_tag_list_offset()
+            .resolve_field(data, FieldSite::new("Axis", "base_tag_list_offset"))
     }
 
     /// Offset to BaseScriptList table, from beginning of Axis table
@@ -189,7 +190,8 @@ impl<'a> Axis<'a> {
     /// Attempt to resolve [`base_script_list_offset`][Self::base_script_list_offset].
     pub fn base_script_list(&self) -> Result<BaseScriptList<'a>, ReadError> {
         let data = self.data;
-        self.base_script_list_offset().resolve(data)
+        self.base_script_list_offset()
+            .resolve_field(data, FieldSite::new("Axis", "base_script_list_offset"))
     }
 }
 
@@ -391,7 +393,10 @@ impl BaseScriptRecord {
 
     /// Attempt to resolve [`base_script_offset`][Self::base_script_offset].
     pub fn base_script<'a>(&self, data: FontData<'a>) -> Result<BaseScript<'a>, ReadError> {
-        self.base_script_offset().resolve(data)
+        self.base_script_offset().resolve_field(
+            data,
+            FieldSite::new("BaseScriptRecord", "base_script_offset"),
+        )
     }
 }
 
@@ -471,7 +476,8 @@ impl<'a> BaseScript<'a> {
     /// Attempt to resolve [`base_values_offset`][Self::base_values_offset].
     pub fn base_values(&self) -> Option<Result<BaseValues<'a>, ReadError>> {
         let data = self.data;
-        self.base_values_offset().resolve(data)
+        self.base_values_offset()
+            .resolve_field(data, FieldSite::new("BaseScript", "base_values_offset"))
     }
 
     /// Offset to MinMax table, from beginning of BaseScript table (may be NULL)
@@ -483,7 +489,8 @@ impl<'a> BaseScript<'a> {
     /// Attempt to resolve [`default_min_max_offset`][Self::default_min_max_offset].
     pub fn default_min_max(&self) -> Option<Result<MinMax<'a>, ReadError>> {
         let data = self.data;
-        self.default_min_max_offset().resolve(data)
+        self.default_min_max_offset()
+            .resolve_field(data, FieldSite::new("BaseScript", "default_min_max_offset"))
     }
 
     /// Number of BaseLangSysRecords defined — may be zero (0)
@@ -563,7 +570,8 @@ impl BaseLangSysRecord {
 
     /// Attempt to resolve [`min_max_offset`][Self::min_max_offset].
     pub fn min_max<'a>(&self, data: FontData<'a>) -> Result<MinMax<'a>, ReadError> {
-        self.min_max_offset().resolve(data)
+        self.min_max_offset()
+            .resolve_field(data, FieldSite::new("BaseLangSysRecord", "min_max_offset"))
     }
 }
 
@@ -656,7 +664,13 @@ impl<'a> BaseValues<'a> {
         let data = self.data;
         self.base_coord_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("BaseValues", "base_coord_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -753,7 +767,8 @@ impl<'a> MinMax<'a> {
     /// Attempt to resolve [`min_coord_offset`][Self::min_coord_offset].
     pub fn min_coord(&self) -> Option<Result<BaseCoord<'a>, ReadError>> {
         let data = self.data;
-        self.min_coord_offset().resolve(data)
+        self.min_coord_offset()
+            .resolve_field(data, FieldSite::new("MinMax", "min_coord_offset"))
     }
 
     /// Offset to BaseCoord table that defines maximum extent value,
@@ -766,7 +781,8 @@ impl<'a> MinMax<'a> {
     /// Attempt to resolve [`max_coord_offset`][Self::max_coord_offset].
     pub fn max_coord(&self) -> Option<Result<BaseCoord<'a>, ReadError>> {
         let data = self.data;
-        self.max_coord_offset().resolve(data)
+        self.max_coord_offset()
+            .resolve_field(data, FieldSite::new("MinMax", "max_coord_offset"))
     }
 
     /// Number of FeatMinMaxRecords — may be zero (0)
@@ -850,7 +866,8 @@ impl FeatMinMaxRecord {
 
     /// Attempt to resolve [`min_coord_offset`][Self::min_coord_offset].
     pub fn min_coord<'a>(&self, data: FontData<'a>) -> Option<Result<MinMax<'a>, ReadError>> {
-        self.min_coord_offset().resolve(data)
+        self.min_coord_offset()
+            .resolve_field(data, FieldSite::new("FeatMinMaxRecord", "min_coord_offset"))
     }
 
     /// Offset to BaseCoord table that defines the maximum extent
@@ -861,7 +878,8 @@ impl FeatMinMaxRecord {
 
     /// Attempt to resolve [`max_coord_offset`][Self::max_coord_offset].
     pub fn max_coord<'a>(&self, data: FontData<'a>) -> Option<Result<MinMax<'a>, ReadError>> {
-        self.max_coord_offset().resolve(data)
+        self.max_coord_offset()
+            .resolve_field(data, FieldSite::new("FeatMinMaxRecord", "max_coord_offset"))
     }
 }
 
@@ -935,6 +953,9 @@ impl<'a> SomeTable<'a> for BaseCoord<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for BaseCoordFormat1Marker {
@@ -1156,7 +1177,8 @@ impl<'a> BaseCoordFormat3<'a> {
     /// Attempt to resolve [`device_offset`][Self::device_offset].
     pub fn device(&self) -> Option<Result<Device<'a>, ReadError>> {
         let data = self.data;
-        self.device_offset().resolve(data)
+        self.device_offset()
+            .resolve_field(data, FieldSite::new("BaseCoordFormat3", "device_offset"))
     }
 }
 
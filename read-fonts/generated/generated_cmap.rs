@@ -129,7 +129,8 @@ impl EncodingRecord {
 
     /// Attempt to resolve [`subtable_offset`][Self::subtable_offset].
     pub fn subtable<'a>(&self, data: FontData<'a>) -> Result<CmapSubtable<'a>, ReadError> {
-        self.subtable_offset().resolve(data)
+        self.subtable_offset()
+            .resolve_field(data, FieldSite::new("EncodingRecord", "subtable_offset"))
     }
 }
 
@@ -201,7 +202,18 @@ impl font_types::Scalar for PlatformId {
 #[cfg(feature = "traversal")]
 impl<'a> From<PlatformId> for FieldType<'a> {
     fn from(src: PlatformId) -> FieldType<'a> {
-        (src as u16).into()
+        let name = match src {
+            PlatformId::Unicode => Some("Unicode"),
+            PlatformId::Macintosh => Some("Macintosh"),
+            PlatformId::ISO => Some("ISO"),
+            PlatformId::Windows => Some("Windows"),
+            PlatformId::Custom => Some("Custom"),
+            PlatformId::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u16).into(),
+            name,
+        })
     }
 }
 
@@ -268,6 +280,9 @@ impl<'a> SomeTable<'a> for CmapSubtable<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for Cmap0Marker {
@@ -613,7 +628,8 @@ impl<'a> FontRead<'a> for Cmap4<'a> {
         cursor.advance_by(id_delta_byte_len);
         let id_range_offsets_byte_len = transforms::half(seg_count_x2) * u16::RAW_BYTE_LEN;
         cursor.advance_by(id_range_offsets_byte_len);
-        let glyph_id_array_byte_len = cursor.remaining_bytes();
+        let glyph_id_array_byte_len =
+            cursor.remaining_bytes() / u16::RAW_BYTE_LEN * u16::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap4Marker {
             end_code_byte_len,
@@ -729,6 +745,9 @@ impl<'a> SomeTable<'a> for Cmap4<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -1106,7 +1125,8 @@ impl<'a> FontRead<'a> for Cmap10<'a> {
         cursor.advance::<u32>();
         cursor.advance::<u32>();
         cursor.advance::<u32>();
-        let glyph_id_array_byte_len = cursor.remaining_bytes();
+        let glyph_id_array_byte_len =
+            cursor.remaining_bytes() / u16::RAW_BYTE_LEN * u16::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap10Marker {
             glyph_id_array_byte_len,
@@ -1172,6 +1192,9 @@ impl<'a> SomeTable<'a> for Cmap10<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -1607,7 +1630,10 @@ impl VariationSelector {
 
     /// Attempt to resolve [`default_uvs_offset`][Self::default_uvs_offset].
     pub fn default_uvs<'a>(&self, data: FontData<'a>) -> Option<Result<DefaultUvs<'a>, ReadError>> {
-        self.default_uvs_offset().resolve(data)
+        self.default_uvs_offset().resolve_field(
+            data,
+            FieldSite::new("VariationSelector", "default_uvs_offset"),
+        )
     }
 
     /// Offset from the start of the format 14 subtable to Non-Default
@@ -1621,7 +1647,10 @@ impl VariationSelector {
         &self,
         data: FontData<'a>,
     ) -> Option<Result<NonDefaultUvs<'a>, ReadError>> {
-        self.non_default_uvs_offset().resolve(data)
+        self.non_default_uvs_offset().resolve_field(
+            data,
+            FieldSite::new("VariationSelector", "non_default_uvs_offset"),
+        )
     }
 }
 
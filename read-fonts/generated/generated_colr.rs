@@ -133,8 +133,11 @@ impl<'a> Colr<'a> {
     pub fn base_glyph_records(&self) -> Option<Result<&'a [BaseGlyph], ReadError>> {
         let data = self.data;
         let args = self.num_base_glyph_records();
-        self.base_glyph_records_offset()
-            .resolve_with_args(data, &args)
+        self.base_glyph_records_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Colr", "base_glyph_records_offset"),
+        )
     }
 
     /// Offset to layerRecords array (may be NULL).
@@ -147,7 +150,11 @@ impl<'a> Colr<'a> {
     pub fn layer_records(&self) -> Option<Result<&'a [Layer], ReadError>> {
         let data = self.data;
         let args = self.num_layer_records();
-        self.layer_records_offset().resolve_with_args(data, &args)
+        self.layer_records_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Colr", "layer_records_offset"),
+        )
     }
 
     /// Number of Layer records; may be 0 in a version 1 table.
@@ -165,7 +172,8 @@ impl<'a> Colr<'a> {
     /// Attempt to resolve [`base_glyph_list_offset`][Self::base_glyph_list_offset].
     pub fn base_glyph_list(&self) -> Option<Result<BaseGlyphList<'a>, ReadError>> {
         let data = self.data;
-        self.base_glyph_list_offset().map(|x| x.resolve(data))?
+        self.base_glyph_list_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Colr", "base_glyph_list_offset")))?
     }
 
     /// Offset to LayerList table (may be NULL).
@@ -177,7 +185,8 @@ impl<'a> Colr<'a> {
     /// Attempt to resolve [`layer_list_offset`][Self::layer_list_offset].
     pub fn layer_list(&self) -> Option<Result<LayerList<'a>, ReadError>> {
         let data = self.data;
-        self.layer_list_offset().map(|x| x.resolve(data))?
+        self.layer_list_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Colr", "layer_list_offset")))?
     }
 
     /// Offset to ClipList table (may be NULL).
@@ -189,7 +198,8 @@ impl<'a> Colr<'a> {
     /// Attempt to resolve [`clip_list_offset`][Self::clip_list_offset].
     pub fn clip_list(&self) -> Option<Result<ClipList<'a>, ReadError>> {
         let data = self.data;
-        self.clip_list_offset().map(|x| x.resolve(data))?
+        self.clip_list_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Colr", "clip_list_offset")))?
     }
 
     /// Offset to DeltaSetIndexMap table (may be NULL).
@@ -201,7 +211,8 @@ impl<'a> Colr<'a> {
     /// Attempt to resolve [`var_index_map_offset`][Self::var_index_map_offset].
     pub fn var_index_map(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.var_index_map_offset().map(|x| x.resolve(data))?
+        self.var_index_map_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Colr", "var_index_map_offset")))?
     }
 
     /// Offset to ItemVariationStore (may be NULL).
@@ -214,7 +225,7 @@ impl<'a> Colr<'a> {
     pub fn item_variation_store(&self) -> Option<Result<ItemVariationStore<'a>, ReadError>> {
         let data = self.data;
         self.item_variation_store_offset()
-            .map(|x| x.resolve(data))?
+            .map(|x| x.resolve_field(data, FieldSite::new("Colr", "item_variation_store_offset")))?
     }
 }
 
@@ -254,32 +265,26 @@ impl<'a> SomeTable<'a> for Colr<'a> {
                 "base_glyph_list_offset",
                 FieldType::offset(
                     self.base_glyph_list_offset().unwrap(),
-                    self.base_glyph_list().unwrap(),
+                    self.base_glyph_list(),
                 ),
             )),
             6usize if version.compatible(1) => Some(Field::new(
                 "layer_list_offset",
-                FieldType::offset(
-                    self.layer_list_offset().unwrap(),
-                    self.layer_list().unwrap(),
-                ),
+                FieldType::offset(self.layer_list_offset().unwrap(), self.layer_list()),
             )),
             7usize if version.compatible(1) => Some(Field::new(
                 "clip_list_offset",
-                FieldType::offset(self.clip_list_offset().unwrap(), self.clip_list().unwrap()),
+                FieldType::offset(self.clip_list_offset().unwrap(), self.clip_list()),
             )),
             8usize if version.compatible(1) => Some(Field::new(
                 "var_index_map_offset",
-                FieldType::offset(
-                    self.var_index_map_offset().unwrap(),
-                    self.var_index_map().unwrap(),
-                ),
+                FieldType::offset(self.var_index_map_offset().unwrap(), self.var_index_map()),
             )),
             9usize if version.compatible(1) => Some(Field::new(
                 "item_variation_store_offset",
                 FieldType::offset(
                     self.item_variation_store_offset().unwrap(),
-                    self.item_variation_store().unwrap(),
+                    self.item_variation_store(),
                 ),
             )),
             _ => None,
@@ -487,7 +492,8 @@ impl BaseGlyphPaint {
 
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint<'a>(&self, data: FontData<'a>) -> Result<Paint<'a>, ReadError> {
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("BaseGlyphPaint", "paint_offset"))
     }
 }
 
@@ -563,7 +569,11 @@ impl<'a> LayerList<'a> {
         let data = self.data;
         self.paint_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get()
+                    .resolve_field(data, FieldSite::new("LayerList", "paint_offsets").at(i))
+            })
     }
 }
 
@@ -717,7 +727,8 @@ impl Clip {
 
     /// Attempt to resolve [`clip_box_offset`][Self::clip_box_offset].
     pub fn clip_box<'a>(&self, data: FontData<'a>) -> Result<ClipBox<'a>, ReadError> {
-        self.clip_box_offset().resolve(data)
+        self.clip_box_offset()
+            .resolve_field(data, FieldSite::new("Clip", "clip_box_offset"))
     }
 }
 
@@ -787,6 +798,9 @@ impl<'a> SomeTable<'a> for ClipBox<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u8> for ClipBoxFormat1Marker {
@@ -1100,7 +1114,7 @@ impl<'a> SomeRecord<'a> for VarColorIndex {
 }
 
 /// [ColorStop](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) record
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[repr(C)]
 #[repr(packed)]
 pub struct ColorStop {
@@ -1150,7 +1164,7 @@ impl<'a> SomeRecord<'a> for ColorStop {
 }
 
 /// [VarColorStop](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) record
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[repr(C)]
 #[repr(packed)]
 pub struct VarColorStop {
@@ -1421,7 +1435,16 @@ impl font_types::Scalar for Extend {
 #[cfg(feature = "traversal")]
 impl<'a> From<Extend> for FieldType<'a> {
     fn from(src: Extend) -> FieldType<'a> {
-        (src as u8).into()
+        let name = match src {
+            Extend::Pad => Some("Pad"),
+            Extend::Repeat => Some("Repeat"),
+            Extend::Reflect => Some("Reflect"),
+            Extend::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u8).into(),
+            name,
+        })
     }
 }
 
@@ -1579,6 +1602,9 @@ impl<'a> SomeTable<'a> for Paint<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u8> for PaintColrLayersMarker {
@@ -1908,7 +1934,10 @@ impl<'a> PaintLinearGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<ColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintLinearGradient", "color_line_offset"),
+        )
     }
 
     /// Start point (p₀) x coordinate.
@@ -2061,7 +2090,10 @@ impl<'a> PaintVarLinearGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<VarColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarLinearGradient", "color_line_offset"),
+        )
     }
 
     /// Start point (p₀) x coordinate. For variation, use
@@ -2222,7 +2254,10 @@ impl<'a> PaintRadialGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<ColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintRadialGradient", "color_line_offset"),
+        )
     }
 
     /// Start circle center x coordinate.
@@ -2375,7 +2410,10 @@ impl<'a> PaintVarRadialGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<VarColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarRadialGradient", "color_line_offset"),
+        )
     }
 
     /// Start circle center x coordinate. For variation, use
@@ -2524,7 +2562,10 @@ impl<'a> PaintSweepGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<ColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintSweepGradient", "color_line_offset"),
+        )
     }
 
     /// Center x coordinate.
@@ -2655,7 +2696,10 @@ impl<'a> PaintVarSweepGradient<'a> {
     /// Attempt to resolve [`color_line_offset`][Self::color_line_offset].
     pub fn color_line(&self) -> Result<VarColorLine<'a>, ReadError> {
         let data = self.data;
-        self.color_line_offset().resolve(data)
+        self.color_line_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarSweepGradient", "color_line_offset"),
+        )
     }
 
     /// Center x coordinate. For variation, use varIndexBase + 0.
@@ -2775,7 +2819,8 @@ impl<'a> PaintGlyph<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintGlyph", "paint_offset"))
     }
 
     /// Glyph ID for the source outline.
@@ -2930,7 +2975,8 @@ impl<'a> PaintTransform<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintTransform", "paint_offset"))
     }
 
     /// Offset to an Affine2x3 table.
@@ -2942,7 +2988,8 @@ impl<'a> PaintTransform<'a> {
     /// Attempt to resolve [`transform_offset`][Self::transform_offset].
     pub fn transform(&self) -> Result<Affine2x3<'a>, ReadError> {
         let data = self.data;
-        self.transform_offset().resolve(data)
+        self.transform_offset()
+            .resolve_field(data, FieldSite::new("PaintTransform", "transform_offset"))
     }
 }
 
@@ -3027,7 +3074,8 @@ impl<'a> PaintVarTransform<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarTransform", "paint_offset"))
     }
 
     /// Offset to a VarAffine2x3 table.
@@ -3039,7 +3087,10 @@ impl<'a> PaintVarTransform<'a> {
     /// Attempt to resolve [`transform_offset`][Self::transform_offset].
     pub fn transform(&self) -> Result<VarAffine2x3<'a>, ReadError> {
         let data = self.data;
-        self.transform_offset().resolve(data)
+        self.transform_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarTransform", "transform_offset"),
+        )
     }
 }
 
@@ -3367,7 +3418,8 @@ impl<'a> PaintTranslate<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintTranslate", "paint_offset"))
     }
 
     /// Translation in x direction.
@@ -3472,7 +3524,8 @@ impl<'a> PaintVarTranslate<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarTranslate", "paint_offset"))
     }
 
     /// Translation in x direction. For variation, use varIndexBase + 0.
@@ -3579,7 +3632,8 @@ impl<'a> PaintScale<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintScale", "paint_offset"))
     }
 
     /// Scale factor in x direction.
@@ -3684,7 +3738,8 @@ impl<'a> PaintVarScale<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarScale", "paint_offset"))
     }
 
     /// Scale factor in x direction. For variation, use varIndexBase +
@@ -3803,7 +3858,10 @@ impl<'a> PaintScaleAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintScaleAroundCenter", "paint_offset"),
+        )
     }
 
     /// Scale factor in x direction.
@@ -3932,7 +3990,10 @@ impl<'a> PaintVarScaleAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarScaleAroundCenter", "paint_offset"),
+        )
     }
 
     /// Scale factor in x direction. For variation, use varIndexBase +
@@ -4052,7 +4113,8 @@ impl<'a> PaintScaleUniform<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintScaleUniform", "paint_offset"))
     }
 
     /// Scale factor in x and y directions.
@@ -4145,7 +4207,8 @@ impl<'a> PaintVarScaleUniform<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarScaleUniform", "paint_offset"))
     }
 
     /// Scale factor in x and y directions. For variation, use
@@ -4251,7 +4314,10 @@ impl<'a> PaintScaleUniformAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintScaleUniformAroundCenter", "paint_offset"),
+        )
     }
 
     /// Scale factor in x and y directions.
@@ -4369,7 +4435,10 @@ impl<'a> PaintVarScaleUniformAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarScaleUniformAroundCenter", "paint_offset"),
+        )
     }
 
     /// Scale factor in x and y directions. For variation, use
@@ -4481,7 +4550,8 @@ impl<'a> PaintRotate<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintRotate", "paint_offset"))
     }
 
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
@@ -4575,7 +4645,8 @@ impl<'a> PaintVarRotate<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarRotate", "paint_offset"))
     }
 
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
@@ -4681,7 +4752,10 @@ impl<'a> PaintRotateAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintRotateAroundCenter", "paint_offset"),
+        )
     }
 
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
@@ -4799,7 +4873,10 @@ impl<'a> PaintVarRotateAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarRotateAroundCenter", "paint_offset"),
+        )
     }
 
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
@@ -4916,7 +4993,8 @@ impl<'a> PaintSkew<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintSkew", "paint_offset"))
     }
 
     /// Angle of skew in the direction of the x-axis, 180° in
@@ -5023,7 +5101,8 @@ impl<'a> PaintVarSkew<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset()
+            .resolve_field(data, FieldSite::new("PaintVarSkew", "paint_offset"))
     }
 
     /// Angle of skew in the direction of the x-axis, 180° ┬░ in
@@ -5144,7 +5223,10 @@ impl<'a> PaintSkewAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintSkewAroundCenter", "paint_offset"),
+        )
     }
 
     /// Angle of skew in the direction of the x-axis, 180° in
@@ -5275,7 +5357,10 @@ impl<'a> PaintVarSkewAroundCenter<'a> {
     /// Attempt to resolve [`paint_offset`][Self::paint_offset].
     pub fn paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.paint_offset().resolve(data)
+        self.paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintVarSkewAroundCenter", "paint_offset"),
+        )
     }
 
     /// Angle of skew in the direction of the x-axis, 180° in
@@ -5402,7 +5487,10 @@ impl<'a> PaintComposite<'a> {
     /// Attempt to resolve [`source_paint_offset`][Self::source_paint_offset].
     pub fn source_paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.source_paint_offset().resolve(data)
+        self.source_paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintComposite", "source_paint_offset"),
+        )
     }
 
     /// A CompositeMode enumeration value.
@@ -5420,7 +5508,10 @@ impl<'a> PaintComposite<'a> {
     /// Attempt to resolve [`backdrop_paint_offset`][Self::backdrop_paint_offset].
     pub fn backdrop_paint(&self) -> Result<Paint<'a>, ReadError> {
         let data = self.data;
-        self.backdrop_paint_offset().resolve(data)
+        self.backdrop_paint_offset().resolve_field(
+            data,
+            FieldSite::new("PaintComposite", "backdrop_paint_offset"),
+        )
     }
 }
 
@@ -5543,6 +5634,40 @@ impl font_types::Scalar for CompositeMode {
 #[cfg(feature = "traversal")]
 impl<'a> From<CompositeMode> for FieldType<'a> {
     fn from(src: CompositeMode) -> FieldType<'a> {
-        (src as u8).into()
+        let name = match src {
+            CompositeMode::Clear => Some("Clear"),
+            CompositeMode::Src => Some("Src"),
+            CompositeMode::Dest => Some("Dest"),
+            CompositeMode::SrcOver => Some("SrcOver"),
+            CompositeMode::DestOver => Some("DestOver"),
+            CompositeMode::SrcIn => Some("SrcIn"),
+            CompositeMode::DestIn => Some("DestIn"),
+            CompositeMode::SrcOut => Some("SrcOut"),
+            CompositeMode::DestOut => Some("DestOut"),
+            CompositeMode::SrcAtop => Some("SrcAtop"),
+            CompositeMode::DestAtop => Some("DestAtop"),
+            CompositeMode::Xor => Some("Xor"),
+            CompositeMode::Plus => Some("Plus"),
+            CompositeMode::Screen => Some("Screen"),
+            CompositeMode::Overlay => Some("Overlay"),
+            CompositeMode::Darken => Some("Darken"),
+            CompositeMode::Lighten => Some("Lighten"),
+            CompositeMode::ColorDodge => Some("ColorDodge"),
+            CompositeMode::ColorBurn => Some("ColorBurn"),
+            CompositeMode::HardLight => Some("HardLight"),
+            CompositeMode::SoftLight => Some("SoftLight"),
+            CompositeMode::Difference => Some("Difference"),
+            CompositeMode::Exclusion => Some("Exclusion"),
+            CompositeMode::Multiply => Some("Multiply"),
+            CompositeMode::HslHue => Some("HslHue"),
+            CompositeMode::HslSaturation => Some("HslSaturation"),
+            CompositeMode::HslColor => Some("HslColor"),
+            CompositeMode::HslLuminosity => Some("HslLuminosity"),
+            CompositeMode::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u8).into(),
+            name,
+        })
     }
 }
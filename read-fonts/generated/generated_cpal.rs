@@ -132,8 +132,11 @@ impl<'a> Cpal<'a> {
     pub fn color_records_array(&self) -> Option<Result<&'a [ColorRecord], ReadError>> {
         let data = self.data;
         let args = self.num_color_records();
-        self.color_records_array_offset()
-            .resolve_with_args(data, &args)
+        self.color_records_array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Cpal", "color_records_array_offset"),
+        )
     }
 
     /// Index of each palette’s first color record in the combined
@@ -157,8 +160,13 @@ impl<'a> Cpal<'a> {
     pub fn palette_types_array(&self) -> Option<Result<&'a [BigEndian<u32>], ReadError>> {
         let data = self.data;
         let args = self.num_palettes();
-        self.palette_types_array_offset()
-            .map(|x| x.resolve_with_args(data, &args))?
+        self.palette_types_array_offset().map(|x| {
+            x.resolve_field_with_args(
+                data,
+                &args,
+                FieldSite::new("Cpal", "palette_types_array_offset"),
+            )
+        })?
     }
 
     /// Offset from the beginning of CPAL table to the [Palette Labels Array][].
@@ -177,8 +185,13 @@ impl<'a> Cpal<'a> {
     pub fn palette_labels_array(&self) -> Option<Result<&'a [BigEndian<u16>], ReadError>> {
         let data = self.data;
         let args = self.num_palettes();
-        self.palette_labels_array_offset()
-            .map(|x| x.resolve_with_args(data, &args))?
+        self.palette_labels_array_offset().map(|x| {
+            x.resolve_field_with_args(
+                data,
+                &args,
+                FieldSite::new("Cpal", "palette_labels_array_offset"),
+            )
+        })?
     }
 
     /// Offset from the beginning of CPAL table to the [Palette Entry Labels Array][].
@@ -199,8 +212,13 @@ impl<'a> Cpal<'a> {
     pub fn palette_entry_labels_array(&self) -> Option<Result<&'a [BigEndian<NameId>], ReadError>> {
         let data = self.data;
         let args = self.num_palette_entries();
-        self.palette_entry_labels_array_offset()
-            .map(|x| x.resolve_with_args(data, &args))?
+        self.palette_entry_labels_array_offset().map(|x| {
+            x.resolve_field_with_args(
+                data,
+                &args,
+                FieldSite::new("Cpal", "palette_entry_labels_array_offset"),
+            )
+        })?
     }
 }
 
@@ -236,21 +254,21 @@ impl<'a> SomeTable<'a> for Cpal<'a> {
                 "palette_types_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_types_array_offset().unwrap(),
-                    self.palette_types_array().unwrap(),
+                    self.palette_types_array(),
                 ),
             )),
             7usize if version.compatible(1) => Some(Field::new(
                 "palette_labels_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_labels_array_offset().unwrap(),
-                    self.palette_labels_array().unwrap(),
+                    self.palette_labels_array(),
                 ),
             )),
             8usize if version.compatible(1) => Some(Field::new(
                 "palette_entry_labels_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_entry_labels_array_offset().unwrap(),
-                    self.palette_entry_labels_array().unwrap(),
+                    self.palette_entry_labels_array(),
                 ),
             )),
             _ => None,
@@ -86,8 +86,11 @@ impl<'a> Fvar<'a> {
             self.instance_count(),
             self.instance_size(),
         );
-        self.axis_instance_arrays_offset()
-            .resolve_with_args(data, &args)
+        self.axis_instance_arrays_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Fvar", "axis_instance_arrays_offset"),
+        )
     }
 
     /// The number of variation axes in the font (the number of records in the axes array).
@@ -98,7 +98,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`glyph_class_def_offset`][Self::glyph_class_def_offset].
     pub fn glyph_class_def(&self) -> Option<Result<ClassDef<'a>, ReadError>> {
         let data = self.data;
-        self.glyph_class_def_offset().resolve(data)
+        self.glyph_class_def_offset()
+            .resolve_field(data, FieldSite::new("Gdef", "glyph_class_def_offset"))
     }
 
     /// Offset to attachment point list table, from beginning of GDEF
@@ -111,7 +112,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`attach_list_offset`][Self::attach_list_offset].
     pub fn attach_list(&self) -> Option<Result<AttachList<'a>, ReadError>> {
         let data = self.data;
-        self.attach_list_offset().resolve(data)
+        self.attach_list_offset()
+            .resolve_field(data, FieldSite::new("Gdef", "attach_list_offset"))
     }
 
     /// Offset to ligature caret list table, from beginning of GDEF
@@ -124,7 +126,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`lig_caret_list_offset`][Self::lig_caret_list_offset].
     pub fn lig_caret_list(&self) -> Option<Result<LigCaretList<'a>, ReadError>> {
         let data = self.data;
-        self.lig_caret_list_offset().resolve(data)
+        self.lig_caret_list_offset()
+            .resolve_field(data, FieldSite::new("Gdef", "lig_caret_list_offset"))
     }
 
     /// Offset to class definition table for mark attachment type, from
@@ -137,7 +140,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`mark_attach_class_def_offset`][Self::mark_attach_class_def_offset].
     pub fn mark_attach_class_def(&self) -> Option<Result<ClassDef<'a>, ReadError>> {
         let data = self.data;
-        self.mark_attach_class_def_offset().resolve(data)
+        self.mark_attach_class_def_offset()
+            .resolve_field(data, FieldSite::new("Gdef", "mark_attach_class_def_offset"))
     }
 
     /// Offset to the table of mark glyph set definitions, from
@@ -150,7 +154,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`mark_glyph_sets_def_offset`][Self::mark_glyph_sets_def_offset].
     pub fn mark_glyph_sets_def(&self) -> Option<Result<MarkGlyphSets<'a>, ReadError>> {
         let data = self.data;
-        self.mark_glyph_sets_def_offset().map(|x| x.resolve(data))?
+        self.mark_glyph_sets_def_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Gdef", "mark_glyph_sets_def_offset")))?
     }
 
     /// Offset to the Item Variation Store table, from beginning of
@@ -163,7 +168,8 @@ impl<'a> Gdef<'a> {
     /// Attempt to resolve [`item_var_store_offset`][Self::item_var_store_offset].
     pub fn item_var_store(&self) -> Option<Result<ItemVariationStore<'a>, ReadError>> {
         let data = self.data;
-        self.item_var_store_offset().map(|x| x.resolve(data))?
+        self.item_var_store_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Gdef", "item_var_store_offset")))?
     }
 }
 
@@ -199,15 +205,12 @@ impl<'a> SomeTable<'a> for Gdef<'a> {
                 "mark_glyph_sets_def_offset",
                 FieldType::offset(
                     self.mark_glyph_sets_def_offset().unwrap(),
-                    self.mark_glyph_sets_def().unwrap(),
+                    self.mark_glyph_sets_def(),
                 ),
             )),
             6usize if version.compatible((1, 3)) => Some(Field::new(
                 "item_var_store_offset",
-                FieldType::offset(
-                    self.item_var_store_offset().unwrap(),
-                    self.item_var_store().unwrap(),
-                ),
+                FieldType::offset(self.item_var_store_offset().unwrap(), self.item_var_store()),
             )),
             _ => None,
         }
@@ -263,7 +266,17 @@ impl font_types::Scalar for GlyphClassDef {
 #[cfg(feature = "traversal")]
 impl<'a> From<GlyphClassDef> for FieldType<'a> {
     fn from(src: GlyphClassDef) -> FieldType<'a> {
-        (src as u16).into()
+        let name = match src {
+            GlyphClassDef::Base => Some("Base"),
+            GlyphClassDef::Ligature => Some("Ligature"),
+            GlyphClassDef::Mark => Some("Mark"),
+            GlyphClassDef::Component => Some("Component"),
+            GlyphClassDef::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u16).into(),
+            name,
+        })
     }
 }
 
@@ -315,7 +328,8 @@ impl<'a> AttachList<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("AttachList", "coverage_offset"))
     }
 
     /// Number of glyphs with attachment points
@@ -336,7 +350,13 @@ impl<'a> AttachList<'a> {
         let data = self.data;
         self.attach_point_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("AttachList", "attach_point_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -494,7 +514,8 @@ impl<'a> LigCaretList<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("LigCaretList", "coverage_offset"))
     }
 
     /// Number of ligature glyphs
@@ -515,7 +536,13 @@ impl<'a> LigCaretList<'a> {
         let data = self.data;
         self.lig_glyph_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("LigCaretList", "lig_glyph_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -609,7 +636,13 @@ impl<'a> LigGlyph<'a> {
         let data = self.data;
         self.caret_value_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("LigGlyph", "caret_value_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -692,6 +725,9 @@ impl<'a> SomeTable<'a> for CaretValue<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for CaretValueFormat1Marker {
@@ -892,7 +928,8 @@ impl<'a> CaretValueFormat3<'a> {
     /// Attempt to resolve [`device_offset`][Self::device_offset].
     pub fn device(&self) -> Result<Device<'a>, ReadError> {
         let data = self.data;
-        self.device_offset().resolve(data)
+        self.device_offset()
+            .resolve_field(data, FieldSite::new("CaretValueFormat3", "device_offset"))
     }
 }
 
@@ -988,7 +1025,13 @@ impl<'a> MarkGlyphSets<'a> {
         let data = self.data;
         self.coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("MarkGlyphSets", "coverage_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -208,6 +208,9 @@ impl<'a> SomeTable<'a> for SimpleGlyph<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -294,6 +297,12 @@ impl SimpleGlyphFlags {
     /// platforms.) When used, it must be set on the first flag byte
     /// for the glyph. See additional details below.
     pub const OVERLAP_SIMPLE: Self = Self { bits: 0x40 };
+
+    /// Bit 7: Off-curve point belongs to a cubic-Bezier segment.
+    /// Only meaningful when ON_CURVE_POINT is not set. See the
+    /// [glyf-cubic](https://github.com/harfbuzz/boring-expansion-spec/blob/main/glyf1-cubicOutlines.md)
+    /// proposal for details.
+    pub const CUBIC: Self = Self { bits: 0x80 };
 }
 
 impl SimpleGlyphFlags {
@@ -313,7 +322,8 @@ impl SimpleGlyphFlags {
                 | Self::REPEAT_FLAG.bits
                 | Self::X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR.bits
                 | Self::Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR.bits
-                | Self::OVERLAP_SIMPLE.bits,
+                | Self::OVERLAP_SIMPLE.bits
+                | Self::CUBIC.bits,
         }
     }
 
@@ -545,6 +555,7 @@ impl std::fmt::Debug for SimpleGlyphFlags {
                 Self::Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
             ),
             ("OVERLAP_SIMPLE", Self::OVERLAP_SIMPLE),
+            ("CUBIC", Self::CUBIC),
         ];
         let mut first = true;
         for (name, value) in members {
@@ -601,7 +612,19 @@ impl font_types::Scalar for SimpleGlyphFlags {
 #[cfg(feature = "traversal")]
 impl<'a> From<SimpleGlyphFlags> for FieldType<'a> {
     fn from(src: SimpleGlyphFlags) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("ON_CURVE_POINT", 0x01),
+                ("X_SHORT_VECTOR", 0x02),
+                ("Y_SHORT_VECTOR", 0x04),
+                ("REPEAT_FLAG", 0x08),
+                ("X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR", 0x10),
+                ("Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR", 0x20),
+                ("OVERLAP_SIMPLE", 0x40),
+                ("CUBIC", 0x80),
+            ],
+        })
     }
 }
 
@@ -715,6 +738,9 @@ impl<'a> SomeTable<'a> for CompositeGlyph<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -1097,7 +1123,23 @@ impl font_types::Scalar for CompositeGlyphFlags {
 #[cfg(feature = "traversal")]
 impl<'a> From<CompositeGlyphFlags> for FieldType<'a> {
     fn from(src: CompositeGlyphFlags) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("ARG_1_AND_2_ARE_WORDS", 0x0001),
+                ("ARGS_ARE_XY_VALUES", 0x0002),
+                ("ROUND_XY_TO_GRID", 0x0004),
+                ("WE_HAVE_A_SCALE", 0x0008),
+                ("MORE_COMPONENTS", 0x0020),
+                ("WE_HAVE_AN_X_AND_Y_SCALE", 0x0040),
+                ("WE_HAVE_A_TWO_BY_TWO", 0x0080),
+                ("WE_HAVE_INSTRUCTIONS", 0x0100),
+                ("USE_MY_METRICS", 0x0200),
+                ("OVERLAP_COMPOUND", 0x0400),
+                ("SCALED_COMPONENT_OFFSET", 0x0800),
+                ("UNSCALED_COMPONENT_OFFSET", 0x1000),
+            ],
+        })
     }
 }
 
@@ -1143,4 +1185,7 @@ impl<'a> SomeTable<'a> for Glyph<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
@@ -81,7 +81,8 @@ impl<'a> Gpos<'a> {
     /// Attempt to resolve [`script_list_offset`][Self::script_list_offset].
     pub fn script_list(&self) -> Result<ScriptList<'a>, ReadError> {
         let data = self.data;
-        self.script_list_offset().resolve(data)
+        self.script_list_offset()
+            .resolve_field(data, FieldSite::new("Gpos", "script_list_offset"))
     }
 
     /// Offset to FeatureList table, from beginning of GPOS table
@@ -93,7 +94,8 @@ impl<'a> Gpos<'a> {
     /// Attempt to resolve [`feature_list_offset`][Self::feature_list_offset].
     pub fn feature_list(&self) -> Result<FeatureList<'a>, ReadError> {
         let data = self.data;
-        self.feature_list_offset().resolve(data)
+        self.feature_list_offset()
+            .resolve_field(data, FieldSite::new("Gpos", "feature_list_offset"))
     }
 
     /// Offset to LookupList table, from beginning of GPOS table
@@ -105,7 +107,8 @@ impl<'a> Gpos<'a> {
     /// Attempt to resolve [`lookup_list_offset`][Self::lookup_list_offset].
     pub fn lookup_list(&self) -> Result<PositionLookupList<'a>, ReadError> {
         let data = self.data;
-        self.lookup_list_offset().resolve(data)
+        self.lookup_list_offset()
+            .resolve_field(data, FieldSite::new("Gpos", "lookup_list_offset"))
     }
 
     pub fn feature_variations_offset(&self) -> Option<Nullable<Offset32>> {
@@ -116,7 +119,8 @@ impl<'a> Gpos<'a> {
     /// Attempt to resolve [`feature_variations_offset`][Self::feature_variations_offset].
     pub fn feature_variations(&self) -> Option<Result<FeatureVariations<'a>, ReadError>> {
         let data = self.data;
-        self.feature_variations_offset().map(|x| x.resolve(data))?
+        self.feature_variations_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Gpos", "feature_variations_offset")))?
     }
 }
 
@@ -145,7 +149,7 @@ impl<'a> SomeTable<'a> for Gpos<'a> {
                 "feature_variations_offset",
                 FieldType::offset(
                     self.feature_variations_offset().unwrap(),
-                    self.feature_variations().unwrap(),
+                    self.feature_variations(),
                 ),
             )),
             _ => None,
@@ -216,6 +220,9 @@ impl<'a> SomeTable<'a> for PositionLookup<'a> {
     fn type_name(&self) -> &str {
         self.as_some_table().type_name()
     }
+    fn is_open_ended(&self) -> bool {
+        self.as_some_table().is_open_ended()
+    }
 }
 
 /// See [ValueRecord]
@@ -555,7 +562,19 @@ impl font_types::Scalar for ValueFormat {
 #[cfg(feature = "traversal")]
 impl<'a> From<ValueFormat> for FieldType<'a> {
     fn from(src: ValueFormat) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("X_PLACEMENT", 0x0001),
+                ("Y_PLACEMENT", 0x0002),
+                ("X_ADVANCE", 0x0004),
+                ("Y_ADVANCE", 0x0008),
+                ("X_PLACEMENT_DEVICE", 0x0010),
+                ("Y_PLACEMENT_DEVICE", 0x0020),
+                ("X_ADVANCE_DEVICE", 0x0040),
+                ("Y_ADVANCE_DEVICE", 0x0080),
+            ],
+        })
     }
 }
 
@@ -605,6 +624,9 @@ impl<'a> SomeTable<'a> for AnchorTable<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for AnchorFormat1Marker {
@@ -854,7 +876,8 @@ impl<'a> AnchorFormat3<'a> {
     /// Attempt to resolve [`x_device_offset`][Self::x_device_offset].
     pub fn x_device(&self) -> Option<Result<Device<'a>, ReadError>> {
         let data = self.data;
-        self.x_device_offset().resolve(data)
+        self.x_device_offset()
+            .resolve_field(data, FieldSite::new("AnchorFormat3", "x_device_offset"))
     }
 
     /// Offset to Device table (non-variable font) / VariationIndex
@@ -868,7 +891,8 @@ impl<'a> AnchorFormat3<'a> {
     /// Attempt to resolve [`y_device_offset`][Self::y_device_offset].
     pub fn y_device(&self) -> Option<Result<Device<'a>, ReadError>> {
         let data = self.data;
-        self.y_device_offset().resolve(data)
+        self.y_device_offset()
+            .resolve_field(data, FieldSite::new("AnchorFormat3", "y_device_offset"))
     }
 }
 
@@ -1002,7 +1026,8 @@ impl MarkRecord {
 
     /// Attempt to resolve [`mark_anchor_offset`][Self::mark_anchor_offset].
     pub fn mark_anchor<'a>(&self, data: FontData<'a>) -> Result<AnchorTable<'a>, ReadError> {
-        self.mark_anchor_offset().resolve(data)
+        self.mark_anchor_offset()
+            .resolve_field(data, FieldSite::new("MarkRecord", "mark_anchor_offset"))
     }
 }
 
@@ -1070,6 +1095,9 @@ impl<'a> SomeTable<'a> for SinglePos<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for SinglePosFormat1Marker {
@@ -1135,7 +1163,8 @@ impl<'a> SinglePosFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("SinglePosFormat1", "coverage_offset"))
     }
 
     /// Defines the types of data in the ValueRecord.
@@ -1252,7 +1281,8 @@ impl<'a> SinglePosFormat2<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("SinglePosFormat2", "coverage_offset"))
     }
 
     /// Defines the types of data in the ValueRecords.
@@ -1353,6 +1383,9 @@ impl<'a> SomeTable<'a> for PairPos<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for PairPosFormat1Marker {
@@ -1428,7 +1461,8 @@ impl<'a> PairPosFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("PairPosFormat1", "coverage_offset"))
     }
 
     /// Defines the types of data in valueRecord1 — for the first
@@ -1464,7 +1498,14 @@ impl<'a> PairPosFormat1<'a> {
         let args = (self.value_format1(), self.value_format2());
         self.pair_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve_with_args(data, &args))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field_with_args(
+                    data,
+                    &args,
+                    FieldSite::new("PairPosFormat1", "pair_set_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1812,7 +1853,8 @@ impl<'a> PairPosFormat2<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("PairPosFormat2", "coverage_offset"))
     }
 
     /// ValueRecord definition — for the first glyph of the pair (may
@@ -1839,7 +1881,8 @@ impl<'a> PairPosFormat2<'a> {
     /// Attempt to resolve [`class_def1_offset`][Self::class_def1_offset].
     pub fn class_def1(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.class_def1_offset().resolve(data)
+        self.class_def1_offset()
+            .resolve_field(data, FieldSite::new("PairPosFormat2", "class_def1_offset"))
     }
 
     /// Offset to ClassDef table, from beginning of PairPos subtable
@@ -1852,7 +1895,8 @@ impl<'a> PairPosFormat2<'a> {
     /// Attempt to resolve [`class_def2_offset`][Self::class_def2_offset].
     pub fn class_def2(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.class_def2_offset().resolve(data)
+        self.class_def2_offset()
+            .resolve_field(data, FieldSite::new("PairPosFormat2", "class_def2_offset"))
     }
 
     /// Number of classes in classDef1 table — includes Class 0.
@@ -2150,7 +2194,8 @@ impl<'a> CursivePosFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset()
+            .resolve_field(data, FieldSite::new("CursivePosFormat1", "coverage_offset"))
     }
 
     /// Number of EntryExit records
@@ -2224,7 +2269,10 @@ impl EntryExitRecord {
         &self,
         data: FontData<'a>,
     ) -> Option<Result<AnchorTable<'a>, ReadError>> {
-        self.entry_anchor_offset().resolve(data)
+        self.entry_anchor_offset().resolve_field(
+            data,
+            FieldSite::new("EntryExitRecord", "entry_anchor_offset"),
+        )
     }
 
     /// Offset to exitAnchor table, from beginning of CursivePos
@@ -2238,7 +2286,10 @@ impl EntryExitRecord {
         &self,
         data: FontData<'a>,
     ) -> Option<Result<AnchorTable<'a>, ReadError>> {
-        self.exit_anchor_offset().resolve(data)
+        self.exit_anchor_offset().resolve_field(
+            data,
+            FieldSite::new("EntryExitRecord", "exit_anchor_offset"),
+        )
     }
 }
 
@@ -2336,7 +2387,10 @@ impl<'a> MarkBasePosFormat1<'a> {
     /// Attempt to resolve [`mark_coverage_offset`][Self::mark_coverage_offset].
     pub fn mark_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.mark_coverage_offset().resolve(data)
+        self.mark_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkBasePosFormat1", "mark_coverage_offset"),
+        )
     }
 
     /// Offset to baseCoverage table, from beginning of MarkBasePos
@@ -2349,7 +2403,10 @@ impl<'a> MarkBasePosFormat1<'a> {
     /// Attempt to resolve [`base_coverage_offset`][Self::base_coverage_offset].
     pub fn base_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.base_coverage_offset().resolve(data)
+        self.base_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkBasePosFormat1", "base_coverage_offset"),
+        )
     }
 
     /// Number of classes defined for marks
@@ -2368,7 +2425,10 @@ impl<'a> MarkBasePosFormat1<'a> {
     /// Attempt to resolve [`mark_array_offset`][Self::mark_array_offset].
     pub fn mark_array(&self) -> Result<MarkArray<'a>, ReadError> {
         let data = self.data;
-        self.mark_array_offset().resolve(data)
+        self.mark_array_offset().resolve_field(
+            data,
+            FieldSite::new("MarkBasePosFormat1", "mark_array_offset"),
+        )
     }
 
     /// Offset to BaseArray table, from beginning of MarkBasePos
@@ -2382,7 +2442,11 @@ impl<'a> MarkBasePosFormat1<'a> {
     pub fn base_array(&self) -> Result<BaseArray<'a>, ReadError> {
         let data = self.data;
         let args = self.mark_class_count();
-        self.base_array_offset().resolve_with_args(data, &args)
+        self.base_array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("MarkBasePosFormat1", "base_array_offset"),
+        )
     }
 }
 
@@ -2547,7 +2611,13 @@ impl<'a> BaseRecord<'a> {
     ) -> impl Iterator<Item = Option<Result<AnchorTable<'a>, ReadError>>> + 'a {
         self.base_anchor_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("BaseRecord", "base_anchor_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -2678,7 +2748,10 @@ impl<'a> MarkLigPosFormat1<'a> {
     /// Attempt to resolve [`mark_coverage_offset`][Self::mark_coverage_offset].
     pub fn mark_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.mark_coverage_offset().resolve(data)
+        self.mark_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkLigPosFormat1", "mark_coverage_offset"),
+        )
     }
 
     /// Offset to ligatureCoverage table, from beginning of MarkLigPos
@@ -2691,7 +2764,10 @@ impl<'a> MarkLigPosFormat1<'a> {
     /// Attempt to resolve [`ligature_coverage_offset`][Self::ligature_coverage_offset].
     pub fn ligature_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.ligature_coverage_offset().resolve(data)
+        self.ligature_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkLigPosFormat1", "ligature_coverage_offset"),
+        )
     }
 
     /// Number of defined mark classes
@@ -2710,7 +2786,10 @@ impl<'a> MarkLigPosFormat1<'a> {
     /// Attempt to resolve [`mark_array_offset`][Self::mark_array_offset].
     pub fn mark_array(&self) -> Result<MarkArray<'a>, ReadError> {
         let data = self.data;
-        self.mark_array_offset().resolve(data)
+        self.mark_array_offset().resolve_field(
+            data,
+            FieldSite::new("MarkLigPosFormat1", "mark_array_offset"),
+        )
     }
 
     /// Offset to LigatureArray table, from beginning of MarkLigPos
@@ -2724,7 +2803,11 @@ impl<'a> MarkLigPosFormat1<'a> {
     pub fn ligature_array(&self) -> Result<LigatureArray<'a>, ReadError> {
         let data = self.data;
         let args = self.mark_class_count();
-        self.ligature_array_offset().resolve_with_args(data, &args)
+        self.ligature_array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("MarkLigPosFormat1", "ligature_array_offset"),
+        )
     }
 }
 
@@ -2839,7 +2922,14 @@ impl<'a> LigatureArray<'a> {
         let args = self.mark_class_count();
         self.ligature_attach_offsets()
             .iter()
-            .map(move |off| off.get().resolve_with_args(data, &args))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field_with_args(
+                    data,
+                    &args,
+                    FieldSite::new("LigatureArray", "ligature_attach_offsets").at(i),
+                )
+            })
     }
 
     pub(crate) fn mark_class_count(&self) -> u16 {
@@ -3006,7 +3096,13 @@ impl<'a> ComponentRecord<'a> {
     ) -> impl Iterator<Item = Option<Result<AnchorTable<'a>, ReadError>>> + 'a {
         self.ligature_anchor_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ComponentRecord", "ligature_anchor_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -3137,7 +3233,10 @@ impl<'a> MarkMarkPosFormat1<'a> {
     /// Attempt to resolve [`mark1_coverage_offset`][Self::mark1_coverage_offset].
     pub fn mark1_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.mark1_coverage_offset().resolve(data)
+        self.mark1_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkMarkPosFormat1", "mark1_coverage_offset"),
+        )
     }
 
     /// Offset to Base Mark Coverage table, from beginning of
@@ -3150,7 +3249,10 @@ impl<'a> MarkMarkPosFormat1<'a> {
     /// Attempt to resolve [`mark2_coverage_offset`][Self::mark2_coverage_offset].
     pub fn mark2_coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.mark2_coverage_offset().resolve(data)
+        self.mark2_coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MarkMarkPosFormat1", "mark2_coverage_offset"),
+        )
     }
 
     /// Number of Combining Mark classes defined
@@ -3169,7 +3271,10 @@ impl<'a> MarkMarkPosFormat1<'a> {
     /// Attempt to resolve [`mark1_array_offset`][Self::mark1_array_offset].
     pub fn mark1_array(&self) -> Result<MarkArray<'a>, ReadError> {
         let data = self.data;
-        self.mark1_array_offset().resolve(data)
+        self.mark1_array_offset().resolve_field(
+            data,
+            FieldSite::new("MarkMarkPosFormat1", "mark1_array_offset"),
+        )
     }
 
     /// Offset to Mark2Array table for mark2, from beginning of
@@ -3183,7 +3288,11 @@ impl<'a> MarkMarkPosFormat1<'a> {
     pub fn mark2_array(&self) -> Result<Mark2Array<'a>, ReadError> {
         let data = self.data;
         let args = self.mark_class_count();
-        self.mark2_array_offset().resolve_with_args(data, &args)
+        self.mark2_array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("MarkMarkPosFormat1", "mark2_array_offset"),
+        )
     }
 }
 
@@ -3348,7 +3457,13 @@ impl<'a> Mark2Record<'a> {
     ) -> impl Iterator<Item = Option<Result<AnchorTable<'a>, ReadError>>> + 'a {
         self.mark2_anchor_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("Mark2Record", "mark2_anchor_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -3502,7 +3617,10 @@ impl<'a, T> ExtensionPosFormat1<'a, T> {
         T: FontRead<'a>,
     {
         let data = self.data;
-        self.extension_offset().resolve(data)
+        self.extension_offset().resolve_field(
+            data,
+            FieldSite::new("ExtensionPosFormat1", "extension_offset"),
+        )
     }
 }
 
@@ -3587,4 +3705,7 @@ impl<'a> SomeTable<'a> for ExtensionSubtable<'a> {
     fn type_name(&self) -> &str {
         self.as_some_table().type_name()
     }
+    fn is_open_ended(&self) -> bool {
+        self.as_some_table().is_open_ended()
+    }
 }
@@ -79,7 +79,8 @@ impl<'a> Gsub<'a> {
     /// Attempt to resolve [`script_list_offset`][Self::script_list_offset].
     pub fn script_list(&self) -> Result<ScriptList<'a>, ReadError> {
         let data = self.data;
-        self.script_list_offset().resolve(data)
+        self.script_list_offset()
+            .resolve_field(data, FieldSite::new("Gsub", "script_list_offset"))
     }
 
     /// Offset to FeatureList table, from beginning of GSUB table
@@ -91,7 +92,8 @@ impl<'a> Gsub<'a> {
     /// Attempt to resolve [`feature_list_offset`][Self::feature_list_offset].
     pub fn feature_list(&self) -> Result<FeatureList<'a>, ReadError> {
         let data = self.data;
-        self.feature_list_offset().resolve(data)
+        self.feature_list_offset()
+            .resolve_field(data, FieldSite::new("Gsub", "feature_list_offset"))
     }
 
     /// Offset to LookupList table, from beginning of GSUB table
@@ -103,7 +105,8 @@ impl<'a> Gsub<'a> {
     /// Attempt to resolve [`lookup_list_offset`][Self::lookup_list_offset].
     pub fn lookup_list(&self) -> Result<SubstitutionLookupList<'a>, ReadError> {
         let data = self.data;
-        self.lookup_list_offset().resolve(data)
+        self.lookup_list_offset()
+            .resolve_field(data, FieldSite::new("Gsub", "lookup_list_offset"))
     }
 
     /// Offset to FeatureVariations table, from beginning of the GSUB
@@ -116,7 +119,8 @@ impl<'a> Gsub<'a> {
     /// Attempt to resolve [`feature_variations_offset`][Self::feature_variations_offset].
     pub fn feature_variations(&self) -> Option<Result<FeatureVariations<'a>, ReadError>> {
         let data = self.data;
-        self.feature_variations_offset().map(|x| x.resolve(data))?
+        self.feature_variations_offset()
+            .map(|x| x.resolve_field(data, FieldSite::new("Gsub", "feature_variations_offset")))?
     }
 }
 
@@ -145,7 +149,7 @@ impl<'a> SomeTable<'a> for Gsub<'a> {
                 "feature_variations_offset",
                 FieldType::offset(
                     self.feature_variations_offset().unwrap(),
-                    self.feature_variations().unwrap(),
+                    self.feature_variations(),
                 ),
             )),
             _ => None,
@@ -213,6 +217,9 @@ impl<'a> SomeTable<'a> for SubstitutionLookup<'a> {
     fn type_name(&self) -> &str {
         self.as_some_table().type_name()
     }
+    fn is_open_ended(&self) -> bool {
+        self.as_some_table().is_open_ended()
+    }
 }
 
 /// LookupType 1: [Single Substitution](https://learn.microsoft.com/en-us/typography/opentype/spec/gsub#lookuptype-1-single-substitution-subtable) Subtable
@@ -257,6 +264,9 @@ impl<'a> SomeTable<'a> for SingleSubst<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for SingleSubstFormat1Marker {
@@ -313,7 +323,10 @@ impl<'a> SingleSubstFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("SingleSubstFormat1", "coverage_offset"),
+        )
     }
 
     /// Add to original glyph ID to get substitute glyph ID
@@ -412,7 +425,10 @@ impl<'a> SingleSubstFormat2<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("SingleSubstFormat2", "coverage_offset"),
+        )
     }
 
     /// Number of glyph IDs in the substituteGlyphIDs array
@@ -521,7 +537,10 @@ impl<'a> MultipleSubstFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("MultipleSubstFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of Sequence table offsets in the sequenceOffsets array
@@ -542,7 +561,13 @@ impl<'a> MultipleSubstFormat1<'a> {
         let data = self.data;
         self.sequence_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("MultipleSubstFormat1", "sequence_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -721,7 +746,10 @@ impl<'a> AlternateSubstFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("AlternateSubstFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of AlternateSet tables
@@ -742,7 +770,13 @@ impl<'a> AlternateSubstFormat1<'a> {
         let data = self.data;
         self.alternate_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("AlternateSubstFormat1", "alternate_set_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -923,7 +957,10 @@ impl<'a> LigatureSubstFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("LigatureSubstFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of LigatureSet tables
@@ -944,7 +981,13 @@ impl<'a> LigatureSubstFormat1<'a> {
         let data = self.data;
         self.ligature_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("LigatureSubstFormat1", "ligature_set_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1039,7 +1082,13 @@ impl<'a> LigatureSet<'a> {
         let data = self.data;
         self.ligature_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("LigatureSet", "ligature_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1254,7 +1303,10 @@ impl<'a, T> ExtensionSubstFormat1<'a, T> {
         T: FontRead<'a>,
     {
         let data = self.data;
-        self.extension_offset().resolve(data)
+        self.extension_offset().resolve_field(
+            data,
+            FieldSite::new("ExtensionSubstFormat1", "extension_offset"),
+        )
     }
 }
 
@@ -1336,6 +1388,9 @@ impl<'a> SomeTable<'a> for ExtensionSubtable<'a> {
     fn type_name(&self) -> &str {
         self.as_some_table().type_name()
     }
+    fn is_open_ended(&self) -> bool {
+        self.as_some_table().is_open_ended()
+    }
 }
 
 impl Format<u16> for ReverseChainSingleSubstFormat1Marker {
@@ -1430,7 +1485,10 @@ impl<'a> ReverseChainSingleSubstFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("ReverseChainSingleSubstFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of glyphs in the backtrack sequence.
@@ -1453,7 +1511,17 @@ impl<'a> ReverseChainSingleSubstFormat1<'a> {
         let data = self.data;
         self.backtrack_coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ReverseChainSingleSubstFormat1",
+                        "backtrack_coverage_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 
     /// Number of glyphs in lookahead sequence.
@@ -1476,7 +1544,17 @@ impl<'a> ReverseChainSingleSubstFormat1<'a> {
         let data = self.data;
         self.lookahead_coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ReverseChainSingleSubstFormat1",
+                        "lookahead_coverage_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 
     /// Number of glyph IDs in the substituteGlyphIDs array.
@@ -107,7 +107,11 @@ impl<'a> Gvar<'a> {
     pub fn shared_tuples(&self) -> Result<SharedTuples<'a>, ReadError> {
         let data = self.data;
         let args = (self.shared_tuple_count(), self.axis_count());
-        self.shared_tuples_offset().resolve_with_args(data, &args)
+        self.shared_tuples_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Gvar", "shared_tuples_offset"),
+        )
     }
 
     /// The number of glyphs in this font. This must match the number
@@ -471,7 +475,10 @@ impl font_types::Scalar for GvarFlags {
 #[cfg(feature = "traversal")]
 impl<'a> From<GvarFlags> for FieldType<'a> {
     fn from(src: GvarFlags) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[("LONG_OFFSETS", 1)],
+        })
     }
 }
 
@@ -618,7 +625,10 @@ impl<'a> GlyphVariationDataHeader<'a> {
     /// Attempt to resolve [`serialized_data_offset`][Self::serialized_data_offset].
     pub fn serialized_data(&self) -> Result<FontData<'a>, ReadError> {
         let data = self.data;
-        self.serialized_data_offset().resolve(data)
+        self.serialized_data_offset().resolve_field(
+            data,
+            FieldSite::new("GlyphVariationDataHeader", "serialized_data_offset"),
+        )
     }
 
     /// Array of tuple variation headers.
@@ -650,6 +660,9 @@ impl<'a> SomeTable<'a> for GlyphVariationDataHeader<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
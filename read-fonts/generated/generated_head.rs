@@ -333,7 +333,18 @@ impl font_types::Scalar for MacStyle {
 #[cfg(feature = "traversal")]
 impl<'a> From<MacStyle> for FieldType<'a> {
     fn from(src: MacStyle) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("BOLD", 0x0001),
+                ("ITALIC", 0x0002),
+                ("UNDERLINE", 0x0004),
+                ("OUTLINE", 0x0008),
+                ("SHADOW", 0x0010),
+                ("CONDENSED", 0x0020),
+                ("EXTENDED", 0x0040),
+            ],
+        })
     }
 }
 
@@ -70,7 +70,8 @@ impl<'a> Hvar<'a> {
     /// Attempt to resolve [`item_variation_store_offset`][Self::item_variation_store_offset].
     pub fn item_variation_store(&self) -> Result<ItemVariationStore<'a>, ReadError> {
         let data = self.data;
-        self.item_variation_store_offset().resolve(data)
+        self.item_variation_store_offset()
+            .resolve_field(data, FieldSite::new("Hvar", "item_variation_store_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for advance widths (may be NULL).
@@ -82,7 +83,8 @@ impl<'a> Hvar<'a> {
     /// Attempt to resolve [`advance_width_mapping_offset`][Self::advance_width_mapping_offset].
     pub fn advance_width_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.advance_width_mapping_offset().resolve(data)
+        self.advance_width_mapping_offset()
+            .resolve_field(data, FieldSite::new("Hvar", "advance_width_mapping_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for left side bearings (may be NULL).
@@ -94,7 +96,8 @@ impl<'a> Hvar<'a> {
     /// Attempt to resolve [`lsb_mapping_offset`][Self::lsb_mapping_offset].
     pub fn lsb_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.lsb_mapping_offset().resolve(data)
+        self.lsb_mapping_offset()
+            .resolve_field(data, FieldSite::new("Hvar", "lsb_mapping_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for right side bearings (may be NULL).
@@ -106,7 +109,8 @@ impl<'a> Hvar<'a> {
     /// Attempt to resolve [`rsb_mapping_offset`][Self::rsb_mapping_offset].
     pub fn rsb_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.rsb_mapping_offset().resolve(data)
+        self.rsb_mapping_offset()
+            .resolve_field(data, FieldSite::new("Hvar", "rsb_mapping_offset"))
     }
 }
 
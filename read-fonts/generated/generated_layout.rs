@@ -104,7 +104,8 @@ impl ScriptRecord {
 
     /// Attempt to resolve [`script_offset`][Self::script_offset].
     pub fn script<'a>(&self, data: FontData<'a>) -> Result<Script<'a>, ReadError> {
-        self.script_offset().resolve(data)
+        self.script_offset()
+            .resolve_field(data, FieldSite::new("ScriptRecord", "script_offset"))
     }
 }
 
@@ -179,7 +180,8 @@ impl<'a> Script<'a> {
     /// Attempt to resolve [`default_lang_sys_offset`][Self::default_lang_sys_offset].
     pub fn default_lang_sys(&self) -> Option<Result<LangSys<'a>, ReadError>> {
         let data = self.data;
-        self.default_lang_sys_offset().resolve(data)
+        self.default_lang_sys_offset()
+            .resolve_field(data, FieldSite::new("Script", "default_lang_sys_offset"))
     }
 
     /// Number of LangSysRecords for this script — excluding the
@@ -251,7 +253,8 @@ impl LangSysRecord {
 
     /// Attempt to resolve [`lang_sys_offset`][Self::lang_sys_offset].
     pub fn lang_sys<'a>(&self, data: FontData<'a>) -> Result<LangSys<'a>, ReadError> {
-        self.lang_sys_offset().resolve(data)
+        self.lang_sys_offset()
+            .resolve_field(data, FieldSite::new("LangSysRecord", "lang_sys_offset"))
     }
 }
 
@@ -471,7 +474,11 @@ impl FeatureRecord {
     /// Attempt to resolve [`feature_offset`][Self::feature_offset].
     pub fn feature<'a>(&self, data: FontData<'a>) -> Result<Feature<'a>, ReadError> {
         let args = self.feature_tag();
-        self.feature_offset().resolve_with_args(data, &args)
+        self.feature_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("FeatureRecord", "feature_offset"),
+        )
     }
 }
 
@@ -564,7 +571,11 @@ impl<'a> Feature<'a> {
     pub fn feature_params(&self) -> Option<Result<FeatureParams<'a>, ReadError>> {
         let data = self.data;
         let args = self.feature_tag();
-        self.feature_params_offset().resolve_with_args(data, &args)
+        self.feature_params_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Feature", "feature_params_offset"),
+        )
     }
 
     /// Number of LookupList indices for this feature
@@ -695,7 +706,11 @@ impl<'a, T> LookupList<'a, T> {
         let data = self.data;
         self.lookup_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get()
+                    .resolve_field(data, FieldSite::new("LookupList", "lookup_offsets").at(i))
+            })
     }
 }
 
@@ -842,7 +857,11 @@ impl<'a, T> Lookup<'a, T> {
         let data = self.data;
         self.subtable_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get()
+                    .resolve_field(data, FieldSite::new("Lookup", "subtable_offsets").at(i))
+            })
     }
 
     /// Index (base 0) into GDEF mark glyph sets structure. This field
@@ -1161,6 +1180,9 @@ impl<'a> SomeTable<'a> for CoverageTable<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for ClassDefFormat1Marker {
@@ -1443,6 +1465,9 @@ impl<'a> SomeTable<'a> for ClassDef<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 /// [Sequence Lookup Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-lookup-record)
@@ -1551,7 +1576,10 @@ impl<'a> SequenceContextFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("SequenceContextFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of SequenceRuleSet tables
@@ -1574,7 +1602,13 @@ impl<'a> SequenceContextFormat1<'a> {
         let data = self.data;
         self.seq_rule_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("SequenceContextFormat1", "seq_rule_set_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1669,7 +1703,13 @@ impl<'a> SequenceRuleSet<'a> {
         let data = self.data;
         self.seq_rule_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("SequenceRuleSet", "seq_rule_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1881,7 +1921,10 @@ impl<'a> SequenceContextFormat2<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("SequenceContextFormat2", "coverage_offset"),
+        )
     }
 
     /// Offset to ClassDef table, from beginning of
@@ -1894,7 +1937,10 @@ impl<'a> SequenceContextFormat2<'a> {
     /// Attempt to resolve [`class_def_offset`][Self::class_def_offset].
     pub fn class_def(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.class_def_offset().resolve(data)
+        self.class_def_offset().resolve_field(
+            data,
+            FieldSite::new("SequenceContextFormat2", "class_def_offset"),
+        )
     }
 
     /// Number of ClassSequenceRuleSet tables
@@ -1917,7 +1963,13 @@ impl<'a> SequenceContextFormat2<'a> {
         let data = self.data;
         self.class_seq_rule_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("SequenceContextFormat2", "class_seq_rule_set_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -2022,7 +2074,13 @@ impl<'a> ClassSequenceRuleSet<'a> {
         let data = self.data;
         self.class_seq_rule_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ClassSequenceRuleSet", "class_seq_rule_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -2255,7 +2313,13 @@ impl<'a> SequenceContextFormat3<'a> {
         let data = self.data;
         self.coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("SequenceContextFormat3", "coverage_offsets").at(i),
+                )
+            })
     }
 
     /// Array of SequenceLookupRecords
@@ -2353,6 +2417,9 @@ impl<'a> SomeTable<'a> for SequenceContext<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for ChainedSequenceContextFormat1Marker {
@@ -2420,7 +2487,10 @@ impl<'a> ChainedSequenceContextFormat1<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("ChainedSequenceContextFormat1", "coverage_offset"),
+        )
     }
 
     /// Number of ChainedSequenceRuleSet tables
@@ -2443,7 +2513,17 @@ impl<'a> ChainedSequenceContextFormat1<'a> {
         let data = self.data;
         self.chained_seq_rule_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ChainedSequenceContextFormat1",
+                        "chained_seq_rule_set_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 }
 
@@ -2544,7 +2624,13 @@ impl<'a> ChainedSequenceRuleSet<'a> {
         let data = self.data;
         self.chained_seq_rule_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ChainedSequenceRuleSet", "chained_seq_rule_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -2829,7 +2915,10 @@ impl<'a> ChainedSequenceContextFormat2<'a> {
     /// Attempt to resolve [`coverage_offset`][Self::coverage_offset].
     pub fn coverage(&self) -> Result<CoverageTable<'a>, ReadError> {
         let data = self.data;
-        self.coverage_offset().resolve(data)
+        self.coverage_offset().resolve_field(
+            data,
+            FieldSite::new("ChainedSequenceContextFormat2", "coverage_offset"),
+        )
     }
 
     /// Offset to ClassDef table containing backtrack sequence context,
@@ -2842,7 +2931,13 @@ impl<'a> ChainedSequenceContextFormat2<'a> {
     /// Attempt to resolve [`backtrack_class_def_offset`][Self::backtrack_class_def_offset].
     pub fn backtrack_class_def(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.backtrack_class_def_offset().resolve(data)
+        self.backtrack_class_def_offset().resolve_field(
+            data,
+            FieldSite::new(
+                "ChainedSequenceContextFormat2",
+                "backtrack_class_def_offset",
+            ),
+        )
     }
 
     /// Offset to ClassDef table containing input sequence context,
@@ -2855,7 +2950,10 @@ impl<'a> ChainedSequenceContextFormat2<'a> {
     /// Attempt to resolve [`input_class_def_offset`][Self::input_class_def_offset].
     pub fn input_class_def(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.input_class_def_offset().resolve(data)
+        self.input_class_def_offset().resolve_field(
+            data,
+            FieldSite::new("ChainedSequenceContextFormat2", "input_class_def_offset"),
+        )
     }
 
     /// Offset to ClassDef table containing lookahead sequence context,
@@ -2868,7 +2966,13 @@ impl<'a> ChainedSequenceContextFormat2<'a> {
     /// Attempt to resolve [`lookahead_class_def_offset`][Self::lookahead_class_def_offset].
     pub fn lookahead_class_def(&self) -> Result<ClassDef<'a>, ReadError> {
         let data = self.data;
-        self.lookahead_class_def_offset().resolve(data)
+        self.lookahead_class_def_offset().resolve_field(
+            data,
+            FieldSite::new(
+                "ChainedSequenceContextFormat2",
+                "lookahead_class_def_offset",
+            ),
+        )
     }
 
     /// Number of ChainedClassSequenceRuleSet tables
@@ -2891,7 +2995,17 @@ impl<'a> ChainedSequenceContextFormat2<'a> {
         let data = self.data;
         self.chained_class_seq_rule_set_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ChainedSequenceContextFormat2",
+                        "chained_class_seq_rule_set_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 }
 
@@ -3010,7 +3124,17 @@ impl<'a> ChainedClassSequenceRuleSet<'a> {
         let data = self.data;
         self.chained_class_seq_rule_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ChainedClassSequenceRuleSet",
+                        "chained_class_seq_rule_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 }
 
@@ -3326,7 +3450,17 @@ impl<'a> ChainedSequenceContextFormat3<'a> {
         let data = self.data;
         self.backtrack_coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ChainedSequenceContextFormat3",
+                        "backtrack_coverage_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 
     /// Number of glyphs in the input sequence
@@ -3348,7 +3482,13 @@ impl<'a> ChainedSequenceContextFormat3<'a> {
         let data = self.data;
         self.input_coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ChainedSequenceContextFormat3", "input_coverage_offsets").at(i),
+                )
+            })
     }
 
     /// Number of glyphs in the lookahead sequence
@@ -3370,7 +3510,17 @@ impl<'a> ChainedSequenceContextFormat3<'a> {
         let data = self.data;
         self.lookahead_coverage_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new(
+                        "ChainedSequenceContextFormat3",
+                        "lookahead_coverage_offsets",
+                    )
+                    .at(i),
+                )
+            })
     }
 
     /// Number of SequenceLookupRecords
@@ -3510,6 +3660,9 @@ impl<'a> SomeTable<'a> for ChainedSequenceContext<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 /// [Device](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#device-and-variationindex-tables)
@@ -3559,7 +3712,17 @@ impl font_types::Scalar for DeltaFormat {
 #[cfg(feature = "traversal")]
 impl<'a> From<DeltaFormat> for FieldType<'a> {
     fn from(src: DeltaFormat) -> FieldType<'a> {
-        (src as u16).into()
+        let name = match src {
+            DeltaFormat::Local2BitDeltas => Some("Local2BitDeltas"),
+            DeltaFormat::Local4BitDeltas => Some("Local4BitDeltas"),
+            DeltaFormat::Local8BitDeltas => Some("Local8BitDeltas"),
+            DeltaFormat::VariationIndex => Some("VariationIndex"),
+            DeltaFormat::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u16).into(),
+            name,
+        })
     }
 }
 
@@ -3851,7 +4014,10 @@ impl FeatureVariationRecord {
 
     /// Attempt to resolve [`condition_set_offset`][Self::condition_set_offset].
     pub fn condition_set<'a>(&self, data: FontData<'a>) -> Result<ConditionSet<'a>, ReadError> {
-        self.condition_set_offset().resolve(data)
+        self.condition_set_offset().resolve_field(
+            data,
+            FieldSite::new("FeatureVariationRecord", "condition_set_offset"),
+        )
     }
 
     /// Offset to a feature table substitution table, from beginning of
@@ -3865,7 +4031,13 @@ impl FeatureVariationRecord {
         &self,
         data: FontData<'a>,
     ) -> Result<FeatureTableSubstitution<'a>, ReadError> {
-        self.feature_table_substitution_offset().resolve(data)
+        self.feature_table_substitution_offset().resolve_field(
+            data,
+            FieldSite::new(
+                "FeatureVariationRecord",
+                "feature_table_substitution_offset",
+            ),
+        )
     }
 }
 
@@ -3949,7 +4121,13 @@ impl<'a> ConditionSet<'a> {
         let data = self.data;
         self.condition_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ConditionSet", "condition_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -92,7 +92,8 @@ impl<'a> Mvar<'a> {
     /// Attempt to resolve [`item_variation_store_offset`][Self::item_variation_store_offset].
     pub fn item_variation_store(&self) -> Option<Result<ItemVariationStore<'a>, ReadError>> {
         let data = self.data;
-        self.item_variation_store_offset().resolve(data)
+        self.item_variation_store_offset()
+            .resolve_field(data, FieldSite::new("Mvar", "item_variation_store_offset"))
     }
 
     /// Array of value records that identify target items and the associated delta-set index for each. The valueTag records must be in binary order of their valueTag field.
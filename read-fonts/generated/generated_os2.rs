@@ -352,7 +352,21 @@ impl font_types::Scalar for SelectionFlags {
 #[cfg(feature = "traversal")]
 impl<'a> From<SelectionFlags> for FieldType<'a> {
     fn from(src: SelectionFlags) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("ITALIC", 0x0001),
+                ("UNDERSCORE", 0x0002),
+                ("NEGATIVE", 0x0004),
+                ("OUTLINED", 0x0008),
+                ("STRIKEOUT", 0x0010),
+                ("BOLD", 0x0020),
+                ("REGULAR", 0x0040),
+                ("USE_TYPO_METRICS", 0x0080),
+                ("WWS", 0x0100),
+                ("OBLIQUE", 0x0200),
+            ],
+        })
     }
 }
 
@@ -250,6 +250,9 @@ impl<'a> SomeTable<'a> for Post<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
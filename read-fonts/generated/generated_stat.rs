@@ -108,7 +108,11 @@ impl<'a> Stat<'a> {
     pub fn design_axes(&self) -> Result<&'a [AxisRecord], ReadError> {
         let data = self.data;
         let args = self.design_axis_count();
-        self.design_axes_offset().resolve_with_args(data, &args)
+        self.design_axes_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Stat", "design_axes_offset"),
+        )
     }
 
     /// The number of axis value tables.
@@ -130,8 +134,11 @@ impl<'a> Stat<'a> {
     pub fn offset_to_axis_values(&self) -> Result<AxisValueArray<'a>, ReadError> {
         let data = self.data;
         let args = self.axis_value_count();
-        self.offset_to_axis_value_offsets()
-            .resolve_with_args(data, &args)
+        self.offset_to_axis_value_offsets().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("Stat", "offset_to_axis_value_offsets"),
+        )
     }
 
     /// Name ID used as fallback when projection of names into a
@@ -300,7 +307,13 @@ impl<'a> AxisValueArray<'a> {
         let data = self.data;
         self.axis_value_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("AxisValueArray", "axis_value_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -385,6 +398,9 @@ impl<'a> SomeTable<'a> for AxisValue<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 impl Format<u16> for AxisValueFormat1Marker {
@@ -1217,6 +1233,12 @@ impl font_types::Scalar for AxisValueTableFlags {
 #[cfg(feature = "traversal")]
 impl<'a> From<AxisValueTableFlags> for FieldType<'a> {
     fn from(src: AxisValueTableFlags) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("OLDER_SIBLING_FONT_ATTRIBUTE", 0x0001),
+                ("ELIDABLE_AXIS_VALUE_NAME", 0x0002),
+            ],
+        })
     }
 }
@@ -44,7 +44,15 @@ impl font_types::Scalar for MyEnum1 {
 #[cfg(feature = "traversal")]
 impl<'a> From<MyEnum1> for FieldType<'a> {
     fn from(src: MyEnum1) -> FieldType<'a> {
-        (src as u16).into()
+        let name = match src {
+            MyEnum1::ItsAZero => Some("ItsAZero"),
+            MyEnum1::ItsAOne => Some("ItsAOne"),
+            MyEnum1::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u16).into(),
+            name,
+        })
     }
 }
 
@@ -86,7 +94,15 @@ impl font_types::Scalar for MyEnum2 {
 #[cfg(feature = "traversal")]
 impl<'a> From<MyEnum2> for FieldType<'a> {
     fn from(src: MyEnum2) -> FieldType<'a> {
-        (src as u16).into()
+        let name = match src {
+            MyEnum2::ItsATwo => Some("ItsATwo"),
+            MyEnum2::ItsAThree => Some("ItsAThree"),
+            MyEnum2::Unknown => None,
+        };
+        FieldType::Enum(EnumValue {
+            value: (src as u16).into(),
+            name,
+        })
     }
 }
 
@@ -307,6 +307,9 @@ impl font_types::Scalar for ValueFormat {
 #[cfg(feature = "traversal")]
 impl<'a> From<ValueFormat> for FieldType<'a> {
     fn from(src: ValueFormat) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[("X_PLACEMENT", 0x0001), ("Y_PLACEMENT", 0x0002)],
+        })
     }
 }
@@ -108,7 +108,8 @@ impl<'a> KindsOfOffsets<'a> {
     /// Attempt to resolve [`nonnullable_offset`][Self::nonnullable_offset].
     pub fn nonnullable(&self) -> Result<Dummy<'a>, ReadError> {
         let data = self.data;
-        self.nonnullable_offset().resolve(data)
+        self.nonnullable_offset()
+            .resolve_field(data, FieldSite::new("KindsOfOffsets", "nonnullable_offset"))
     }
 
     /// An offset that is nullable, but always present
@@ -120,7 +121,8 @@ impl<'a> KindsOfOffsets<'a> {
     /// Attempt to resolve [`nullable_offset`][Self::nullable_offset].
     pub fn nullable(&self) -> Option<Result<Dummy<'a>, ReadError>> {
         let data = self.data;
-        self.nullable_offset().resolve(data)
+        self.nullable_offset()
+            .resolve_field(data, FieldSite::new("KindsOfOffsets", "nullable_offset"))
     }
 
     /// count of the array at array_offset
@@ -139,7 +141,11 @@ impl<'a> KindsOfOffsets<'a> {
     pub fn array(&self) -> Result<&'a [BigEndian<u16>], ReadError> {
         let data = self.data;
         let args = self.array_offset_count();
-        self.array_offset().resolve_with_args(data, &args)
+        self.array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("KindsOfOffsets", "array_offset"),
+        )
     }
 
     /// An offset to an array of records
@@ -152,7 +158,11 @@ impl<'a> KindsOfOffsets<'a> {
     pub fn record_array(&self) -> Result<&'a [Shmecord], ReadError> {
         let data = self.data;
         let args = self.array_offset_count();
-        self.record_array_offset().resolve_with_args(data, &args)
+        self.record_array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("KindsOfOffsets", "record_array_offset"),
+        )
     }
 
     /// A nullable, versioned offset to an array of records
@@ -167,8 +177,13 @@ impl<'a> KindsOfOffsets<'a> {
     pub fn versioned_nullable_record_array(&self) -> Option<Result<&'a [Shmecord], ReadError>> {
         let data = self.data;
         let args = self.array_offset_count();
-        self.versioned_nullable_record_array_offset()
-            .map(|x| x.resolve_with_args(data, &args))?
+        self.versioned_nullable_record_array_offset().map(|x| {
+            x.resolve_field_with_args(
+                data,
+                &args,
+                FieldSite::new("KindsOfOffsets", "versioned_nullable_record_array_offset"),
+            )
+        })?
     }
 
     /// A normal offset that is versioned
@@ -180,7 +195,12 @@ impl<'a> KindsOfOffsets<'a> {
     /// Attempt to resolve [`versioned_nonnullable_offset`][Self::versioned_nonnullable_offset].
     pub fn versioned_nonnullable(&self) -> Option<Result<Dummy<'a>, ReadError>> {
         let data = self.data;
-        self.versioned_nonnullable_offset().map(|x| x.resolve(data))
+        self.versioned_nonnullable_offset().map(|x| {
+            x.resolve_field(
+                data,
+                FieldSite::new("KindsOfOffsets", "versioned_nonnullable_offset"),
+            )
+        })
     }
 
     /// An offset that is nullable and versioned
@@ -192,7 +212,12 @@ impl<'a> KindsOfOffsets<'a> {
     /// Attempt to resolve [`versioned_nullable_offset`][Self::versioned_nullable_offset].
     pub fn versioned_nullable(&self) -> Option<Result<Dummy<'a>, ReadError>> {
         let data = self.data;
-        self.versioned_nullable_offset().map(|x| x.resolve(data))?
+        self.versioned_nullable_offset().map(|x| {
+            x.resolve_field(
+                data,
+                FieldSite::new("KindsOfOffsets", "versioned_nullable_offset"),
+            )
+        })?
     }
 }
 
@@ -231,7 +256,7 @@ impl<'a> SomeTable<'a> for KindsOfOffsets<'a> {
                 "versioned_nullable_record_array_offset",
                 traversal::FieldType::offset_to_array_of_records(
                     self.versioned_nullable_record_array_offset().unwrap(),
-                    self.versioned_nullable_record_array().unwrap(),
+                    self.versioned_nullable_record_array(),
                     stringify!(Shmecord),
                     self.offset_data(),
                 ),
@@ -240,14 +265,14 @@ impl<'a> SomeTable<'a> for KindsOfOffsets<'a> {
                 "versioned_nonnullable_offset",
                 FieldType::offset(
                     self.versioned_nonnullable_offset().unwrap(),
-                    self.versioned_nonnullable().unwrap(),
+                    self.versioned_nonnullable(),
                 ),
             )),
             8usize if version.compatible((1, 1)) => Some(Field::new(
                 "versioned_nullable_offset",
                 FieldType::offset(
                     self.versioned_nullable_offset().unwrap(),
-                    self.versioned_nullable().unwrap(),
+                    self.versioned_nullable(),
                 ),
             )),
             _ => None,
@@ -366,7 +391,13 @@ impl<'a> KindsOfArraysOfOffsets<'a> {
         let data = self.data;
         self.nonnullable_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("KindsOfArraysOfOffsets", "nonnullable_offsets").at(i),
+                )
+            })
     }
 
     /// An offset that is nullable, but always present
@@ -380,7 +411,13 @@ impl<'a> KindsOfArraysOfOffsets<'a> {
         let data = self.data;
         self.nullable_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("KindsOfArraysOfOffsets", "nullable_offsets").at(i),
+                )
+            })
     }
 
     /// A normal offset that is versioned
@@ -394,8 +431,14 @@ impl<'a> KindsOfArraysOfOffsets<'a> {
         &self,
     ) -> Option<impl Iterator<Item = Result<Dummy<'a>, ReadError>> + 'a> {
         let data = self.data;
-        self.versioned_nonnullable_offsets()
-            .map(|x| x.iter().map(move |off| off.get().resolve(data)))
+        self.versioned_nonnullable_offsets().map(|x| {
+            x.iter().enumerate().map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("KindsOfArraysOfOffsets", "versioned_nonnullable_offsets").at(i),
+                )
+            })
+        })
     }
 
     /// An offset that is nullable and versioned
@@ -409,8 +452,14 @@ impl<'a> KindsOfArraysOfOffsets<'a> {
         &self,
     ) -> Option<impl Iterator<Item = Option<Result<Dummy<'a>, ReadError>>> + 'a> {
         let data = self.data;
-        self.versioned_nullable_offsets()
-            .map(|x| x.iter().map(move |off| off.get().resolve(data)))
+        self.versioned_nullable_offsets().map(|x| {
+            x.iter().enumerate().map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("KindsOfArraysOfOffsets", "versioned_nullable_offsets").at(i),
+                )
+            })
+        })
     }
 }
 
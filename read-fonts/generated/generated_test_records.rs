@@ -254,7 +254,11 @@ impl ContainsOffests {
     /// Attempt to resolve [`array_offset`][Self::array_offset].
     pub fn array<'a>(&self, data: FontData<'a>) -> Result<&'a [SimpleRecord], ReadError> {
         let args = self.off_array_count();
-        self.array_offset().resolve_with_args(data, &args)
+        self.array_offset().resolve_field_with_args(
+            data,
+            &args,
+            FieldSite::new("ContainsOffests", "array_offset"),
+        )
     }
 
     pub fn other_offset(&self) -> Offset32 {
@@ -263,7 +267,8 @@ impl ContainsOffests {
 
     /// Attempt to resolve [`other_offset`][Self::other_offset].
     pub fn other<'a>(&self, data: FontData<'a>) -> Result<BasicTable<'a>, ReadError> {
-        self.other_offset().resolve(data)
+        self.other_offset()
+            .resolve_field(data, FieldSite::new("ContainsOffests", "other_offset"))
     }
 }
 
@@ -0,0 +1,90 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[cfg(feature = "traversal")]
+impl<'a> FontRef<'a> {
+    /// Returns the table with the given tag as a [`SomeTable`], or `None`
+    /// if the tag is not a generated top-level table.
+    ///
+    /// [`SomeTable`]: crate::traversal::SomeTable
+    pub(crate) fn generated_traversable_table(
+        &self,
+        tag: Tag,
+    ) -> Option<Result<Box<dyn crate::traversal::SomeTable<'a> + 'a>, ReadError>> {
+        fn boxed<'a, T: crate::traversal::SomeTable<'a> + 'a>(
+            table: Result<T, ReadError>,
+        ) -> Option<Result<Box<dyn crate::traversal::SomeTable<'a> + 'a>, ReadError>> {
+            Some(table.map(|table| Box::new(table) as _))
+        }
+        match tag {
+            crate::tables::avar::Avar::TAG => {
+                boxed(self.expect_table::<crate::tables::avar::Avar>())
+            }
+            crate::tables::base::Base::TAG => {
+                boxed(self.expect_table::<crate::tables::base::Base>())
+            }
+            crate::tables::cmap::Cmap::TAG => {
+                boxed(self.expect_table::<crate::tables::cmap::Cmap>())
+            }
+            crate::tables::colr::Colr::TAG => {
+                boxed(self.expect_table::<crate::tables::colr::Colr>())
+            }
+            crate::tables::cpal::Cpal::TAG => {
+                boxed(self.expect_table::<crate::tables::cpal::Cpal>())
+            }
+            crate::tables::fvar::Fvar::TAG => {
+                boxed(self.expect_table::<crate::tables::fvar::Fvar>())
+            }
+            crate::tables::gdef::Gdef::TAG => {
+                boxed(self.expect_table::<crate::tables::gdef::Gdef>())
+            }
+            crate::tables::glyf::Glyf::TAG => {
+                boxed(self.expect_table::<crate::tables::glyf::Glyf>())
+            }
+            crate::tables::gpos::Gpos::TAG => {
+                boxed(self.expect_table::<crate::tables::gpos::Gpos>())
+            }
+            crate::tables::gsub::Gsub::TAG => {
+                boxed(self.expect_table::<crate::tables::gsub::Gsub>())
+            }
+            crate::tables::gvar::Gvar::TAG => {
+                boxed(self.expect_table::<crate::tables::gvar::Gvar>())
+            }
+            crate::tables::head::Head::TAG => {
+                boxed(self.expect_table::<crate::tables::head::Head>())
+            }
+            crate::tables::hhea::Hhea::TAG => {
+                boxed(self.expect_table::<crate::tables::hhea::Hhea>())
+            }
+            crate::tables::hmtx::Hmtx::TAG => boxed(self.hmtx()),
+            crate::tables::hvar::Hvar::TAG => {
+                boxed(self.expect_table::<crate::tables::hvar::Hvar>())
+            }
+            crate::tables::maxp::Maxp::TAG => {
+                boxed(self.expect_table::<crate::tables::maxp::Maxp>())
+            }
+            crate::tables::mvar::Mvar::TAG => {
+                boxed(self.expect_table::<crate::tables::mvar::Mvar>())
+            }
+            crate::tables::name::Name::TAG => {
+                boxed(self.expect_table::<crate::tables::name::Name>())
+            }
+            crate::tables::os2::Os2::TAG => boxed(self.expect_table::<crate::tables::os2::Os2>()),
+            crate::tables::post::Post::TAG => {
+                boxed(self.expect_table::<crate::tables::post::Post>())
+            }
+            crate::tables::stat::Stat::TAG => {
+                boxed(self.expect_table::<crate::tables::stat::Stat>())
+            }
+            crate::tables::vhea::Vhea::TAG => {
+                boxed(self.expect_table::<crate::tables::vhea::Vhea>())
+            }
+            crate::tables::vmtx::Vmtx::TAG => boxed(self.vmtx()),
+            crate::tables::vvar::Vvar::TAG => {
+                boxed(self.expect_table::<crate::tables::vvar::Vvar>())
+            }
+            _ => None,
+        }
+    }
+}
@@ -420,6 +420,9 @@ impl<'a> SomeTable<'a> for DeltaSetIndexMap<'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.dyn_inner().get_field(idx)
     }
+    fn is_open_ended(&self) -> bool {
+        self.dyn_inner().is_open_ended()
+    }
 }
 
 /// Entry format for a [DeltaSetIndexMap].
@@ -727,7 +730,13 @@ impl font_types::Scalar for EntryFormat {
 #[cfg(feature = "traversal")]
 impl<'a> From<EntryFormat> for FieldType<'a> {
     fn from(src: EntryFormat) -> FieldType<'a> {
-        src.bits().into()
+        FieldType::Flags(FlagsValue {
+            bits: src.bits().into(),
+            flags: &[
+                ("INNER_INDEX_BIT_COUNT_MASK", 0x0F),
+                ("MAP_ENTRY_SIZE_MASK", 0x30),
+            ],
+        })
     }
 }
 
@@ -1002,7 +1011,10 @@ impl<'a> ItemVariationStore<'a> {
     /// Attempt to resolve [`variation_region_list_offset`][Self::variation_region_list_offset].
     pub fn variation_region_list(&self) -> Result<VariationRegionList<'a>, ReadError> {
         let data = self.data;
-        self.variation_region_list_offset().resolve(data)
+        self.variation_region_list_offset().resolve_field(
+            data,
+            FieldSite::new("ItemVariationStore", "variation_region_list_offset"),
+        )
     }
 
     /// The number of item variation data subtables.
@@ -1025,7 +1037,13 @@ impl<'a> ItemVariationStore<'a> {
         let data = self.data;
         self.item_variation_data_offsets()
             .iter()
-            .map(move |off| off.get().resolve(data))
+            .enumerate()
+            .map(move |(i, off)| {
+                off.get().resolve_field(
+                    data,
+                    FieldSite::new("ItemVariationStore", "item_variation_data_offsets").at(i),
+                )
+            })
     }
 }
 
@@ -1173,6 +1191,9 @@ impl<'a> SomeTable<'a> for ItemVariationData<'a> {
             _ => None,
         }
     }
+    fn is_open_ended(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "traversal")]
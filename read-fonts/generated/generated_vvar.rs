@@ -75,7 +75,8 @@ impl<'a> Vvar<'a> {
     /// Attempt to resolve [`item_variation_store_offset`][Self::item_variation_store_offset].
     pub fn item_variation_store(&self) -> Result<ItemVariationStore<'a>, ReadError> {
         let data = self.data;
-        self.item_variation_store_offset().resolve(data)
+        self.item_variation_store_offset()
+            .resolve_field(data, FieldSite::new("Vvar", "item_variation_store_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for advance heights (may be NULL).
@@ -87,7 +88,10 @@ impl<'a> Vvar<'a> {
     /// Attempt to resolve [`advance_height_mapping_offset`][Self::advance_height_mapping_offset].
     pub fn advance_height_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.advance_height_mapping_offset().resolve(data)
+        self.advance_height_mapping_offset().resolve_field(
+            data,
+            FieldSite::new("Vvar", "advance_height_mapping_offset"),
+        )
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for top side bearings (may be NULL).
@@ -99,7 +103,8 @@ impl<'a> Vvar<'a> {
     /// Attempt to resolve [`tsb_mapping_offset`][Self::tsb_mapping_offset].
     pub fn tsb_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.tsb_mapping_offset().resolve(data)
+        self.tsb_mapping_offset()
+            .resolve_field(data, FieldSite::new("Vvar", "tsb_mapping_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for bottom side bearings (may be NULL).
@@ -111,7 +116,8 @@ impl<'a> Vvar<'a> {
     /// Attempt to resolve [`bsb_mapping_offset`][Self::bsb_mapping_offset].
     pub fn bsb_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.bsb_mapping_offset().resolve(data)
+        self.bsb_mapping_offset()
+            .resolve_field(data, FieldSite::new("Vvar", "bsb_mapping_offset"))
     }
 
     /// Offset in bytes from the start of this table to the delta-set index mapping for Y coordinates of vertical origins (may be NULL).
@@ -123,7 +129,8 @@ impl<'a> Vvar<'a> {
     /// Attempt to resolve [`v_org_mapping_offset`][Self::v_org_mapping_offset].
     pub fn v_org_mapping(&self) -> Option<Result<DeltaSetIndexMap<'a>, ReadError>> {
         let data = self.data;
-        self.v_org_mapping_offset().resolve(data)
+        self.v_org_mapping_offset()
+            .resolve_field(data, FieldSite::new("Vvar", "v_org_mapping_offset"))
     }
 }
 
@@ -25,7 +25,8 @@ pub struct ComputedArray<'a, T: ReadArgs> {
 impl<'a, T: ComputeSize> ComputedArray<'a, T> {
     pub fn new(data: FontData<'a>, args: T::Args) -> Self {
         let item_len = T::compute_size(&args);
-        let len = data.len() / item_len;
+        // zero-sized items have no data to count
+        let len = data.len().checked_div(item_len).unwrap_or(0);
         ComputedArray {
             item_len,
             len,
@@ -82,6 +83,9 @@ where
     }
 
     pub fn get(&self, idx: usize) -> Result<T, ReadError> {
+        if idx >= self.len {
+            return Err(ReadError::OutOfBounds);
+        }
         let item_start = idx * self.item_len;
         self.data
             .split_off(item_start)
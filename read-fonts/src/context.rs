@@ -0,0 +1,466 @@
+//! Optional context for read errors.
+//!
+//! Accessors on generated tables return a bare [`ReadError`] when a subtable
+//! cannot be parsed, which says nothing about where in the font the problem
+//! is. To recover that, generated offset getters pass the name of the field
+//! they resolve down to offset resolution, and with the `error_context`
+//! feature enabled [`FontRef::with_error_context`] records each offset that
+//! is resolved while it runs. An error can then be traced back through the
+//! chain of fields that led to it.
+//!
+//! Nothing is recorded outside of `with_error_context`, and without the
+//! feature the hooks compile to nothing.
+//!
+//! [`FontRef::with_error_context`]: crate::FontRef::with_error_context
+
+#[cfg(any(test, feature = "error_context"))]
+pub(crate) use recorder::capture;
+#[cfg(any(test, feature = "error_context"))]
+pub use recorder::ReadErrorContext;
+
+use crate::{FontData, ReadError};
+
+/// The name of an offset field, and of the table or record containing it.
+///
+/// This is passed to offset resolution by generated code.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(any(test, feature = "error_context")), allow(dead_code))]
+pub struct FieldSite {
+    type_name: &'static str,
+    field: &'static str,
+    index: Option<usize>,
+}
+
+impl FieldSite {
+    pub const fn new(type_name: &'static str, field: &'static str) -> Self {
+        FieldSite {
+            type_name,
+            field,
+            index: None,
+        }
+    }
+
+    /// The same field, at the given position in an array of offsets.
+    pub const fn at(self, index: usize) -> Self {
+        FieldSite {
+            index: Some(index),
+            ..self
+        }
+    }
+}
+
+/// A single step in the path to an error.
+#[cfg(any(test, feature = "std"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathSegment {
+    /// The name of the table or record containing the field.
+    pub type_name: std::string::String,
+    /// The name of the field.
+    pub field: &'static str,
+    /// The position in the field, if the field is an array.
+    pub index: Option<usize>,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl std::fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.type_name, self.field)?;
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the result of resolving the offset at `site`.
+///
+/// `data` is the data the offset is relative to.
+#[inline(always)]
+pub(crate) fn record_offset<T>(
+    data: FontData,
+    offset: usize,
+    site: FieldSite,
+    result: &Result<T, ReadError>,
+) {
+    #[cfg(any(test, feature = "error_context"))]
+    recorder::record_offset(data, offset, site, result.as_ref().err());
+    #[cfg(not(any(test, feature = "error_context")))]
+    let _ = (data, offset, site, result);
+}
+
+/// Records the result of reading the top-level table with the given tag.
+#[inline(always)]
+pub(crate) fn record_table<T>(tag: types::Tag, data: FontData, result: &Result<T, ReadError>) {
+    #[cfg(any(test, feature = "error_context"))]
+    recorder::record_table(tag, data, result.as_ref().err());
+    #[cfg(not(any(test, feature = "error_context")))]
+    let _ = (tag, data, result);
+}
+
+#[cfg(any(test, feature = "error_context"))]
+mod recorder {
+    use std::{cell::RefCell, collections::HashMap, vec::Vec};
+
+    use types::Tag;
+
+    use super::{FieldSite, PathSegment};
+    use crate::{FontData, FontRef, ReadError};
+
+    // generous; real tables are rarely more than a handful of levels deep
+    const MAX_DEPTH: usize = 64;
+
+    std::thread_local! {
+        static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+    }
+
+    /// A [`ReadError`] along with the location in the font where it occurred.
+    ///
+    /// This is returned by [`FontRef::with_error_context`].
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ReadErrorContext {
+        /// The underlying error.
+        pub error: ReadError,
+        /// The tag of the table containing the error, if known.
+        pub table: Option<Tag>,
+        /// The chain of offset fields that were followed to reach the error,
+        /// starting at the top-level table.
+        ///
+        /// This is empty if the top-level table itself could not be read, or
+        /// if the error did not come from resolving an offset.
+        pub path: Vec<PathSegment>,
+        /// The position, from the start of the font data, of the table that
+        /// failed to parse, if known.
+        pub offset: Option<usize>,
+    }
+
+    /// How a table was reached.
+    #[derive(Clone, Copy)]
+    enum Origin {
+        Table(Tag),
+        // an offset field in the table or record at `parent`
+        Field { parent: usize, site: FieldSite },
+    }
+
+    struct Failure {
+        error: ReadError,
+        offset: usize,
+        origin: Origin,
+    }
+
+    struct Recorder {
+        // the address and length of the font data
+        start: usize,
+        len: usize,
+        // the first way each position was reached
+        origins: HashMap<usize, Origin>,
+        // the most recent failure
+        failure: Option<Failure>,
+    }
+
+    impl Recorder {
+        fn position(&self, data: FontData) -> Option<usize> {
+            let addr = data.as_bytes().as_ptr() as usize;
+            addr.checked_sub(self.start).filter(|pos| *pos <= self.len)
+        }
+
+        fn context(self, font: &FontRef, error: ReadError) -> ReadErrorContext {
+            let failure = match self.failure {
+                Some(failure) if failure.error == error => failure,
+                _ => {
+                    return ReadErrorContext {
+                        error,
+                        table: None,
+                        path: Vec::new(),
+                        offset: None,
+                    }
+                }
+            };
+            let mut path = Vec::new();
+            let mut origin = failure.origin;
+            let table = loop {
+                match origin {
+                    Origin::Table(tag) => break Some(tag),
+                    Origin::Field { parent, site } => {
+                        path.push(PathSegment {
+                            type_name: site.type_name.into(),
+                            field: site.field,
+                            index: site.index,
+                        });
+                        match self.origins.get(&parent) {
+                            Some(next) if path.len() < MAX_DEPTH => origin = *next,
+                            _ => break table_containing(font, parent),
+                        }
+                    }
+                }
+            };
+            path.reverse();
+            ReadErrorContext {
+                error,
+                table,
+                path,
+                offset: Some(failure.offset),
+            }
+        }
+    }
+
+    fn table_containing(font: &FontRef, pos: usize) -> Option<Tag> {
+        font.table_directory
+            .table_records()
+            .iter()
+            .find(|record| {
+                let start = record.offset() as usize;
+                start <= pos && pos < start.saturating_add(record.length() as usize)
+            })
+            .map(|record| record.tag())
+    }
+
+    // `origin` is given the position of `data`
+    fn record(
+        data: FontData,
+        offset: usize,
+        origin: impl FnOnce(usize) -> Origin,
+        error: Option<&ReadError>,
+    ) {
+        // this may be called while thread locals are being destroyed
+        let _ = RECORDER.try_with(|recorder| {
+            let mut recorder = recorder.borrow_mut();
+            let recorder = match recorder.as_mut() {
+                Some(recorder) => recorder,
+                None => return,
+            };
+            let (origin, pos) = match recorder.position(data) {
+                Some(pos) => (origin(pos), pos.saturating_add(offset)),
+                None => return,
+            };
+            match error {
+                None => {
+                    recorder.origins.entry(pos).or_insert(origin);
+                }
+                Some(ReadError::NullOffset) => (),
+                Some(error) => {
+                    recorder.failure = Some(Failure {
+                        error: error.clone(),
+                        offset: pos,
+                        origin,
+                    })
+                }
+            }
+        });
+    }
+
+    pub(super) fn record_offset(
+        data: FontData,
+        offset: usize,
+        site: FieldSite,
+        error: Option<&ReadError>,
+    ) {
+        let origin = |parent| Origin::Field { parent, site };
+        record(data, offset, origin, error);
+    }
+
+    pub(super) fn record_table(tag: Tag, data: FontData, error: Option<&ReadError>) {
+        record(data, 0, |_| Origin::Table(tag), error);
+    }
+
+    /// Restores the previous recorder when dropped, even if the closure
+    /// passed to [`capture`] panics.
+    struct Guard {
+        previous: Option<Recorder>,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let previous = self.previous.take();
+            let _ = RECORDER.try_with(|recorder| recorder.replace(previous));
+        }
+    }
+
+    impl Guard {
+        fn finish(mut self) -> Option<Recorder> {
+            let previous = self.previous.take();
+            RECORDER
+                .try_with(|recorder| recorder.replace(previous))
+                .ok()
+                .flatten()
+        }
+    }
+
+    pub(crate) fn capture<T>(
+        font: &FontRef,
+        f: impl FnOnce() -> Result<T, ReadError>,
+    ) -> Result<T, ReadErrorContext> {
+        let data = font.data.as_bytes();
+        let recorder = Recorder {
+            start: data.as_ptr() as usize,
+            len: data.len(),
+            origins: HashMap::new(),
+            failure: None,
+        };
+        let guard = Guard {
+            previous: RECORDER
+                .try_with(|cell| cell.replace(Some(recorder)))
+                .ok()
+                .flatten(),
+        };
+        let result = f();
+        let recorder = guard.finish();
+        result.map_err(|error| match recorder {
+            Some(recorder) => recorder.context(font, error),
+            None => ReadErrorContext {
+                error,
+                table: None,
+                path: Vec::new(),
+                offset: None,
+            },
+        })
+    }
+
+    impl std::fmt::Display for ReadErrorContext {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if let Some(table) = self.table {
+                write!(f, "'{table}'")?;
+            }
+            for (i, segment) in self.path.iter().enumerate() {
+                let sep = if i == 0 && self.table.is_some() {
+                    ": "
+                } else if i == 0 {
+                    ""
+                } else {
+                    " -> "
+                };
+                write!(f, "{sep}{segment}")?;
+            }
+            if let Some(offset) = self.offset {
+                write!(f, " (at byte {offset:#x})")?;
+            }
+            if self.table.is_some() || !self.path.is_empty() || self.offset.is_some() {
+                write!(f, ": ")?;
+            }
+            write!(f, "{}", self.error)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for ReadErrorContext {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::Tag;
+
+    use crate::{tables::gdef::Gdef, FontRef, ReadError, TableProvider, TopLevelTable};
+
+    fn gdef_offset(data: &[u8]) -> usize {
+        let font = FontRef::new(data).unwrap();
+        let record = font
+            .table_directory
+            .table_records()
+            .iter()
+            .find(|record| record.tag() == Gdef::TAG)
+            .unwrap();
+        record.offset() as usize
+    }
+
+    fn read_u16(data: &[u8], pos: usize) -> usize {
+        u16::from_be_bytes([data[pos], data[pos + 1]]) as usize
+    }
+
+    // reads the ligature caret list and each of its ligature glyphs
+    fn read_lig_glyphs(font: &FontRef) -> Result<usize, ReadError> {
+        let lig_caret_list = font.gdef()?.lig_caret_list().unwrap()?;
+        let mut count = 0;
+        for lig_glyph in lig_caret_list.lig_glyphs() {
+            lig_glyph?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    #[test]
+    fn no_errors() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        assert_eq!(font.with_error_context(read_lig_glyphs), Ok(3));
+    }
+
+    #[test]
+    fn bad_subtable_offset() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = gdef_offset(&data);
+        // ligCaretListOffset
+        data[gdef + 8..gdef + 10].copy_from_slice(&0xFFF0u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let context = font.with_error_context(read_lig_glyphs).unwrap_err();
+        assert_eq!(context.error, ReadError::OutOfBounds);
+        assert_eq!(context.table, Some(Gdef::TAG));
+        assert_eq!(context.offset, Some(gdef + 0xFFF0));
+        let path = context
+            .path
+            .iter()
+            .map(|seg| seg.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(path, ["Gdef.lig_caret_list_offset"]);
+    }
+
+    #[test]
+    fn bad_nested_offset() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = gdef_offset(&data);
+        let lig_caret_list = gdef + read_u16(&data, gdef + 8);
+        // second entry in ligGlyphOffsets
+        let pos = lig_caret_list + 6;
+        data[pos..pos + 2].copy_from_slice(&0xFFF0u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let context = font.with_error_context(read_lig_glyphs).unwrap_err();
+        assert_eq!(context.error, ReadError::OutOfBounds);
+        assert_eq!(context.offset, Some(lig_caret_list + 0xFFF0));
+        assert_eq!(
+            context.to_string(),
+            format!(
+                "'GDEF': Gdef.lig_caret_list_offset -> LigCaretList.lig_glyph_offsets[1] \
+                 (at byte {:#x}): An offset was out of bounds",
+                lig_caret_list + 0xFFF0
+            )
+        );
+        // without context, only the error is available
+        assert_eq!(read_lig_glyphs(&font), Err(ReadError::OutOfBounds));
+    }
+
+    #[test]
+    fn bad_top_level_table() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = gdef_offset(&data);
+        // shrink the table record's length so the header can't be read
+        let num_tables = read_u16(&data, 4);
+        let record = (0..num_tables)
+            .map(|i| 12 + i * 16)
+            .find(|pos| data[*pos..*pos + 4] == Gdef::TAG.to_be_bytes())
+            .unwrap();
+        data[record + 12..record + 16].copy_from_slice(&2u32.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let context = font.with_error_context(read_lig_glyphs).unwrap_err();
+        assert_eq!(context.error, ReadError::OutOfBounds);
+        assert_eq!(context.table, Some(Gdef::TAG));
+        assert!(context.path.is_empty());
+        assert_eq!(context.offset, Some(gdef));
+    }
+
+    #[test]
+    fn other_errors() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let context = font
+            .with_error_context(|font| {
+                read_lig_glyphs(font)?;
+                Err::<(), _>(ReadError::TableIsMissing(Tag::new(b"ABCD")))
+            })
+            .unwrap_err();
+        assert_eq!(context.table, None);
+        assert!(context.path.is_empty());
+        assert_eq!(context.offset, None);
+        assert_eq!(context.to_string(), "the ABCD table is missing");
+    }
+}
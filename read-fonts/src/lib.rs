@@ -12,9 +12,11 @@ extern crate std;
 extern crate core as std;
 
 pub mod array;
+mod context;
 mod font_data;
 mod offset;
 mod read;
+mod resource_fork;
 mod table_provider;
 mod table_ref;
 pub mod tables;
@@ -28,9 +30,14 @@ pub mod codegen_test;
 #[path = "tests/test_helpers.rs"]
 mod test_helpers;
 
+#[cfg(any(test, feature = "std"))]
+pub use context::PathSegment;
+#[cfg(any(test, feature = "error_context"))]
+pub use context::ReadErrorContext;
 pub use font_data::FontData;
 pub use offset::{Offset, ResolveNullableOffset, ResolveOffset};
 pub use read::{ComputeSize, FontRead, FontReadWithArgs, ReadArgs, ReadError, VarSize};
+pub use resource_fork::ResourceForkRef;
 pub use table_provider::{TableProvider, TopLevelTable};
 pub use table_ref::TableRef;
 
@@ -41,6 +48,7 @@ pub extern crate font_types as types;
 #[doc(hidden)]
 pub(crate) mod codegen_prelude {
     pub use crate::array::{ComputedArray, VarLenArray};
+    pub use crate::context::FieldSite;
     pub use crate::font_data::{Cursor, FontData};
     pub use crate::offset::{Offset, ResolveNullableOffset, ResolveOffset};
     pub use crate::read::{
@@ -53,7 +61,9 @@ pub(crate) mod codegen_prelude {
     pub use types::*;
 
     #[cfg(feature = "traversal")]
-    pub use crate::traversal::{self, Field, FieldType, RecordResolver, SomeRecord, SomeTable};
+    pub use crate::traversal::{
+        self, EnumValue, Field, FieldType, FlagsValue, RecordResolver, SomeRecord, SomeTable,
+    };
 
     // used in generated traversal code to get type names of offset fields, which
     // may include generics
@@ -86,33 +96,47 @@ pub(crate) mod codegen_prelude {
 }
 
 include!("../generated/font.rs");
+include!("../generated/generated_traversal_dispatch.rs");
 
 #[derive(Clone)]
 /// Reference to the content of a font or font collection file.
+#[non_exhaustive]
 pub enum FileRef<'a> {
     /// A single font.
     Font(FontRef<'a>),
     /// A collection of fonts.
     Collection(CollectionRef<'a>),
+    /// A Mac OS resource fork or `.dfont` file containing fonts.
+    ResourceFork(ResourceForkRef<'a>),
 }
 
 impl<'a> FileRef<'a> {
-    /// Creates a new reference to a file representing a font or font collection.
+    /// Creates a new reference to a file representing a font, font collection
+    /// or resource fork.
     pub fn new(data: &'a [u8]) -> Result<Self, ReadError> {
-        Ok(if let Ok(collection) = CollectionRef::new(data) {
-            Self::Collection(collection)
-        } else {
-            Self::Font(FontRef::new(data)?)
-        })
+        if let Ok(collection) = CollectionRef::new(data) {
+            return Ok(Self::Collection(collection));
+        }
+        match FontRef::new(data) {
+            Ok(font) => Ok(Self::Font(font)),
+            Err(err) => ResourceForkRef::new(data)
+                .map(Self::ResourceFork)
+                .map_err(|_| err),
+        }
     }
 
     /// Returns an iterator over the fonts contained in the file.
     pub fn fonts(&self) -> impl Iterator<Item = Result<FontRef<'a>, ReadError>> + 'a + Clone {
-        let (iter_one, iter_two) = match self {
-            Self::Font(font) => (Some(Ok(font.clone())), None),
-            Self::Collection(collection) => (None, Some(collection.iter())),
+        let (iter_one, iter_two, iter_three) = match self {
+            Self::Font(font) => (Some(Ok(font.clone())), None, None),
+            Self::Collection(collection) => (None, Some(collection.iter()), None),
+            Self::ResourceFork(fork) => (None, None, Some(fork.iter())),
         };
-        iter_two.into_iter().flatten().chain(iter_one)
+        iter_two
+            .into_iter()
+            .flatten()
+            .chain(iter_three.into_iter().flatten())
+            .chain(iter_one)
     }
 }
 
@@ -188,14 +212,14 @@ impl<'a> FontRef<'a> {
     /// Creates a new reference to an in-memory font at the specified index
     /// backed by the given data.
     ///
-    /// The data slice must begin with either a
-    /// [table directory](https://learn.microsoft.com/en-us/typography/opentype/spec/otff#table-directory)
-    /// or a [ttc header](https://learn.microsoft.com/en-us/typography/opentype/spec/otff#ttc-header)
-    /// to be considered valid.
+    /// The data slice must begin with a
+    /// [table directory](https://learn.microsoft.com/en-us/typography/opentype/spec/otff#table-directory),
+    /// a [ttc header](https://learn.microsoft.com/en-us/typography/opentype/spec/otff#ttc-header)
+    /// or a resource fork header to be considered valid.
     ///
-    /// In other words, this accepts either font collection (ttc) or single
-    /// font (ttf/otf) files. If a single font file is provided, the index
-    /// parameter must be 0.
+    /// In other words, this accepts font collection (ttc), single font
+    /// (ttf/otf) and resource fork (dfont) files. If a single font file is
+    /// provided, the index parameter must be 0.
     pub fn from_index(data: &'a [u8], index: u32) -> Result<Self, ReadError> {
         let file = FileRef::new(data)?;
         match file {
@@ -207,6 +231,7 @@ impl<'a> FontRef<'a> {
                 }
             }
             FileRef::Collection(collection) => collection.get(index),
+            FileRef::ResourceFork(fork) => fork.get(index),
         }
     }
 
@@ -224,6 +249,109 @@ impl<'a> FontRef<'a> {
             })
     }
 
+    /// Computes the checksum of the table with the specified tag.
+    ///
+    /// As required by the specification, the `checkSumAdjustment` field is
+    /// treated as zero when computing the checksum of the `head` table.
+    ///
+    /// Returns `None` if the table is not present.
+    pub fn compute_table_checksum(&self, tag: Tag) -> Option<u32> {
+        self.table_data(tag)
+            .map(|data| table_checksum(tag, data.as_bytes()))
+    }
+
+    /// Returns an iterator over the tags of tables whose data does not match
+    /// the checksum recorded in the table directory.
+    ///
+    /// This includes tables that extend past the end of the font data.
+    pub fn checksum_mismatches(&self) -> impl Iterator<Item = Tag> + 'a {
+        let font = self.clone();
+        self.table_directory
+            .table_records()
+            .iter()
+            .filter(move |record| {
+                font.compute_table_checksum(record.tag()) != Some(record.checksum())
+            })
+            .map(|record| record.tag())
+    }
+
+    /// Computes the expected value of the `checkSumAdjustment` field of the
+    /// `head` table.
+    ///
+    /// This is the value that makes the checksum of the entire font equal to
+    /// `0xB1B0AFBA`. For fonts in a collection, only the table directory and
+    /// tables of this font are included.
+    ///
+    /// Returns `None` if any table extends past the end of the font data.
+    pub fn compute_checksum_adjustment(&self) -> Option<u32> {
+        let records = self.table_directory.table_records();
+        let directory_len = TABLE_DIRECTORY_HEADER_LEN + records.len() * TableRecord::RAW_BYTE_LEN;
+        let directory = self.table_directory.offset_data().slice(..directory_len)?;
+        let mut sum = table_checksum(Tag::default(), directory.as_bytes());
+        for record in records {
+            sum = sum.wrapping_add(self.compute_table_checksum(record.tag())?);
+        }
+        Some(CHECKSUM_MAGIC.wrapping_sub(sum))
+    }
+
+    /// Returns `true` if the `checkSumAdjustment` field of the `head` table
+    /// matches the value computed for the font.
+    pub fn has_valid_checksum_adjustment(&self) -> bool {
+        let Ok(head) = self.head() else {
+            return false;
+        };
+        Some(head.checksum_adjustment()) == self.compute_checksum_adjustment()
+    }
+
+    /// Returns the table with the specified tag as a [`SomeTable`] for
+    /// generic traversal.
+    ///
+    /// This supports every table that implements [`TopLevelTable`], reading
+    /// any other tables required to parse it. An error is returned if the
+    /// table is not present or not supported.
+    ///
+    /// [`SomeTable`]: traversal::SomeTable
+    #[cfg(feature = "traversal")]
+    pub fn table_as_traversable(
+        &self,
+        tag: Tag,
+    ) -> Result<Box<dyn traversal::SomeTable<'a> + 'a>, ReadError> {
+        self.traversable_table(tag)
+            .unwrap_or(Err(ReadError::TableIsMissing(tag)))
+    }
+
+    /// Returns `None` if the tag is not supported, so that callers can
+    /// distinguish that case from a missing table.
+    #[cfg(feature = "traversal")]
+    pub(crate) fn traversable_table(
+        &self,
+        tag: Tag,
+    ) -> Option<Result<Box<dyn traversal::SomeTable<'a> + 'a>, ReadError>> {
+        match tag {
+            // loca is not generated, since reading it requires head
+            tables::loca::Loca::TAG => Some(self.loca(None).map(|table| Box::new(table) as _)),
+            _ => self.generated_traversable_table(tag),
+        }
+    }
+
+    /// Calls `f`, adding the location in the font to any error it returns.
+    ///
+    /// While `f` runs, the offsets resolved by generated table accessors are
+    /// recorded, so that an error from one of them can be traced back to the
+    /// top-level table and the chain of fields that led to it. Errors that
+    /// don't come from resolving an offset or reading a top-level table are
+    /// returned without a location.
+    ///
+    /// This requires the `error_context` feature. Outside of this method,
+    /// nothing is recorded.
+    #[cfg(any(test, feature = "error_context"))]
+    pub fn with_error_context<T>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, ReadError>,
+    ) -> Result<T, ReadErrorContext> {
+        context::capture(self, || f(self))
+    }
+
     fn with_table_directory(
         data: FontData<'a>,
         table_directory: TableDirectory<'a>,
@@ -239,8 +367,111 @@ impl<'a> FontRef<'a> {
     }
 }
 
+/// The size of the fixed portion of a table directory.
+const TABLE_DIRECTORY_HEADER_LEN: usize = 12;
+
+/// The expected checksum of an entire font file.
+///
+/// The `checkSumAdjustment` field in `head` is set to this value minus the
+/// checksum of the font.
+pub const CHECKSUM_MAGIC: u32 = 0xB1B0AFBA;
+
+/// Computes the checksum of some table data.
+fn table_checksum(tag: Tag, data: &[u8]) -> u32 {
+    let is_head = tag == tables::head::Head::TAG;
+    let mut sum = 0u32;
+    for (i, chunk) in data.chunks(4).enumerate() {
+        // checkSumAdjustment is the third u32 in head
+        if is_head && i == 2 {
+            continue;
+        }
+        let mut quad = [0u8; 4];
+        quad[..chunk.len()].copy_from_slice(chunk);
+        sum = sum.wrapping_add(u32::from_be_bytes(quad));
+    }
+    sum
+}
+
 impl<'a> TableProvider<'a> for FontRef<'a> {
     fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
         self.table_data(tag)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traversable_tables() {
+        for data in [
+            font_test_data::LAYOUT_FEATURES,
+            font_test_data::VAZIRMATN_VAR,
+        ] {
+            let font = FontRef::new(data).unwrap();
+            for record in font.table_directory.table_records() {
+                let tag = record.tag();
+                // there is no gasp table (yet)
+                if tag != Tag::new(b"gasp") {
+                    assert!(font.table_as_traversable(tag).is_ok(), "{tag}");
+                }
+            }
+        }
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let hmtx = font.table_as_traversable(Tag::new(b"hmtx")).unwrap();
+        assert_eq!(hmtx.type_name(), "Hmtx");
+        let loca = font.table_as_traversable(Tag::new(b"loca")).unwrap();
+        assert_eq!(loca.type_name(), "loca");
+        assert!(matches!(
+            font.table_as_traversable(Tag::new(b"DSIG")),
+            Err(ReadError::TableIsMissing(_))
+        ));
+    }
+
+    #[test]
+    fn table_checksums() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        assert_eq!(font.checksum_mismatches().count(), 0);
+        for record in font.table_directory.table_records() {
+            assert_eq!(
+                font.compute_table_checksum(record.tag()),
+                Some(record.checksum())
+            );
+        }
+        assert_eq!(font.compute_table_checksum(Tag::new(b"CFF ")), None);
+    }
+
+    #[test]
+    fn checksum_mismatches() {
+        let mut data = font_test_data::CMAP14_FONT1.to_vec();
+        let start = {
+            let font = FontRef::new(&data).unwrap();
+            let head = &font.table_directory.table_records()[2];
+            assert_eq!(head.tag(), Tag::new(b"head"));
+            head.offset() as usize
+        };
+        // checkSumAdjustment is excluded from the head checksum
+        data[start + 8..start + 12].copy_from_slice(&[1, 2, 3, 4]);
+        // ...but fontRevision is not
+        data[start + 4] ^= 1;
+        let font = FontRef::new(&data).unwrap();
+        assert_eq!(
+            font.checksum_mismatches().collect::<Vec<_>>(),
+            [Tag::new(b"head")]
+        );
+    }
+
+    #[test]
+    fn checksum_adjustment() {
+        let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
+        assert!(font.has_valid_checksum_adjustment());
+        assert_eq!(
+            font.compute_checksum_adjustment(),
+            Some(font.head().unwrap().checksum_adjustment())
+        );
+        // fonts assembled by hand in our test data don't set this
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        assert_eq!(font.head().unwrap().checksum_adjustment(), 0);
+        assert!(!font.has_valid_checksum_adjustment());
+    }
+}
@@ -1,7 +1,11 @@
 //! Handling offsets
 
 use super::read::{FontRead, ReadError};
-use crate::{font_data::FontData, read::FontReadWithArgs};
+use crate::{
+    context::{self, FieldSite},
+    font_data::FontData,
+    read::FontReadWithArgs,
+};
 use types::{Nullable, Offset16, Offset24, Offset32};
 
 /// Any offset type.
@@ -40,6 +44,31 @@ pub trait ResolveOffset {
         data: FontData<'a>,
         args: &T::Args,
     ) -> Result<T, ReadError>;
+
+    /// Resolve the offset, noting the field it was read from.
+    ///
+    /// This is used by generated code, to locate errors for
+    /// [`FontRef::with_error_context`](crate::FontRef::with_error_context).
+    #[doc(hidden)]
+    fn resolve_field<'a, T: FontRead<'a>>(
+        &self,
+        data: FontData<'a>,
+        site: FieldSite,
+    ) -> Result<T, ReadError> {
+        let _ = site;
+        self.resolve(data)
+    }
+
+    #[doc(hidden)]
+    fn resolve_field_with_args<'a, T: FontReadWithArgs<'a>>(
+        &self,
+        data: FontData<'a>,
+        args: &T::Args,
+        site: FieldSite,
+    ) -> Result<T, ReadError> {
+        let _ = site;
+        self.resolve_with_args(data, args)
+    }
 }
 
 pub trait ResolveNullableOffset {
@@ -50,15 +79,45 @@ pub trait ResolveNullableOffset {
         data: FontData<'a>,
         args: &T::Args,
     ) -> Option<Result<T, ReadError>>;
+
+    /// Resolve the offset, noting the field it was read from.
+    ///
+    /// This is used by generated code, to locate errors for
+    /// [`FontRef::with_error_context`](crate::FontRef::with_error_context).
+    #[doc(hidden)]
+    fn resolve_field<'a, T: FontRead<'a>>(
+        &self,
+        data: FontData<'a>,
+        site: FieldSite,
+    ) -> Option<Result<T, ReadError>> {
+        let _ = site;
+        self.resolve(data)
+    }
+
+    #[doc(hidden)]
+    fn resolve_field_with_args<'a, T: FontReadWithArgs<'a>>(
+        &self,
+        data: FontData<'a>,
+        args: &T::Args,
+        site: FieldSite,
+    ) -> Option<Result<T, ReadError>> {
+        let _ = site;
+        self.resolve_with_args(data, args)
+    }
+}
+
+// a null offset is not an error here
+fn nullable<T>(result: Result<T, ReadError>) -> Option<Result<T, ReadError>> {
+    match result {
+        Ok(thing) => Some(Ok(thing)),
+        Err(ReadError::NullOffset) => None,
+        Err(e) => Some(Err(e)),
+    }
 }
 
 impl<O: Offset> ResolveNullableOffset for Nullable<O> {
     fn resolve<'a, T: FontRead<'a>>(&self, data: FontData<'a>) -> Option<Result<T, ReadError>> {
-        match self.offset().resolve(data) {
-            Ok(thing) => Some(Ok(thing)),
-            Err(ReadError::NullOffset) => None,
-            Err(e) => Some(Err(e)),
-        }
+        nullable(self.offset().resolve(data))
     }
 
     fn resolve_with_args<'a, T: FontReadWithArgs<'a>>(
@@ -66,11 +125,24 @@ impl<O: Offset> ResolveNullableOffset for Nullable<O> {
         data: FontData<'a>,
         args: &T::Args,
     ) -> Option<Result<T, ReadError>> {
-        match self.offset().resolve_with_args(data, args) {
-            Ok(thing) => Some(Ok(thing)),
-            Err(ReadError::NullOffset) => None,
-            Err(e) => Some(Err(e)),
-        }
+        nullable(self.offset().resolve_with_args(data, args))
+    }
+
+    fn resolve_field<'a, T: FontRead<'a>>(
+        &self,
+        data: FontData<'a>,
+        site: FieldSite,
+    ) -> Option<Result<T, ReadError>> {
+        nullable(self.offset().resolve_field(data, site))
+    }
+
+    fn resolve_field_with_args<'a, T: FontReadWithArgs<'a>>(
+        &self,
+        data: FontData<'a>,
+        args: &T::Args,
+        site: FieldSite,
+    ) -> Option<Result<T, ReadError>> {
+        nullable(self.offset().resolve_field_with_args(data, args, site))
     }
 }
 
@@ -92,4 +164,25 @@ impl<O: Offset> ResolveOffset for O {
             .and_then(|off| data.split_off(off).ok_or(ReadError::OutOfBounds))
             .and_then(|data| T::read_with_args(data, args))
     }
+
+    fn resolve_field<'a, T: FontRead<'a>>(
+        &self,
+        data: FontData<'a>,
+        site: FieldSite,
+    ) -> Result<T, ReadError> {
+        let result = self.resolve(data);
+        context::record_offset(data, self.to_usize(), site, &result);
+        result
+    }
+
+    fn resolve_field_with_args<'a, T: FontReadWithArgs<'a>>(
+        &self,
+        data: FontData<'a>,
+        args: &T::Args,
+        site: FieldSite,
+    ) -> Result<T, ReadError> {
+        let result = self.resolve_with_args(data, args);
+        context::record_offset(data, self.to_usize(), site, &result);
+        result
+    }
 }
@@ -81,7 +81,7 @@ pub trait VarSize {
 }
 
 /// An error that occurs when reading font data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReadError {
     OutOfBounds,
     // i64 is flexible enough to store any value we might encounter
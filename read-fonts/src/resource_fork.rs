@@ -0,0 +1,326 @@
+//! Fonts stored in Mac OS resource forks.
+//!
+//! Classic Mac OS stored fonts as `sfnt` resources in the resource fork of a
+//! suitcase file. Mac OS X `.dfont` files have the same layout, stored in the
+//! data fork. The format is described in
+//! [Inside Macintosh: More Macintosh Toolbox](https://developer.apple.com/library/archive/documentation/mac/pdf/MoreMacintoshToolbox.pdf),
+//! chapter 1.
+
+use types::{Tag, Uint24};
+
+use crate::{tables::name::NameString, FontData, FontRead, FontRef, ReadError, TableDirectory};
+
+const SFNT_TYPE: Tag = Tag::new(b"sfnt");
+const FOND_TYPE: Tag = Tag::new(b"FOND");
+
+// sizes of the fixed portions of the structures below
+const HEADER_LEN: usize = 16;
+const MAP_HEADER_LEN: usize = 28;
+const TYPE_RECORD_LEN: usize = 8;
+const REFERENCE_LEN: usize = 12;
+// the font association table follows the fixed fields of a FOND resource
+const FOND_ASSOCIATIONS_OFFSET: usize = 52;
+const FONT_ASSOCIATION_LEN: usize = 6;
+// the offset used for resources without a name
+const NO_NAME: u16 = 0xFFFF;
+
+/// Reference to the content of a Mac OS resource fork.
+///
+/// This provides access to the `sfnt` resources in a resource fork or
+/// `.dfont` file, in the same way that [`CollectionRef`](crate::CollectionRef)
+/// provides access to the fonts in a collection.
+#[derive(Clone)]
+pub struct ResourceForkRef<'a> {
+    /// The resource data area.
+    resources: FontData<'a>,
+    /// The resource map.
+    map: FontData<'a>,
+    /// The reference list for `sfnt` resources.
+    sfnts: ReferenceList<'a>,
+    /// The reference list for `FOND` resources, if any.
+    fonds: Option<ReferenceList<'a>>,
+}
+
+/// The references to resources of a single type.
+#[derive(Clone, Copy)]
+struct ReferenceList<'a> {
+    data: FontData<'a>,
+    len: usize,
+}
+
+impl<'a> ResourceForkRef<'a> {
+    /// Creates a new reference to a resource fork.
+    ///
+    /// The resource fork must contain at least one `sfnt` resource.
+    pub fn new(data: &'a [u8]) -> Result<Self, ReadError> {
+        let data = FontData::new(data);
+        let data_offset: u32 = data.read_at(0)?;
+        let map_offset: u32 = data.read_at(4)?;
+        let data_len: u32 = data.read_at(8)?;
+        let map_len: u32 = data.read_at(12)?;
+        let resources = slice(data, data_offset, data_len)?;
+        let map = slice(data, map_offset, map_len)?;
+        if (data_offset as usize) < HEADER_LEN || map.len() < MAP_HEADER_LEN {
+            return Err(ReadError::MalformedData("invalid resource fork header"));
+        }
+        let type_list_offset: u16 = map.read_at(24)?;
+        let type_list = map
+            .split_off(type_list_offset as usize)
+            .ok_or(ReadError::OutOfBounds)?;
+        // stored as the number of types minus one
+        let num_types = type_list.read_at::<u16>(0)?.wrapping_add(1);
+        let mut sfnts = None;
+        let mut fonds = None;
+        for i in 0..num_types as usize {
+            let record = 2 + i * TYPE_RECORD_LEN;
+            let resource_type: Tag = type_list.read_at(record)?;
+            let list = ReferenceList {
+                data: type_list
+                    .split_off(type_list.read_at::<u16>(record + 6)? as usize)
+                    .ok_or(ReadError::OutOfBounds)?,
+                len: type_list.read_at::<u16>(record + 4)? as usize + 1,
+            };
+            if list.data.len() < list.len * REFERENCE_LEN {
+                return Err(ReadError::OutOfBounds);
+            }
+            match resource_type {
+                SFNT_TYPE => sfnts = Some(list),
+                FOND_TYPE => fonds = Some(list),
+                _ => (),
+            }
+        }
+        let sfnts = sfnts.ok_or(ReadError::MalformedData("no sfnt resources"))?;
+        Ok(Self {
+            resources,
+            map,
+            sfnts,
+            fonds,
+        })
+    }
+
+    /// Returns the number of fonts in the resource fork.
+    pub fn len(&self) -> u32 {
+        self.sfnts.len as u32
+    }
+
+    /// Returns true if the resource fork contains no fonts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the font at the specified index.
+    pub fn get(&self, index: u32) -> Result<FontRef<'a>, ReadError> {
+        let data = self.resource_data(self.sfnts, index)?;
+        FontRef::with_table_directory(data, TableDirectory::read(data)?)
+    }
+
+    /// Returns an iterator over the fonts in the resource fork.
+    pub fn iter(&self) -> impl Iterator<Item = Result<FontRef<'a>, ReadError>> + 'a + Clone {
+        let copy = self.clone();
+        (0..self.len()).map(move |ix| copy.get(ix))
+    }
+
+    /// Returns the resource ID of the font at the specified index.
+    pub fn resource_id(&self, index: u32) -> Option<i16> {
+        self.sfnts.reference(index).ok()?.read_at(0).ok()
+    }
+
+    /// Returns the name of the font family containing the font at the
+    /// specified index.
+    ///
+    /// This is the name of the `FOND` resource that lists the font in its
+    /// font association table, if there is one.
+    pub fn family_name(&self, index: u32) -> Option<NameString<'a>> {
+        let id = self.resource_id(index)?;
+        let fonds = self.fonds?;
+        (0..fonds.len as u32).find_map(|fond_index| {
+            let fond = self.resource_data(fonds, fond_index).ok()?;
+            // stored as the number of entries minus one
+            let count = fond
+                .read_at::<u16>(FOND_ASSOCIATIONS_OFFSET)
+                .ok()?
+                .wrapping_add(1);
+            let has_font = (0..count as usize).any(|i| {
+                let entry = FOND_ASSOCIATIONS_OFFSET + 2 + i * FONT_ASSOCIATION_LEN;
+                // a size of zero indicates an outline font
+                fond.read_at::<u16>(entry) == Ok(0) && fond.read_at::<i16>(entry + 4) == Ok(id)
+            });
+            has_font.then(|| self.resource_name(fonds, fond_index))?
+        })
+    }
+
+    fn resource_data(
+        &self,
+        list: ReferenceList<'a>,
+        index: u32,
+    ) -> Result<FontData<'a>, ReadError> {
+        let offset: u32 = list.reference(index)?.read_at::<Uint24>(5)?.into();
+        let len: u32 = self.resources.read_at(offset as usize)?;
+        slice(self.resources, offset + 4, len)
+    }
+
+    fn resource_name(&self, list: ReferenceList<'a>, index: u32) -> Option<NameString<'a>> {
+        let name_offset: u16 = list.reference(index).ok()?.read_at(2).ok()?;
+        if name_offset == NO_NAME {
+            return None;
+        }
+        let name_list_offset: u16 = self.map.read_at(26).ok()?;
+        let pos = name_list_offset as usize + name_offset as usize;
+        // names are stored as pascal strings
+        let len: u8 = self.map.read_at(pos).ok()?;
+        let name = self.map.slice(pos + 1..pos + 1 + len as usize)?;
+        Some(NameString::mac_roman(name.as_bytes()))
+    }
+}
+
+impl<'a> ReferenceList<'a> {
+    fn reference(&self, index: u32) -> Result<FontData<'a>, ReadError> {
+        let index = index as usize;
+        if index >= self.len {
+            return Err(ReadError::InvalidCollectionIndex(index as u32));
+        }
+        let start = index * REFERENCE_LEN;
+        self.data
+            .slice(start..start + REFERENCE_LEN)
+            .ok_or(ReadError::OutOfBounds)
+    }
+}
+
+fn slice(data: FontData, offset: u32, len: u32) -> Result<FontData, ReadError> {
+    let start = offset as usize;
+    let end = start
+        .checked_add(len as usize)
+        .ok_or(ReadError::OutOfBounds)?;
+    data.slice(start..end).ok_or(ReadError::OutOfBounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileRef, TableProvider};
+
+    const SFNT_IDS: [i16; 2] = [256, 257];
+
+    /// Builds a resource fork containing the given fonts, along with a
+    /// `FOND` resource named "Family" listing the first font.
+    fn make_resource_fork(fonts: &[&[u8]]) -> Vec<u8> {
+        // resource data
+        let mut resources = Vec::new();
+        let mut sfnt_offsets = Vec::new();
+        for font in fonts {
+            sfnt_offsets.push(resources.len() as u32);
+            resources.extend((font.len() as u32).to_be_bytes());
+            resources.extend(*font);
+        }
+        let fond_offset = resources.len() as u32;
+        let mut fond = vec![0u8; FOND_ASSOCIATIONS_OFFSET];
+        fond.extend(1u16.to_be_bytes()); // two entries
+        for (size, id) in [(12u16, 300i16), (0, SFNT_IDS[0])] {
+            fond.extend(size.to_be_bytes());
+            fond.extend(0u16.to_be_bytes());
+            fond.extend(id.to_be_bytes());
+        }
+        resources.extend((fond.len() as u32).to_be_bytes());
+        resources.extend(fond);
+
+        // resource map
+        let mut map = vec![0u8; 24];
+        let type_list_offset = MAP_HEADER_LEN as u16;
+        let num_types = 2;
+        let sfnt_refs_offset = 2 + num_types * TYPE_RECORD_LEN as u16;
+        let fond_refs_offset = sfnt_refs_offset + fonts.len() as u16 * REFERENCE_LEN as u16;
+        let name_list_offset = type_list_offset + fond_refs_offset + REFERENCE_LEN as u16;
+        map.extend(type_list_offset.to_be_bytes());
+        map.extend(name_list_offset.to_be_bytes());
+        map.extend((num_types - 1).to_be_bytes());
+        map.extend(b"sfnt");
+        map.extend((fonts.len() as u16 - 1).to_be_bytes());
+        map.extend(sfnt_refs_offset.to_be_bytes());
+        map.extend(b"FOND");
+        map.extend(0u16.to_be_bytes());
+        map.extend(fond_refs_offset.to_be_bytes());
+        let push_ref = |map: &mut Vec<u8>, id: i16, name: u16, offset: u32| {
+            map.extend(id.to_be_bytes());
+            map.extend(name.to_be_bytes());
+            map.extend(offset.to_be_bytes()); // attributes byte is zero
+            map.extend([0; 4]);
+        };
+        for (id, offset) in SFNT_IDS.iter().zip(&sfnt_offsets) {
+            push_ref(&mut map, *id, NO_NAME, *offset);
+        }
+        push_ref(&mut map, 128, 0, fond_offset);
+        map.push(6);
+        map.extend(b"Family");
+
+        let mut data = Vec::new();
+        let data_offset = 256u32;
+        let map_offset = data_offset + resources.len() as u32;
+        for val in [
+            data_offset,
+            map_offset,
+            resources.len() as u32,
+            map.len() as u32,
+        ] {
+            data.extend(val.to_be_bytes());
+        }
+        data.resize(data_offset as usize, 0);
+        data.extend(resources);
+        data.extend(map);
+        data
+    }
+
+    #[test]
+    fn read_fonts() {
+        let data = make_resource_fork(&[
+            font_test_data::LAYOUT_FEATURES,
+            font_test_data::CMAP14_FONT1,
+        ]);
+        let FileRef::ResourceFork(fork) = FileRef::new(&data).unwrap() else {
+            panic!("expected a resource fork");
+        };
+        assert_eq!(fork.len(), 2);
+        assert_eq!(fork.resource_id(1), Some(257));
+        let fonts = fork.iter().map(|font| font.unwrap()).collect::<Vec<_>>();
+        for (font, expected) in fonts.iter().zip([
+            font_test_data::LAYOUT_FEATURES,
+            font_test_data::CMAP14_FONT1,
+        ]) {
+            let expected = FontRef::new(expected).unwrap();
+            assert_eq!(
+                font.head().unwrap().checksum_adjustment(),
+                expected.head().unwrap().checksum_adjustment()
+            );
+            assert_eq!(font.checksum_mismatches().count(), 0);
+        }
+        assert!(matches!(
+            fork.get(2),
+            Err(ReadError::InvalidCollectionIndex(2))
+        ));
+        let from_index = FontRef::from_index(&data, 1).unwrap();
+        assert!(from_index.has_valid_checksum_adjustment());
+    }
+
+    #[test]
+    fn family_name() {
+        let data = make_resource_fork(&[
+            font_test_data::LAYOUT_FEATURES,
+            font_test_data::CMAP14_FONT1,
+        ]);
+        let fork = ResourceForkRef::new(&data).unwrap();
+        let name = fork.family_name(0).unwrap();
+        assert_eq!(name.chars().collect::<String>(), "Family");
+        assert!(fork.family_name(1).is_none());
+    }
+
+    #[test]
+    fn not_a_resource_fork() {
+        assert!(ResourceForkRef::new(font_test_data::LAYOUT_FEATURES).is_err());
+        // the error from parsing as a single font is preserved
+        let mut data = [0u8; 64];
+        data[..4].copy_from_slice(b"BBBB");
+        assert!(matches!(
+            FileRef::new(&data),
+            Err(ReadError::InvalidSfnt(0x42424242))
+        ));
+    }
+}
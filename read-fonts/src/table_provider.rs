@@ -2,7 +2,7 @@
 
 use types::Tag;
 
-use crate::{tables, FontData, FontRead, ReadError};
+use crate::{context, tables, FontData, FontRead, ReadError};
 
 /// A table that has an associated tag.
 ///
@@ -21,7 +21,10 @@ pub trait TableProvider<'a> {
     }
 
     fn expect_table<T: TopLevelTable + FontRead<'a>>(&self) -> Result<T, ReadError> {
-        self.expect_data_for_tag(T::TAG).and_then(FontRead::read)
+        let data = self.expect_data_for_tag(T::TAG)?;
+        let result = T::read(data);
+        context::record_table(T::TAG, data, &result);
+        result
     }
 
     fn head(&self) -> Result<tables::head::Head<'a>, ReadError> {
@@ -510,6 +510,17 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn cmap4_ignores_trailing_byte() {
+        let font = FontRef::new(font_test_data::SIMPLE_GLYF).unwrap();
+        let cmap4 = find_cmap4(&font.cmap().unwrap()).unwrap();
+        let mut data = cmap4.offset_data().as_bytes().to_vec();
+        assert_eq!(data.len() % 2, 0);
+        data.push(0xff);
+        let padded = Cmap4::read(FontData::new(&data)).unwrap();
+        assert_eq!(padded.glyph_id_array(), cmap4.glyph_id_array());
+    }
+
     #[test]
     fn cmap12_iter() {
         let font = FontRef::new(font_test_data::CMAP12_FONT1).unwrap();
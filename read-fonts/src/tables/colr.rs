@@ -698,3 +698,28 @@ impl ApplyDelta for UfWord {
         self.to_fixed() + Fixed::from_i32(delta)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FontRef;
+
+    #[test]
+    #[cfg(feature = "traversal")]
+    fn traverse_null_clip_list() {
+        let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+        let mut data = font.table_data(Colr::TAG).unwrap().as_ref().to_vec();
+        // clipListOffset
+        data[22..26].copy_from_slice(&[0; 4]);
+        let colr = Colr::read(FontData::new(&data)).unwrap();
+        assert!(colr.clip_list_offset().unwrap().is_null());
+        assert!(colr.clip_list().is_none());
+        let fields = (0..)
+            .map_while(|ix| colr.get_field(ix))
+            .map(|field| field.name)
+            .collect::<Vec<_>>();
+        assert!(fields.contains(&"clip_list_offset"));
+        // the debug impl walks the whole table
+        assert!(!format!("{colr:?}").is_empty());
+    }
+}
@@ -58,9 +58,10 @@ pub struct PointFlags(u8);
 impl PointFlags {
     // Note: OFF_CURVE_QUAD is signified by the absence of both ON_CURVE
     // and OFF_CURVE_CUBIC bits, per FreeType and TrueType convention.
+    // These match the ON_CURVE_POINT and CUBIC bits of SimpleGlyphFlags.
     const CURVE_MASK: u8 = 0x81;
     const ON_CURVE: u8 = 0x1;
-    const OFF_CURVE_CUBIC: u8 = 0x8;
+    const OFF_CURVE_CUBIC: u8 = 0x80;
 
     /// Creates a new on curve point flag.
     pub fn on_curve() -> Self {
@@ -208,8 +209,8 @@ impl<'a> SimpleGlyph<'a> {
             }
             y = y.wrapping_add(delta);
             point.y = y;
-            // Only keep the on-curve bit
-            point_flags.0 &= 1;
+            // Only keep the on-curve and cubic bits
+            *point_flags = PointFlags::from_bits(point_flags.0);
         }
         Ok(())
     }
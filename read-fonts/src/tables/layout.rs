@@ -5,6 +5,8 @@ mod lookupflag;
 
 pub use lookupflag::LookupFlag;
 
+use super::variations::{DeltaSetIndex, ItemVariationStore};
+
 #[cfg(test)]
 #[path = "../tests/layout.rs"]
 mod tests;
@@ -93,6 +95,66 @@ impl CoverageTable<'_> {
             .flatten()
             .chain(iter2.into_iter().flatten())
     }
+
+    /// If this glyph is in the coverage table, returns its index
+    pub fn get(&self, gid: GlyphId) -> Option<u16> {
+        match self {
+            CoverageTable::Format1(t) => t
+                .glyph_array()
+                .binary_search_by(|g| g.get().cmp(&gid))
+                .ok()
+                .map(|idx| idx as _),
+            CoverageTable::Format2(t) => {
+                let records = t.range_records();
+                let idx = records
+                    .binary_search_by(|rec| {
+                        if rec.end_glyph_id() < gid {
+                            std::cmp::Ordering::Less
+                        } else if rec.start_glyph_id() > gid {
+                            std::cmp::Ordering::Greater
+                        } else {
+                            std::cmp::Ordering::Equal
+                        }
+                    })
+                    .ok()?;
+                let rec = &records[idx];
+                gid.to_u16()
+                    .checked_sub(rec.start_glyph_id().to_u16())
+                    .and_then(|delta| rec.start_coverage_index().checked_add(delta))
+            }
+        }
+    }
+}
+
+impl ClassDef<'_> {
+    /// Returns the class of the given glyph.
+    ///
+    /// Glyphs that are not covered by the table belong to class 0.
+    pub fn get(&self, gid: GlyphId) -> u16 {
+        match self {
+            ClassDef::Format1(t) => gid
+                .to_u16()
+                .checked_sub(t.start_glyph_id().to_u16())
+                .and_then(|idx| t.class_value_array().get(idx as usize))
+                .map(|class| class.get())
+                .unwrap_or_default(),
+            ClassDef::Format2(t) => {
+                let records = t.class_range_records();
+                records
+                    .binary_search_by(|rec| {
+                        if rec.end_glyph_id() < gid {
+                            std::cmp::Ordering::Less
+                        } else if rec.start_glyph_id() > gid {
+                            std::cmp::Ordering::Greater
+                        } else {
+                            std::cmp::Ordering::Equal
+                        }
+                    })
+                    .map(|idx| records[idx].class())
+                    .unwrap_or_default()
+            }
+        }
+    }
 }
 
 impl RangeRecord {
@@ -101,6 +163,89 @@ impl RangeRecord {
     }
 }
 
+impl Device<'_> {
+    /// Returns the adjustment, in pixels, to apply at the given size in ppem.
+    ///
+    /// Returns zero if the size is outside the range covered by the table
+    /// or if the table is a [`VariationIndex`] table.
+    pub fn pixel_delta(&self, ppem: u16) -> i32 {
+        let bits_per_value = match self.delta_format() {
+            DeltaFormat::Local2BitDeltas => 2,
+            DeltaFormat::Local4BitDeltas => 4,
+            DeltaFormat::Local8BitDeltas => 8,
+            _ => return 0,
+        };
+        if ppem < self.start_size() || ppem > self.end_size() {
+            return 0;
+        }
+        let values_per_word = 16 / bits_per_value;
+        let index = (ppem - self.start_size()) as usize;
+        let Some(word) = self.delta_value().get(index / values_per_word) else {
+            return 0;
+        };
+        let shift = 16 - bits_per_value * (index % values_per_word + 1);
+        // shift the value into the high bits and back down to sign extend
+        let value = (word.get() >> shift) << (16 - bits_per_value);
+        (value as i16 >> (16 - bits_per_value)) as i32
+    }
+
+    /// Returns the delta-set index if this is a [`VariationIndex`] table.
+    ///
+    /// A VariationIndex table shares the layout of a device table, with the
+    /// outer and inner indices in place of the size range.
+    pub fn variation_index(&self) -> Option<DeltaSetIndex> {
+        (self.delta_format() == DeltaFormat::VariationIndex).then(|| DeltaSetIndex {
+            outer: self.start_size(),
+            inner: self.end_size(),
+        })
+    }
+
+    /// Resolves the adjustment described by this table at the given size
+    /// and location.
+    ///
+    /// Device tables produce an adjustment in pixels when a size is given.
+    /// VariationIndex tables produce an adjustment in design units that is
+    /// computed from the item variation store for the given normalized
+    /// coordinates.
+    pub fn resolve(
+        &self,
+        ppem: Option<u16>,
+        coords: &[F2Dot14],
+        var_store: Option<&ItemVariationStore>,
+    ) -> Result<DeviceDelta, ReadError> {
+        match self.variation_index() {
+            Some(index) => Ok(DeviceDelta {
+                units: match var_store {
+                    Some(var_store) => var_store.compute_delta(index, coords)?,
+                    None => 0,
+                },
+                pixels: 0,
+            }),
+            None => Ok(DeviceDelta {
+                units: 0,
+                pixels: ppem.map(|ppem| self.pixel_delta(ppem)).unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+/// Adjustment produced by a [`Device`] or [`VariationIndex`] table.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct DeviceDelta {
+    /// Adjustment in design units.
+    pub units: i32,
+    /// Adjustment in pixels.
+    pub pixels: i32,
+}
+
+impl DeviceDelta {
+    /// Applies this adjustment to a value in design units and returns the
+    /// result at the given scale factor.
+    pub fn apply(self, value: i32, scale: f32) -> f32 {
+        (value + self.units) as f32 * scale + self.pixels as f32
+    }
+}
+
 impl DeltaFormat {
     pub(crate) fn value_count(self, start_size: u16, end_size: u16) -> usize {
         let range_len = end_size.saturating_add(1).saturating_sub(start_size) as usize;
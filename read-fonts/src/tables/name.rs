@@ -2,6 +2,10 @@
 
 include!("../../generated/generated_name.rs");
 
+mod legacy_encoding;
+
+use legacy_encoding::{DoubleByteTable, SingleByteTable};
+
 pub use types::NameId;
 
 impl<'a> Name<'a> {
@@ -56,6 +60,14 @@ pub struct NameString<'a> {
 }
 
 impl<'a> NameString<'a> {
+    /// Creates a name from Mac OS Roman encoded data.
+    pub(crate) fn mac_roman(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            encoding: Encoding::MacRoman,
+        }
+    }
+
     /// An iterator over the `char`s in this name.
     pub fn chars(&self) -> CharIter<'a> {
         CharIter {
@@ -139,6 +151,44 @@ impl CharIter<'_> {
         self.pos += 1;
         Some(*result)
     }
+
+    /// Decodes a character from a stream of bytes in a double byte encoding.
+    fn next_double_byte(&mut self, table: DoubleByteTable) -> Option<char> {
+        let lead = self.bump_u8()?;
+        if !table.is_lead(lead) {
+            return Some(table.decode_single(lead));
+        }
+        match self.data.get(self.pos) {
+            Some(trail) if table.is_trail(*trail) => {
+                self.pos += 1;
+                Some(table.decode_double(lead, *trail))
+            }
+            _ => Some(core::char::REPLACEMENT_CHARACTER),
+        }
+    }
+
+    /// Decodes a character from a string in a double byte encoding as stored
+    /// in the Windows platform.
+    ///
+    /// Each code is stored as a big-endian 16-bit value, so single byte codes
+    /// have a high byte of zero. Some fonts split two byte codes across a pair
+    /// of 16-bit values, which we also accept.
+    fn next_packed_double_byte(&mut self, table: DoubleByteTable) -> Option<char> {
+        let [hi, lo] = self.bump_u16()?.to_be_bytes();
+        if hi != 0 {
+            return Some(table.decode_double(hi, lo));
+        }
+        if !table.is_lead(lo) {
+            return Some(table.decode_single(lo));
+        }
+        match self.data.get(self.pos..self.pos + 2) {
+            Some(&[0, trail]) if table.is_trail(trail) => {
+                self.pos += 2;
+                Some(table.decode_double(lo, trail))
+            }
+            _ => Some(core::char::REPLACEMENT_CHARACTER),
+        }
+    }
 }
 
 impl<'a> Iterator for CharIter<'a> {
@@ -165,17 +215,61 @@ impl<'a> Iterator for CharIter<'a> {
                 let c = self.bump_u8()?;
                 MacRomanMapping.decode(c) as u32
             }
-            _ => return None,
+            Encoding::MacArabic
+            | Encoding::MacGreek
+            | Encoding::MacCyrillic
+            | Encoding::MacCentralEuropean => {
+                let table = self.encoding.single_byte_table()?;
+                return Some(table.decode(self.bump_u8()?));
+            }
+            Encoding::MacJapanese
+            | Encoding::MacChineseTraditional
+            | Encoding::MacKorean
+            | Encoding::MacChineseSimplified => {
+                let table = self.encoding.double_byte_table()?;
+                return self.next_double_byte(table);
+            }
+            Encoding::ShiftJis | Encoding::Gbk | Encoding::Big5 | Encoding::Wansung => {
+                let table = self.encoding.double_byte_table()?;
+                return self.next_packed_double_byte(table);
+            }
+            Encoding::Unknown => return None,
         };
         Some(std::char::from_u32(raw_c).unwrap_or(rep))
     }
 }
 
 /// The encoding used by the name table.
-#[derive(Copy, Clone, PartialEq, Eq)]
+///
+/// Decoding the double byte CJK encodings requires the
+/// `legacy_cjk_encodings` feature. Without it, only the ASCII subset of
+/// these encodings is decoded and all other characters are replaced with
+/// [`char::REPLACEMENT_CHARACTER`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Encoding {
     Utf16Be,
     MacRoman,
+    /// Mac Japanese, decoded as Windows code page 932.
+    MacJapanese,
+    /// Mac Traditional Chinese, decoded as Windows code page 950.
+    MacChineseTraditional,
+    /// Mac Korean, decoded as Windows code page 949.
+    MacKorean,
+    MacArabic,
+    MacGreek,
+    MacCyrillic,
+    /// Mac Simplified Chinese, decoded as Windows code page 936.
+    MacChineseSimplified,
+    MacCentralEuropean,
+    /// Windows Shift-JIS (code page 932).
+    ShiftJis,
+    /// Windows PRC (GB2312/GBK, code page 936).
+    Gbk,
+    /// Windows Big5 (code page 950).
+    Big5,
+    /// Windows Wansung (code page 949).
+    Wansung,
     Unknown,
 }
 
@@ -185,12 +279,44 @@ impl Encoding {
         match (platform_id, encoding_id) {
             (0, _) => Encoding::Utf16Be,
             (1, 0) => Encoding::MacRoman,
+            (1, 1) => Encoding::MacJapanese,
+            (1, 2) => Encoding::MacChineseTraditional,
+            (1, 3) => Encoding::MacKorean,
+            (1, 4) => Encoding::MacArabic,
+            (1, 6) => Encoding::MacGreek,
+            (1, 7) => Encoding::MacCyrillic,
+            (1, 25) => Encoding::MacChineseSimplified,
+            (1, 29) => Encoding::MacCentralEuropean,
             (3, 0) => Encoding::Utf16Be,
             (3, 1) => Encoding::Utf16Be,
+            (3, 2) => Encoding::ShiftJis,
+            (3, 3) => Encoding::Gbk,
+            (3, 4) => Encoding::Big5,
+            (3, 5) => Encoding::Wansung,
             (3, 10) => Encoding::Utf16Be,
             _ => Encoding::Unknown,
         }
     }
+
+    fn single_byte_table(self) -> Option<SingleByteTable> {
+        match self {
+            Encoding::MacArabic => Some(legacy_encoding::MAC_ARABIC),
+            Encoding::MacGreek => Some(legacy_encoding::MAC_GREEK),
+            Encoding::MacCyrillic => Some(legacy_encoding::MAC_CYRILLIC),
+            Encoding::MacCentralEuropean => Some(legacy_encoding::MAC_CENTRAL_EUROPEAN),
+            _ => None,
+        }
+    }
+
+    fn double_byte_table(self) -> Option<DoubleByteTable> {
+        match self {
+            Encoding::MacJapanese | Encoding::ShiftJis => Some(legacy_encoding::SHIFT_JIS),
+            Encoding::MacChineseTraditional | Encoding::Big5 => Some(legacy_encoding::BIG5),
+            Encoding::MacKorean | Encoding::Wansung => Some(legacy_encoding::WANSUNG),
+            Encoding::MacChineseSimplified | Encoding::Gbk => Some(legacy_encoding::GBK),
+            _ => None,
+        }
+    }
 }
 
 /// A helper for encoding and decoding Mac OS Roman encoded strings.
@@ -299,4 +425,66 @@ mod tests {
         };
         assert!(chars.eq(['ऄ', std::char::REPLACEMENT_CHARACTER].into_iter()))
     }
+
+    fn decode(data: &[u8], platform_id: u16, encoding_id: u16) -> String {
+        NameString {
+            data,
+            encoding: Encoding::new(platform_id, encoding_id),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn mac_script_encodings() {
+        // "Шрифт" in Mac Cyrillic
+        assert_eq!(decode(&[0x98, 0xF0, 0xE8, 0xF4, 0xF2], 1, 7), "Шрифт");
+        // "Γραμμα" in Mac Greek
+        assert_eq!(
+            decode(&[0xA1, 0xF2, 0xE1, 0xED, 0xED, 0xE1], 1, 6),
+            "Γραμμα"
+        );
+        // "Łódź" in Mac Central European
+        assert_eq!(decode(&[0xFC, 0x97, 0x64, 0x90], 1, 29), "Łódź");
+    }
+
+    #[test]
+    fn packed_ascii_in_double_byte_encoding() {
+        let data = [0x00, b'M', 0x00, b'S', 0x00, b' ', 0x00, b'G'];
+        assert_eq!(decode(&data, 3, 2), "MS G");
+    }
+
+    #[test]
+    #[cfg(feature = "legacy_cjk_encodings")]
+    fn mac_double_byte_encodings() {
+        // "ＭＳ 明朝" in Shift-JIS
+        let data = [0x82, 0x6C, 0x82, 0x72, 0x20, 0x96, 0xBE, 0x92, 0xA9];
+        assert_eq!(decode(&data, 1, 1), "ＭＳ 明朝");
+        // "新細明體" in Big5
+        let data = [0xB7, 0x73, 0xB2, 0xD3, 0xA9, 0xFA, 0xC5, 0xE9];
+        assert_eq!(decode(&data, 1, 2), "新細明體");
+        // "굴림" in Wansung
+        assert_eq!(decode(&[0xB1, 0xBC, 0xB8, 0xB2], 1, 3), "굴림");
+        // "宋体" in GB2312
+        assert_eq!(decode(&[0xCB, 0xCE, 0xCC, 0xE5], 1, 25), "宋体");
+        // truncated two byte sequence
+        assert_eq!(decode(&[b'a', 0xCB], 1, 25), "a\u{FFFD}");
+    }
+
+    #[test]
+    #[cfg(feature = "legacy_cjk_encodings")]
+    fn windows_double_byte_encodings() {
+        // "ＭＳ 明朝" in Shift-JIS, stored as 16-bit values
+        let data = [0x82, 0x6C, 0x82, 0x72, 0x00, 0x20, 0x96, 0xBE, 0x92, 0xA9];
+        assert_eq!(decode(&data, 3, 2), "ＭＳ 明朝");
+        // half-width katakana "ｱ" is a single byte code
+        assert_eq!(decode(&[0x00, 0xB1], 3, 2), "ｱ");
+        // "宋体" in GBK, with the lead and trail bytes split across values
+        let data = [0x00, 0xCB, 0x00, 0xCE, 0xCC, 0xE5];
+        assert_eq!(decode(&data, 3, 3), "宋体");
+        // "新細明體" in Big5
+        let data = [0xB7, 0x73, 0xB2, 0xD3, 0xA9, 0xFA, 0xC5, 0xE9];
+        assert_eq!(decode(&data, 3, 4), "新細明體");
+        // "굴림" in Wansung
+        assert_eq!(decode(&[0xB1, 0xBC, 0xB8, 0xB2], 3, 5), "굴림");
+    }
 }
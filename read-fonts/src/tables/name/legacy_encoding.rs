@@ -0,0 +1,188 @@
+//! Decoding of legacy (non-Unicode) name string encodings.
+//!
+//! The single byte Mac script encodings are small and always available. The
+//! double byte CJK encodings require large tables and are only available
+//! with the `legacy_cjk_encodings` feature; without it, non-ASCII characters
+//! in these encodings decode to the replacement character.
+
+/// A single byte encoding where the codes 0x00..=0x7F map to ASCII.
+#[derive(Copy, Clone)]
+pub(super) struct SingleByteTable(&'static [u16; 128]);
+
+impl SingleByteTable {
+    pub fn decode(self, raw: u8) -> char {
+        if raw < 0x80 {
+            raw as char
+        } else {
+            char::from_u32(self.0[raw as usize - 0x80] as u32)
+                .unwrap_or(core::char::REPLACEMENT_CHARACTER)
+        }
+    }
+}
+
+/// A double byte encoding where the codes 0x00..=0x7F map to ASCII.
+///
+/// The table data is generated by `resources/scripts/gen_name_encodings.py`
+/// and consists of big-endian unicode values for the single byte codes
+/// 0x80..=0xFF, followed by a grid covering every pair of lead and trail
+/// bytes. A value of zero indicates an unmapped code.
+#[derive(Copy, Clone)]
+pub(super) struct DoubleByteTable {
+    data: &'static [u8],
+    lead_bytes: &'static [(u8, u8)],
+    first_lead: u8,
+    first_trail: u8,
+    last_trail: u8,
+}
+
+impl DoubleByteTable {
+    const SINGLE_BYTE_LEN: usize = 128;
+
+    /// Returns true if the given byte begins a two byte sequence.
+    pub fn is_lead(self, byte: u8) -> bool {
+        self.lead_bytes
+            .iter()
+            .any(|(first, last)| (*first..=*last).contains(&byte))
+    }
+
+    /// Returns true if the given byte can end a two byte sequence.
+    pub fn is_trail(self, byte: u8) -> bool {
+        (self.first_trail..=self.last_trail).contains(&byte)
+    }
+
+    pub fn decode_single(self, raw: u8) -> char {
+        if raw < 0x80 {
+            raw as char
+        } else {
+            self.get(raw as usize - 0x80)
+        }
+    }
+
+    pub fn decode_double(self, lead: u8, trail: u8) -> char {
+        if lead < self.first_lead || !self.is_trail(trail) {
+            return core::char::REPLACEMENT_CHARACTER;
+        }
+        let trail_count = (self.last_trail - self.first_trail) as usize + 1;
+        let index =
+            (lead - self.first_lead) as usize * trail_count + (trail - self.first_trail) as usize;
+        self.get(Self::SINGLE_BYTE_LEN + index)
+    }
+
+    fn get(self, index: usize) -> char {
+        self.data
+            .get(index * 2..index * 2 + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .filter(|value| *value != 0)
+            .and_then(|value| char::from_u32(value as u32))
+            .unwrap_or(core::char::REPLACEMENT_CHARACTER)
+    }
+}
+
+macro_rules! double_byte_data {
+    ($file:literal) => {{
+        #[cfg(feature = "legacy_cjk_encodings")]
+        {
+            include_bytes!($file)
+        }
+        #[cfg(not(feature = "legacy_cjk_encodings"))]
+        {
+            &[]
+        }
+    }};
+}
+
+/// Windows code page 932, used for Shift-JIS and Mac Japanese names.
+pub(super) static SHIFT_JIS: DoubleByteTable = DoubleByteTable {
+    data: double_byte_data!("shift_jis.bin"),
+    lead_bytes: &[(0x81, 0x9F), (0xE0, 0xFC)],
+    first_lead: 0x81,
+    first_trail: 0x40,
+    last_trail: 0xFC,
+};
+
+/// Windows code page 950, used for Big5 and Mac Traditional Chinese names.
+pub(super) static BIG5: DoubleByteTable = DoubleByteTable {
+    data: double_byte_data!("big5.bin"),
+    lead_bytes: &[(0x81, 0xFE)],
+    first_lead: 0x81,
+    first_trail: 0x40,
+    last_trail: 0xFE,
+};
+
+/// Windows code page 936, used for PRC (GB2312/GBK) and Mac Simplified
+/// Chinese names.
+pub(super) static GBK: DoubleByteTable = DoubleByteTable {
+    data: double_byte_data!("gbk.bin"),
+    lead_bytes: &[(0x81, 0xFE)],
+    first_lead: 0x81,
+    first_trail: 0x40,
+    last_trail: 0xFE,
+};
+
+/// Windows code page 949, used for Wansung and Mac Korean names.
+pub(super) static WANSUNG: DoubleByteTable = DoubleByteTable {
+    data: double_byte_data!("wansung.bin"),
+    lead_bytes: &[(0x81, 0xFE)],
+    first_lead: 0x81,
+    first_trail: 0x41,
+    last_trail: 0xFE,
+};
+
+/// Maps the values 128..=255 of the Mac OS Arabic encoding to unicode.
+#[rustfmt::skip]
+pub(super) static MAC_ARABIC: SingleByteTable = SingleByteTable(&[
+    196, 160, 199, 201, 209, 214, 220, 225, 224, 226, 228, 1722, 171, 231, 233,
+    232, 234, 235, 237, 8230, 238, 239, 241, 243, 187, 244, 246, 247, 250, 249,
+    251, 252, 32, 33, 34, 35, 36, 1642, 38, 39, 40, 41, 42, 43, 1548, 45, 46,
+    47, 1632, 1633, 1634, 1635, 1636, 1637, 1638, 1639, 1640, 1641, 58, 1563,
+    60, 61, 62, 1567, 10058, 1569, 1570, 1571, 1572, 1573, 1574, 1575, 1576,
+    1577, 1578, 1579, 1580, 1581, 1582, 1583, 1584, 1585, 1586, 1587, 1588,
+    1589, 1590, 1591, 1592, 1593, 1594, 91, 92, 93, 94, 95, 1600, 1601, 1602,
+    1603, 1604, 1605, 1606, 1607, 1608, 1609, 1610, 1611, 1612, 1613, 1614,
+    1615, 1616, 1617, 1618, 1662, 1657, 1670, 1749, 1700, 1711, 1672, 1681,
+    123, 124, 125, 1688, 1746,
+]);
+
+/// Maps the values 128..=255 of the Mac OS Greek encoding to unicode.
+#[rustfmt::skip]
+pub(super) static MAC_GREEK: SingleByteTable = SingleByteTable(&[
+    196, 185, 178, 201, 179, 214, 220, 901, 224, 226, 228, 900, 168, 231, 233,
+    232, 234, 235, 163, 8482, 238, 239, 8226, 189, 8240, 244, 246, 166, 8364,
+    249, 251, 252, 8224, 915, 916, 920, 923, 926, 928, 223, 174, 169, 931, 938,
+    167, 8800, 176, 183, 913, 177, 8804, 8805, 165, 914, 917, 918, 919, 921,
+    922, 924, 934, 939, 936, 937, 940, 925, 172, 927, 929, 8776, 932, 171, 187,
+    8230, 160, 933, 935, 902, 904, 339, 8211, 8213, 8220, 8221, 8216, 8217,
+    247, 905, 906, 908, 910, 941, 942, 943, 972, 911, 973, 945, 946, 968, 948,
+    949, 966, 947, 951, 953, 958, 954, 955, 956, 957, 959, 960, 974, 961, 963,
+    964, 952, 969, 962, 967, 965, 950, 970, 971, 912, 944, 173,
+]);
+
+/// Maps the values 128..=255 of the Mac OS Cyrillic encoding to unicode.
+#[rustfmt::skip]
+pub(super) static MAC_CYRILLIC: SingleByteTable = SingleByteTable(&[
+    1040, 1041, 1042, 1043, 1044, 1045, 1046, 1047, 1048, 1049, 1050, 1051,
+    1052, 1053, 1054, 1055, 1056, 1057, 1058, 1059, 1060, 1061, 1062, 1063,
+    1064, 1065, 1066, 1067, 1068, 1069, 1070, 1071, 8224, 176, 1168, 163, 167,
+    8226, 182, 1030, 174, 169, 8482, 1026, 1106, 8800, 1027, 1107, 8734, 177,
+    8804, 8805, 1110, 181, 1169, 1032, 1028, 1108, 1031, 1111, 1033, 1113,
+    1034, 1114, 1112, 1029, 172, 8730, 402, 8776, 8710, 171, 187, 8230, 160,
+    1035, 1115, 1036, 1116, 1109, 8211, 8212, 8220, 8221, 8216, 8217, 247,
+    8222, 1038, 1118, 1039, 1119, 8470, 1025, 1105, 1103, 1072, 1073, 1074,
+    1075, 1076, 1077, 1078, 1079, 1080, 1081, 1082, 1083, 1084, 1085, 1086,
+    1087, 1088, 1089, 1090, 1091, 1092, 1093, 1094, 1095, 1096, 1097, 1098,
+    1099, 1100, 1101, 1102, 8364,
+]);
+
+/// Maps the values 128..=255 of the Mac OS Central European encoding to unicode.
+#[rustfmt::skip]
+pub(super) static MAC_CENTRAL_EUROPEAN: SingleByteTable = SingleByteTable(&[
+    196, 256, 257, 201, 260, 214, 220, 225, 261, 268, 228, 269, 262, 263, 233,
+    377, 378, 270, 237, 271, 274, 275, 278, 243, 279, 244, 246, 245, 250, 282,
+    283, 252, 8224, 176, 280, 163, 167, 8226, 182, 223, 174, 169, 8482, 281,
+    168, 8800, 291, 302, 303, 298, 8804, 8805, 299, 310, 8706, 8721, 322, 315,
+    316, 317, 318, 313, 314, 325, 326, 323, 172, 8730, 324, 327, 8710, 171,
+    187, 8230, 160, 328, 336, 213, 337, 332, 8211, 8212, 8220, 8221, 8216,
+    8217, 247, 9674, 333, 340, 341, 344, 8249, 8250, 345, 342, 343, 352, 8218,
+    8222, 353, 346, 347, 193, 356, 357, 205, 381, 382, 362, 211, 212, 363, 366,
+    218, 367, 368, 369, 370, 371, 221, 253, 311, 379, 321, 380, 290, 711,
+]);
@@ -71,6 +71,39 @@ impl ValueRecord {
         Ok(this)
     }
 
+    /// Returns the format of this record, based on the fields that are present.
+    pub fn format(&self) -> ValueFormat {
+        let mut format = ValueFormat::empty();
+        let fields = [
+            (self.x_placement.is_some(), ValueFormat::X_PLACEMENT),
+            (self.y_placement.is_some(), ValueFormat::Y_PLACEMENT),
+            (self.x_advance.is_some(), ValueFormat::X_ADVANCE),
+            (self.y_advance.is_some(), ValueFormat::Y_ADVANCE),
+            (
+                self.x_placement_device.is_some(),
+                ValueFormat::X_PLACEMENT_DEVICE,
+            ),
+            (
+                self.y_placement_device.is_some(),
+                ValueFormat::Y_PLACEMENT_DEVICE,
+            ),
+            (
+                self.x_advance_device.is_some(),
+                ValueFormat::X_ADVANCE_DEVICE,
+            ),
+            (
+                self.y_advance_device.is_some(),
+                ValueFormat::Y_ADVANCE_DEVICE,
+            ),
+        ];
+        for (present, flag) in fields {
+            if present {
+                format |= flag;
+            }
+        }
+        format
+    }
+
     pub fn x_placement(&self) -> Option<i16> {
         self.x_placement.map(|val| val.get())
     }
@@ -91,28 +124,40 @@ impl ValueRecord {
         &self,
         data: FontData<'a>,
     ) -> Option<Result<Device<'a>, ReadError>> {
-        self.x_placement_device.map(|val| val.get().resolve(data))
+        self.x_placement_device
+            .and_then(|val| resolve_device(val.get(), data))
     }
 
     pub fn y_placement_device<'a>(
         &self,
         data: FontData<'a>,
     ) -> Option<Result<Device<'a>, ReadError>> {
-        self.y_placement_device.map(|val| val.get().resolve(data))
+        self.y_placement_device
+            .and_then(|val| resolve_device(val.get(), data))
     }
 
     pub fn x_advance_device<'a>(
         &self,
         data: FontData<'a>,
     ) -> Option<Result<Device<'a>, ReadError>> {
-        self.x_advance_device.map(|val| val.get().resolve(data))
+        self.x_advance_device
+            .and_then(|val| resolve_device(val.get(), data))
     }
 
     pub fn y_advance_device<'a>(
         &self,
         data: FontData<'a>,
     ) -> Option<Result<Device<'a>, ReadError>> {
-        self.y_advance_device.map(|val| val.get().resolve(data))
+        self.y_advance_device
+            .and_then(|val| resolve_device(val.get(), data))
+    }
+}
+
+/// Resolves a device offset, which may be null.
+fn resolve_device(offset: Offset16, data: FontData) -> Option<Result<Device, ReadError>> {
+    match offset.resolve(data) {
+        Err(ReadError::NullOffset) => None,
+        result => Some(result),
     }
 }
 
@@ -1,4 +1,5 @@
 use super::*;
+use crate::tables::layout::DeltaFormat;
 use font_test_data::gpos as test_data;
 
 #[test]
@@ -194,25 +195,36 @@ fn sequencelookuprecord() {
     assert_eq!(record.lookup_list_index(), 1);
 }
 
-//FIXME: turn this back on when we support device records
-//#[test]
-//fn valueformattable() {
-//// https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#example-14-valueformat-table-and-valuerecord
-
-//#[rustfmt::skip]
-//let bytes = [
-//0x00, 0x01, 0x00, 0x0E, 0x00, 0x99, 0x00, 0x50, 0x00, 0xD2,
-//0x00, 0x18, 0x00, 0x20, 0x00, 0x02, 0x00, 0x01, 0x00, 0xC8,
-//0x00, 0xD1, 0x00, 0x00, 0x00, 0x0B, 0x00, 0x0F, 0x00, 0x01,
-//0x55, 0x40, 0x00, 0x0B, 0x00, 0x0F, 0x00, 0x01, 0x55, 0x40,
-//];
-
-//let table = SinglePosFormat1::read(&bytes).unwrap();
-//let owned = table.to_owned_table().unwrap();
-//let dumped = crate::write::dump_table(&owned);
+#[test]
+fn valueformattable() {
+    // https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#example-14-valueformat-table-and-valuerecord
 
-//assert_hex_eq!(&bytes, &dumped);
-//}
+    let table = SinglePosFormat1::read(test_data::VALUEFORMATTABLE.into()).unwrap();
+    assert_eq!(
+        table.value_format(),
+        ValueFormat::X_PLACEMENT
+            | ValueFormat::Y_ADVANCE
+            | ValueFormat::X_PLACEMENT_DEVICE
+            | ValueFormat::Y_ADVANCE_DEVICE
+    );
+    let record = table.value_record();
+    assert_eq!(record.format(), table.value_format());
+    assert_eq!(record.x_placement(), Some(80));
+    assert_eq!(record.y_advance(), Some(210));
+    let data = table.offset_data();
+    for device in [
+        record.x_placement_device(data).unwrap().unwrap(),
+        record.y_advance_device(data).unwrap().unwrap(),
+    ] {
+        assert_eq!(device.start_size(), 11);
+        assert_eq!(device.end_size(), 15);
+        assert_eq!(device.delta_format(), DeltaFormat::Local2BitDeltas);
+        assert_eq!(device.pixel_delta(11), 1);
+        assert_eq!(device.pixel_delta(14), 1);
+        assert_eq!(device.pixel_delta(15), 1);
+    }
+    assert!(record.x_advance_device(data).is_none());
+}
 
 #[test]
 fn anchorformat1() {
@@ -232,22 +244,26 @@ fn anchorformat2() {
     assert_eq!(table.anchor_point(), 13);
 }
 
-//FIXME: enable when we have device tables working
-//#[test]
-//fn anchorformat3() {
-//// https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#example-17-anchorformat3-table
-
-//let bytes = [
-//0x00, 0x03, 0x01, 0x17, 0x05, 0x15, 0x00, 0x0A, 0x00, 0x14,
-//0x00, 0x0C, 0x00, 0x11, 0x00, 0x02, 0x11, 0x11, 0x22, 0x00,
-//0x00, 0x0C, 0x00, 0x11, 0x00, 0x02, 0x11, 0x11, 0x22, 0x00,
-//];
-//let table = AnchorFormat3::read(&bytes).unwrap();
-//let owned = table.to_owned_obj(&[]).unwrap();
-//let dumped = crate::write::dump_table(&owned);
-
-//assert_hex_eq!(&bytes, &dumped);
-//}
+#[test]
+fn anchorformat3() {
+    // https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#example-17-anchorformat3-table
+
+    let table = AnchorFormat3::read(test_data::ANCHORFORMAT3.into()).unwrap();
+    assert_eq!(table.x_coordinate(), 279);
+    assert_eq!(table.y_coordinate(), 1301);
+    for device in [
+        table.x_device().unwrap().unwrap(),
+        table.y_device().unwrap().unwrap(),
+    ] {
+        assert_eq!(device.start_size(), 12);
+        assert_eq!(device.end_size(), 17);
+        assert_eq!(device.delta_format(), DeltaFormat::Local4BitDeltas);
+        assert_eq!(device.pixel_delta(12), 1);
+        assert_eq!(device.pixel_delta(15), 1);
+        assert_eq!(device.pixel_delta(16), 2);
+        assert_eq!(device.pixel_delta(17), 2);
+    }
+}
 
 //NOTE: I think the sample bites are missing the actual anchor tables??
 // and so we can't really round-trip this...
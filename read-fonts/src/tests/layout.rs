@@ -39,3 +39,142 @@ fn example_3_featurelist_and_feature() {
     assert!(feature.feature_params_offset().is_null());
     assert_eq!(feature.lookup_list_indices().len(), 1);
 }
+
+#[test]
+fn coverage_get() {
+    use crate::test_helpers::BeBuffer;
+
+    let format1 = BeBuffer::new().push(1u16).push(3u16).extend([2u16, 5, 9]);
+    let format1 = CoverageTable::read(format1.font_data()).unwrap();
+    assert_eq!(format1.get(GlyphId::new(2)), Some(0));
+    assert_eq!(format1.get(GlyphId::new(9)), Some(2));
+    assert_eq!(format1.get(GlyphId::new(4)), None);
+
+    // ranges 4..=6 and 10..=12
+    let format2 = BeBuffer::new()
+        .push(2u16)
+        .push(2u16)
+        .extend([4u16, 6, 0])
+        .extend([10u16, 12, 3]);
+    let format2 = CoverageTable::read(format2.font_data()).unwrap();
+    assert_eq!(format2.get(GlyphId::new(4)), Some(0));
+    assert_eq!(format2.get(GlyphId::new(6)), Some(2));
+    assert_eq!(format2.get(GlyphId::new(11)), Some(4));
+    assert_eq!(format2.get(GlyphId::new(7)), None);
+    assert_eq!(format2.get(GlyphId::new(13)), None);
+
+    // a malformed range whose coverage index overflows
+    let overflow = BeBuffer::new()
+        .push(2u16)
+        .push(1u16)
+        .extend([4u16, 6, 0xFFFF]);
+    let overflow = CoverageTable::read(overflow.font_data()).unwrap();
+    assert_eq!(overflow.get(GlyphId::new(4)), Some(0xFFFF));
+    assert_eq!(overflow.get(GlyphId::new(5)), None);
+}
+
+#[test]
+fn device_pixel_delta() {
+    use crate::test_helpers::BeBuffer;
+
+    // https://learn.microsoft.com/en-us/typography/opentype/spec/chapter2#example-9-device-table
+    let two_bit = BeBuffer::new().extend([11u16, 15, 1, 0x5540]);
+    let two_bit = Device::read(two_bit.font_data()).unwrap();
+    assert_eq!(two_bit.pixel_delta(10), 0);
+    assert_eq!(two_bit.pixel_delta(11), 1);
+    assert_eq!(two_bit.pixel_delta(15), 1);
+    assert_eq!(two_bit.pixel_delta(16), 0);
+
+    // deltas [1, 2, -1] for 10..=12
+    let eight_bit = BeBuffer::new().extend([10u16, 12, 3, 0x0102, 0xFF00]);
+    let eight_bit = Device::read(eight_bit.font_data()).unwrap();
+    assert_eq!(eight_bit.pixel_delta(10), 1);
+    assert_eq!(eight_bit.pixel_delta(11), 2);
+    assert_eq!(eight_bit.pixel_delta(12), -1);
+
+    // deltas [-8, 7, -1] for 8..=10
+    let four_bit = BeBuffer::new().extend([8u16, 10, 2, 0x87F0]);
+    let four_bit = Device::read(four_bit.font_data()).unwrap();
+    assert_eq!(four_bit.pixel_delta(8), -8);
+    assert_eq!(four_bit.pixel_delta(9), 7);
+    assert_eq!(four_bit.pixel_delta(10), -1);
+
+    let var_index = BeBuffer::new().extend([0u16, 0, 0x8000]);
+    let var_index = Device::read(var_index.font_data()).unwrap();
+    assert_eq!(var_index.pixel_delta(0), 0);
+}
+
+#[test]
+fn class_def_get() {
+    use crate::test_helpers::BeBuffer;
+
+    // glyphs 3..=5 with classes [1, 0, 2]
+    let format1 = BeBuffer::new().extend([1u16, 3, 3, 1, 0, 2]);
+    let format1 = ClassDef::read(format1.font_data()).unwrap();
+    assert_eq!(format1.get(GlyphId::new(2)), 0);
+    assert_eq!(format1.get(GlyphId::new(3)), 1);
+    assert_eq!(format1.get(GlyphId::new(4)), 0);
+    assert_eq!(format1.get(GlyphId::new(5)), 2);
+    assert_eq!(format1.get(GlyphId::new(6)), 0);
+
+    // ranges 4..=6 => 1 and 10..=12 => 3
+    let format2 = BeBuffer::new()
+        .extend([2u16, 2])
+        .extend([4u16, 6, 1])
+        .extend([10u16, 12, 3]);
+    let format2 = ClassDef::read(format2.font_data()).unwrap();
+    assert_eq!(format2.get(GlyphId::new(4)), 1);
+    assert_eq!(format2.get(GlyphId::new(6)), 1);
+    assert_eq!(format2.get(GlyphId::new(8)), 0);
+    assert_eq!(format2.get(GlyphId::new(11)), 3);
+    assert_eq!(format2.get(GlyphId::new(13)), 0);
+}
+
+#[test]
+fn device_resolve() {
+    use crate::{test_helpers::BeBuffer, FontRef, TableProvider};
+
+    let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+    let var_store = font.gdef().unwrap().item_var_store().unwrap().unwrap();
+    let coords = [F2Dot14::from_f32(1.0)];
+
+    let var_index = BeBuffer::new().extend([0u16, 0, 0x8000]);
+    let var_index = Device::read(var_index.font_data()).unwrap();
+    let index = var_index.variation_index().unwrap();
+    assert_eq!((index.outer, index.inner), (0, 0));
+    let delta = var_index
+        .resolve(Some(12), &coords, Some(&var_store))
+        .unwrap();
+    assert_eq!(
+        delta,
+        DeviceDelta {
+            units: 50,
+            pixels: 0
+        }
+    );
+    assert_eq!(delta.apply(100, 0.5), 75.0);
+    // no variation store
+    assert_eq!(
+        var_index.resolve(Some(12), &coords, None).unwrap(),
+        DeviceDelta::default()
+    );
+
+    // deltas [1, 2, -1] for 10..=12
+    let device = BeBuffer::new().extend([10u16, 12, 3, 0x0102, 0xFF00]);
+    let device = Device::read(device.font_data()).unwrap();
+    assert!(device.variation_index().is_none());
+    let delta = device.resolve(Some(11), &coords, Some(&var_store)).unwrap();
+    assert_eq!(
+        delta,
+        DeviceDelta {
+            units: 0,
+            pixels: 2
+        }
+    );
+    assert_eq!(delta.apply(100, 0.5), 52.0);
+    // unscaled
+    assert_eq!(
+        device.resolve(None, &coords, Some(&var_store)).unwrap(),
+        DeviceDelta::default()
+    );
+}
@@ -7,9 +7,17 @@
 //! all font tables. This trait provides the table's name, as well as ordered access
 //! to the table's fields. Using this, it is possible to iterate through a table
 //! and its subtables, records, and values.
+//!
+//! With the `serde` feature enabled, any table can also be serialized, for
+//! instance to JSON.
 
 use std::{fmt::Debug, ops::Deref};
 
+mod sanitize;
+#[cfg(feature = "serde")]
+mod serialize;
+mod walk;
+
 use types::{
     BigEndian, F2Dot14, FWord, Fixed, GlyphId, LongDateTime, MajorMinor, NameId, Nullable,
     Offset16, Offset24, Offset32, Scalar, Tag, UfWord, Uint24, Version16Dot16,
@@ -21,6 +29,9 @@ use crate::{
     FontData, FontRead, FontReadWithArgs, ReadError, VarSize,
 };
 
+pub use crate::context::PathSegment;
+pub use sanitize::{sanitize, Issue, IssueKind, SanitizeReport};
+
 /// Types of fields in font tables.
 ///
 /// Fields can either be scalars, offsets to tables, or arrays.
@@ -42,6 +53,10 @@ pub enum FieldType<'a> {
     LongDateTime(LongDateTime),
     GlyphId(GlyphId),
     NameId(NameId),
+    /// A value from an enumeration, such as a platform id.
+    Enum(EnumValue),
+    /// A set of bit flags.
+    Flags(FlagsValue),
     BareOffset(OffsetType),
     ResolvedOffset(ResolvedOffset<'a>),
     /// Used in tables like name/post so we can actually print the strings
@@ -72,6 +87,34 @@ impl OffsetType {
     }
 }
 
+/// The value of an enum field, along with the name of its variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnumValue {
+    /// The raw value.
+    pub value: u32,
+    /// The name of the variant, or `None` if the value is not recognized.
+    pub name: Option<&'static str>,
+}
+
+/// The value of a flags field, along with the names of the known flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlagsValue {
+    /// The raw value.
+    pub bits: u32,
+    /// The name and value of each flag defined for this field.
+    pub flags: &'static [(&'static str, u32)],
+}
+
+impl FlagsValue {
+    /// Returns an iterator over the names of the flags that are set.
+    pub fn set_flags(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.flags
+            .iter()
+            .filter(|(_, value)| self.bits & value == *value)
+            .map(|(name, _)| *name)
+    }
+}
+
 /// An offset, as well as the table it references.
 pub struct ResolvedOffset<'a> {
     /// The raw offset
@@ -267,6 +310,12 @@ pub trait SomeTable<'a> {
     fn type_name(&self) -> &str;
     /// Access this table's fields, in declaration order.
     fn get_field(&self, idx: usize) -> Option<Field<'a>>;
+    /// Returns `true` if the last field of this table takes up all of the
+    /// remaining data, so that the table's length can't be known from its
+    /// fields.
+    fn is_open_ended(&self) -> bool {
+        false
+    }
 }
 
 impl<'a> dyn SomeTable<'a> + 'a {
@@ -302,6 +351,10 @@ impl<'a> SomeTable<'a> for Box<dyn SomeTable<'a> + 'a> {
     fn get_field(&self, idx: usize) -> Option<Field<'a>> {
         self.deref().get_field(idx)
     }
+
+    fn is_open_ended(&self) -> bool {
+        self.deref().is_open_ended()
+    }
 }
 
 /// A generic trait for records, which need to be passed in data
@@ -534,6 +587,24 @@ impl<'a> Debug for FieldType<'a> {
                 arg0.to_u16().fmt(f)
             }
             Self::NameId(arg0) => arg0.fmt(f),
+            Self::Enum(arg0) => match arg0.name {
+                Some(name) => f.write_str(name),
+                None => arg0.value.fmt(f),
+            },
+            Self::Flags(arg0) => {
+                let mut first = true;
+                for name in arg0.set_flags() {
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+                    first = false;
+                    f.write_str(name)?;
+                }
+                if first {
+                    f.write_str("(empty)")?;
+                }
+                Ok(())
+            }
             Self::StringOffset(string) => match &string.target {
                 Ok(arg0) => arg0.as_ref().fmt(f),
                 Err(_) => string.target.fmt(f),
@@ -0,0 +1,512 @@
+//! Checking the structure of an untrusted font.
+
+use std::collections::BTreeSet;
+
+use types::Tag;
+
+use super::walk::{Problem, Walker};
+use super::PathSegment;
+use crate::{tables, FontRef, ReadError, TableProvider, TopLevelTable};
+
+/// Checks the structure of every table in a font.
+///
+/// Each table in the directory is checked for a valid location and checksum.
+/// Tables supported by the traversal API are then parsed, along with every
+/// subtable reachable from them, and the glyph counts implied by the
+/// metrics, `loca` and `post` tables are compared against `maxp`.
+///
+/// Subtables that overlap each other are also reported. Only the leading
+/// part of each subtable whose size is known is considered, so for instance
+/// a table is assumed to end at the first enum or flags field, and an array
+/// of records containing one is ignored.
+pub fn sanitize(font: &FontRef) -> SanitizeReport {
+    let mut issues = Vec::new();
+    let font_len = font.data.len();
+    let mut ranges = Vec::new();
+    for record in font.table_directory.table_records() {
+        let tag = record.tag();
+        let start = record.offset() as usize;
+        let mut issue = |kind| issues.push(Issue::new(tag, kind, start));
+        let end = match start.checked_add(record.length() as usize) {
+            Some(end) if end <= font_len => end,
+            _ => {
+                issue(IssueKind::TableOutOfBounds);
+                continue;
+            }
+        };
+        if let Some((other, _)) =
+            ranges
+                .iter()
+                .find(|(_, range): &&(Tag, std::ops::Range<usize>)| {
+                    range.start < end && start < range.end
+                })
+        {
+            issue(IssueKind::OverlappingTables(*other));
+        }
+        ranges.push((tag, start..end));
+        let actual = font.compute_table_checksum(tag).unwrap_or_default();
+        if actual != record.checksum() {
+            issue(IssueKind::ChecksumMismatch {
+                expected: record.checksum(),
+                actual,
+            });
+        }
+        match font.traversable_table(tag) {
+            Some(Ok(table)) => Walker::new(|problem, path: &[PathSegment], offset| {
+                let kind = match problem {
+                    Problem::Read(error) => error.into(),
+                    Problem::Cycle => IssueKind::Cycle,
+                    Problem::Overlap(other) => IssueKind::OverlappingSubtables { other },
+                };
+                issues.push(Issue {
+                    table: tag,
+                    kind,
+                    path: path.to_owned(),
+                    offset,
+                });
+                false
+            })
+            .check_overlaps()
+            .walk(&*table, start),
+            Some(Err(error)) => issues.push(Issue::new(tag, error.into(), start)),
+            None => (),
+        }
+    }
+    check_glyph_counts(font, &mut issues);
+    SanitizeReport { issues }
+}
+
+/// The result of [`sanitize`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// The problems that were found, in table directory order.
+    pub issues: Vec<Issue>,
+}
+
+/// A single problem found by [`sanitize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Issue {
+    /// The table containing the problem.
+    pub table: Tag,
+    /// The kind of problem.
+    pub kind: IssueKind,
+    /// The chain of fields followed to reach the problem, starting at the
+    /// top-level table.
+    ///
+    /// This is empty for problems with the table as a whole.
+    pub path: Vec<PathSegment>,
+    /// The position in the font data of the table or subtable with the
+    /// problem.
+    pub offset: usize,
+}
+
+/// The kinds of problems reported by [`sanitize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The table extends past the end of the font data.
+    TableOutOfBounds,
+    /// The table overlaps the table with the given tag.
+    OverlappingTables(Tag),
+    /// The checksum in the table directory does not match the table data.
+    ChecksumMismatch { expected: u32, actual: u32 },
+    /// An offset points outside of the table, or a subtable is truncated.
+    OffsetOutOfBounds,
+    /// A table or subtable has a format or version that is not recognized.
+    UnknownFormat(i64),
+    /// An offset refers back to a table that encloses it.
+    Cycle,
+    /// A subtable overlaps a different subtable, which begins at the given
+    /// position in the font data.
+    OverlappingSubtables { other: usize },
+    /// The number of glyphs covered by a table does not match the number
+    /// of glyphs in `maxp`.
+    GlyphCountMismatch { expected: u16, actual: usize },
+    /// Some other error occured while reading.
+    Malformed(ReadError),
+}
+
+impl SanitizeReport {
+    /// Returns `true` if no problems were found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the set of tables with at least one problem.
+    pub fn failing_tables(&self) -> BTreeSet<Tag> {
+        self.issues.iter().map(|issue| issue.table).collect()
+    }
+}
+
+impl Issue {
+    fn new(table: Tag, kind: IssueKind, offset: usize) -> Self {
+        Issue {
+            table,
+            kind,
+            path: Vec::new(),
+            offset,
+        }
+    }
+}
+
+impl From<ReadError> for IssueKind {
+    fn from(src: ReadError) -> IssueKind {
+        match src {
+            ReadError::OutOfBounds => IssueKind::OffsetOutOfBounds,
+            ReadError::InvalidFormat(format) => IssueKind::UnknownFormat(format),
+            other => IssueKind::Malformed(other),
+        }
+    }
+}
+
+fn check_glyph_counts(font: &FontRef, issues: &mut Vec<Issue>) {
+    let Ok(num_glyphs) = font.maxp().map(|maxp| maxp.num_glyphs()) else {
+        return;
+    };
+    let mut check = |tag: Tag, actual: usize| {
+        if actual != num_glyphs as usize {
+            let offset = table_offset(font, tag);
+            let kind = IssueKind::GlyphCountMismatch {
+                expected: num_glyphs,
+                actual,
+            };
+            issues.push(Issue::new(tag, kind, offset));
+        }
+    };
+    // long metrics are followed by one side bearing for each remaining glyph
+    let metrics = [
+        (
+            tables::hmtx::Hmtx::TAG,
+            font.hhea().map(|hhea| hhea.number_of_long_metrics()),
+        ),
+        (
+            tables::vmtx::Vmtx::TAG,
+            font.vhea().map(|vhea| vhea.number_of_long_ver_metrics()),
+        ),
+    ];
+    for (tag, num_long_metrics) in metrics {
+        let (Ok(num_long_metrics), Some(data)) = (num_long_metrics, font.table_data(tag)) else {
+            continue;
+        };
+        let num_long_metrics = num_long_metrics as usize;
+        let long_len = num_long_metrics * 4;
+        if num_long_metrics > num_glyphs as usize || data.len() < long_len {
+            check(tag, num_long_metrics);
+        } else {
+            check(tag, num_long_metrics + (data.len() - long_len) / 2);
+        }
+    }
+    if let (Ok(head), Some(data)) = (font.head(), font.table_data(tables::loca::Loca::TAG)) {
+        let entry_len = if head.index_to_loc_format() == 1 {
+            4
+        } else {
+            2
+        };
+        // loca has an extra entry for the end of the last glyph
+        check(
+            tables::loca::Loca::TAG,
+            (data.len() / entry_len).saturating_sub(1),
+        );
+    }
+    if let Some(post_glyphs) = font.post().ok().and_then(|post| post.num_glyphs()) {
+        check(tables::post::Post::TAG, post_glyphs as usize);
+    }
+}
+
+fn table_offset(font: &FontRef, tag: Tag) -> usize {
+    font.table_directory
+        .table_records()
+        .iter()
+        .find(|record| record.tag() == tag)
+        .map(|record| record.offset() as usize)
+        .unwrap_or_default()
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueKind::TableOutOfBounds => write!(f, "Table extends past the end of the font"),
+            IssueKind::OverlappingTables(other) => write!(f, "Table overlaps '{other}'"),
+            IssueKind::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum mismatch: expected {expected:#010x}, found {actual:#010x}"
+            ),
+            IssueKind::OffsetOutOfBounds => write!(f, "An offset was out of bounds"),
+            IssueKind::UnknownFormat(format) => write!(f, "Unknown format {format}"),
+            IssueKind::Cycle => write!(f, "Offset refers to an enclosing table"),
+            IssueKind::OverlappingSubtables { other } => {
+                write!(f, "Subtable overlaps the subtable at byte {other:#x}")
+            }
+            IssueKind::GlyphCountMismatch { expected, actual } => {
+                write!(f, "Table covers {actual} glyphs but maxp has {expected}")
+            }
+            IssueKind::Malformed(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}'", self.table)?;
+        for (i, segment) in self.path.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { " -> " };
+            write!(f, "{sep}{segment}")?;
+        }
+        write!(f, " (at byte {:#x}): {}", self.offset, self.kind)
+    }
+}
+
+impl std::fmt::Display for SanitizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_pos(data: &[u8], tag: Tag) -> usize {
+        let num_tables = u16::from_be_bytes([data[4], data[5]]) as usize;
+        (0..num_tables)
+            .map(|i| 12 + i * 16)
+            .find(|pos| data[*pos..*pos + 4] == tag.to_be_bytes())
+            .unwrap()
+    }
+
+    fn read_u32(data: &[u8], pos: usize) -> u32 {
+        u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap())
+    }
+
+    fn kinds(report: &SanitizeReport) -> Vec<(Tag, IssueKind)> {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.table, issue.kind.clone()))
+            .collect()
+    }
+
+    const TEST_FONTS: &[&[u8]] = &[
+        font_test_data::CMAP12_FONT1,
+        font_test_data::CMAP14_FONT1,
+        font_test_data::CMAP4_SYMBOL_PUA,
+        font_test_data::COLR_GRADIENT_RECT,
+        font_test_data::LAYOUT_FEATURES,
+        font_test_data::NAMES_ONLY,
+        font_test_data::SIMPLE_GLYF,
+        font_test_data::VAZIRMATN_VAR,
+    ];
+
+    // sanitize the font, returning `false` if it panics
+    fn sanitize_without_panic(data: &[u8]) -> bool {
+        std::panic::catch_unwind(|| {
+            if let Ok(font) = FontRef::new(data) {
+                sanitize(&font);
+            }
+        })
+        .is_ok()
+    }
+
+    #[test]
+    fn truncated_fonts_dont_panic() {
+        let mut failures = Vec::new();
+        for (i, font) in TEST_FONTS.iter().enumerate() {
+            for len in 0..font.len() {
+                if !sanitize_without_panic(&font[..len]) {
+                    failures.push((i, len));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "(font, length): {failures:?}");
+    }
+
+    #[test]
+    fn corrupted_fonts_dont_panic() {
+        let mut failures = Vec::new();
+        for (i, font) in TEST_FONTS.iter().enumerate() {
+            let mut data = font.to_vec();
+            for pos in 0..data.len() {
+                for mask in [0x01, 0x10, 0x80, 0xFF] {
+                    data[pos] ^= mask;
+                    if !sanitize_without_panic(&data) {
+                        failures.push((i, pos, mask));
+                    }
+                    data[pos] ^= mask;
+                }
+            }
+        }
+        assert!(failures.is_empty(), "(font, byte, mask): {failures:?}");
+    }
+
+    #[test]
+    fn clean_fonts() {
+        for data in [
+            font_test_data::LAYOUT_FEATURES,
+            font_test_data::VAZIRMATN_VAR,
+        ] {
+            let font = FontRef::new(data).unwrap();
+            let report = sanitize(&font);
+            assert!(report.is_clean(), "{report}");
+        }
+    }
+
+    #[test]
+    fn missing_dependency() {
+        // this font has hmtx but no hhea
+        let font = FontRef::new(font_test_data::SIMPLE_GLYF).unwrap();
+        let report = sanitize(&font);
+        assert_eq!(
+            kinds(&report),
+            [(
+                Tag::new(b"hmtx"),
+                IssueKind::Malformed(ReadError::TableIsMissing(Tag::new(b"hhea")))
+            )]
+        );
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let record = record_pos(&data, Tag::new(b"GSUB"));
+        let expected = read_u32(&data, record + 4) ^ 1;
+        data[record + 4..record + 8].copy_from_slice(&expected.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        assert_eq!(
+            kinds(&report),
+            [(
+                Tag::new(b"GSUB"),
+                IssueKind::ChecksumMismatch {
+                    expected,
+                    actual: expected ^ 1
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn head_checksum_ignores_adjustment() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let head = read_u32(&data, record_pos(&data, Tag::new(b"head")) + 8) as usize;
+        data[head + 8..head + 12].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let font = FontRef::new(&data).unwrap();
+        assert!(sanitize(&font).is_clean());
+    }
+
+    #[test]
+    fn out_of_bounds_table() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let record = record_pos(&data, Tag::new(b"GPOS"));
+        data[record + 12..record + 16].copy_from_slice(&0x10000u32.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        assert_eq!(
+            kinds(&report),
+            [(Tag::new(b"GPOS"), IssueKind::TableOutOfBounds)]
+        );
+    }
+
+    #[test]
+    fn overlapping_tables() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = record_pos(&data, Tag::new(b"GDEF"));
+        let gpos = record_pos(&data, Tag::new(b"GPOS"));
+        // move GPOS into the middle of GDEF
+        let gdef_start = read_u32(&data, gdef + 8);
+        data[gpos + 8..gpos + 12].copy_from_slice(&(gdef_start + 4).to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        assert!(report.issues.contains(&Issue::new(
+            Tag::new(b"GPOS"),
+            IssueKind::OverlappingTables(Tag::new(b"GDEF")),
+            gdef_start as usize + 4
+        )));
+        assert!(report.failing_tables().contains(&Tag::new(b"GPOS")));
+    }
+
+    #[test]
+    fn bad_subtables() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = read_u32(&data, record_pos(&data, Tag::new(b"GDEF")) + 8) as usize;
+        // glyphClassDefOffset is out of bounds, and the first subtable of
+        // ligCaretList has an invalid coverage format
+        data[gdef + 4..gdef + 6].copy_from_slice(&0xFFF0u16.to_be_bytes());
+        let lig_caret_list = gdef + u16::from_be_bytes([data[gdef + 8], data[gdef + 9]]) as usize;
+        let coverage = lig_caret_list
+            + u16::from_be_bytes([data[lig_caret_list], data[lig_caret_list + 1]]) as usize;
+        data[coverage..coverage + 2].copy_from_slice(&7u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        let gdef_issues = report
+            .issues
+            .iter()
+            .filter(|issue| issue.table == Tag::new(b"GDEF"))
+            .filter(|issue| !matches!(issue.kind, IssueKind::ChecksumMismatch { .. }))
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gdef_issues,
+            [
+                format!(
+                    "'GDEF': Gdef.glyph_class_def_offset (at byte {:#x}): An offset was out of bounds",
+                    gdef + 0xFFF0
+                ),
+                format!(
+                    "'GDEF': Gdef.lig_caret_list_offset -> LigCaretList.coverage_offset (at byte {coverage:#x}): Unknown format 7"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_subtables() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let gdef = read_u32(&data, record_pos(&data, Tag::new(b"GDEF")) + 8) as usize;
+        // point markAttachClassDefOffset four bytes into the glyph class def,
+        // where the class range records happen to form a format 1 class def
+        let glyph_class_def = gdef + u16::from_be_bytes([data[gdef + 4], data[gdef + 5]]) as usize;
+        let offset = (glyph_class_def + 4 - gdef) as u16;
+        data[gdef + 10..gdef + 12].copy_from_slice(&offset.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        let gdef_issues = report
+            .issues
+            .iter()
+            .filter(|issue| issue.table == Tag::new(b"GDEF"))
+            .filter(|issue| !matches!(issue.kind, IssueKind::ChecksumMismatch { .. }))
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gdef_issues,
+            [format!(
+                "'GDEF': Gdef.mark_attach_class_def_offset (at byte {:#x}): Subtable overlaps the subtable at byte {glyph_class_def:#x}",
+                glyph_class_def + 4
+            )]
+        );
+    }
+
+    #[test]
+    fn glyph_count_mismatch() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let maxp = read_u32(&data, record_pos(&data, Tag::new(b"maxp")) + 8) as usize;
+        data[maxp + 4..maxp + 6].copy_from_slice(&20u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let report = sanitize(&font);
+        let counts = report
+            .issues
+            .iter()
+            .filter_map(|issue| match issue.kind {
+                IssueKind::GlyphCountMismatch { expected, actual } => {
+                    Some((issue.table, expected, actual))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            [(Tag::new(b"hmtx"), 20, 14), (Tag::new(b"loca"), 20, 14),]
+        );
+    }
+}
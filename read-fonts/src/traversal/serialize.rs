@@ -0,0 +1,261 @@
+//! Serializing tables with serde.
+//!
+//! Any table (or array) exposed through the traversal API can be serialized,
+//! producing a tree that mirrors the table's structure:
+//!
+//! - tables and records become `{"type": <type name>, "fields": {...}}`, with
+//!   the fields in declaration order
+//! - arrays become sequences
+//! - offsets become `{"offset": <offset>, "target": <target>}`, where the
+//!   target is the referenced table, array or string, `null` if the offset
+//!   is null, or `"unknown"` if the type of its target is not known
+//! - offsets that can't be resolved become `{"offset": <offset>, "error": <message>}`
+//! - enums become `{"value": <raw value>, "name": <variant name or null>}`
+//! - flags become `{"bits": <raw value>, "flags": [<names of set flags>]}`
+//! - tags and versions become strings, and other scalars become numbers
+//!
+//! Offsets in a font may form cycles, so serialization fails if tables are
+//! nested more deeply than any real font would require.
+
+use serde::ser::{Error, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use super::{walk::MAX_DEPTH, FieldType, OffsetType, SomeArray, SomeTable};
+use crate::ReadError;
+
+impl<'a> Serialize for dyn SomeTable<'a> + 'a {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeTable(self, 0).serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for dyn SomeArray<'a> + 'a {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeArray(self, 0).serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for FieldType<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeField(self, 0).serialize(serializer)
+    }
+}
+
+/// A table or record, along with its nesting depth.
+struct SerializeTable<'a, 'b>(&'b (dyn SomeTable<'a> + 'a), usize);
+
+/// The fields of a table or record.
+struct SerializeFields<'a, 'b>(&'b (dyn SomeTable<'a> + 'a), usize);
+
+struct SerializeArray<'a, 'b>(&'b (dyn SomeArray<'a> + 'a), usize);
+
+struct SerializeField<'a, 'b>(&'b FieldType<'a>, usize);
+
+fn check_depth<E: Error>(depth: usize) -> Result<(), E> {
+    if depth > MAX_DEPTH {
+        return Err(E::custom("tables are nested too deeply"));
+    }
+    Ok(())
+}
+
+impl<'a, 'b> Serialize for SerializeTable<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SerializeTable(table, depth) = *self;
+        check_depth(depth)?;
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("type", table.type_name())?;
+        map.serialize_entry("fields", &SerializeFields(table, depth))?;
+        map.end()
+    }
+}
+
+impl<'a, 'b> Serialize for SerializeFields<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SerializeFields(table, depth) = *self;
+        let mut map = serializer.serialize_map(None)?;
+        for field in table.iter() {
+            map.serialize_entry(field.name, &SerializeField(&field.value, depth))?;
+        }
+        map.end()
+    }
+}
+
+impl<'a, 'b> Serialize for SerializeArray<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SerializeArray(array, depth) = *self;
+        check_depth(depth)?;
+        let mut seq = serializer.serialize_seq(Some(array.len()))?;
+        for item in array.iter() {
+            seq.serialize_element(&SerializeField(&item, depth + 1))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'a, 'b> Serialize for SerializeField<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let depth = self.1;
+        match self.0 {
+            FieldType::I8(val) => serializer.serialize_i8(*val),
+            FieldType::U8(val) => serializer.serialize_u8(*val),
+            FieldType::I16(val) => serializer.serialize_i16(*val),
+            FieldType::U16(val) => serializer.serialize_u16(*val),
+            FieldType::I32(val) => serializer.serialize_i32(*val),
+            FieldType::U32(val) => serializer.serialize_u32(*val),
+            FieldType::U24(val) => serializer.serialize_u32(u32::from(*val)),
+            FieldType::Tag(val) => serializer.collect_str(val),
+            FieldType::FWord(val) => serializer.serialize_i16(val.to_i16()),
+            FieldType::UfWord(val) => serializer.serialize_u16(val.to_u16()),
+            FieldType::MajorMinor(val) => serializer.collect_str(val),
+            FieldType::Version16Dot16(val) => serializer.collect_str(val),
+            FieldType::F2Dot14(val) => serializer.serialize_f32(val.to_f32()),
+            FieldType::Fixed(val) => serializer.serialize_f64(val.to_f64()),
+            FieldType::LongDateTime(val) => serializer.serialize_i64(val.as_secs()),
+            FieldType::GlyphId(val) => serializer.serialize_u16(val.to_u16()),
+            FieldType::NameId(val) => serializer.serialize_u16(val.to_u16()),
+            FieldType::Enum(val) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("value", &val.value)?;
+                map.serialize_entry("name", &val.name)?;
+                map.end()
+            }
+            FieldType::Flags(val) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("bits", &val.bits)?;
+                map.serialize_entry("flags", &val.set_flags().collect::<Vec<_>>())?;
+                map.end()
+            }
+            FieldType::BareOffset(offset) if offset.to_u32() == 0 => {
+                serialize_offset(serializer, *offset, Ok(None::<()>))
+            }
+            FieldType::BareOffset(offset) => {
+                serialize_offset(serializer, *offset, Ok(Some("unknown")))
+            }
+            FieldType::ResolvedOffset(offset) => {
+                let target = offset.target.as_ref();
+                let target = target.map(|table| Some(SerializeTable(&**table, depth + 1)));
+                serialize_offset(serializer, offset.offset, target)
+            }
+            FieldType::ArrayOffset(offset) => {
+                let target = offset.target.as_ref();
+                let target = target.map(|array| Some(SerializeArray(&**array, depth + 1)));
+                serialize_offset(serializer, offset.offset, target)
+            }
+            FieldType::StringOffset(offset) => {
+                let target = offset.target.as_ref();
+                let target = target.map(|string| Some(string.iter_chars().collect::<String>()));
+                serialize_offset(serializer, offset.offset, target)
+            }
+            FieldType::Record(record) => SerializeTable(record, depth + 1).serialize(serializer),
+            FieldType::Array(array) => SerializeArray(&**array, depth).serialize(serializer),
+            FieldType::Unknown => serializer.serialize_unit(),
+        }
+    }
+}
+
+fn serialize_offset<S: Serializer, T: Serialize>(
+    serializer: S,
+    offset: OffsetType,
+    target: Result<Option<T>, &ReadError>,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("offset", &offset.to_u32())?;
+    match target {
+        Ok(target) => map.serialize_entry("target", &target)?,
+        Err(error) => map.serialize_entry("error", &error.to_string())?,
+    }
+    map.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{tables::head::MacStyle, FontRef};
+    use types::{Offset16, Offset32, Tag};
+
+    fn to_json(font: &FontRef, tag: Tag) -> Value {
+        let table = font.table_as_traversable(tag).unwrap();
+        serde_json::to_value(&*table).unwrap()
+    }
+
+    #[test]
+    fn tables_and_offsets() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let gdef = to_json(&font, Tag::new(b"GDEF"));
+        assert_eq!(gdef["type"], "Gdef");
+        assert_eq!(gdef["fields"]["version"], "1.3");
+        let lig_carets = &gdef["fields"]["lig_caret_list_offset"];
+        assert!(lig_carets["offset"].as_u64().unwrap() > 0);
+        assert_eq!(lig_carets["target"]["type"], "LigCaretList");
+        let carets = &lig_carets["target"]["fields"]["lig_glyph_offsets"];
+        assert!(carets.as_array().unwrap().len() > 1);
+        assert_eq!(carets[0]["target"]["type"], "LigGlyph");
+        // null offsets have no target
+        assert_eq!(
+            gdef["fields"]["attach_list_offset"],
+            json!({"offset": 0, "target": null})
+        );
+    }
+
+    #[test]
+    fn field_order() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let gdef = font.table_as_traversable(Tag::new(b"GDEF")).unwrap();
+        let json = serde_json::to_string(&*gdef).unwrap();
+        let version = json.find("\"version\"").unwrap();
+        let glyph_class_def = json.find("\"glyph_class_def_offset\"").unwrap();
+        assert!(version < glyph_class_def);
+    }
+
+    #[test]
+    fn enums_and_flags() {
+        let style = MacStyle::BOLD | MacStyle::ITALIC;
+        assert_eq!(
+            serde_json::to_value(FieldType::from(style)).unwrap(),
+            json!({"bits": 3, "flags": ["BOLD", "ITALIC"]})
+        );
+
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let cmap = to_json(&font, Tag::new(b"cmap"));
+        let record = &cmap["fields"]["encoding_records"][0];
+        assert_eq!(record["type"], "EncodingRecord");
+        assert_eq!(
+            record["fields"]["platform_id"],
+            json!({"value": 0, "name": "Unicode"})
+        );
+    }
+
+    #[test]
+    fn bare_offsets() {
+        assert_eq!(
+            serde_json::to_value(FieldType::unknown_offset(Offset16::new(0))).unwrap(),
+            json!({"offset": 0, "target": null})
+        );
+        assert_eq!(
+            serde_json::to_value(FieldType::unknown_offset(Offset32::new(0x20))).unwrap(),
+            json!({"offset": 0x20, "target": "unknown"})
+        );
+    }
+
+    #[test]
+    fn bad_offsets() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let font = FontRef::new(&data).unwrap();
+        let gdef = font
+            .table_directory
+            .table_records()
+            .iter()
+            .find(|record| record.tag() == Tag::new(b"GDEF"))
+            .unwrap()
+            .offset() as usize;
+        // ligCaretListOffset
+        data[gdef + 8..gdef + 10].copy_from_slice(&0xFFF0u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let gdef = to_json(&font, Tag::new(b"GDEF"));
+        assert_eq!(
+            gdef["fields"]["lig_caret_list_offset"],
+            json!({"offset": 0xFFF0, "error": "An offset was out of bounds"})
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Walking every subtable reachable from a table.
+//!
+//! This is the basis of [`sanitize`](super::sanitize), which reports every
+//! problem found along the way.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+
+use super::{FieldType, OffsetType, PathSegment, SomeArray, SomeTable};
+use crate::ReadError;
+
+// generous; real tables are rarely more than a handful of levels deep
+pub(super) const MAX_DEPTH: usize = 64;
+
+/// A problem encountered while walking a table.
+pub(super) enum Problem {
+    /// A subtable could not be read.
+    Read(ReadError),
+    /// An offset refers back to a table that encloses it.
+    Cycle,
+    /// A subtable overlaps a different subtable starting at the given
+    /// position.
+    Overlap(usize),
+}
+
+/// Visits every subtable reachable from a table, reporting problems.
+///
+/// The callback receives each problem along with the path to it and its
+/// position in the font data, and returns `true` to stop the walk.
+pub(super) struct Walker<F> {
+    path: Vec<PathSegment>,
+    // (offset, type name) of tables already checked, so that shared
+    // subtables are only visited once.
+    visited: HashSet<(usize, String)>,
+    // the same, for the tables enclosing the current one
+    ancestors: Vec<(usize, String)>,
+    // if checking for overlaps, the range of each table or array seen so
+    // far, keyed by its start. These never overlap each other; a range that
+    // overlaps an existing one is reported instead of being added.
+    extents: Option<BTreeMap<usize, (usize, String)>>,
+    report: F,
+}
+
+impl<F> Walker<F>
+where
+    F: FnMut(Problem, &[PathSegment], usize) -> bool,
+{
+    pub(super) fn new(report: F) -> Self {
+        Walker {
+            path: Vec::new(),
+            visited: HashSet::new(),
+            ancestors: Vec::new(),
+            extents: None,
+            report,
+        }
+    }
+
+    /// Also report subtables that overlap each other.
+    ///
+    /// Only the part of each subtable with a known layout is checked: this
+    /// stops at the first field whose size can't be determined.
+    pub(super) fn check_overlaps(mut self) -> Self {
+        self.extents = Some(BTreeMap::new());
+        self
+    }
+
+    /// Walks `table`, which is located at `offset` in the font data.
+    pub(super) fn walk<'a>(mut self, table: &(dyn SomeTable<'a> + 'a), offset: usize) {
+        self.visit_table(table, offset, 0);
+    }
+
+    fn visit_table<'a>(
+        &mut self,
+        table: &(dyn SomeTable<'a> + 'a),
+        base: usize,
+        depth: usize,
+    ) -> bool {
+        let key = (base, table.type_name().to_owned());
+        if self.ancestors.contains(&key) {
+            return (self.report)(Problem::Cycle, &self.path, base);
+        }
+        if !self.visited.insert(key.clone()) {
+            return false;
+        }
+        if self.extents.is_some() {
+            let range = base..base + known_len(table);
+            if self.add_extent(range, table.type_name()) {
+                return true;
+            }
+        }
+        self.ancestors.push(key);
+        let stop = self.visit_fields(table, base, depth);
+        self.ancestors.pop();
+        stop
+    }
+
+    fn visit_fields<'a>(
+        &mut self,
+        table: &(dyn SomeTable<'a> + 'a),
+        base: usize,
+        depth: usize,
+    ) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+        for field in table.iter() {
+            self.path.push(PathSegment {
+                type_name: table.type_name().to_owned(),
+                field: field.name,
+                index: None,
+            });
+            if self.visit_value(field.value, base, depth) {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+
+    fn visit_array<'a>(
+        &mut self,
+        array: &(dyn SomeArray<'a> + 'a),
+        base: usize,
+        depth: usize,
+    ) -> bool {
+        for (i, item) in array.iter().enumerate() {
+            if !may_contain_offsets(&item) {
+                // arrays are homogeneous, so there is nothing to find here
+                return false;
+            }
+            if let Some(segment) = self.path.last_mut() {
+                segment.index = Some(i);
+            }
+            if self.visit_value(item, base, depth) {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Records the bytes used by a table or array, reporting it if it overlaps
+    // one seen earlier.
+    fn add_extent(&mut self, range: Range<usize>, type_name: &str) -> bool {
+        let extents = match self.extents.as_mut() {
+            Some(extents) if !range.is_empty() => extents,
+            _ => return false,
+        };
+        // shared tables are only visited once, but shared arrays are not
+        if let Some((end, name)) = extents.get(&range.start) {
+            if *end == range.end && name == type_name {
+                return false;
+            }
+        }
+        let before = extents
+            .range(..=range.start)
+            .next_back()
+            .filter(|(_, (end, _))| *end > range.start);
+        let after = extents.range(range.start..range.end).next();
+        match before.or(after).map(|(start, _)| *start) {
+            Some(other) => (self.report)(Problem::Overlap(other), &self.path, range.start),
+            None => {
+                extents.insert(range.start, (range.end, type_name.to_owned()));
+                false
+            }
+        }
+    }
+
+    fn visit_value(&mut self, value: FieldType, base: usize, depth: usize) -> bool {
+        match value {
+            FieldType::ResolvedOffset(offset) => {
+                let target_pos = base + offset.offset.to_u32() as usize;
+                match offset.target {
+                    Ok(table) => self.visit_table(&*table, target_pos, depth + 1),
+                    Err(error) => (self.report)(Problem::Read(error), &self.path, target_pos),
+                }
+            }
+            FieldType::ArrayOffset(offset) => {
+                let target_pos = base + offset.offset.to_u32() as usize;
+                match offset.target {
+                    Ok(array) => {
+                        if self.extents.is_some() {
+                            let len: Option<usize> = array.iter().map(|item| byte_len(&item)).sum();
+                            if let Some(len) = len {
+                                let range = target_pos..target_pos + len;
+                                if self.add_extent(range, array.type_name()) {
+                                    return true;
+                                }
+                            }
+                        }
+                        self.visit_array(&*array, target_pos, depth + 1)
+                    }
+                    Err(error) => (self.report)(Problem::Read(error), &self.path, target_pos),
+                }
+            }
+            FieldType::StringOffset(offset) => match offset.target {
+                Ok(_) => false,
+                Err(error) => {
+                    let target_pos = base + offset.offset.to_u32() as usize;
+                    (self.report)(Problem::Read(error), &self.path, target_pos)
+                }
+            },
+            // offsets in records are relative to the enclosing table
+            FieldType::Record(record) => self.visit_fields(&record, base, depth + 1),
+            FieldType::Array(array) => self.visit_array(&*array, base, depth),
+            _ => false,
+        }
+    }
+}
+
+/// The number of bytes at the start of a table with a known layout.
+fn known_len<'a>(table: &(dyn SomeTable<'a> + 'a)) -> usize {
+    let mut lens = table
+        .iter()
+        .map(|field| byte_len(&field.value))
+        .collect::<Vec<_>>();
+    // the last field runs to the end of the data, wherever that is
+    if table.is_open_ended() {
+        lens.pop();
+    }
+    lens.into_iter().map_while(|len| len).sum()
+}
+
+/// The number of bytes used by a value, if it can be determined.
+///
+/// For offsets, this is the size of the offset itself.
+fn byte_len(value: &FieldType) -> Option<usize> {
+    let len = match value {
+        FieldType::I8(_) | FieldType::U8(_) => 1,
+        FieldType::I16(_)
+        | FieldType::U16(_)
+        | FieldType::FWord(_)
+        | FieldType::UfWord(_)
+        | FieldType::F2Dot14(_)
+        | FieldType::GlyphId(_)
+        | FieldType::NameId(_) => 2,
+        FieldType::U24(_) => 3,
+        FieldType::I32(_)
+        | FieldType::U32(_)
+        | FieldType::Tag(_)
+        | FieldType::MajorMinor(_)
+        | FieldType::Version16Dot16(_)
+        | FieldType::Fixed(_) => 4,
+        FieldType::LongDateTime(_) => 8,
+        FieldType::BareOffset(offset) => offset_len(*offset),
+        FieldType::ResolvedOffset(offset) => offset_len(offset.offset),
+        FieldType::StringOffset(offset) => offset_len(offset.offset),
+        FieldType::ArrayOffset(offset) => offset_len(offset.offset),
+        FieldType::Record(record) => {
+            let record: &dyn SomeTable = record;
+            return record.iter().map(|field| byte_len(&field.value)).sum();
+        }
+        FieldType::Array(array) => return array.iter().map(|item| byte_len(&item)).sum(),
+        // these don't record the size of the underlying value
+        FieldType::Enum(_) | FieldType::Flags(_) | FieldType::Unknown => return None,
+    };
+    Some(len)
+}
+
+fn offset_len(offset: OffsetType) -> usize {
+    match offset {
+        OffsetType::Offset16(_) => 2,
+        OffsetType::Offset24(_) => 3,
+        OffsetType::Offset32(_) => 4,
+    }
+}
+
+fn may_contain_offsets(value: &FieldType) -> bool {
+    matches!(
+        value,
+        FieldType::ResolvedOffset(_)
+            | FieldType::ArrayOffset(_)
+            | FieldType::StringOffset(_)
+            | FieldType::Record(_)
+            | FieldType::Array(_)
+    )
+}
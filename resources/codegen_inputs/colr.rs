@@ -6,8 +6,10 @@
 table Colr {
     /// Table version number - set to 0 or 1.
     #[version]
+    #[compile(self.compute_version())]
     version: u16,
     /// Number of BaseGlyph records; may be 0 in a version 1 table.
+    #[compile(array_len($base_glyph_records_offset))]
     num_base_glyph_records: u16,
     /// Offset to baseGlyphRecords array (may be NULL).
     #[nullable]
@@ -18,6 +20,7 @@ table Colr {
     #[read_offset_with($num_layer_records)]
     layer_records_offset: Offset32<[Layer]>,
     /// Number of Layer records; may be 0 in a version 1 table.
+    #[compile(array_len($layer_records_offset))]
     num_layer_records: u16,
     /// Offset to BaseGlyphList table.
     #[since_version(1)]
@@ -61,6 +64,7 @@ record Layer {
 
 /// [BaseGlyphList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table BaseGlyphList {
+    #[compile(array_len($base_glyph_paint_records))]
     num_base_glyph_paint_records: u32,
     #[count($num_base_glyph_paint_records)]
     base_glyph_paint_records: [BaseGlyphPaint],
@@ -76,6 +80,7 @@ record BaseGlyphPaint {
 
 /// [LayerList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table LayerList {
+    #[compile(array_len($paint_offsets))]
     num_layers: u32,
     /// Offsets to Paint tables.
     #[count($num_layers)]
@@ -85,8 +90,10 @@ table LayerList {
 /// [ClipList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table ClipList {
     /// Set to 1.
+    #[compile(1)]
     format: u8,
     /// Number of Clip records.
+    #[compile(array_len($clips))]
     num_clips: u32,
     /// Clip records. Sorted by startGlyphID.
     #[count($num_clips)]
@@ -104,12 +111,14 @@ record Clip {
 }
 
 /// [ClipBox](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
+#[capabilites(equality, hash)]
 format u8 ClipBox {
     Format1(ClipBoxFormat1),
     Format2(ClipBoxFormat2),
 }
 
 /// [ClipBoxFormat1](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) record
+#[capabilites(equality, hash)]
 table ClipBoxFormat1 {
     /// Set to 1.
     #[format = 1]
@@ -125,6 +134,7 @@ table ClipBoxFormat1 {
 }
 
 /// [ClipBoxFormat2](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) record
+#[capabilites(equality, hash)]
 table ClipBoxFormat2 {
     /// Set to 2.
     #[format = 2]
@@ -160,6 +170,7 @@ record VarColorIndex {
 }
 
 /// [ColorStop](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) record
+#[capabilites(equality, hash)]
 record ColorStop {
     /// Position on a color line.
     stop_offset: F2Dot14,
@@ -170,6 +181,7 @@ record ColorStop {
 }
 
 /// [VarColorStop](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) record
+#[capabilites(equality, hash)]
 record VarColorStop {
     /// Position on a color line. For variation, use varIndexBase + 0.
     stop_offset: F2Dot14,
@@ -182,20 +194,24 @@ record VarColorStop {
 }
 
 /// [ColorLine](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) table
+#[capabilites(equality, hash)]
 table ColorLine {
     /// An Extend enum value.
     extend: Extend,
     /// Number of ColorStop records.
+    #[compile(array_len($color_stops))]
     num_stops: u16,
     #[count($num_stops)]
     color_stops: [ColorStop],
 }
 
 /// [VarColorLine](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline) table
+#[capabilites(equality, hash)]
 table VarColorLine {
     /// An Extend enum value.
     extend: Extend,
     /// Number of ColorStop records.
+    #[compile(array_len($color_stops))]
     num_stops: u16,
     /// Allows for variations.
     #[count($num_stops)]
@@ -211,6 +227,7 @@ enum u8 Extend {
 }
 
 /// [Paint](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#paint-tables) tables
+#[capabilites(equality, hash)]
 format u8 Paint {
     ColrLayers(PaintColrLayers),
     Solid(PaintSolid),
@@ -247,6 +264,7 @@ format u8 Paint {
 }
 
 /// [PaintColrLayers](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#format-1-paintcolrlayers) table
+#[capabilites(equality, hash)]
 table PaintColrLayers {
     /// Set to 1.
     #[format = 1]
@@ -258,6 +276,7 @@ table PaintColrLayers {
 }
 
 /// [PaintSolid](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-2-and-3-paintsolid-paintvarsolid) table
+#[capabilites(equality, hash)]
 table PaintSolid {
     /// Set to 2.
     #[format = 2]
@@ -269,6 +288,7 @@ table PaintSolid {
 }
 
 /// [PaintVarSolid](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-2-and-3-paintsolid-paintvarsolid) table
+#[capabilites(equality, hash)]
 table PaintVarSolid {
     /// Set to 3.
     #[format = 3]
@@ -282,6 +302,7 @@ table PaintVarSolid {
 }
 
 /// [PaintLinearGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-4-and-5-paintlineargradient-paintvarlineargradient) table
+#[capabilites(equality, hash)]
 table PaintLinearGradient {
     /// Set to 4.
     #[format = 4]
@@ -303,6 +324,7 @@ table PaintLinearGradient {
 }
 
 /// [PaintVarLinearGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-4-and-5-paintlineargradient-paintvarlineargradient) table
+#[capabilites(equality, hash)]
 table PaintVarLinearGradient {
     /// Set to 5.
     #[format = 5]
@@ -332,6 +354,7 @@ table PaintVarLinearGradient {
 }
 
 /// [PaintRadialGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-6-and-7-paintradialgradient-paintvarradialgradient) table
+#[capabilites(equality, hash)]
 table PaintRadialGradient {
     /// Set to 6.
     #[format = 6]
@@ -353,6 +376,7 @@ table PaintRadialGradient {
 }
 
 /// [PaintVarRadialGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-6-and-7-paintradialgradient-paintvarradialgradient) table
+#[capabilites(equality, hash)]
 table PaintVarRadialGradient {
     /// Set to 7.
     #[format = 7]
@@ -380,6 +404,7 @@ table PaintVarRadialGradient {
 }
 
 /// [PaintSweepGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-8-and-9-paintsweepgradient-paintvarsweepgradient) table
+#[capabilites(equality, hash)]
 table PaintSweepGradient {
     /// Set to 8.
     #[format = 8]
@@ -399,6 +424,7 @@ table PaintSweepGradient {
 }
 
 /// [PaintVarSweepGradient](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-8-and-9-paintsweepgradient-paintvarsweepgradient) table
+#[capabilites(equality, hash)]
 table PaintVarSweepGradient {
     /// Set to 9.
     #[format = 9]
@@ -422,17 +448,20 @@ table PaintVarSweepGradient {
 }
 
 /// [PaintGlyph](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#format-10-paintglyph) table
+#[capabilites(equality, hash)]
 table PaintGlyph {
     /// Set to 10.
     #[format = 10]
     format: u8,
     /// Offset to a Paint table.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Glyph ID for the source outline.
     glyph_id: GlyphId,
 }
 
 /// [PaintColrGlyph](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#format-11-paintcolrglyph) table
+#[capabilites(equality, hash)]
 table PaintColrGlyph {
     /// Set to 11.
     #[format = 11]
@@ -442,28 +471,33 @@ table PaintColrGlyph {
 }
 
 /// [PaintTransform](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-12-and-13-painttransform-paintvartransform) table
+#[capabilites(equality, hash)]
 table PaintTransform {
     /// Set to 12.
     #[format = 12]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Offset to an Affine2x3 table.
     transform_offset: Offset24<Affine2x3>,
 }
 
 /// [PaintVarTransform](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-12-and-13-painttransform-paintvartransform) table
+#[capabilites(equality, hash)]
 table PaintVarTransform {
     /// Set to 13.
     #[format = 13]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Offset to a VarAffine2x3 table.
     transform_offset: Offset24<VarAffine2x3>,
 }
 
 /// [Affine2x3](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-12-and-13-painttransform-paintvartransform) record
+#[capabilites(equality, hash)]
 table Affine2x3 {
     /// x-component of transformed x-basis vector.
     xx: Fixed,
@@ -480,6 +514,7 @@ table Affine2x3 {
 }
 
 /// [VarAffine2x3](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-12-and-13-painttransform-paintvartransform) record
+#[capabilites(equality, hash)]
 table VarAffine2x3 {
     /// x-component of transformed x-basis vector. For variation, use
     /// varIndexBase + 0.
@@ -502,11 +537,13 @@ table VarAffine2x3 {
 }
 
 /// [PaintTranslate](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-14-and-15-painttranslate-paintvartranslate) table
+#[capabilites(equality, hash)]
 table PaintTranslate {
     /// Set to 14.
     #[format = 14]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Translation in x direction.
     dx: FWord,
@@ -515,11 +552,13 @@ table PaintTranslate {
 }
 
 /// [PaintVarTranslate](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-14-and-15-painttranslate-paintvartranslate) table
+#[capabilites(equality, hash)]
 table PaintVarTranslate {
     /// Set to 15.
     #[format = 15]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Translation in x direction. For variation, use varIndexBase + 0.
     dx: FWord,
@@ -530,11 +569,13 @@ table PaintVarTranslate {
 }
 
 /// [PaintScale](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintScale {
     /// Set to 16.
     #[format = 16]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x direction.
     scale_x: F2Dot14,
@@ -543,11 +584,13 @@ table PaintScale {
 }
 
 /// [PaintVarScale](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintVarScale {
     /// Set to 17.
     #[format = 17]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x direction. For variation, use varIndexBase +
     /// 0.
@@ -560,11 +603,13 @@ table PaintVarScale {
 }
 
 /// [PaintScaleAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintScaleAroundCenter {
     /// Set to 18.
     #[format = 18]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x direction.
     scale_x: F2Dot14,
@@ -577,11 +622,13 @@ table PaintScaleAroundCenter {
 }
 
 /// [PaintVarScaleAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintVarScaleAroundCenter {
     /// Set to 19.
     #[format = 19]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x direction. For variation, use varIndexBase +
     /// 0.
@@ -600,22 +647,26 @@ table PaintVarScaleAroundCenter {
 }
 
 /// [PaintScaleUniform](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintScaleUniform {
     /// Set to 20.
     #[format = 20]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x and y directions.
     scale: F2Dot14,
 }
 
 /// [PaintVarScaleUniform](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintVarScaleUniform {
     /// Set to 21.
     #[format = 21]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x and y directions. For variation, use
     /// varIndexBase + 0.
@@ -625,11 +676,13 @@ table PaintVarScaleUniform {
 }
 
 /// [PaintScaleUniformAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintScaleUniformAroundCenter {
     /// Set to 22.
     #[format = 22]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x and y directions.
     scale: F2Dot14,
@@ -640,11 +693,13 @@ table PaintScaleUniformAroundCenter {
 }
 
 /// [PaintVarScaleUniformAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-16-to-23-paintscale-and-variant-scaling-formats) table
+#[capabilites(equality, hash)]
 table PaintVarScaleUniformAroundCenter {
     /// Set to 23.
     #[format = 23]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Scale factor in x and y directions. For variation, use
     /// varIndexBase + 0.
@@ -660,11 +715,13 @@ table PaintVarScaleUniformAroundCenter {
 }
 
 /// [PaintRotate](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-24-to-27-paintrotate-paintvarrotate-paintrotatearoundcenter-paintvarrotatearoundcenter) table
+#[capabilites(equality, hash)]
 table PaintRotate {
     /// Set to 24.
     #[format = 24]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
     /// value.
@@ -672,11 +729,13 @@ table PaintRotate {
 }
 
 /// [PaintVarRotate](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-24-to-27-paintrotate-paintvarrotate-paintrotatearoundcenter-paintvarrotatearoundcenter) table
+#[capabilites(equality, hash)]
 table PaintVarRotate {
     /// Set to 25.
     #[format = 25]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
     /// value. For variation, use varIndexBase + 0.
@@ -686,11 +745,13 @@ table PaintVarRotate {
 }
 
 /// [PaintRotateAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-24-to-27-paintrotate-paintvarrotate-paintrotatearoundcenter-paintvarrotatearoundcenter) table
+#[capabilites(equality, hash)]
 table PaintRotateAroundCenter {
     /// Set to 26.
     #[format = 26]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
     /// value.
@@ -702,11 +763,13 @@ table PaintRotateAroundCenter {
 }
 
 /// [PaintVarRotateAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-24-to-27-paintrotate-paintvarrotate-paintrotatearoundcenter-paintvarrotatearoundcenter) table
+#[capabilites(equality, hash)]
 table PaintVarRotateAroundCenter {
     /// Set to 27.
     #[format = 27]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Rotation angle, 180° in counter-clockwise degrees per 1.0 of
     /// value. For variation, use varIndexBase + 0.
@@ -722,11 +785,13 @@ table PaintVarRotateAroundCenter {
 }
 
 /// [PaintSkew](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-28-to-31-paintskew-paintvarskew-paintskewaroundcenter-paintvarskewaroundcenter) table
+#[capabilites(equality, hash)]
 table PaintSkew {
     /// Set to 28.
     #[format = 28]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Angle of skew in the direction of the x-axis, 180° in
     /// counter-clockwise degrees per 1.0 of value.
@@ -737,11 +802,13 @@ table PaintSkew {
 }
 
 /// [PaintVarSkew](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-28-to-31-paintskew-paintvarskew-paintskewaroundcenter-paintvarskewaroundcenter) table
+#[capabilites(equality, hash)]
 table PaintVarSkew {
     /// Set to 29.
     #[format = 29]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Angle of skew in the direction of the x-axis, 180° ┬░ in
     /// counter-clockwise degrees per 1.0 of value. For variation, use
@@ -756,11 +823,13 @@ table PaintVarSkew {
 }
 
 /// [PaintSkewAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-28-to-31-paintskew-paintvarskew-paintskewaroundcenter-paintvarskewaroundcenter) table
+#[capabilites(equality, hash)]
 table PaintSkewAroundCenter {
     /// Set to 30.
     #[format = 30]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Angle of skew in the direction of the x-axis, 180° in
     /// counter-clockwise degrees per 1.0 of value.
@@ -775,11 +844,13 @@ table PaintSkewAroundCenter {
 }
 
 /// [PaintVarSkewAroundCenter](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#formats-28-to-31-paintskew-paintvarskew-paintskewaroundcenter-paintvarskewaroundcenter) table
+#[capabilites(equality, hash)]
 table PaintVarSkewAroundCenter {
     /// Set to 31.
     #[format = 31]
     format: u8,
     /// Offset to a Paint subtable.
+    #[compile_boxed]
     paint_offset: Offset24<Paint>,
     /// Angle of skew in the direction of the x-axis, 180° in
     /// counter-clockwise degrees per 1.0 of value. For variation, use
@@ -800,15 +871,18 @@ table PaintVarSkewAroundCenter {
 }
 
 /// [PaintComposite](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#format-32-paintcomposite) table
+#[capabilites(equality, hash)]
 table PaintComposite {
     /// Set to 32.
     #[format = 32]
     format: u8,
     /// Offset to a source Paint table.
+    #[compile_boxed]
     source_paint_offset: Offset24<Paint>,
     /// A CompositeMode enumeration value.
     composite_mode: CompositeMode,
     /// Offset to a backdrop Paint table.
+    #[compile_boxed]
     backdrop_paint_offset: Offset24<Paint>,
 }
 
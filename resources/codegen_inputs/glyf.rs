@@ -134,9 +134,11 @@ flags u8 SimpleGlyphFlags {
     /// platforms.) When used, it must be set on the first flag byte
     /// for the glyph. See additional details below.
     OVERLAP_SIMPLE = 0x40,
-
-    ///// Bit 7 is reserved: set to zero.
-    //Reserved = 0x80,
+    /// Bit 7: Off-curve point belongs to a cubic-Bezier segment.
+    /// Only meaningful when ON_CURVE_POINT is not set. See the
+    /// [glyf-cubic](https://github.com/harfbuzz/boring-expansion-spec/blob/main/glyf1-cubicOutlines.md)
+    /// proposal for details.
+    CUBIC = 0x80,
 }
 
 /// [CompositeGlyph](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf#glyph-headers)
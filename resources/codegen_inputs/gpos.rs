@@ -154,6 +154,7 @@ table SinglePosFormat1 {
 }
 
 /// [Single Adjustment Positioning Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#single-adjustment-positioning-format-2-array-of-positioning-values): Array of Positioning Values
+#[validate(check_value_formats)]
 table SinglePosFormat2 {
     /// Format identifier: format = 2
     #[format = 2]
@@ -180,6 +181,7 @@ format u16 PairPos {
 }
 
 /// [Pair Adjustment Positioning Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#pair-adjustment-positioning-format-1-adjustments-for-glyph-pairs): Adjustments for Glyph Pairs
+#[validate(check_value_formats)]
 table PairPosFormat1 {
     /// Format identifier: format = 1
     #[format = 1]
@@ -232,6 +234,7 @@ record PairValueRecord {
 }
 
 /// [Pair Adjustment Positioning Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#pair-adjustment-positioning-format-2-class-pair-adjustment): Class Pair Adjustment
+#[validate(check_value_formats)]
 table PairPosFormat2 {
     /// Format identifier: format = 2
     #[format = 2]
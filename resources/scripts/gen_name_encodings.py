@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+"""Generate the legacy CJK decoding tables used by the read-fonts name table.
+
+Each table is written as a sequence of big-endian u16 unicode values: 128
+entries for the single byte codes 0x80..=0xFF, followed by a grid of
+(lead byte, trail byte) pairs covering the ranges listed below. A value of
+zero means the code is unmapped.
+
+The tables are derived from the codecs shipped with Python, which follow the
+Windows code pages used by fonts in the wild.
+
+usage: gen_name_encodings.py [OUT_DIR]
+"""
+
+import os
+import struct
+import sys
+
+# (file name, python codec, lead byte range, trail byte range)
+TABLES = [
+    ("shift_jis.bin", "cp932", (0x81, 0xFC), (0x40, 0xFC)),
+    ("big5.bin", "cp950", (0x81, 0xFE), (0x40, 0xFE)),
+    ("gbk.bin", "cp936", (0x81, 0xFE), (0x40, 0xFE)),
+    ("wansung.bin", "cp949", (0x81, 0xFE), (0x41, 0xFE)),
+]
+
+
+def decode_one(codec, raw):
+    try:
+        decoded = raw.decode(codec)
+    except UnicodeDecodeError:
+        return 0
+    if len(decoded) != 1 or ord(decoded) > 0xFFFF:
+        return 0
+    return ord(decoded)
+
+
+def main():
+    default_dir = os.path.join(
+        os.path.dirname(os.path.realpath(__file__)),
+        "../../read-fonts/src/tables/name",
+    )
+    out_dir = sys.argv[1] if len(sys.argv) > 1 else default_dir
+    for file_name, codec, (lead_lo, lead_hi), (trail_lo, trail_hi) in TABLES:
+        values = [decode_one(codec, bytes([b])) for b in range(0x80, 0x100)]
+        for lead in range(lead_lo, lead_hi + 1):
+            for trail in range(trail_lo, trail_hi + 1):
+                values.append(decode_one(codec, bytes([lead, trail])))
+        with open(os.path.join(out_dir, file_name), "wb") as f:
+            f.write(struct.pack(">%dH" % len(values), *values))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,104 @@
+#!/usr/bin/env python3
+"""Generate the Unicode script and block tables used by skrifa's coverage
+analysis.
+
+Reads Scripts.txt, Blocks.txt and PropertyValueAliases.txt from a directory
+containing the Unicode Character Database and writes
+skrifa/src/coverage/unicode_data.rs.
+
+usage: gen_unicode_coverage.py UCD_DIR [OUT_FILE]
+"""
+
+import os
+import re
+import sys
+
+LINE_RE = re.compile(r"^([0-9A-F]+)(?:\.\.([0-9A-F]+))?\s*;\s*([^#]+?)\s*(?:#.*)?$")
+
+
+def parse_ranges(path):
+    ranges = []
+    version = None
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            if version is None:
+                match = re.search(r"-(\d+\.\d+\.\d+)\.txt", line)
+                if match:
+                    version = match.group(1)
+            match = LINE_RE.match(line.strip())
+            if match:
+                first = int(match.group(1), 16)
+                last = int(match.group(2) or match.group(1), 16)
+                ranges.append((first, last, match.group(3)))
+    ranges.sort()
+    return version, ranges
+
+
+def parse_script_aliases(path):
+    aliases = {}
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            fields = [field.strip() for field in line.split("#")[0].split(";")]
+            if len(fields) >= 3 and fields[0] == "sc":
+                aliases[fields[2]] = fields[1]
+    return aliases
+
+
+def merge(ranges):
+    merged = []
+    for first, last, value in ranges:
+        if merged and merged[-1][2] == value and merged[-1][1] + 1 == first:
+            merged[-1] = (merged[-1][0], last, value)
+        else:
+            merged.append((first, last, value))
+    return merged
+
+
+def main():
+    ucd_dir = sys.argv[1]
+    default_out = os.path.join(
+        os.path.dirname(os.path.realpath(__file__)),
+        "../../skrifa/src/coverage/unicode_data.rs",
+    )
+    out_path = sys.argv[2] if len(sys.argv) > 2 else default_out
+    version, script_ranges = parse_ranges(os.path.join(ucd_dir, "Scripts.txt"))
+    _, blocks = parse_ranges(os.path.join(ucd_dir, "Blocks.txt"))
+    aliases = parse_script_aliases(os.path.join(ucd_dir, "PropertyValueAliases.txt"))
+    script_ranges = merge(script_ranges)
+    scripts = sorted({name for _, _, name in script_ranges})
+    script_index = {name: i for i, name in enumerate(scripts)}
+
+    out = []
+    out.append("// THIS FILE IS AUTOGENERATED.")
+    out.append("// Any changes to this file will be overwritten.")
+    out.append("// Generated by resources/scripts/gen_unicode_coverage.py")
+    out.append("")
+    out.append("/// Version of the Unicode Character Database used to generate this file.")
+    out.append(f'pub(super) const UNICODE_VERSION: &str = "{version}";')
+    out.append("")
+    out.append("/// ISO 15924 code and name for each script, sorted by name.")
+    out.append("pub(super) static SCRIPTS: &[([u8; 4], &str)] = &[")
+    for name in scripts:
+        out.append(f'    (*b"{aliases[name]}", "{name.replace("_", " ")}"),')
+    out.append("];")
+    out.append("")
+    out.append("/// First and last codepoints of each range with an assigned script, along")
+    out.append("/// with the index of the script in `SCRIPTS`, sorted by codepoint.")
+    out.append("#[rustfmt::skip]")
+    out.append("pub(super) static SCRIPT_RANGES: &[(u32, u32, u8)] = &[")
+    for first, last, name in script_ranges:
+        out.append(f"    (0x{first:04X}, 0x{last:04X}, {script_index[name]}),")
+    out.append("];")
+    out.append("")
+    out.append("/// First and last codepoints and name of each block, sorted by codepoint.")
+    out.append("#[rustfmt::skip]")
+    out.append("pub(super) static BLOCKS: &[(u32, u32, &str)] = &[")
+    for first, last, name in blocks:
+        out.append(f'    (0x{first:04X}, 0x{last:04X}, "{name}"),')
+    out.append("];")
+    with open(out_path, "w", encoding="utf-8") as f:
+        f.write("\n".join(out) + "\n")
+
+
+if __name__ == "__main__":
+    main()
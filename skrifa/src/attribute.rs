@@ -88,6 +88,310 @@ impl Attributes {
     }
 }
 
+/// Secondary attributes of a font, used for classification, licensing and
+/// coverage queries.
+///
+/// These are derived from values in the
+/// [OS/2](https://learn.microsoft.com/en-us/typography/opentype/spec/os2)
+/// table.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ExtendedAttributes {
+    /// Permissions for embedding the font in documents.
+    pub embedding: EmbeddingPermissions,
+    /// PANOSE classification of the font.
+    pub panose: Panose,
+    /// Identifier of the font vendor.
+    pub vendor_id: Tag,
+    /// Unicode ranges that the font claims to functionally cover.
+    pub unicode_ranges: UnicodeRanges,
+    /// Code pages that the font claims to functionally cover.
+    ///
+    /// This is empty for version 0 OS/2 tables.
+    pub code_page_ranges: CodePageRanges,
+    /// True if the typographic ascender, descender and line gap should be
+    /// used for default line spacing.
+    pub use_typo_metrics: bool,
+    /// True if the name table strings are consistent with a
+    /// weight/width/slope family without requiring name IDs 21 and 22.
+    pub wws: bool,
+    /// True if the font contains oblique glyphs.
+    pub oblique: bool,
+}
+
+impl ExtendedAttributes {
+    /// Extracts the extended attributes from the given font.
+    ///
+    /// Returns `None` if the font does not contain an OS/2 table.
+    pub fn new<'a>(font: &impl TableProvider<'a>) -> Option<Self> {
+        let os2 = font.os2().ok()?;
+        let mut panose = [0u8; 10];
+        panose.copy_from_slice(os2.panose_10());
+        let unicode_ranges = UnicodeRanges(
+            os2.ul_unicode_range_1() as u128
+                | (os2.ul_unicode_range_2() as u128) << 32
+                | (os2.ul_unicode_range_3() as u128) << 64
+                | (os2.ul_unicode_range_4() as u128) << 96,
+        );
+        let code_page_ranges = CodePageRanges(
+            os2.ul_code_page_range_1().unwrap_or_default() as u64
+                | (os2.ul_code_page_range_2().unwrap_or_default() as u64) << 32,
+        );
+        let fs_selection = os2.fs_selection();
+        Some(Self {
+            embedding: EmbeddingPermissions::from_fs_type(os2.fs_type()),
+            panose: Panose(panose),
+            vendor_id: os2.ach_vend_id(),
+            unicode_ranges,
+            code_page_ranges,
+            use_typo_metrics: fs_selection.contains(SelectionFlags::USE_TYPO_METRICS),
+            wws: fs_selection.contains(SelectionFlags::WWS),
+            oblique: fs_selection.contains(SelectionFlags::OBLIQUE),
+        })
+    }
+}
+
+/// Licensing restrictions on embedding a font in documents.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/os2#fstype>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct EmbeddingPermissions {
+    /// The embedding level.
+    pub level: EmbeddingLevel,
+    /// True if the font must not be subsetted prior to embedding.
+    pub no_subsetting: bool,
+    /// True if only bitmaps contained in the font may be embedded.
+    pub bitmap_only: bool,
+}
+
+impl EmbeddingPermissions {
+    /// Creates embedding permissions from the
+    /// [fsType](https://learn.microsoft.com/en-us/typography/opentype/spec/os2#fstype)
+    /// field of the OS/2 table.
+    pub fn from_fs_type(fs_type: u16) -> Self {
+        // Older fonts may set multiple level bits. The spec recommends
+        // that the least restrictive permission be used in that case.
+        let level = if fs_type & 0x0008 != 0 {
+            EmbeddingLevel::Editable
+        } else if fs_type & 0x0004 != 0 {
+            EmbeddingLevel::PreviewAndPrint
+        } else if fs_type & 0x0002 != 0 {
+            EmbeddingLevel::Restricted
+        } else {
+            EmbeddingLevel::Installable
+        };
+        Self {
+            level,
+            no_subsetting: fs_type & 0x0100 != 0,
+            bitmap_only: fs_type & 0x0200 != 0,
+        }
+    }
+}
+
+/// Level of permission for embedding a font in documents.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum EmbeddingLevel {
+    /// The font may be embedded and permanently installed on the remote
+    /// system.
+    #[default]
+    Installable,
+    /// The font must not be embedded without explicit permission from the
+    /// legal owner.
+    Restricted,
+    /// The font may be embedded in documents that are only viewed or
+    /// printed.
+    PreviewAndPrint,
+    /// The font may be embedded in documents that are viewed, printed and
+    /// edited.
+    Editable,
+}
+
+/// The ten digit [PANOSE](https://monotype.github.io/panose/) classification
+/// of a font.
+///
+/// The meaning of each digit after the first depends on the family kind. The
+/// accessors on this type are named according to the Latin Text
+/// classification.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Panose(pub [u8; 10]);
+
+impl Panose {
+    /// Returns the raw classification digits.
+    pub fn digits(self) -> [u8; 10] {
+        self.0
+    }
+
+    /// Returns the family kind, which determines the interpretation of the
+    /// remaining digits.
+    pub fn family_kind(self) -> PanoseFamily {
+        match self.0[0] {
+            0 => PanoseFamily::Any,
+            1 => PanoseFamily::NoFit,
+            2 => PanoseFamily::LatinText,
+            3 => PanoseFamily::LatinHandWritten,
+            4 => PanoseFamily::LatinDecorative,
+            5 => PanoseFamily::LatinSymbol,
+            other => PanoseFamily::Other(other),
+        }
+    }
+
+    /// Returns the serif style digit.
+    pub fn serif_style(self) -> u8 {
+        self.0[1]
+    }
+
+    /// Returns the weight digit.
+    pub fn weight(self) -> u8 {
+        self.0[2]
+    }
+
+    /// Returns the proportion digit.
+    pub fn proportion(self) -> u8 {
+        self.0[3]
+    }
+
+    /// Returns the contrast digit.
+    pub fn contrast(self) -> u8 {
+        self.0[4]
+    }
+
+    /// Returns the stroke variation digit.
+    pub fn stroke_variation(self) -> u8 {
+        self.0[5]
+    }
+
+    /// Returns the arm style digit.
+    pub fn arm_style(self) -> u8 {
+        self.0[6]
+    }
+
+    /// Returns the letterform digit.
+    pub fn letterform(self) -> u8 {
+        self.0[7]
+    }
+
+    /// Returns the midline digit.
+    pub fn midline(self) -> u8 {
+        self.0[8]
+    }
+
+    /// Returns the x-height digit.
+    pub fn x_height(self) -> u8 {
+        self.0[9]
+    }
+
+    /// Returns true if the classification indicates a monospaced font.
+    pub fn is_monospaced(self) -> bool {
+        // The proportion (or spacing) digit has a family specific value
+        // for monospaced designs.
+        match self.family_kind() {
+            PanoseFamily::LatinText => self.0[3] == 9,
+            PanoseFamily::LatinHandWritten | PanoseFamily::LatinSymbol => self.0[3] == 3,
+            _ => false,
+        }
+    }
+}
+
+/// Family kind of a PANOSE classification.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PanoseFamily {
+    Any,
+    NoFit,
+    LatinText,
+    LatinHandWritten,
+    LatinDecorative,
+    LatinSymbol,
+    Other(u8),
+}
+
+/// Set of Unicode ranges from the
+/// [ulUnicodeRange](https://learn.microsoft.com/en-us/typography/opentype/spec/os2#ulunicoderange1-bits-031ulunicoderange2-bits-3263ulunicoderange3-bits-6495ulunicoderange4-bits-96127)
+/// fields of the OS/2 table.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct UnicodeRanges(pub u128);
+
+impl UnicodeRanges {
+    /// Returns true if the given range is present in the set.
+    pub fn contains(self, range: UnicodeRange) -> bool {
+        self.0 & (1 << range.0) != 0
+    }
+
+    /// Returns an iterator over the defined ranges in the set.
+    pub fn iter(self) -> impl Iterator<Item = UnicodeRange> + Clone {
+        (0..UNICODE_RANGE_NAMES.len() as u8)
+            .map(UnicodeRange)
+            .filter(move |range| self.contains(*range))
+    }
+}
+
+/// A Unicode range, identified by its bit in the OS/2 table.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UnicodeRange(u8);
+
+impl UnicodeRange {
+    /// Returns the range for the given bit, or `None` if the bit is
+    /// reserved.
+    pub fn from_bit(bit: u8) -> Option<Self> {
+        ((bit as usize) < UNICODE_RANGE_NAMES.len()).then_some(Self(bit))
+    }
+
+    /// Returns the bit associated with the range.
+    pub fn bit(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the name of the primary Unicode block for the range.
+    pub fn name(self) -> &'static str {
+        UNICODE_RANGE_NAMES[self.0 as usize]
+    }
+}
+
+/// Set of code pages from the
+/// [ulCodePageRange](https://learn.microsoft.com/en-us/typography/opentype/spec/os2#ulcodepagerange1-bits-031ulcodepagerange2-bits-3263)
+/// fields of the OS/2 table.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct CodePageRanges(pub u64);
+
+impl CodePageRanges {
+    /// Returns true if the given code page is present in the set.
+    pub fn contains(self, code_page: CodePage) -> bool {
+        self.0 & (1 << CODE_PAGES[code_page.0].0) != 0
+    }
+
+    /// Returns an iterator over the defined code pages in the set.
+    pub fn iter(self) -> impl Iterator<Item = CodePage> + Clone {
+        (0..CODE_PAGES.len())
+            .map(CodePage)
+            .filter(move |code_page| self.contains(*code_page))
+    }
+}
+
+/// A character set, identified by its bit in the OS/2 table.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CodePage(usize);
+
+impl CodePage {
+    /// Returns the code page for the given bit, or `None` if the bit is
+    /// reserved.
+    pub fn from_bit(bit: u8) -> Option<Self> {
+        CODE_PAGES.iter().position(|entry| entry.0 == bit).map(Self)
+    }
+
+    /// Returns the bit associated with the code page.
+    pub fn bit(self) -> u8 {
+        CODE_PAGES[self.0].0
+    }
+
+    /// Returns the Windows code page number, if any.
+    pub fn number(self) -> Option<u16> {
+        CODE_PAGES[self.0].1
+    }
+
+    /// Returns a description of the code page.
+    pub fn name(self) -> &'static str {
+        CODE_PAGES[self.0].2
+    }
+}
+
 /// Visual width of a font-- a relative change from the normal aspect
 /// ratio, typically in the range 0.5 to 2.0.
 ///
@@ -253,6 +557,180 @@ impl Default for Weight {
     }
 }
 
+/// Names of the Unicode ranges, indexed by bit.
+const UNICODE_RANGE_NAMES: &[&str] = &[
+    "Basic Latin",
+    "Latin-1 Supplement",
+    "Latin Extended-A",
+    "Latin Extended-B",
+    "IPA Extensions",
+    "Spacing Modifier Letters",
+    "Combining Diacritical Marks",
+    "Greek and Coptic",
+    "Coptic",
+    "Cyrillic",
+    "Armenian",
+    "Hebrew",
+    "Vai",
+    "Arabic",
+    "NKo",
+    "Devanagari",
+    "Bengali",
+    "Gurmukhi",
+    "Gujarati",
+    "Oriya",
+    "Tamil",
+    "Telugu",
+    "Kannada",
+    "Malayalam",
+    "Thai",
+    "Lao",
+    "Georgian",
+    "Balinese",
+    "Hangul Jamo",
+    "Latin Extended Additional",
+    "Greek Extended",
+    "General Punctuation",
+    "Superscripts And Subscripts",
+    "Currency Symbols",
+    "Combining Diacritical Marks For Symbols",
+    "Letterlike Symbols",
+    "Number Forms",
+    "Arrows",
+    "Mathematical Operators",
+    "Miscellaneous Technical",
+    "Control Pictures",
+    "Optical Character Recognition",
+    "Enclosed Alphanumerics",
+    "Box Drawing",
+    "Block Elements",
+    "Geometric Shapes",
+    "Miscellaneous Symbols",
+    "Dingbats",
+    "CJK Symbols And Punctuation",
+    "Hiragana",
+    "Katakana",
+    "Bopomofo",
+    "Hangul Compatibility Jamo",
+    "Phags-pa",
+    "Enclosed CJK Letters And Months",
+    "CJK Compatibility",
+    "Hangul Syllables",
+    "Non-Plane 0",
+    "Phoenician",
+    "CJK Unified Ideographs",
+    "Private Use Area (plane 0)",
+    "CJK Strokes",
+    "Alphabetic Presentation Forms",
+    "Arabic Presentation Forms-A",
+    "Combining Half Marks",
+    "Vertical Forms",
+    "Small Form Variants",
+    "Arabic Presentation Forms-B",
+    "Halfwidth And Fullwidth Forms",
+    "Specials",
+    "Tibetan",
+    "Syriac",
+    "Thaana",
+    "Sinhala",
+    "Myanmar",
+    "Ethiopic",
+    "Cherokee",
+    "Unified Canadian Aboriginal Syllabics",
+    "Ogham",
+    "Runic",
+    "Khmer",
+    "Mongolian",
+    "Braille Patterns",
+    "Yi Syllables",
+    "Tagalog",
+    "Old Italic",
+    "Gothic",
+    "Deseret",
+    "Byzantine Musical Symbols",
+    "Mathematical Alphanumeric Symbols",
+    "Private Use (plane 15)",
+    "Variation Selectors",
+    "Tags",
+    "Limbu",
+    "Tai Le",
+    "New Tai Lue",
+    "Buginese",
+    "Glagolitic",
+    "Tifinagh",
+    "Yijing Hexagram Symbols",
+    "Syloti Nagri",
+    "Linear B Syllabary",
+    "Ancient Greek Numbers",
+    "Ugaritic",
+    "Old Persian",
+    "Shavian",
+    "Osmanya",
+    "Cypriot Syllabary",
+    "Kharoshthi",
+    "Tai Xuan Jing Symbols",
+    "Cuneiform",
+    "Counting Rod Numerals",
+    "Sundanese",
+    "Lepcha",
+    "Ol Chiki",
+    "Saurashtra",
+    "Kayah Li",
+    "Rejang",
+    "Cham",
+    "Ancient Symbols",
+    "Phaistos Disc",
+    "Carian",
+    "Domino Tiles",
+];
+
+/// Bit, Windows code page number and description for each defined code
+/// page.
+const CODE_PAGES: &[(u8, Option<u16>, &str)] = &[
+    (0, Some(1252), "Latin 1"),
+    (1, Some(1250), "Latin 2: Eastern Europe"),
+    (2, Some(1251), "Cyrillic"),
+    (3, Some(1253), "Greek"),
+    (4, Some(1254), "Turkish"),
+    (5, Some(1255), "Hebrew"),
+    (6, Some(1256), "Arabic"),
+    (7, Some(1257), "Windows Baltic"),
+    (8, Some(1258), "Vietnamese"),
+    (16, Some(874), "Thai"),
+    (17, Some(932), "JIS/Japan"),
+    (
+        18,
+        Some(936),
+        "Chinese: Simplified chars--PRC and Singapore",
+    ),
+    (19, Some(949), "Korean Wansung"),
+    (
+        20,
+        Some(950),
+        "Chinese: Traditional chars--Taiwan and Hong Kong",
+    ),
+    (21, Some(1361), "Korean Johab"),
+    (29, None, "Macintosh Character Set (US Roman)"),
+    (30, None, "OEM Character Set"),
+    (31, None, "Symbol Character Set"),
+    (48, Some(869), "IBM Greek"),
+    (49, Some(866), "MS-DOS Russian"),
+    (50, Some(865), "MS-DOS Nordic"),
+    (51, Some(864), "Arabic"),
+    (52, Some(863), "MS-DOS Canadian French"),
+    (53, Some(862), "Hebrew"),
+    (54, Some(861), "MS-DOS Icelandic"),
+    (55, Some(860), "MS-DOS Portuguese"),
+    (56, Some(857), "IBM Turkish"),
+    (57, Some(855), "IBM Cyrillic; primarily Russian"),
+    (58, Some(852), "Latin 2"),
+    (59, Some(775), "MS-DOS Baltic"),
+    (60, Some(737), "Greek; former 437 G"),
+    (61, Some(708), "Arabic; ASMO 708"),
+    (62, Some(850), "WE/Latin 1"),
+    (63, Some(437), "US"),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,4 +753,74 @@ mod tests {
         assert_eq!(attrs.style, Style::Oblique(Some(-14.0)));
         assert_eq!(attrs.weight, Weight::EXTRA_BOLD);
     }
+
+    #[test]
+    fn extended() {
+        let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
+        let attrs = font.extended_attributes().unwrap();
+        assert_eq!(attrs.embedding.level, EmbeddingLevel::Editable);
+        assert!(!attrs.embedding.no_subsetting);
+        assert_eq!(attrs.panose.family_kind(), PanoseFamily::LatinText);
+        assert_eq!(attrs.panose.weight(), 6);
+        assert!(!attrs.panose.is_monospaced());
+        assert_eq!(attrs.vendor_id, Tag::new(b"ADBE"));
+        assert!(attrs.oblique);
+        assert!(!attrs.use_typo_metrics);
+        let ranges = attrs
+            .unicode_ranges
+            .iter()
+            .map(|r| r.name())
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, ["Basic Latin"]);
+        let code_pages = attrs
+            .code_page_ranges
+            .iter()
+            .filter_map(|cp| cp.number())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            code_pages,
+            [
+                1252, 1250, 1251, 1253, 1254, 1255, 1256, 1257, 1258, 874, 932, 936, 949, 950,
+                1361, 869, 866, 865, 864, 863, 862, 861, 860, 857, 855, 852, 775, 737, 708, 850,
+                437
+            ]
+        );
+    }
+
+    #[test]
+    fn extended_use_typo_metrics() {
+        let font = FontRef::new(font_test_data::SIMPLE_GLYF).unwrap();
+        let attrs = font.extended_attributes().unwrap();
+        assert_eq!(attrs.embedding.level, EmbeddingLevel::PreviewAndPrint);
+        assert_eq!(attrs.panose.family_kind(), PanoseFamily::Any);
+        assert!(attrs.use_typo_metrics);
+        assert!(!attrs.oblique);
+        let latin_1 = CodePage::from_bit(0).unwrap();
+        assert_eq!(attrs.code_page_ranges.iter().collect::<Vec<_>>(), [latin_1]);
+    }
+
+    #[test]
+    fn missing_os2_extended() {
+        let font = FontRef::new(font_test_data::CMAP12_FONT1).unwrap();
+        assert!(font.extended_attributes().is_none());
+    }
+
+    #[test]
+    fn embedding_levels() {
+        let level = |fs_type| EmbeddingPermissions::from_fs_type(fs_type).level;
+        assert_eq!(level(0), EmbeddingLevel::Installable);
+        assert_eq!(level(0x2), EmbeddingLevel::Restricted);
+        assert_eq!(level(0x6), EmbeddingLevel::PreviewAndPrint);
+        assert_eq!(level(0xA), EmbeddingLevel::Editable);
+        let perms = EmbeddingPermissions::from_fs_type(0x302);
+        assert!(perms.no_subsetting && perms.bitmap_only);
+    }
+
+    #[test]
+    fn range_names() {
+        assert_eq!(UnicodeRange::from_bit(122).unwrap().name(), "Domino Tiles");
+        assert!(UnicodeRange::from_bit(123).is_none());
+        assert_eq!(CodePage::from_bit(17).unwrap().number(), Some(932));
+        assert!(CodePage::from_bit(9).is_none());
+    }
 }
@@ -19,6 +19,8 @@ use read_fonts::{
     FontData, TableProvider,
 };
 
+use crate::coverage::CodepointSet;
+
 pub use read_fonts::tables::cmap::MapVariant;
 
 /// Mapping of characters to nominal glyph identifiers.
@@ -51,7 +53,9 @@ pub struct Charmap<'a> {
 impl<'a> Charmap<'a> {
     /// Creates a new character map from the given font.
     pub fn new(font: &impl TableProvider<'a>) -> Self {
-        let Ok(cmap) = font.cmap() else { return Default::default() };
+        let Ok(cmap) = font.cmap() else {
+            return Default::default();
+        };
         let selection = MappingSelection::new(&cmap);
         Self {
             codepoint_subtable: selection
@@ -103,6 +107,30 @@ impl<'a> Charmap<'a> {
             .unwrap_or(Mappings(MappingsInner::None))
     }
 
+    /// Returns the ranges of codepoints that the subtable maps to a glyph
+    /// other than `.notdef`.
+    ///
+    /// These are computed from the segments of the subtable where possible,
+    /// rather than one codepoint at a time. The ranges are in subtable order
+    /// and may be adjacent.
+    pub(crate) fn mapped_ranges(&self) -> Vec<(u32, u32)> {
+        let mut ranges = vec![];
+        match self.codepoint_subtable.as_ref().map(|x| &x.subtable) {
+            Some(SupportedSubtable::Format4(cmap4)) => cmap4_ranges(cmap4, &mut ranges),
+            Some(SupportedSubtable::Format12(cmap12)) => cmap12_ranges(cmap12, &mut ranges),
+            None => {}
+        }
+        ranges
+    }
+
+    /// Returns the set of all codepoints mapped by the character map.
+    ///
+    /// See the [`coverage`](crate::coverage) module for script and block
+    /// coverage analysis.
+    pub fn coverage(&self) -> CodepointSet {
+        CodepointSet::from_charmap(self)
+    }
+
     /// Maps a character and variation selector to a nominal glyph identifier.
     ///
     /// Returns `None` if a mapping does not exist.
@@ -138,7 +166,9 @@ impl MappingIndex {
     /// Finds the indices of the most suitable Unicode mapping tables in the
     /// given font.
     pub fn new<'a>(font: &impl TableProvider<'a>) -> Self {
-        let Ok(cmap) = font.cmap() else { return Default::default() };
+        let Ok(cmap) = font.cmap() else {
+            return Default::default();
+        };
         MappingSelection::new(&cmap).mapping_index
     }
 
@@ -147,7 +177,9 @@ impl MappingIndex {
     ///
     /// The font should be the same as the one used to construct this object.
     pub fn charmap<'a>(&self, font: &impl TableProvider<'a>) -> Charmap<'a> {
-        let Ok(cmap) = font.cmap() else { return Default::default() };
+        let Ok(cmap) = font.cmap() else {
+            return Default::default();
+        };
         let records = cmap.encoding_records();
         let data = cmap.offset_data();
         Charmap {
@@ -254,6 +286,77 @@ impl<'a> CodepointSubtable<'a> {
     }
 }
 
+/// Pushes the ranges of codepoints mapped by a format 4 subtable.
+fn cmap4_ranges(cmap4: &Cmap4, ranges: &mut Vec<(u32, u32)>) {
+    let deltas = cmap4.id_delta();
+    let range_offsets = cmap4.id_range_offsets();
+    let glyph_ids = cmap4.glyph_id_array();
+    let segments = cmap4.start_code().iter().zip(cmap4.end_code());
+    for (index, (start, end)) in segments.enumerate() {
+        let (start, end) = (start.get() as u32, end.get() as u32);
+        if start > end {
+            continue;
+        }
+        // Cmap4Iter stops at the first segment it can't look up
+        let (delta, range_offset) = match (deltas.get(index), range_offsets.get(index)) {
+            (Some(delta), Some(range_offset)) => (delta.get() as i32, range_offset.get() as usize),
+            _ => return,
+        };
+        if range_offset == 0 {
+            // glyph ids wrap around, so one codepoint may map to 0
+            let notdef = (-(start as i32 + delta)).rem_euclid(0x10000) as u32;
+            push_excluding_notdef(ranges, start, end, notdef);
+            continue;
+        }
+        for codepoint in start..=end {
+            let offset = (range_offset / 2 + (codepoint - start) as usize)
+                .saturating_sub(range_offsets.len() - index);
+            let Some(glyph_id) = glyph_ids.get(offset) else {
+                return;
+            };
+            let glyph_id = glyph_id.get();
+            if glyph_id != 0 && (glyph_id as i32 + delta) as u16 != 0 {
+                match ranges.last_mut() {
+                    Some(last) if last.1 + 1 == codepoint => last.1 = codepoint,
+                    _ => ranges.push((codepoint, codepoint)),
+                }
+            }
+        }
+    }
+}
+
+/// Pushes the ranges of codepoints mapped by a format 12 subtable.
+fn cmap12_ranges(cmap12: &Cmap12, ranges: &mut Vec<(u32, u32)>) {
+    for group in cmap12.groups() {
+        let (start, end) = (group.start_char_code(), group.end_char_code());
+        if start > end {
+            continue;
+        }
+        // glyph ids are truncated to 16 bits, so every 65536th codepoint
+        // may map to 0
+        let notdef = 0x10000 - (group.start_glyph_id() & 0xFFFF);
+        push_excluding_notdef(ranges, start, end, notdef & 0xFFFF);
+    }
+}
+
+/// Pushes the range `first..=last`, excluding the codepoint `notdef_offset`
+/// after `first` and every 0x10000th codepoint after that.
+fn push_excluding_notdef(ranges: &mut Vec<(u32, u32)>, first: u32, last: u32, notdef_offset: u32) {
+    let (mut first, last) = (first as u64, last as u64);
+    let mut notdef = first + notdef_offset as u64;
+    while first <= last {
+        if notdef > last {
+            ranges.push((first as u32, last as u32));
+            break;
+        }
+        if notdef > first {
+            ranges.push((first as u32, notdef as u32 - 1));
+        }
+        first = notdef + 1;
+        notdef += 0x10000;
+    }
+}
+
 #[derive(Clone)]
 enum SupportedSubtable<'a> {
     Format4(Cmap4<'a>),
@@ -378,7 +481,7 @@ impl<'a> MappingSelection<'a> {
 mod tests {
     use super::*;
     use crate::MetadataProvider;
-    use read_fonts::FontRef;
+    use read_fonts::{FontRead, FontRef};
 
     #[test]
     fn choose_format_12_over_4() {
@@ -485,6 +588,97 @@ mod tests {
         }
     }
 
+    fn assert_ranges_match_mappings(ranges: &[(u32, u32)], mappings: impl Iterator<Item = u32>) {
+        let from_ranges = ranges
+            .iter()
+            .flat_map(|(first, last)| *first..=*last)
+            .collect::<Vec<_>>();
+        assert_eq!(from_ranges, mappings.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mapped_ranges() {
+        for font_data in [
+            font_test_data::VAZIRMATN_VAR,
+            font_test_data::CMAP12_FONT1,
+            font_test_data::SIMPLE_GLYF,
+            font_test_data::CMAP4_SYMBOL_PUA,
+        ] {
+            let font = FontRef::new(font_data).unwrap();
+            let charmap = font.charmap();
+            assert_ranges_match_mappings(
+                &charmap.mapped_ranges(),
+                charmap.mappings().map(|(cp, _)| cp),
+            );
+        }
+    }
+
+    #[test]
+    fn cmap4_ranges_skip_notdef() {
+        // a delta segment with U+25 mapped to glyph 0, a glyph id array
+        // segment with U+41 mapped to glyph 0, and the final segment
+        let words: [u16; 23] = [
+            4,
+            46,
+            0,
+            6,
+            4,
+            1,
+            2, // header
+            0x30,
+            0x42,
+            0xFFFF, // endCode
+            0,      // reservedPad
+            0x20,
+            0x40,
+            0xFFFF, // startCode
+            -0x25i16 as u16,
+            0,
+            1, // idDelta
+            0,
+            4,
+            0, // idRangeOffset
+            5,
+            0,
+            7, // glyphIdArray
+        ];
+        let data = words
+            .iter()
+            .flat_map(|w| w.to_be_bytes())
+            .collect::<Vec<_>>();
+        let cmap4 = Cmap4::read(FontData::new(&data)).unwrap();
+        let mut ranges = vec![];
+        cmap4_ranges(&cmap4, &mut ranges);
+        assert_eq!(
+            ranges,
+            [(0x20, 0x24), (0x26, 0x30), (0x40, 0x40), (0x42, 0x42)]
+        );
+        assert_ranges_match_mappings(
+            &ranges,
+            (0..=0xFFFF_u32)
+                .filter(|cp| cmap4.map_codepoint(*cp).unwrap_or_default() != GlyphId::NOTDEF),
+        );
+    }
+
+    #[test]
+    fn cmap12_ranges_skip_notdef() {
+        // one group whose glyph ids wrap around to 0 twice
+        let words: [u32; 7] = [12 << 16, 28, 0, 1, 0x40, 0x10050, 0x10000 - 0x10];
+        let data = words
+            .iter()
+            .flat_map(|w| w.to_be_bytes())
+            .collect::<Vec<_>>();
+        let cmap12 = Cmap12::read(FontData::new(&data)).unwrap();
+        let mut ranges = vec![];
+        cmap12_ranges(&cmap12, &mut ranges);
+        assert_eq!(ranges, [(0x40, 0x4F), (0x51, 0x1004F)]);
+        assert_ranges_match_mappings(
+            &ranges,
+            (0..=0x20000_u32)
+                .filter(|cp| cmap12.map_codepoint(*cp).unwrap_or_default() != GlyphId::NOTDEF),
+        );
+    }
+
     #[test]
     fn variant_mappings() {
         let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
@@ -0,0 +1,450 @@
+//! Unicode coverage and script support analysis.
+//!
+//! This provides a compact, range based set of codepoints along with
+//! reports describing how completely the set covers each Unicode script
+//! and block. This is useful for font fallback and for presenting language
+//! support in font pickers.
+//!
+//! # Example
+//! The following function will print the scripts supported by a font:
+//! ```
+//! use skrifa::MetadataProvider;
+//!
+//! fn print_scripts<'a>(font: &impl MetadataProvider<'a>) {
+//!     let coverage = font.charmap().coverage();
+//!     for script in coverage.scripts() {
+//!         println!(
+//!             "{} ({}): {}/{}",
+//!             script.script.name(),
+//!             script.script.tag(),
+//!             script.covered,
+//!             script.total
+//!         );
+//!     }
+//! }
+//! ```
+
+mod unicode_data;
+
+use super::{charmap::Charmap, Tag};
+use core::ops::RangeInclusive;
+
+/// Version of the Unicode Character Database used for script and block
+/// reports.
+pub const UNICODE_VERSION: &str = unicode_data::UNICODE_VERSION;
+
+/// Set of Unicode codepoints, stored as sorted, non-overlapping ranges.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct CodepointSet {
+    // Ranges are sorted, disjoint and non-adjacent.
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CodepointSet {
+    /// Creates a new empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new set containing all codepoints mapped by the given
+    /// character map.
+    pub fn from_charmap(charmap: &Charmap) -> Self {
+        // Subtables are generally sorted, but we don't rely on it.
+        Self::from_unsorted(charmap.mapped_ranges())
+    }
+
+    /// Creates a new set from a sequence of codepoint ranges.
+    ///
+    /// The ranges may be unsorted and overlapping.
+    pub fn from_ranges(ranges: impl IntoIterator<Item = RangeInclusive<u32>>) -> Self {
+        Self::from_unsorted(
+            ranges
+                .into_iter()
+                .filter(|range| !range.is_empty())
+                .map(|range| (*range.start(), *range.end()))
+                .collect(),
+        )
+    }
+
+    fn from_unsorted(mut ranges: Vec<(u32, u32)>) -> Self {
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (first, last) in ranges {
+            match merged.last_mut() {
+                Some(prev) if first <= prev.1.saturating_add(1) => prev.1 = prev.1.max(last),
+                _ => merged.push((first, last)),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Returns the number of codepoints in the set.
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|(first, last)| (last - first) as usize + 1)
+            .sum()
+    }
+
+    /// Returns true if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns true if the set contains the given codepoint.
+    pub fn contains(&self, codepoint: impl Into<u32>) -> bool {
+        let codepoint = codepoint.into();
+        self.ranges
+            .binary_search_by(|(first, last)| {
+                if codepoint < *first {
+                    core::cmp::Ordering::Greater
+                } else if codepoint > *last {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    /// Returns an iterator over the ranges of codepoints in the set, in
+    /// ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<u32>> + Clone + '_ {
+        self.ranges.iter().map(|(first, last)| *first..=*last)
+    }
+
+    /// Returns an iterator over the codepoints in the set, in ascending
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + Clone + '_ {
+        self.ranges().flatten()
+    }
+
+    /// Returns the set of codepoints contained in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_unsorted(
+            self.ranges
+                .iter()
+                .chain(other.ranges.iter())
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// Returns the set of codepoints contained in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let first = x.0.max(y.0);
+            let last = x.1.min(y.1);
+            if first <= last {
+                ranges.push((first, last));
+            }
+            if x.1 < y.1 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the set of codepoints contained in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+        for &(first, last) in &self.ranges {
+            // Start of the remaining portion of the current range, or
+            // `None` if it has been entirely removed.
+            let mut start = Some(first);
+            while let (Some(cur), Some(&&(other_first, other_last))) = (start, others.peek()) {
+                if other_last < cur {
+                    others.next();
+                    continue;
+                }
+                if other_first > last {
+                    break;
+                }
+                if other_first > cur {
+                    ranges.push((cur, other_first - 1));
+                }
+                if other_last >= last {
+                    // The other range may overlap the next range in the set,
+                    // so keep it.
+                    start = None;
+                } else {
+                    start = Some(other_last + 1);
+                    others.next();
+                }
+            }
+            if let Some(start) = start {
+                ranges.push((start, last));
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns true if every codepoint in `self` is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Returns a report describing the coverage of each Unicode script
+    /// that is at least partially covered by the set.
+    ///
+    /// The reports are ordered by script name.
+    pub fn scripts(&self) -> Vec<ScriptCoverage> {
+        let covered = self.count_overlaps(
+            unicode_data::SCRIPT_RANGES
+                .iter()
+                .map(|r| (r.0, r.1, r.2 as usize)),
+            unicode_data::SCRIPTS.len(),
+        );
+        let mut totals = vec![0u32; unicode_data::SCRIPTS.len()];
+        for (first, last, script) in unicode_data::SCRIPT_RANGES {
+            totals[*script as usize] += last - first + 1;
+        }
+        covered
+            .into_iter()
+            .zip(totals)
+            .enumerate()
+            .filter(|(_, (covered, _))| *covered != 0)
+            .map(|(index, (covered, total))| ScriptCoverage {
+                script: Script(index as u8),
+                covered,
+                total,
+            })
+            .collect()
+    }
+
+    /// Returns a report describing the coverage of each Unicode block that
+    /// is at least partially covered by the set.
+    ///
+    /// The reports are ordered by codepoint.
+    pub fn blocks(&self) -> Vec<BlockCoverage> {
+        let covered = self.count_overlaps(
+            unicode_data::BLOCKS
+                .iter()
+                .enumerate()
+                .map(|(index, block)| (block.0, block.1, index)),
+            unicode_data::BLOCKS.len(),
+        );
+        covered
+            .into_iter()
+            .enumerate()
+            .filter(|(_, covered)| *covered != 0)
+            .map(|(index, covered)| {
+                let (first, last, name) = unicode_data::BLOCKS[index];
+                BlockCoverage {
+                    name,
+                    range: first..=last,
+                    covered,
+                    total: last - first + 1,
+                }
+            })
+            .collect()
+    }
+
+    /// Counts the number of codepoints in the set that overlap each of the
+    /// given sorted, disjoint ranges, accumulating into a vector indexed by
+    /// the associated value.
+    fn count_overlaps(
+        &self,
+        ranges: impl Iterator<Item = (u32, u32, usize)>,
+        len: usize,
+    ) -> Vec<u32> {
+        let mut counts = vec![0u32; len];
+        let mut set_ranges = self.ranges.iter().peekable();
+        for (first, last, index) in ranges {
+            while let Some(&&(set_first, set_last)) = set_ranges.peek() {
+                if set_last < first {
+                    set_ranges.next();
+                    continue;
+                }
+                if set_first > last {
+                    break;
+                }
+                counts[index] += set_last.min(last) - set_first.max(first) + 1;
+                if set_last > last {
+                    break;
+                }
+                set_ranges.next();
+            }
+        }
+        counts
+    }
+}
+
+impl FromIterator<u32> for CodepointSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        Self::from_ranges(iter.into_iter().map(|codepoint| codepoint..=codepoint))
+    }
+}
+
+impl FromIterator<char> for CodepointSet {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        iter.into_iter().map(u32::from).collect()
+    }
+}
+
+/// A Unicode script.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Script(u8);
+
+impl Script {
+    /// Returns the script with the given ISO 15924 code, if any.
+    pub fn from_tag(tag: Tag) -> Option<Self> {
+        unicode_data::SCRIPTS
+            .iter()
+            .position(|(code, _)| Tag::new(code) == tag)
+            .map(|index| Self(index as u8))
+    }
+
+    /// Returns the ISO 15924 code for the script.
+    pub fn tag(self) -> Tag {
+        Tag::new(&unicode_data::SCRIPTS[self.0 as usize].0)
+    }
+
+    /// Returns the Unicode name for the script.
+    pub fn name(self) -> &'static str {
+        unicode_data::SCRIPTS[self.0 as usize].1
+    }
+}
+
+/// Coverage of a single Unicode script.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ScriptCoverage {
+    /// The script that this report describes.
+    pub script: Script,
+    /// Number of codepoints of the script in the set.
+    pub covered: u32,
+    /// Number of codepoints assigned to the script.
+    pub total: u32,
+}
+
+impl ScriptCoverage {
+    /// Returns the fraction of the script that is covered, in the range
+    /// 0.0 to 1.0.
+    pub fn ratio(&self) -> f32 {
+        self.covered as f32 / self.total as f32
+    }
+}
+
+/// Coverage of a single Unicode block.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BlockCoverage {
+    /// Name of the block.
+    pub name: &'static str,
+    /// Range of codepoints allocated to the block.
+    pub range: RangeInclusive<u32>,
+    /// Number of codepoints of the block in the set.
+    pub covered: u32,
+    /// Number of codepoints allocated to the block.
+    pub total: u32,
+}
+
+impl BlockCoverage {
+    /// Returns the fraction of the block that is covered, in the range
+    /// 0.0 to 1.0.
+    pub fn ratio(&self) -> f32 {
+        self.covered as f32 / self.total as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider;
+    use read_fonts::FontRef;
+
+    fn set(ranges: &[RangeInclusive<u32>]) -> CodepointSet {
+        CodepointSet::from_ranges(ranges.iter().cloned())
+    }
+
+    #[test]
+    fn from_charmap() {
+        let font = FontRef::new(font_test_data::CMAP12_FONT1).unwrap();
+        let coverage = font.charmap().coverage();
+        assert_eq!(
+            coverage.ranges().collect::<Vec<_>>(),
+            [0x101723..=0x101727, 0x102523..=0x102527]
+        );
+        assert_eq!(coverage.len(), 10);
+        assert!(coverage.contains(0x101725_u32));
+        assert!(!coverage.contains(0x101728_u32));
+        // Supplementary private use doesn't belong to a script
+        assert!(coverage.scripts().is_empty());
+    }
+
+    #[test]
+    fn script_and_block_reports() {
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let coverage = font.charmap().coverage();
+        let scripts = coverage
+            .scripts()
+            .iter()
+            .map(|cov| (cov.script.tag(), cov.covered))
+            .collect::<Vec<_>>();
+        assert_eq!(scripts, [(Tag::new(b"Zyyy"), 1), (Tag::new(b"Latn"), 2)]);
+        let blocks = coverage
+            .blocks()
+            .iter()
+            .map(|cov| (cov.name, cov.covered, cov.total))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            blocks,
+            [("Basic Latin", 2, 128), ("Latin-1 Supplement", 1, 128)]
+        );
+    }
+
+    #[test]
+    fn han_coverage() {
+        let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
+        let coverage = font.charmap().coverage();
+        let han = Script::from_tag(Tag::new(b"Hani")).unwrap();
+        assert_eq!(han.name(), "Han");
+        let scripts = coverage.scripts();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].script, han);
+        assert_eq!(scripts[0].covered, 10);
+        assert!(scripts[0].ratio() < 0.001);
+    }
+
+    #[test]
+    fn normalize() {
+        let s = set(&[10..=20, 5..=9, 30..=40, 15..=25]);
+        assert_eq!(s.ranges().collect::<Vec<_>>(), [5..=25, 30..=40]);
+        assert!(set(&[RangeInclusive::new(50, 49)]).is_empty());
+        let s: CodepointSet = "cab".chars().collect();
+        assert_eq!(s.ranges().collect::<Vec<_>>(), [97..=99]);
+        assert_eq!(s.iter().collect::<Vec<_>>(), [97, 98, 99]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..=10, 20..=30, 40..=50]);
+        let b = set(&[5..=25, 45..=45, 60..=70]);
+        assert_eq!(
+            a.union(&b).ranges().collect::<Vec<_>>(),
+            [0..=30, 40..=50, 60..=70]
+        );
+        assert_eq!(
+            a.intersection(&b).ranges().collect::<Vec<_>>(),
+            [5..=10, 20..=25, 45..=45]
+        );
+        assert_eq!(
+            a.difference(&b).ranges().collect::<Vec<_>>(),
+            [0..=4, 26..=30, 40..=44, 46..=50]
+        );
+        assert_eq!(
+            b.difference(&a).ranges().collect::<Vec<_>>(),
+            [11..=19, 60..=70]
+        );
+        assert!(set(&[21..=22, 45..=45]).is_subset(&a));
+        assert!(!b.is_subset(&a));
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+//! Discovery of alternate glyphs and ligatures through GSUB features.
+
+use read_fonts::{
+    tables::gsub::{
+        AlternateSet, AlternateSubstFormat1, ExtensionSubtable, LigatureSet, LigatureSubstFormat1,
+        SingleSubst, SubstitutionLookup,
+    },
+    types::GlyphId,
+    ResolveOffset,
+};
+
+use super::{LayoutFeatures, Tag};
+
+/// Glyph that can be substituted for another through a single or alternate
+/// substitution feature.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GlyphAlternate {
+    /// Tag of the feature that selects the alternate.
+    pub feature: Tag,
+    /// Identifier of the alternate glyph.
+    pub glyph_id: GlyphId,
+    /// Index of the alternate in its alternate set.
+    ///
+    /// This is always 0 for single substitutions. For alternate substitutions,
+    /// the alternate is selected by a feature value of `index + 1`.
+    pub index: u16,
+}
+
+/// Ligature that can be formed from a sequence of glyphs through a ligature
+/// substitution feature.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GlyphLigature {
+    /// Tag of the feature that forms the ligature.
+    pub feature: Tag,
+    /// Identifier of the ligature glyph.
+    pub glyph_id: GlyphId,
+    /// Sequence of glyphs that are replaced by the ligature, including the
+    /// first glyph.
+    pub components: Vec<GlyphId>,
+}
+
+impl<'a> LayoutFeatures<'a> {
+    /// Returns the alternates for the given glyph that are reachable through
+    /// the single and alternate substitution lookups of each GSUB feature.
+    ///
+    /// Alternates are listed in feature list order. Substitutions applied
+    /// only through contextual lookups are not included.
+    pub fn alternates(&self, glyph_id: GlyphId) -> Vec<GlyphAlternate> {
+        let mut alternates = vec![];
+        self.visit_subtables(|feature, subtable| {
+            let mut push = |glyph_id, index| {
+                let alternate = GlyphAlternate {
+                    feature,
+                    glyph_id,
+                    index,
+                };
+                if !alternates.contains(&alternate) {
+                    alternates.push(alternate);
+                }
+            };
+            match subtable {
+                Subtable::Single(subst) => {
+                    if let Some(alternate) = single_subst(&subst, glyph_id) {
+                        push(alternate, 0);
+                    }
+                }
+                Subtable::Alternate(subst) => {
+                    for (index, alternate) in alternate_subst(&subst, glyph_id)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                    {
+                        push(alternate, index as u16);
+                    }
+                }
+                Subtable::Ligature(_) => {}
+            }
+        });
+        alternates
+    }
+
+    /// Returns the ligatures that start with the given glyph and are formed
+    /// through the ligature substitution lookups of each GSUB feature.
+    pub fn ligatures(&self, glyph_id: GlyphId) -> Vec<GlyphLigature> {
+        let mut ligatures = vec![];
+        self.visit_subtables(|feature, subtable| {
+            let Subtable::Ligature(subst) = subtable else {
+                return;
+            };
+            let Some(set): Option<LigatureSet> = subst
+                .coverage()
+                .ok()
+                .and_then(|coverage| coverage.get(glyph_id))
+                .and_then(|index| subst.ligature_set_offsets().get(index as usize))
+                .and_then(|offset| offset.get().resolve(subst.offset_data()).ok())
+            else {
+                return;
+            };
+            for ligature in set.ligatures().filter_map(|ligature| ligature.ok()) {
+                let ligature = GlyphLigature {
+                    feature,
+                    glyph_id: ligature.ligature_glyph(),
+                    components: core::iter::once(glyph_id)
+                        .chain(ligature.component_glyph_ids().iter().map(|id| id.get()))
+                        .collect(),
+                };
+                if !ligatures.contains(&ligature) {
+                    ligatures.push(ligature);
+                }
+            }
+        });
+        ligatures
+    }
+
+    /// Invokes the visitor for each single, alternate and ligature
+    /// substitution subtable referenced by a GSUB feature.
+    fn visit_subtables(&self, mut visitor: impl FnMut(Tag, Subtable<'a>)) {
+        let (Some(gsub), Some(lookups)) = (self.gsub.as_ref(), self.gsub_lookups.as_ref()) else {
+            return;
+        };
+        for feature in gsub.features() {
+            for index in feature.lookup_indices() {
+                let Some(lookup) = lookups
+                    .lookup_offsets()
+                    .get(index as usize)
+                    .and_then(|offset| offset.get().resolve(lookups.offset_data()).ok())
+                else {
+                    continue;
+                };
+                let tag = feature.tag();
+                match lookup {
+                    SubstitutionLookup::Single(lookup) => lookup
+                        .subtables()
+                        .filter_map(|subtable| subtable.ok())
+                        .for_each(|subtable| visitor(tag, Subtable::Single(subtable))),
+                    SubstitutionLookup::Alternate(lookup) => lookup
+                        .subtables()
+                        .filter_map(|subtable| subtable.ok())
+                        .for_each(|subtable| visitor(tag, Subtable::Alternate(subtable))),
+                    SubstitutionLookup::Ligature(lookup) => lookup
+                        .subtables()
+                        .filter_map(|subtable| subtable.ok())
+                        .for_each(|subtable| visitor(tag, Subtable::Ligature(subtable))),
+                    SubstitutionLookup::Extension(lookup) => {
+                        for subtable in lookup.subtables().filter_map(|subtable| subtable.ok()) {
+                            let subtable = match subtable {
+                                ExtensionSubtable::Single(ext) => {
+                                    ext.extension().map(Subtable::Single)
+                                }
+                                ExtensionSubtable::Alternate(ext) => {
+                                    ext.extension().map(Subtable::Alternate)
+                                }
+                                ExtensionSubtable::Ligature(ext) => {
+                                    ext.extension().map(Subtable::Ligature)
+                                }
+                                _ => continue,
+                            };
+                            if let Ok(subtable) = subtable {
+                                visitor(tag, subtable);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// GSUB subtables that may produce alternates or ligatures.
+enum Subtable<'a> {
+    Single(SingleSubst<'a>),
+    Alternate(AlternateSubstFormat1<'a>),
+    Ligature(LigatureSubstFormat1<'a>),
+}
+
+fn single_subst(subst: &SingleSubst, glyph_id: GlyphId) -> Option<GlyphId> {
+    match subst {
+        SingleSubst::Format1(subst) => {
+            subst.coverage().ok()?.get(glyph_id)?;
+            // Addition is modulo 65536
+            let delta = subst.delta_glyph_id() as u16;
+            Some(GlyphId::new(glyph_id.to_u16().wrapping_add(delta)))
+        }
+        SingleSubst::Format2(subst) => {
+            let index = subst.coverage().ok()?.get(glyph_id)?;
+            subst
+                .substitute_glyph_ids()
+                .get(index as usize)
+                .map(|id| id.get())
+        }
+    }
+}
+
+fn alternate_subst<'a>(
+    subst: &AlternateSubstFormat1<'a>,
+    glyph_id: GlyphId,
+) -> Option<impl Iterator<Item = GlyphId> + 'a> {
+    let index = subst.coverage().ok()?.get(glyph_id)?;
+    let set: AlternateSet = subst
+        .alternate_set_offsets()
+        .get(index as usize)?
+        .get()
+        .resolve(subst.offset_data())
+        .ok()?;
+    Some(set.alternate_glyph_ids().iter().map(|id| id.get()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider;
+    use read_fonts::FontRef;
+
+    fn alternate(feature: &[u8; 4], glyph_id: u16, index: u16) -> GlyphAlternate {
+        GlyphAlternate {
+            feature: Tag::new(feature),
+            glyph_id: GlyphId::new(glyph_id),
+            index,
+        }
+    }
+
+    #[test]
+    fn alternates() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let features = font.layout_features();
+        // glyph 1 is "a"
+        assert_eq!(
+            features.alternates(GlyphId::new(1)),
+            [
+                alternate(b"cv01", 8, 0),
+                alternate(b"cv01", 9, 1),
+                alternate(b"salt", 8, 0),
+                alternate(b"salt", 9, 1),
+                alternate(b"smcp", 10, 0),
+                alternate(b"ss01", 8, 0),
+            ]
+        );
+        // glyph 2 is "b"
+        assert_eq!(
+            features.alternates(GlyphId::new(2)),
+            [alternate(b"smcp", 11, 0)]
+        );
+        assert!(features.alternates(GlyphId::new(3)).is_empty());
+    }
+
+    #[test]
+    fn ligatures() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let features = font.layout_features();
+        let ligature = |glyph_id, components: &[u16]| GlyphLigature {
+            feature: Tag::new(b"liga"),
+            glyph_id: GlyphId::new(glyph_id),
+            components: components.iter().copied().map(GlyphId::new).collect(),
+        };
+        // glyph 4 is "f"
+        assert_eq!(
+            features.ligatures(GlyphId::new(4)),
+            [ligature(7, &[4, 4, 5]), ligature(6, &[4, 5])]
+        );
+        assert!(features.ligatures(GlyphId::new(5)).is_empty());
+    }
+
+    #[test]
+    fn missing_gsub() {
+        let font = FontRef::new(font_test_data::NAMES_ONLY).unwrap();
+        let features = font.layout_features();
+        assert!(features.alternates(GlyphId::new(1)).is_empty());
+        assert!(features.ligatures(GlyphId::new(1)).is_empty());
+    }
+}
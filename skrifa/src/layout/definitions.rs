@@ -0,0 +1,193 @@
+//! Glyph classes and ligature carets from the GDEF table.
+
+use read_fonts::{
+    tables::{
+        gdef::{CaretValue, ClassDef, GlyphClassDef, LigCaretList},
+        variations::ItemVariationStore,
+    },
+    types::GlyphId,
+    TableProvider,
+};
+
+#[cfg(feature = "scale")]
+use crate::scale::Scaler;
+
+/// Glyph classification and ligature caret data from the
+/// [GDEF](https://learn.microsoft.com/en-us/typography/opentype/spec/gdef)
+/// table of a font.
+pub struct GlyphDefinitions<'a> {
+    glyph_classes: Option<ClassDef<'a>>,
+    mark_attachment_classes: Option<ClassDef<'a>>,
+    lig_carets: Option<LigCaretList<'a>>,
+    var_store: Option<ItemVariationStore<'a>>,
+    units_per_em: u16,
+}
+
+impl<'a> GlyphDefinitions<'a> {
+    /// Creates a new glyph definition collection for the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        let units_per_em = font
+            .head()
+            .map(|head| head.units_per_em())
+            .unwrap_or_default();
+        let Ok(gdef) = font.gdef() else {
+            return Self {
+                glyph_classes: None,
+                mark_attachment_classes: None,
+                lig_carets: None,
+                var_store: None,
+                units_per_em,
+            };
+        };
+        Self {
+            glyph_classes: gdef.glyph_class_def().and_then(|table| table.ok()),
+            mark_attachment_classes: gdef.mark_attach_class_def().and_then(|table| table.ok()),
+            lig_carets: gdef.lig_caret_list().and_then(|table| table.ok()),
+            var_store: gdef.item_var_store().and_then(|table| table.ok()),
+            units_per_em,
+        }
+    }
+
+    /// Returns the class of the given glyph.
+    ///
+    /// Returns `None` if the font does not define a glyph class for the
+    /// glyph.
+    pub fn glyph_class(&self, glyph_id: GlyphId) -> Option<GlyphClassDef> {
+        match self.glyph_classes.as_ref()?.get(glyph_id) {
+            0 => None,
+            class => Some(GlyphClassDef::new(class)),
+        }
+    }
+
+    /// Returns the mark attachment class of the given glyph.
+    ///
+    /// This is zero for glyphs that are not assigned a class.
+    pub fn mark_attachment_class(&self, glyph_id: GlyphId) -> u16 {
+        self.mark_attachment_classes
+            .as_ref()
+            .map(|classes| classes.get(glyph_id))
+            .unwrap_or_default()
+    }
+
+    /// Returns the caret positions for the given ligature glyph at the size
+    /// and location of the scaler.
+    ///
+    /// Positions are x-coordinates for horizontal text, in pixels for a
+    /// scaled size or in font units otherwise. Carets that refer to a
+    /// contour point are resolved using the outline loaded by the scaler
+    /// and are omitted if the point is not available.
+    #[cfg(feature = "scale")]
+    pub fn ligature_carets(&self, glyph_id: GlyphId, scaler: &mut Scaler) -> Vec<f32> {
+        let Some(lig_glyph) = self.lig_carets.as_ref().and_then(|lig_carets| {
+            let index = lig_carets.coverage().ok()?.get(glyph_id)?;
+            lig_carets.lig_glyphs().nth(index as usize)?.ok()
+        }) else {
+            return vec![];
+        };
+        let size = scaler.size();
+        let scale = size.linear_scale(self.units_per_em);
+        let coords = scaler.normalized_coords();
+        let mut carets = vec![];
+        for caret in lig_glyph.caret_values().filter_map(|caret| caret.ok()) {
+            match caret {
+                CaretValue::Format1(caret) => carets.push(caret.coordinate() as f32 * scale),
+                CaretValue::Format2(caret) => {
+                    if let Ok(point) =
+                        scaler.outline_point(glyph_id, caret.caret_value_point_index())
+                    {
+                        carets.push(point.x);
+                    }
+                }
+                CaretValue::Format3(caret) => {
+                    let ppem = size.ppem().map(|ppem| ppem.round() as u16);
+                    let delta = caret
+                        .device()
+                        .and_then(|device| device.resolve(ppem, coords, self.var_store.as_ref()))
+                        .unwrap_or_default();
+                    carets.push(delta.apply(caret.coordinate() as i32, scale));
+                }
+            }
+        }
+        carets
+    }
+}
+
+#[cfg(all(test, feature = "scale"))]
+mod tests {
+    use super::*;
+    use crate::{instance::Size, scale::Context, MetadataProvider};
+    use read_fonts::{types::F2Dot14, FontRef};
+
+    #[test]
+    fn glyph_classes() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let definitions = font.glyph_definitions();
+        let class = |gid| definitions.glyph_class(GlyphId::new(gid));
+        assert_eq!(class(0), None);
+        assert_eq!(class(1), Some(GlyphClassDef::Base));
+        assert_eq!(class(6), Some(GlyphClassDef::Ligature));
+        assert_eq!(class(12), Some(GlyphClassDef::Mark));
+        assert_eq!(class(13), Some(GlyphClassDef::Ligature));
+        assert_eq!(definitions.mark_attachment_class(GlyphId::new(12)), 1);
+        assert_eq!(definitions.mark_attachment_class(GlyphId::new(1)), 0);
+    }
+
+    #[test]
+    fn unscaled_carets() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let definitions = font.glyph_definitions();
+        let mut context = Context::new();
+        let mut scaler = context.new_scaler().build(&font);
+        let mut carets = |gid| definitions.ligature_carets(GlyphId::new(gid), &mut scaler);
+        // f_i: variation index delta is zero at the default location
+        assert_eq!(carets(6), [250.0]);
+        // f_f_i: coordinate and contour point
+        assert_eq!(carets(7), [300.0, 600.0]);
+        // f_f: device adjustments only apply to scaled sizes
+        assert_eq!(carets(13), [280.0]);
+        assert!(carets(1).is_empty());
+    }
+
+    #[test]
+    fn scaled_carets() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let definitions = font.glyph_definitions();
+        let mut context = Context::new();
+        let mut scaler = context.new_scaler().size(Size::new(12.0)).build(&font);
+        let f_f_i = definitions.ligature_carets(GlyphId::new(7), &mut scaler);
+        assert!((f_f_i[0] - 3.6).abs() < 1e-4);
+        assert!((f_f_i[1] - 7.2).abs() < 1.0 / 64.0);
+        // f_f: 280 units at 12ppem with a device adjustment of -1
+        let f_f = definitions.ligature_carets(GlyphId::new(13), &mut scaler);
+        assert!((f_f[0] - 2.36).abs() < 1e-4);
+    }
+
+    #[test]
+    fn variable_carets() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let definitions = font.glyph_definitions();
+        let mut context = Context::new();
+        let mut scaler = context
+            .new_scaler()
+            .normalized_coords([F2Dot14::from_f32(0.5)])
+            .build(&font);
+        // f_i: 250 plus half of the 50 unit delta
+        assert_eq!(
+            definitions.ligature_carets(GlyphId::new(6), &mut scaler),
+            [275.0]
+        );
+    }
+
+    #[test]
+    fn missing_gdef() {
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let definitions = font.glyph_definitions();
+        assert_eq!(definitions.glyph_class(GlyphId::new(1)), None);
+        assert_eq!(definitions.mark_attachment_class(GlyphId::new(1)), 0);
+        let mut context = Context::new();
+        let mut scaler = context.new_scaler().build(&font);
+        assert!(definitions
+            .ligature_carets(GlyphId::new(1), &mut scaler)
+            .is_empty());
+    }
+}
@@ -0,0 +1,698 @@
+//! OpenType layout scripts, language systems and features.
+//!
+//! This provides a unified view of the
+//! [script](https://learn.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record),
+//! [language system](https://learn.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table)
+//! and [feature](https://learn.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-list-table)
+//! lists in the GSUB and GPOS tables, along with the user interface strings
+//! associated with stylistic sets, character variants and optical size
+//! features.
+//!
+//! # Example
+//! The following function will print the features available for the Latin
+//! script using the default language system:
+//! ```
+//! use skrifa::{MetadataProvider, Tag};
+//!
+//! fn print_latin_features<'a>(font: &impl MetadataProvider<'a>) {
+//!     let features = font.layout_features();
+//!     for feature in features.features(Tag::new(b"latn"), None) {
+//!         print!("{} ({:?})", feature.tag(), feature.table());
+//!         if let Some(name) = feature.ui_name().and_then(|name| name.english_or_first()) {
+//!             print!(": {name}");
+//!         }
+//!         println!();
+//!     }
+//! }
+//! ```
+
+use read_fonts::{
+    tables::{
+        gsub::SubstitutionLookupList,
+        layout::{
+            CharacterVariantParams, Feature as RawFeature, FeatureList,
+            FeatureParams as RawFeatureParams, LangSys as RawLangSys, Script as RawScript,
+            ScriptList,
+        },
+        name::Name,
+    },
+    TableProvider,
+};
+
+use super::{
+    string::{LocalizedStrings, StringId},
+    Tag,
+};
+
+use core::ops::RangeInclusive;
+
+mod alternates;
+mod definitions;
+
+pub use alternates::{GlyphAlternate, GlyphLigature};
+pub use definitions::GlyphDefinitions;
+
+/// Tag for the default script.
+const DFLT: Tag = Tag::new(b"DFLT");
+
+/// Layout table that contains a script, language system or feature.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum LayoutTableKind {
+    /// Glyph substitution table.
+    Gsub,
+    /// Glyph positioning table.
+    Gpos,
+}
+
+/// Scripts, language systems and features from the GSUB and GPOS tables of
+/// a font.
+#[derive(Clone)]
+pub struct LayoutFeatures<'a> {
+    gsub: Option<LayoutTable<'a>>,
+    gpos: Option<LayoutTable<'a>>,
+    gsub_lookups: Option<SubstitutionLookupList<'a>>,
+}
+
+impl<'a> LayoutFeatures<'a> {
+    /// Creates a new layout feature collection for the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        let name = font.name().ok();
+        let gsub_table = font.gsub().ok();
+        let gsub_lookups = gsub_table.as_ref().and_then(|gsub| gsub.lookup_list().ok());
+        let gsub = gsub_table.and_then(|gsub| {
+            Some(LayoutTable::new(
+                LayoutTableKind::Gsub,
+                gsub.script_list().ok()?,
+                gsub.feature_list().ok()?,
+                name.clone(),
+            ))
+        });
+        let gpos = font.gpos().ok().and_then(|gpos| {
+            Some(LayoutTable::new(
+                LayoutTableKind::Gpos,
+                gpos.script_list().ok()?,
+                gpos.feature_list().ok()?,
+                name,
+            ))
+        });
+        Self {
+            gsub,
+            gpos,
+            gsub_lookups,
+        }
+    }
+
+    /// Returns the layout table of the given kind, if present.
+    pub fn table(&self, kind: LayoutTableKind) -> Option<&LayoutTable<'a>> {
+        match kind {
+            LayoutTableKind::Gsub => self.gsub.as_ref(),
+            LayoutTableKind::Gpos => self.gpos.as_ref(),
+        }
+    }
+
+    /// Returns an iterator over the layout tables present in the font.
+    pub fn tables(&self) -> impl Iterator<Item = &LayoutTable<'a>> + Clone {
+        self.gsub.iter().chain(self.gpos.iter())
+    }
+
+    /// Returns the sorted set of script tags defined in either table.
+    pub fn scripts(&self) -> Vec<Tag> {
+        let mut tags = self
+            .tables()
+            .flat_map(|table| table.scripts().map(|script| script.tag()))
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Returns the sorted set of language system tags defined for the given
+    /// script in either table.
+    ///
+    /// The default language system is not included.
+    pub fn languages(&self, script: Tag) -> Vec<Tag> {
+        let mut tags = self
+            .tables()
+            .filter_map(|table| table.script(script))
+            .flat_map(|script| script.languages().map(|lang_sys| lang_sys.tag()))
+            .flatten()
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Returns the features from both tables that apply to the given script
+    /// and language.
+    ///
+    /// Missing scripts and languages fall back to the `DFLT` script and the
+    /// default language system respectively, following the selection process
+    /// used during shaping. A language of `None` selects the default language
+    /// system. GSUB features are listed before GPOS features and the required
+    /// feature, if any, is listed first for each table.
+    pub fn features(&self, script: Tag, language: Option<Tag>) -> Vec<Feature<'a>> {
+        self.tables()
+            .filter_map(|table| table.select(script, language))
+            .flat_map(|lang_sys| {
+                lang_sys
+                    .required_feature()
+                    .into_iter()
+                    .chain(lang_sys.features())
+            })
+            .collect()
+    }
+
+    /// Returns the sorted set of feature tags from both tables that apply to
+    /// the given script and language.
+    ///
+    /// See [`features`](Self::features) for details on the selection of
+    /// the script and language system.
+    pub fn feature_tags(&self, script: Tag, language: Option<Tag>) -> Vec<Tag> {
+        let mut tags = self
+            .features(script, language)
+            .iter()
+            .map(|feature| feature.tag())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Returns the sorted set of all feature tags defined in either table.
+    pub fn all_feature_tags(&self) -> Vec<Tag> {
+        let mut tags = self
+            .tables()
+            .flat_map(|table| table.features().map(|feature| feature.tag()))
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+}
+
+/// Script and feature lists for a single GSUB or GPOS table.
+#[derive(Clone)]
+pub struct LayoutTable<'a> {
+    kind: LayoutTableKind,
+    script_list: ScriptList<'a>,
+    feature_list: FeatureList<'a>,
+    name: Option<Name<'a>>,
+}
+
+impl<'a> LayoutTable<'a> {
+    fn new(
+        kind: LayoutTableKind,
+        script_list: ScriptList<'a>,
+        feature_list: FeatureList<'a>,
+        name: Option<Name<'a>>,
+    ) -> Self {
+        Self {
+            kind,
+            script_list,
+            feature_list,
+            name,
+        }
+    }
+
+    /// Returns the kind of this table.
+    pub fn kind(&self) -> LayoutTableKind {
+        self.kind
+    }
+
+    /// Returns an iterator over the scripts defined in the table.
+    pub fn scripts(&self) -> impl Iterator<Item = Script<'a>> + 'a + Clone {
+        let table = self.clone();
+        let data = self.script_list.offset_data();
+        self.script_list
+            .script_records()
+            .iter()
+            .filter_map(move |record| {
+                Some(Script {
+                    table: table.clone(),
+                    tag: record.script_tag(),
+                    script: record.script(data).ok()?,
+                })
+            })
+    }
+
+    /// Returns the script with the given tag.
+    pub fn script(&self, tag: Tag) -> Option<Script<'a>> {
+        let records = self.script_list.script_records();
+        let record = records
+            .binary_search_by_key(&tag, |record| record.script_tag())
+            .ok()
+            .and_then(|index| records.get(index))
+            // Fall back to a linear search for unsorted lists
+            .or_else(|| records.iter().find(|record| record.script_tag() == tag))?;
+        Some(Script {
+            table: self.clone(),
+            tag,
+            script: record.script(self.script_list.offset_data()).ok()?,
+        })
+    }
+
+    /// Returns the number of features in the feature list.
+    pub fn feature_count(&self) -> u16 {
+        self.feature_list.feature_count()
+    }
+
+    /// Returns the feature at the given index in the feature list.
+    pub fn feature(&self, index: u16) -> Option<Feature<'a>> {
+        let record = self.feature_list.feature_records().get(index as usize)?;
+        Some(Feature {
+            table: self.kind,
+            index,
+            tag: record.feature_tag(),
+            feature: record.feature(self.feature_list.offset_data()).ok()?,
+            name: self.name.clone(),
+        })
+    }
+
+    /// Returns an iterator over all features in the feature list.
+    pub fn features(&self) -> impl Iterator<Item = Feature<'a>> + 'a + Clone {
+        let table = self.clone();
+        (0..self.feature_count()).filter_map(move |index| table.feature(index))
+    }
+
+    /// Selects the language system for the given script and language,
+    /// falling back to `DFLT` and the default language system when
+    /// necessary.
+    fn select(&self, script: Tag, language: Option<Tag>) -> Option<LanguageSystem<'a>> {
+        let script = self.script(script).or_else(|| self.script(DFLT))?;
+        language
+            .and_then(|language| script.language(language))
+            .or_else(|| script.default_language())
+    }
+}
+
+/// Script table with a set of language systems.
+#[derive(Clone)]
+pub struct Script<'a> {
+    table: LayoutTable<'a>,
+    tag: Tag,
+    script: RawScript<'a>,
+}
+
+impl<'a> Script<'a> {
+    /// Returns the kind of table that contains this script.
+    pub fn table(&self) -> LayoutTableKind {
+        self.table.kind
+    }
+
+    /// Returns the script tag.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the default language system for the script.
+    pub fn default_language(&self) -> Option<LanguageSystem<'a>> {
+        Some(LanguageSystem {
+            table: self.table.clone(),
+            tag: None,
+            lang_sys: self.script.default_lang_sys()?.ok()?,
+        })
+    }
+
+    /// Returns an iterator over the language systems defined for the script,
+    /// not including the default.
+    pub fn languages(&self) -> impl Iterator<Item = LanguageSystem<'a>> + 'a + Clone {
+        let table = self.table.clone();
+        let data = self.script.offset_data();
+        self.script
+            .lang_sys_records()
+            .iter()
+            .filter_map(move |record| {
+                Some(LanguageSystem {
+                    table: table.clone(),
+                    tag: Some(record.lang_sys_tag()),
+                    lang_sys: record.lang_sys(data).ok()?,
+                })
+            })
+    }
+
+    /// Returns the language system with the given tag.
+    pub fn language(&self, tag: Tag) -> Option<LanguageSystem<'a>> {
+        self.languages()
+            .find(|lang_sys| lang_sys.tag() == Some(tag))
+    }
+}
+
+/// Set of features for a particular language within a script.
+#[derive(Clone)]
+pub struct LanguageSystem<'a> {
+    table: LayoutTable<'a>,
+    tag: Option<Tag>,
+    lang_sys: RawLangSys<'a>,
+}
+
+impl<'a> LanguageSystem<'a> {
+    /// Returns the kind of table that contains this language system.
+    pub fn table(&self) -> LayoutTableKind {
+        self.table.kind
+    }
+
+    /// Returns the language system tag, or `None` for the default language
+    /// system of a script.
+    pub fn tag(&self) -> Option<Tag> {
+        self.tag
+    }
+
+    /// Returns the feature that must always be applied for this language
+    /// system, if any.
+    pub fn required_feature(&self) -> Option<Feature<'a>> {
+        match self.lang_sys.required_feature_index() {
+            0xFFFF => None,
+            index => self.table.feature(index),
+        }
+    }
+
+    /// Returns an iterator over the optional features for this language
+    /// system.
+    pub fn features(&self) -> impl Iterator<Item = Feature<'a>> + 'a + Clone {
+        let table = self.table.clone();
+        self.lang_sys
+            .feature_indices()
+            .iter()
+            .filter_map(move |index| table.feature(index.get()))
+    }
+}
+
+/// Feature from the GSUB or GPOS table.
+#[derive(Clone)]
+pub struct Feature<'a> {
+    table: LayoutTableKind,
+    index: u16,
+    tag: Tag,
+    feature: RawFeature<'a>,
+    name: Option<Name<'a>>,
+}
+
+impl<'a> Feature<'a> {
+    /// Returns the kind of table that contains this feature.
+    pub fn table(&self) -> LayoutTableKind {
+        self.table
+    }
+
+    /// Returns the index of the feature in the feature list.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the feature tag.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the indices of the lookups referenced by this feature.
+    pub fn lookup_indices(&self) -> impl Iterator<Item = u16> + 'a + Clone {
+        self.feature
+            .lookup_list_indices()
+            .iter()
+            .map(|index| index.get())
+    }
+
+    /// Returns the additional parameters for stylistic set, character
+    /// variant and optical size features.
+    pub fn params(&self) -> Option<FeatureParams<'a>> {
+        let strings = |id: StringId| LocalizedStrings::from_name(self.name.clone(), id);
+        Some(match self.feature.feature_params()?.ok()? {
+            RawFeatureParams::StylisticSet(params) => FeatureParams::StylisticSet(StylisticSet {
+                ui_name: strings(params.ui_name_id()),
+            }),
+            RawFeatureParams::CharacterVariant(params) => {
+                FeatureParams::CharacterVariant(CharacterVariant {
+                    params,
+                    name: self.name.clone(),
+                })
+            }
+            RawFeatureParams::Size(params) => {
+                let subfamily_id = params.identifier();
+                let range = (params.range_start(), params.range_end());
+                FeatureParams::Size(DesignSize {
+                    size: params.design_size() as f32 / 10.0,
+                    subfamily_id,
+                    subfamily_name: (subfamily_id != 0)
+                        .then(|| strings(StringId::new(params.name_entry()))),
+                    range: (range.1 != 0).then(|| range.0 as f32 / 10.0..=range.1 as f32 / 10.0),
+                })
+            }
+        })
+    }
+
+    /// Returns the user interface name for stylistic set and character
+    /// variant features.
+    pub fn ui_name(&self) -> Option<LocalizedStrings<'a>> {
+        match self.params()? {
+            FeatureParams::StylisticSet(ss) => Some(ss.ui_name),
+            FeatureParams::CharacterVariant(cv) => cv.ui_label(),
+            FeatureParams::Size(_) => None,
+        }
+    }
+}
+
+/// Additional parameters associated with a feature.
+#[derive(Clone)]
+pub enum FeatureParams<'a> {
+    /// Parameters for the `ss01` through `ss20` features.
+    StylisticSet(StylisticSet<'a>),
+    /// Parameters for the `cv01` through `cv99` features.
+    CharacterVariant(CharacterVariant<'a>),
+    /// Parameters for the `size` feature.
+    Size(DesignSize<'a>),
+}
+
+/// User interface strings for a stylistic set feature.
+///
+/// See [`ssXX`](https://learn.microsoft.com/en-us/typography/opentype/spec/features_pt#ssxx)
+#[derive(Clone)]
+pub struct StylisticSet<'a> {
+    /// Name of the stylistic set.
+    pub ui_name: LocalizedStrings<'a>,
+}
+
+/// User interface strings and characters for a character variant feature.
+///
+/// See [`cvXX`](https://learn.microsoft.com/en-us/typography/opentype/spec/features_ae#cv01-cv99)
+#[derive(Clone)]
+pub struct CharacterVariant<'a> {
+    params: CharacterVariantParams<'a>,
+    name: Option<Name<'a>>,
+}
+
+impl<'a> CharacterVariant<'a> {
+    /// Returns the label for the feature.
+    pub fn ui_label(&self) -> Option<LocalizedStrings<'a>> {
+        self.strings(self.params.feat_ui_label_name_id())
+    }
+
+    /// Returns the tooltip text for the feature.
+    pub fn tooltip(&self) -> Option<LocalizedStrings<'a>> {
+        self.strings(self.params.feat_ui_tooltip_text_name_id())
+    }
+
+    /// Returns sample text that illustrates the effect of the feature.
+    pub fn sample_text(&self) -> Option<LocalizedStrings<'a>> {
+        self.strings(self.params.sample_text_name_id())
+    }
+
+    /// Returns an iterator over the labels for each named parameter of the
+    /// feature.
+    ///
+    /// The first label corresponds to a feature value of 1.
+    pub fn param_labels(&self) -> impl Iterator<Item = LocalizedStrings<'a>> + 'a + Clone {
+        let name = self.name.clone();
+        let first = self.params.first_param_ui_label_name_id().to_u16();
+        let count = if first == 0 {
+            0
+        } else {
+            self.params.num_named_parameters()
+        };
+        (0..count).filter_map(move |i| {
+            let id = first.checked_add(i)?;
+            Some(LocalizedStrings::from_name(name.clone(), StringId::new(id)))
+        })
+    }
+
+    /// Returns an iterator over the characters for which the feature provides
+    /// glyph variants.
+    pub fn characters(&self) -> impl Iterator<Item = char> + 'a + Clone {
+        self.params
+            .character()
+            .iter()
+            .filter_map(|ch| char::from_u32(ch.get().into()))
+    }
+
+    fn strings(&self, id: StringId) -> Option<LocalizedStrings<'a>> {
+        (id.to_u16() != 0).then(|| LocalizedStrings::from_name(self.name.clone(), id))
+    }
+}
+
+/// Design size information from the `size` feature.
+///
+/// See [`size`](https://learn.microsoft.com/en-us/typography/opentype/spec/features_pt#size)
+#[derive(Clone)]
+pub struct DesignSize<'a> {
+    /// Design size in points.
+    pub size: f32,
+    /// Identifier shared by fonts in a family that differ only in their
+    /// intended design size, or 0 if the font is not part of such a family.
+    pub subfamily_id: u16,
+    /// Name of the subfamily, usually describing the intended usage (such as
+    /// "Caption" or "Display").
+    pub subfamily_name: Option<LocalizedStrings<'a>>,
+    /// Range of sizes, in points, for which the font is intended.
+    pub range: Option<RangeInclusive<f32>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider;
+    use read_fonts::FontRef;
+
+    fn tags(tags: &[&[u8; 4]]) -> Vec<Tag> {
+        tags.iter().map(|tag| Tag::new(*tag)).collect()
+    }
+
+    #[test]
+    fn scripts_and_languages() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let features = font.layout_features();
+        assert_eq!(features.scripts(), tags(&[b"DFLT", b"latn"]));
+        assert_eq!(features.languages(Tag::new(b"latn")), tags(&[b"TRK "]));
+        assert!(features.languages(Tag::new(b"DFLT")).is_empty());
+        let gpos = features.table(LayoutTableKind::Gpos).unwrap();
+        let scripts = gpos
+            .scripts()
+            .map(|script| script.tag())
+            .collect::<Vec<_>>();
+        assert_eq!(scripts, tags(&[b"DFLT", b"latn"]));
+        assert_eq!(gpos.feature_count(), 2);
+    }
+
+    #[test]
+    fn merged_features() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let features = font.layout_features();
+        let latn = Tag::new(b"latn");
+        assert_eq!(
+            features.feature_tags(latn, None),
+            tags(&[b"cv01", b"kern", b"liga", b"salt", b"smcp", b"ss01"])
+        );
+        // Turkish has a required smcp feature followed by liga, then kern
+        // from the default GPOS language system
+        let trk = features
+            .features(latn, Some(Tag::new(b"TRK ")))
+            .iter()
+            .map(|feature| (feature.table(), feature.tag()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trk,
+            [
+                (LayoutTableKind::Gsub, Tag::new(b"smcp")),
+                (LayoutTableKind::Gsub, Tag::new(b"liga")),
+                (LayoutTableKind::Gpos, Tag::new(b"kern")),
+            ]
+        );
+        // Unknown scripts fall back to DFLT which includes the size feature
+        assert_eq!(
+            features.feature_tags(Tag::new(b"cyrl"), Some(Tag::new(b"SRB "))),
+            tags(&[b"cv01", b"kern", b"liga", b"salt", b"size", b"smcp", b"ss01"])
+        );
+        assert_eq!(
+            features.all_feature_tags(),
+            tags(&[b"cv01", b"kern", b"liga", b"salt", b"size", b"smcp", b"ss01"])
+        );
+    }
+
+    #[test]
+    fn lookup_indices() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let features = font.layout_features();
+        let gsub = features.table(LayoutTableKind::Gsub).unwrap();
+        let lookups = gsub
+            .features()
+            .map(|feature| (feature.tag(), feature.lookup_indices().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lookups,
+            [
+                (Tag::new(b"cv01"), vec![2]),
+                (Tag::new(b"liga"), vec![0]),
+                (Tag::new(b"salt"), vec![2]),
+                (Tag::new(b"smcp"), vec![1]),
+                (Tag::new(b"ss01"), vec![3]),
+            ]
+        );
+    }
+
+    fn english(strings: LocalizedStrings) -> String {
+        strings.english_or_first().unwrap().to_string()
+    }
+
+    #[test]
+    fn stylistic_set_params() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let gsub = font.layout_features();
+        let gsub = gsub.table(LayoutTableKind::Gsub).unwrap();
+        let ss01 = gsub
+            .features()
+            .find(|f| f.tag() == Tag::new(b"ss01"))
+            .unwrap();
+        assert_eq!(english(ss01.ui_name().unwrap()), "Rounded forms");
+        let liga = gsub.feature(1).unwrap();
+        assert!(liga.params().is_none());
+        assert!(liga.ui_name().is_none());
+    }
+
+    #[test]
+    fn character_variant_params() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let gsub = font.layout_features();
+        let cv01 = gsub
+            .table(LayoutTableKind::Gsub)
+            .unwrap()
+            .feature(0)
+            .unwrap();
+        let Some(FeatureParams::CharacterVariant(cv)) = cv01.params() else {
+            panic!("expected character variant params");
+        };
+        assert_eq!(english(cv.ui_label().unwrap()), "Alternate a");
+        assert_eq!(
+            english(cv.tooltip().unwrap()),
+            "Select an alternate form of a"
+        );
+        assert_eq!(english(cv.sample_text().unwrap()), "a");
+        let labels = cv.param_labels().map(english).collect::<Vec<_>>();
+        assert_eq!(labels, ["Single storey", "Double storey"]);
+        assert_eq!(cv.characters().collect::<Vec<_>>(), ['a']);
+    }
+
+    #[test]
+    fn size_params() {
+        let font = FontRef::new(font_test_data::LAYOUT_FEATURES).unwrap();
+        let gpos = font.layout_features();
+        let size = gpos
+            .table(LayoutTableKind::Gpos)
+            .unwrap()
+            .feature(1)
+            .unwrap();
+        assert_eq!(size.tag(), Tag::new(b"size"));
+        let Some(FeatureParams::Size(size)) = size.params() else {
+            panic!("expected size params");
+        };
+        assert_eq!(size.size, 10.0);
+        assert_eq!(size.subfamily_id, 1);
+        assert_eq!(english(size.subfamily_name.unwrap()), "Text");
+        assert_eq!(size.range, Some(8.0..=14.0));
+    }
+
+    #[test]
+    fn missing_tables() {
+        let font = FontRef::new(font_test_data::NAMES_ONLY).unwrap();
+        let features = font.layout_features();
+        assert!(features.tables().next().is_none());
+        assert!(features.scripts().is_empty());
+        assert!(features.features(DFLT, None).is_empty());
+    }
+}
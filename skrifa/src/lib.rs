@@ -20,8 +20,11 @@ pub extern crate read_fonts as raw;
 
 pub mod attribute;
 pub mod charmap;
+pub mod coverage;
 pub mod font;
 pub mod instance;
+pub mod layout;
+pub mod matching;
 pub mod metrics;
 #[cfg(feature = "scale")]
 pub mod scale;
@@ -0,0 +1,602 @@
+//! Selection of font faces using the CSS font matching algorithm.
+//!
+//! This implements the stretch, style and weight portions of the
+//! [font matching algorithm](https://www.w3.org/TR/css-fonts-4/#font-style-matching)
+//! from CSS Fonts Level 4. Variable fonts are matched using the ranges of
+//! their `wdth`, `slnt`, `ital` and `wght` axes and the result includes the
+//! variation settings required to select the requested attributes.
+//!
+//! # Example
+//! ```
+//! use skrifa::{
+//!     attribute::{Attributes, Style, Weight},
+//!     matching::{match_face, FaceAttributes},
+//!     prelude::*,
+//! };
+//!
+//! fn best_bold_face<'a>(fonts: &[FontRef<'a>]) -> Option<usize> {
+//!     let faces = fonts.iter().map(FaceAttributes::new).collect::<Vec<_>>();
+//!     let request = Attributes {
+//!         weight: Weight::BOLD,
+//!         ..Default::default()
+//!     };
+//!     match_face(&faces, &request).map(|matched| matched.index)
+//! }
+//! ```
+
+use super::{
+    attribute::{Attributes, Stretch, Style, Weight},
+    setting::VariationSetting,
+    Tag,
+};
+use core::ops::RangeInclusive;
+use read_fonts::TableProvider;
+
+/// Default angle for oblique styles without an explicit angle, in degrees
+/// counter-clockwise from the vertical.
+const DEFAULT_OBLIQUE_ANGLE: f32 = -14.0;
+
+const WGHT: Tag = Tag::new(b"wght");
+const WDTH: Tag = Tag::new(b"wdth");
+const SLNT: Tag = Tag::new(b"slnt");
+const ITAL: Tag = Tag::new(b"ital");
+
+/// Ranges of stretch, style and weight attributes supported by a font face.
+///
+/// For static fonts, each range contains a single value. For variable
+/// fonts, the ranges are derived from the `wdth`, `slnt`, `ital` and `wght`
+/// axes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FaceAttributes {
+    stretch: RangeInclusive<f32>,
+    weight: RangeInclusive<f32>,
+    /// Oblique angles in degrees counter-clockwise from the vertical. An
+    /// upright face has a range of 0.0..=0.0.
+    slant: Option<RangeInclusive<f32>>,
+    italic: bool,
+    axes: VariableAxes,
+}
+
+/// Attribute axes present in a variable font.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+struct VariableAxes {
+    wght: bool,
+    wdth: bool,
+    slnt: bool,
+    ital: bool,
+}
+
+impl FaceAttributes {
+    /// Extracts the supported attribute ranges from the given font.
+    pub fn new<'a>(font: &impl TableProvider<'a>) -> Self {
+        let mut face = Self::from_attributes(&Attributes::new(font));
+        let Ok(axes) = font.fvar().and_then(|fvar| fvar.axes()) else {
+            return face;
+        };
+        for axis in axes {
+            let min = axis.min_value().to_f64() as f32;
+            let max = axis.max_value().to_f64() as f32;
+            match axis.axis_tag() {
+                WGHT => {
+                    face.weight = min..=max;
+                    face.axes.wght = true;
+                }
+                WDTH => {
+                    face.stretch = min / 100.0..=max / 100.0;
+                    face.axes.wdth = true;
+                }
+                SLNT => {
+                    face.slant = Some(min..=max);
+                    face.axes.slnt = true;
+                }
+                ITAL => {
+                    face.italic = max >= 1.0;
+                    if min <= 0.0 && face.slant.is_none() {
+                        face.slant = Some(0.0..=0.0);
+                    }
+                    face.axes.ital = true;
+                }
+                _ => {}
+            }
+        }
+        face
+    }
+
+    /// Creates the attribute ranges for a static face with the given
+    /// attributes.
+    pub fn from_attributes(attributes: &Attributes) -> Self {
+        let stretch = attributes.stretch.ratio();
+        let weight = attributes.weight.value();
+        let (slant, italic) = match attributes.style {
+            Style::Normal => (Some(0.0..=0.0), false),
+            Style::Italic => (None, true),
+            Style::Oblique(angle) => {
+                let angle = angle.unwrap_or(DEFAULT_OBLIQUE_ANGLE);
+                (Some(angle..=angle), false)
+            }
+        };
+        Self {
+            stretch: stretch..=stretch,
+            weight: weight..=weight,
+            slant,
+            italic,
+            axes: VariableAxes::default(),
+        }
+    }
+
+    /// Returns the supported range of stretch values.
+    pub fn stretch(&self) -> RangeInclusive<Stretch> {
+        Stretch::new(*self.stretch.start())..=Stretch::new(*self.stretch.end())
+    }
+
+    /// Returns the supported range of weights.
+    pub fn weight(&self) -> RangeInclusive<Weight> {
+        Weight::new(*self.weight.start())..=Weight::new(*self.weight.end())
+    }
+
+    /// Returns the supported range of oblique angles in degrees,
+    /// counter-clockwise from the vertical.
+    ///
+    /// Upright faces have a range of `0.0..=0.0`. Returns `None` for
+    /// faces that only support an italic style.
+    pub fn slant(&self) -> Option<RangeInclusive<f32>> {
+        self.slant.clone()
+    }
+
+    /// Returns true if the face supports an italic style.
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    /// Returns true if the face is a variable font with at least one axis
+    /// that modifies stretch, style or weight.
+    pub fn is_variable(&self) -> bool {
+        self.axes != VariableAxes::default()
+    }
+}
+
+impl From<Attributes> for FaceAttributes {
+    fn from(attributes: Attributes) -> Self {
+        Self::from_attributes(&attributes)
+    }
+}
+
+/// The result of matching a set of faces against requested attributes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FaceMatch {
+    /// Index of the best face in the candidate list.
+    pub index: usize,
+    /// Variation settings that select the closest available attributes in
+    /// the matched face.
+    ///
+    /// This is empty for static faces.
+    pub variation_settings: Vec<VariationSetting>,
+}
+
+/// Selects the face that best matches the requested attributes.
+///
+/// Candidates are narrowed by stretch, then style, then weight, following
+/// the CSS Fonts Level 4 algorithm. When multiple faces remain, the first
+/// in the list is chosen.
+///
+/// Returns `None` if the list of candidates is empty.
+pub fn match_face(candidates: &[FaceAttributes], request: &Attributes) -> Option<FaceMatch> {
+    let mut matches = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, face)| Match {
+            index,
+            ..Match::default()
+        })
+        .collect::<Vec<_>>();
+    // Stretch
+    let tiers = stretch_tiers(request.stretch.percentage());
+    narrow(&mut matches, |m| {
+        let face = &candidates[m.index];
+        let stretch = face.stretch.start() * 100.0..=face.stretch.end() * 100.0;
+        let best = best_in_tiers(&stretch, &tiers)?;
+        m.stretch = best.value / 100.0;
+        Some(best)
+    });
+    // Style
+    let tiers = style_tiers(request.style);
+    narrow(&mut matches, |m| {
+        let face = &candidates[m.index];
+        let best = tiers.iter().enumerate().find_map(|(i, tier)| match tier {
+            StyleTier::Italic => face.italic.then_some(Best {
+                tier: i,
+                distance: 0.0,
+                value: 0.0,
+            }),
+            StyleTier::Oblique(interval) => {
+                // Tiers are expressed using CSS angles (clockwise) so
+                // flip the face range.
+                let slant = face.slant.as_ref()?;
+                let slant = -*slant.end()..=-*slant.start();
+                interval.best(&slant).map(|(distance, value)| Best {
+                    tier: i,
+                    distance,
+                    value,
+                })
+            }
+        })?;
+        m.italic = matches!(tiers[best.tier], StyleTier::Italic);
+        m.slant = -best.value;
+        Some(best)
+    });
+    // Weight
+    let tiers = weight_tiers(request.weight.value());
+    narrow(&mut matches, |m| {
+        let best = best_in_tiers(&candidates[m.index].weight, &tiers)?;
+        m.weight = best.value;
+        Some(best)
+    });
+    let best = matches.first()?;
+    let face = &candidates[best.index];
+    let mut variation_settings = vec![];
+    if face.axes.wdth {
+        variation_settings.push(VariationSetting::new(WDTH, best.stretch * 100.0));
+    }
+    if face.axes.ital {
+        let ital = if best.italic { 1.0 } else { 0.0 };
+        variation_settings.push(VariationSetting::new(ITAL, ital));
+    }
+    if face.axes.slnt && !best.italic {
+        variation_settings.push(VariationSetting::new(SLNT, best.slant));
+    }
+    if face.axes.wght {
+        variation_settings.push(VariationSetting::new(WGHT, best.weight));
+    }
+    Some(FaceMatch {
+        index: best.index,
+        variation_settings,
+    })
+}
+
+/// Candidate face along with the attribute values selected so far.
+#[derive(Copy, Clone, Default)]
+struct Match {
+    index: usize,
+    stretch: f32,
+    italic: bool,
+    slant: f32,
+    weight: f32,
+}
+
+/// Retains only the matches with the best score for a single attribute.
+fn narrow(matches: &mut Vec<Match>, mut score: impl FnMut(&mut Match) -> Option<Best>) {
+    let mut best_key: Option<(usize, f32)> = None;
+    let mut scored = Vec::with_capacity(matches.len());
+    for mut m in matches.drain(..) {
+        if let Some(best) = score(&mut m) {
+            let key = (best.tier, best.distance);
+            if best_key.map(|best_key| key < best_key).unwrap_or(true) {
+                best_key = Some(key);
+            }
+            scored.push((key, m));
+        }
+    }
+    matches.extend(
+        scored
+            .into_iter()
+            .filter(|(key, _)| Some(*key) == best_key)
+            .map(|(_, m)| m),
+    );
+}
+
+/// The closest value found in a face's supported range.
+#[derive(Copy, Clone, Debug)]
+struct Best {
+    /// Index of the first tier that intersects the range.
+    tier: usize,
+    /// Distance from the start of the tier.
+    distance: f32,
+    /// Selected value.
+    value: f32,
+}
+
+fn best_in_tiers(range: &RangeInclusive<f32>, tiers: &[Interval]) -> Option<Best> {
+    tiers.iter().enumerate().find_map(|(tier, interval)| {
+        interval.best(range).map(|(distance, value)| Best {
+            tier,
+            distance,
+            value,
+        })
+    })
+}
+
+/// An interval of attribute values that are searched in a particular
+/// direction, starting from `start`.
+#[derive(Copy, Clone, Debug)]
+struct Interval {
+    start: f32,
+    /// True if `start` itself is part of the interval.
+    inclusive: bool,
+    /// Direction of the search: values above `start` if true, otherwise
+    /// values below.
+    ascending: bool,
+    /// Optional limit on the far end of the interval and whether the limit
+    /// itself is part of the interval.
+    limit: Option<(f32, bool)>,
+}
+
+impl Interval {
+    fn above(start: f32, inclusive: bool) -> Self {
+        Self {
+            start,
+            inclusive,
+            ascending: true,
+            limit: None,
+        }
+    }
+
+    fn below(start: f32, inclusive: bool) -> Self {
+        Self {
+            start,
+            inclusive,
+            ascending: false,
+            limit: None,
+        }
+    }
+
+    fn limit(self, limit: f32, inclusive: bool) -> Self {
+        Self {
+            limit: Some((limit, inclusive)),
+            ..self
+        }
+    }
+
+    /// Returns the distance and value of the point in `range` closest to
+    /// the start of the interval, if any.
+    fn best(&self, range: &RangeInclusive<f32>) -> Option<(f32, f32)> {
+        let (min, max) = (*range.start(), *range.end());
+        // The far end of the range, in the direction of the search, must
+        // reach past the start of the interval (or touch it, if inclusive).
+        let (value, far) = if self.ascending {
+            (min.max(self.start), max)
+        } else {
+            (max.min(self.start), min)
+        };
+        let reaches = if self.inclusive {
+            (far - self.start) * self.sign() >= 0.0
+        } else {
+            (far - self.start) * self.sign() > 0.0
+        };
+        if !reaches {
+            return None;
+        }
+        if let Some((limit, inclusive)) = self.limit {
+            let past = (value - limit) * self.sign();
+            if past > 0.0 || (!inclusive && past == 0.0) {
+                return None;
+            }
+        }
+        Some(((value - self.start).abs(), value))
+    }
+
+    fn sign(&self) -> f32 {
+        if self.ascending {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// Search order for stretch, as a percentage.
+fn stretch_tiers(desired: f32) -> [Interval; 2] {
+    if desired <= 100.0 {
+        [
+            Interval::below(desired, true),
+            Interval::above(desired, false),
+        ]
+    } else {
+        [
+            Interval::above(desired, true),
+            Interval::below(desired, false),
+        ]
+    }
+}
+
+/// Search order for weight.
+fn weight_tiers(desired: f32) -> Vec<Interval> {
+    if (400.0..=500.0).contains(&desired) {
+        vec![
+            Interval::above(desired, true).limit(500.0, true),
+            Interval::below(desired, false),
+            Interval::above(500.0, false),
+        ]
+    } else if desired < 400.0 {
+        vec![
+            Interval::below(desired, true),
+            Interval::above(desired, false),
+        ]
+    } else {
+        vec![
+            Interval::above(desired, true),
+            Interval::below(desired, false),
+        ]
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum StyleTier {
+    Italic,
+    /// Oblique angles in degrees, clockwise from the vertical as in CSS.
+    Oblique(Interval),
+}
+
+/// Search order for style.
+fn style_tiers(style: Style) -> Vec<StyleTier> {
+    use StyleTier::*;
+    // Convert to a CSS angle, which is clockwise.
+    let angle = match style {
+        Style::Normal => 0.0,
+        Style::Italic => -DEFAULT_OBLIQUE_ANGLE,
+        Style::Oblique(angle) => -angle.unwrap_or(DEFAULT_OBLIQUE_ANGLE),
+    };
+    let mut tiers = vec![];
+    if style == Style::Italic {
+        tiers.push(Italic);
+    }
+    if angle == 0.0 {
+        tiers.push(Oblique(Interval::above(0.0, true)));
+        tiers.push(Italic);
+        tiers.push(Oblique(Interval::below(0.0, false)));
+        return tiers;
+    }
+    if angle >= 11.0 {
+        tiers.push(Oblique(Interval::above(angle, true)));
+        tiers.push(Oblique(Interval::below(angle, false).limit(0.0, false)));
+    } else if angle > 0.0 {
+        tiers.push(Oblique(Interval::below(angle, true).limit(0.0, false)));
+        tiers.push(Oblique(Interval::above(angle, false)));
+    } else if angle <= -11.0 {
+        tiers.push(Oblique(Interval::below(angle, true)));
+        tiers.push(Oblique(Interval::above(angle, false).limit(0.0, false)));
+    } else {
+        tiers.push(Oblique(Interval::above(angle, true).limit(0.0, false)));
+        tiers.push(Oblique(Interval::below(angle, false)));
+    }
+    if style != Style::Italic {
+        tiers.push(Italic);
+    }
+    // Finally, fall back to upright and slanted in the opposite direction.
+    if angle > 0.0 {
+        tiers.push(Oblique(Interval::below(0.0, true)));
+    } else {
+        tiers.push(Oblique(Interval::above(0.0, true)));
+    }
+    tiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_fonts::FontRef;
+
+    fn face(stretch: Stretch, style: Style, weight: f32) -> FaceAttributes {
+        Attributes {
+            stretch,
+            style,
+            weight: Weight::new(weight),
+        }
+        .into()
+    }
+
+    fn request(stretch: Stretch, style: Style, weight: f32) -> Attributes {
+        Attributes {
+            stretch,
+            style,
+            weight: Weight::new(weight),
+        }
+    }
+
+    fn matched_index(faces: &[FaceAttributes], request: Attributes) -> usize {
+        match_face(faces, &request).unwrap().index
+    }
+
+    #[test]
+    fn weight_matching() {
+        let faces = [300.0, 500.0, 600.0].map(|w| face(Stretch::NORMAL, Style::Normal, w));
+        let weight = |w| matched_index(&faces, request(Stretch::NORMAL, Style::Normal, w));
+        // 400 checks up to 500 first
+        assert_eq!(weight(400.0), 1);
+        // then lighter weights
+        let faces = [300.0, 600.0].map(|w| face(Stretch::NORMAL, Style::Normal, w));
+        let weight = |w| matched_index(&faces, request(Stretch::NORMAL, Style::Normal, w));
+        assert_eq!(weight(450.0), 0);
+        assert_eq!(weight(200.0), 0);
+        assert_eq!(weight(350.0), 0);
+        assert_eq!(weight(550.0), 1);
+        assert_eq!(weight(700.0), 1);
+    }
+
+    #[test]
+    fn stretch_before_weight() {
+        let faces = [
+            face(Stretch::CONDENSED, Style::Normal, 400.0),
+            face(Stretch::NORMAL, Style::Normal, 700.0),
+            face(Stretch::EXPANDED, Style::Normal, 400.0),
+        ];
+        let stretch = |s| matched_index(&faces, request(s, Style::Normal, 400.0));
+        assert_eq!(stretch(Stretch::NORMAL), 1);
+        // narrower is preferred for condensed requests
+        assert_eq!(stretch(Stretch::SEMI_CONDENSED), 0);
+        // and wider for expanded
+        assert_eq!(stretch(Stretch::SEMI_EXPANDED), 2);
+        assert_eq!(stretch(Stretch::ULTRA_CONDENSED), 0);
+    }
+
+    #[test]
+    fn style_matching() {
+        let normal = face(Stretch::NORMAL, Style::Normal, 400.0);
+        let italic = face(Stretch::NORMAL, Style::Italic, 400.0);
+        let oblique = face(Stretch::NORMAL, Style::Oblique(Some(-10.0)), 400.0);
+        let style =
+            |faces: &[FaceAttributes], s| matched_index(faces, request(Stretch::NORMAL, s, 400.0));
+        let all = [normal.clone(), italic.clone(), oblique.clone()];
+        assert_eq!(style(&all, Style::Normal), 0);
+        assert_eq!(style(&all, Style::Italic), 1);
+        assert_eq!(style(&all, Style::Oblique(None)), 2);
+        // italic falls back to oblique before normal
+        assert_eq!(style(&[normal.clone(), oblique.clone()], Style::Italic), 1);
+        // oblique falls back to italic before normal
+        assert_eq!(
+            style(
+                &[normal.clone(), italic.clone()],
+                Style::Oblique(Some(-20.0))
+            ),
+            1
+        );
+        // normal prefers italic over backward slanted oblique
+        let backslant = face(Stretch::NORMAL, Style::Oblique(Some(10.0)), 400.0);
+        assert_eq!(style(&[backslant, italic], Style::Normal), 1);
+    }
+
+    #[test]
+    fn variable_settings() {
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let variable = FaceAttributes::new(&font);
+        assert!(variable.is_variable());
+        assert_eq!(variable.weight(), Weight::new(100.0)..=Weight::new(900.0));
+        let regular = face(Stretch::NORMAL, Style::Normal, 400.0);
+        let faces = [regular, variable];
+        let matched = match_face(&faces, &request(Stretch::NORMAL, Style::Normal, 650.0)).unwrap();
+        assert_eq!(matched.index, 1);
+        assert_eq!(
+            matched.variation_settings,
+            [VariationSetting::new(WGHT, 650.0)]
+        );
+        // static face wins when it matches exactly
+        let matched = match_face(&faces, &request(Stretch::NORMAL, Style::Normal, 400.0)).unwrap();
+        assert_eq!(matched.index, 0);
+        assert!(matched.variation_settings.is_empty());
+    }
+
+    #[test]
+    fn variable_slant() {
+        let mut face = face(Stretch::NORMAL, Style::Normal, 400.0);
+        face.slant = Some(-12.0..=0.0);
+        face.axes.slnt = true;
+        let matched = match_face(
+            &[face.clone()],
+            &request(Stretch::NORMAL, Style::Oblique(Some(-20.0)), 400.0),
+        )
+        .unwrap();
+        assert_eq!(
+            matched.variation_settings,
+            [VariationSetting::new(SLNT, -12.0)]
+        );
+        let matched = match_face(&[face], &request(Stretch::NORMAL, Style::Normal, 400.0)).unwrap();
+        assert_eq!(
+            matched.variation_settings,
+            [VariationSetting::new(SLNT, 0.0)]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(match_face(&[], &Attributes::default()).is_none());
+    }
+}
@@ -1,7 +1,8 @@
 use super::{
-    attribute::Attributes,
+    attribute::{Attributes, ExtendedAttributes},
     charmap::Charmap,
     instance::{LocationRef, NormalizedCoord, Size},
+    layout::{GlyphDefinitions, LayoutFeatures},
     metrics::{GlyphMetrics, Metrics},
     string::{LocalizedStrings, StringId},
 };
@@ -14,6 +15,12 @@ pub trait MetadataProvider<'a>: raw::TableProvider<'a> + Sized {
         Attributes::new(self)
     }
 
+    /// Returns the secondary attributes used for classification, licensing
+    /// and coverage queries, or `None` if the font has no OS/2 table.
+    fn extended_attributes(&self) -> Option<ExtendedAttributes> {
+        ExtendedAttributes::new(self)
+    }
+
     /// Returns an iterator over the collection of localized strings for the
     /// given informational string identifier.
     fn localized_strings(&self, id: StringId) -> LocalizedStrings<'a> {
@@ -32,6 +39,18 @@ pub trait MetadataProvider<'a>: raw::TableProvider<'a> + Sized {
         GlyphMetrics::new(self, size, location)
     }
 
+    /// Returns the scripts, language systems and features defined in the
+    /// GSUB and GPOS tables.
+    fn layout_features(&self) -> LayoutFeatures<'a> {
+        LayoutFeatures::new(self)
+    }
+
+    /// Returns the glyph classes and ligature carets defined in the GDEF
+    /// table.
+    fn glyph_definitions(&self) -> GlyphDefinitions<'a> {
+        GlyphDefinitions::new(self)
+    }
+
     /// Returns the character to nominal glyph identifier mapping.
     fn charmap(&self) -> Charmap<'a> {
         Charmap::new(self)
@@ -16,6 +16,8 @@ pub enum Error {
     HintingFailed(GlyphId),
     /// An anchor point had invalid indices.
     InvalidAnchorPoint(GlyphId, u16),
+    /// A requested point index was out of range for the outline.
+    InvalidPointIndex(GlyphId, u16),
     /// Conversion from outline to path failed.
     ToPath(ToPathError),
     /// Error occured when reading font data.
@@ -50,6 +52,9 @@ impl fmt::Display for Error {
                 f,
                 "Invalid anchor point index ({index}) for composite glyph {gid}",
             ),
+            Self::InvalidPointIndex(gid, index) => {
+                write!(f, "Invalid point index ({index}) for glyph {gid}")
+            }
             Self::ToPath(e) => write!(f, "{e}"),
             Self::Read(e) => write!(f, "{e}"),
         }
@@ -5,7 +5,7 @@ use super::Hinting;
 
 use core::{borrow::Borrow, str::FromStr};
 use read_fonts::{
-    types::{Fixed, GlyphId, Tag},
+    types::{Fixed, GlyphId, Point, Tag},
     TableProvider,
 };
 
@@ -136,6 +136,7 @@ impl<'a> ScalerBuilder<'a> {
             None
         };
         Scaler {
+            size: self.size,
             coords,
             outlines: Outlines { glyf },
         }
@@ -186,11 +187,17 @@ impl<'a> ScalerBuilder<'a> {
 /// See the [module level documentation](crate::scale#getting-an-outline)
 /// for more detail.
 pub struct Scaler<'a> {
+    size: Size,
     coords: &'a [NormalizedCoord],
     outlines: Outlines<'a>,
 }
 
 impl<'a> Scaler<'a> {
+    /// Returns the size in use by the scaler.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
     /// Returns the current set of normalized coordinates in use by the scaler.
     pub fn normalized_coords(&self) -> &'a [NormalizedCoord] {
         self.coords
@@ -206,6 +213,15 @@ impl<'a> Scaler<'a> {
     pub fn outline(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
         self.outlines.outline(glyph_id, sink)
     }
+
+    /// Loads a simple outline for the specified glyph identifier and returns
+    /// the position of the point at the given index.
+    ///
+    /// The point reflects the current size, variation and hinting settings
+    /// of the scaler.
+    pub fn outline_point(&mut self, glyph_id: GlyphId, point_index: u16) -> Result<Point<f32>> {
+        self.outlines.point(glyph_id, point_index)
+    }
 }
 
 /// Outline glyph scalers.
@@ -226,4 +242,17 @@ impl<'a> Outlines<'a> {
             Err(Error::NoSources)
         }
     }
+
+    fn point(&mut self, glyph_id: GlyphId, point_index: u16) -> Result<Point<f32>> {
+        if let Some((scaler, glyf_outline)) = &mut self.glyf {
+            scaler.load(glyph_id, glyf_outline)?;
+            glyf_outline
+                .points
+                .get(point_index as usize)
+                .map(|point| point.map(|v| v.to_f64() as f32))
+                .ok_or(Error::InvalidPointIndex(glyph_id, point_index))
+        } else {
+            Err(Error::NoSources)
+        }
+    }
 }
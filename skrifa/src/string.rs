@@ -58,7 +58,10 @@ pub struct LocalizedStrings<'a> {
 impl<'a> LocalizedStrings<'a> {
     /// Creates a new localized string iterator from the given font and string identifier.
     pub fn new(font: &impl TableProvider<'a>, id: StringId) -> Self {
-        let name = font.name().ok();
+        Self::from_name(font.name().ok(), id)
+    }
+
+    pub(crate) fn from_name(name: Option<Name<'a>>, id: StringId) -> Self {
         let records = name
             .as_ref()
             .map(|name| name.name_record().iter())
@@ -97,6 +100,46 @@ impl<'a> LocalizedStrings<'a> {
         }
         best_string
     }
+
+    /// Returns the string that best matches the given list of preferred
+    /// BCP-47 language tags.
+    ///
+    /// Languages are considered in order of preference. For each, the
+    /// requested tag is tried first, followed by its parent locales (for
+    /// example, "zh-Hant-HK" falls back to "zh-Hant" and then "zh-TW"). A
+    /// string is also accepted when one of its own parent locales matches,
+    /// so a request for "de" will select a string tagged "de-DE".
+    ///
+    /// Tags are compared case-insensitively and "_" is treated as a subtag
+    /// separator.
+    ///
+    /// If no preferred language is available, this falls back to
+    /// [`english_or_first`](Self::english_or_first).
+    pub fn best_match<'l>(
+        self,
+        languages: impl IntoIterator<Item = &'l str>,
+    ) -> Option<LocalizedString<'a>> {
+        for language in languages {
+            for candidate in LanguageFallbacks::new(language).iter() {
+                // Prefer exact matches at each level of the fallback chain.
+                if let Some(string) = self.clone().find(|string| {
+                    string.language().map(|lang| tags_eq(lang, candidate)) == Some(true)
+                }) {
+                    return Some(string);
+                }
+                if let Some(string) = self.clone().find(|string| {
+                    string.language().map(|lang| {
+                        LanguageFallbacks::new(lang)
+                            .iter()
+                            .any(|parent| tags_eq(parent, candidate))
+                    }) == Some(true)
+                }) {
+                    return Some(string);
+                }
+            }
+        }
+        self.english_or_first()
+    }
 }
 
 impl<'a> Iterator for LocalizedStrings<'a> {
@@ -235,6 +278,124 @@ impl Language {
     }
 }
 
+/// Maximum number of entries in a language fallback chain.
+const MAX_LANGUAGE_FALLBACKS: usize = 8;
+
+/// Sequence of progressively less specific language tags to try when
+/// matching a requested language.
+struct LanguageFallbacks<'a> {
+    tags: [&'a str; MAX_LANGUAGE_FALLBACKS],
+    len: usize,
+}
+
+impl<'a> LanguageFallbacks<'a> {
+    fn new(tag: &'a str) -> Self {
+        let mut fallbacks = Self {
+            tags: [""; MAX_LANGUAGE_FALLBACKS],
+            len: 0,
+        };
+        let mut current = Some(strip_extensions(tag));
+        while let Some(tag) = current {
+            fallbacks.push(tag);
+            for (_, alias) in LANGUAGE_ALIASES
+                .iter()
+                .filter(|(from, _)| tags_eq(from, tag))
+            {
+                fallbacks.push(alias);
+            }
+            current = parent_language(tag);
+        }
+        fallbacks
+    }
+
+    fn push(&mut self, tag: &'a str) {
+        if tag.is_empty()
+            || self.len == MAX_LANGUAGE_FALLBACKS
+            || self.iter().any(|existing| tags_eq(existing, tag))
+        {
+            return;
+        }
+        self.tags[self.len] = tag;
+        self.len += 1;
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.tags[..self.len].iter().copied()
+    }
+}
+
+/// Removes extension and private use subtags (`-u-...`, `-x-...`) which
+/// are not meaningful for selecting a localized string.
+fn strip_extensions(tag: &str) -> &str {
+    let mut end = 0;
+    for (i, subtag) in subtags_with_offsets(tag) {
+        if i != 0 && subtag.len() == 1 {
+            break;
+        }
+        end = i + subtag.len();
+    }
+    &tag[..end]
+}
+
+/// Returns the parent locale of the given tag, or `None` if the parent is
+/// the root locale.
+fn parent_language(tag: &str) -> Option<&str> {
+    if let Some((_, parent)) = LANGUAGE_PARENTS.iter().find(|(from, _)| tags_eq(from, tag)) {
+        return *parent;
+    }
+    let (last_start, _) = subtags_with_offsets(tag).last()?;
+    if last_start == 0 {
+        None
+    } else {
+        Some(&tag[..last_start - 1])
+    }
+}
+
+/// Iterator over the subtags of a language tag along with their byte
+/// offsets.
+fn subtags_with_offsets(tag: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = 0;
+    tag.split(['-', '_']).map(move |subtag| {
+        let offset = start;
+        start += subtag.len() + 1;
+        (offset, subtag)
+    })
+}
+
+/// Compares two language tags for equality, ignoring case and treating
+/// "-" and "_" as equivalent separators.
+fn tags_eq(a: &str, b: &str) -> bool {
+    let normalize = |c: u8| match c {
+        b'_' => b'-',
+        _ => c.to_ascii_lowercase(),
+    };
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .all(|(a, b)| normalize(a) == normalize(b))
+}
+
+/// Parent locales that don't follow from simple truncation of subtags.
+///
+/// A value of `None` means the parent is the root locale. This is a subset
+/// of the CLDR parent locale data that is relevant to the languages which
+/// appear in font naming tables.
+const LANGUAGE_PARENTS: &[(&str, Option<&str>)] = &[
+    ("zh-Hant", None),
+    ("zh-TW", Some("zh-Hant")),
+    ("zh-HK", Some("zh-Hant")),
+    ("zh-MO", Some("zh-Hant")),
+    ("zh-CN", Some("zh-Hans")),
+    ("zh-SG", Some("zh-Hans")),
+];
+
+/// Tags that are considered equivalent for the purpose of matching, tried
+/// immediately after the source tag in a fallback chain.
+///
+/// Fonts frequently label Chinese strings with a region rather than a
+/// script (for example, Windows LCID 0x0404 is "zh-TW").
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[("zh-Hant", "zh-TW"), ("zh-Hans", "zh-CN")];
+
 /// Converts an OpenType language identifier to a BCP-47 language tag.
 fn language_id_to_bcp47(language_id: u16) -> Option<&'static str> {
     match LANGUAGE_ID_TO_BCP47.binary_search_by(|entry| entry.0.cmp(&language_id)) {
@@ -635,6 +796,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn best_match() {
+        let font = FontRef::new(font_test_data::NAMES_ONLY).unwrap();
+        let best_match = |languages: &[&str]| {
+            font.localized_strings(StringId::SUBFAMILY_NAME)
+                .best_match(languages.iter().copied())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(best_match(&["pl"]), "Normalny");
+        assert_eq!(best_match(&["fr-FR", "el"]), "Κανονικά");
+        assert_eq!(best_match(&["EU_es"]), "Arrunta");
+        assert_eq!(best_match(&["zh-SG"]), "正常");
+        assert_eq!(best_match(&["zh-Hans-CN-u-nu-hanidec"]), "正常");
+        // Traditional Chinese doesn't fall back to simplified
+        assert_eq!(best_match(&["zh-Hant-HK"]), "Regular");
+        assert_eq!(best_match(&["fr"]), "Regular");
+        assert_eq!(best_match(&[]), "Regular");
+    }
+
+    #[test]
+    fn language_fallbacks() {
+        let chain = |tag| LanguageFallbacks::new(tag).iter().collect::<Vec<_>>();
+        assert_eq!(chain("zh-Hant-HK"), ["zh-Hant-HK", "zh-Hant", "zh-TW"]);
+        assert_eq!(chain("zh-HK"), ["zh-HK", "zh-Hant", "zh-TW"]);
+        assert_eq!(chain("zh-CN"), ["zh-CN", "zh-Hans", "zh"]);
+        assert_eq!(chain("sr-Latn-RS"), ["sr-Latn-RS", "sr-Latn", "sr"]);
+        assert_eq!(chain("de-DE-x-private"), ["de-DE", "de"]);
+        assert_eq!(chain("es-ES_tradnl"), ["es-ES_tradnl", "es-ES", "es"]);
+    }
+
     #[test]
     fn english_or_first() {
         let font = FontRef::new(font_test_data::NAMES_ONLY).unwrap();
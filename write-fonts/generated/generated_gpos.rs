@@ -762,6 +762,7 @@ impl Validate for SinglePosFormat2 {
                 }
                 self.value_records.validate_impl(ctx);
             });
+            self.check_value_formats(ctx);
         })
     }
 }
@@ -913,6 +914,7 @@ impl Validate for PairPosFormat1 {
                 }
                 self.pair_sets.validate_impl(ctx);
             });
+            self.check_value_formats(ctx);
         })
     }
 }
@@ -1112,6 +1114,7 @@ impl Validate for PairPosFormat2 {
                 }
                 self.class1_records.validate_impl(ctx);
             });
+            self.check_value_formats(ctx);
         })
     }
 }
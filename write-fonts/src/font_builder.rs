@@ -3,11 +3,15 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 
+use read_fonts::CHECKSUM_MAGIC;
 use types::{Tag, TT_SFNT_VERSION};
 
 include!("../generated/generated_font.rs");
 
 const TABLE_RECORD_LEN: usize = 16;
+const HEAD: Tag = Tag::new(b"head");
+// the position of checkSumAdjustment in head
+const CHECKSUM_ADJUSTMENT_RANGE: std::ops::Range<usize> = 8..12;
 
 /// Build a font from some set of tables.
 #[derive(Debug, Clone, Default)]
@@ -53,10 +57,16 @@ impl<'a> FontBuilder<'a> {
             + self.tables.len() * TABLE_RECORD_LEN;
 
         let mut position = header_len as u32;
+        let mut head_offset = None;
         let table_records: Vec<_> = self
             .tables
             .iter_mut()
             .map(|(tag, data)| {
+                if *tag == HEAD && data.len() >= CHECKSUM_ADJUSTMENT_RANGE.end {
+                    // must be zero when computing checksums; we fill it in below
+                    data.to_mut()[CHECKSUM_ADJUSTMENT_RANGE].fill(0);
+                    head_offset = Some(position as usize);
+                }
                 let offset = position;
                 let length = data.len() as u32;
                 position += length;
@@ -77,6 +87,12 @@ impl<'a> FontBuilder<'a> {
             let padding = [0u8; 4];
             data.extend_from_slice(&padding[..rem]);
         }
+        if let Some(head_offset) = head_offset {
+            let adjustment = CHECKSUM_MAGIC.wrapping_sub(checksum_and_padding(&data).0);
+            let range = head_offset + CHECKSUM_ADJUSTMENT_RANGE.start
+                ..head_offset + CHECKSUM_ADJUSTMENT_RANGE.end;
+            data[range].copy_from_slice(&adjustment.to_be_bytes());
+        }
         data
     }
 }
@@ -136,6 +152,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sets_checksum_adjustment() {
+        let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
+        let mut builder = FontBuilder::default();
+        for record in font.table_directory.table_records() {
+            let mut data = font.table_data(record.tag()).unwrap().as_ref().to_vec();
+            if record.tag() == Tag::new(b"head") {
+                // should be ignored
+                data[8..12].copy_from_slice(&[1, 2, 3, 4]);
+            }
+            builder.add_table(record.tag(), data);
+        }
+        let bytes = builder.build();
+        let font = FontRef::new(&bytes).unwrap();
+        assert_eq!(font.checksum_mismatches().count(), 0);
+        assert!(font.has_valid_checksum_adjustment());
+    }
+
     #[test]
     fn survives_no_tables() {
         FontBuilder::default().build();
@@ -7,7 +7,9 @@ pub mod from_obj;
 mod graph;
 mod offsets;
 pub mod pens;
+pub mod recalc;
 mod round;
+pub mod sanitize;
 pub mod tables;
 pub mod util;
 pub mod validate;
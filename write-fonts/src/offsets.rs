@@ -5,7 +5,6 @@ use super::write::{FontWrite, TableWriter};
 /// The width in bytes of an Offset16
 pub const WIDTH_16: usize = 2;
 /// The width in bytes of an Offset24
-#[allow(dead_code)] // will be used one day :')
 pub const WIDTH_24: usize = 3;
 /// The width in bytes of an Offset32
 pub const WIDTH_32: usize = 4;
@@ -1,8 +1,33 @@
 //! Pen implementations based on <https://github.com/fonttools/fonttools/tree/main/Lib/fontTools/pens>
 
+mod cu2qu;
+mod direction;
+mod overlap;
+
 use font_types::{Pen, PenCommand};
 use kurbo::{Affine, BezPath, PathEl, Point, Rect};
 
+pub use cu2qu::{
+    cubic_to_quadratic, cubics_to_quadratic, path_to_quadratic, paths_to_quadratic, Cu2QuError,
+    Cu2QuPen,
+};
+pub use direction::{
+    contour_info, correct_direction, ContourDirectionPen, ContourInfo, DirectionError,
+};
+pub use overlap::{remove_overlaps, union, OverlapError};
+
+/// The direction of the outer contours of an outline.
+///
+/// Holes go the opposite way. Directions are given with the y axis pointing
+/// up, as in font units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// The TrueType convention, used in the `glyf` table.
+    Clockwise,
+    /// The PostScript convention, used in the `CFF` and `CFF2` tables.
+    CounterClockwise,
+}
+
 pub fn write_to_pen(path: &BezPath, pen: &mut impl Pen) {
     path.elements()
         .iter()
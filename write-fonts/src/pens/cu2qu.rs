@@ -0,0 +1,475 @@
+//! Converting cubic curves to quadratic splines.
+//!
+//! A port of fontTools' [cu2qu], which approximates each cubic with a
+//! quadratic spline: a sequence of off-curve points, with implied on-curve
+//! points half way between them, as used by TrueType outlines.
+//!
+//! [cu2qu]: https://github.com/fonttools/fonttools/blob/main/Lib/fontTools/cu2qu/cu2qu.py
+
+use font_types::Pen;
+use kurbo::{BezPath, CubicBez, ParamCurve, PathEl, Point, Vec2};
+
+/// The maximum number of quadratic segments used to approximate a cubic.
+const MAX_SEGMENTS: usize = 100;
+
+/// An error that occurs when converting cubic curves.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cu2QuError {
+    /// No approximation within the maximum error was found.
+    ApproxNotFound(Vec<CubicBez>),
+    /// The paths being converted together have different elements at the
+    /// given index.
+    IncompatiblePaths(usize),
+}
+
+/// Approximate a cubic curve with a quadratic spline.
+///
+/// The spline is returned as its points: the start point, followed by the
+/// off-curve points and the end point. The on-curve points between
+/// consecutive off-curve points are implied, at their midpoint.
+///
+/// `max_err` is the maximum allowed distance between the cubic and the
+/// spline, in font units.
+pub fn cubic_to_quadratic(cubic: CubicBez, max_err: f64) -> Result<Vec<Point>, Cu2QuError> {
+    (1..=MAX_SEGMENTS)
+        .find_map(|n| cubic_approx_spline(cubic, n, max_err))
+        .ok_or_else(|| Cu2QuError::ApproxNotFound(vec![cubic]))
+}
+
+/// Approximate several cubic curves with quadratic splines that have the
+/// same number of points.
+///
+/// This is used to convert the corresponding curves of each master of a
+/// variable font, which must remain compatible.
+pub fn cubics_to_quadratic(
+    cubics: &[CubicBez],
+    max_err: f64,
+) -> Result<Vec<Vec<Point>>, Cu2QuError> {
+    let mut splines = vec![Vec::new(); cubics.len()];
+    if cubics.is_empty() {
+        return Ok(splines);
+    }
+    // find the smallest number of segments that works for every curve,
+    // retrying the others whenever one needs more
+    let mut n = 1;
+    let mut i = 0;
+    let mut last_i = 0;
+    loop {
+        match cubic_approx_spline(cubics[i], n, max_err) {
+            Some(spline) => {
+                splines[i] = spline;
+                i = (i + 1) % cubics.len();
+                if i == last_i {
+                    return Ok(splines);
+                }
+            }
+            None if n == MAX_SEGMENTS => return Err(Cu2QuError::ApproxNotFound(cubics.to_vec())),
+            None => {
+                n += 1;
+                last_i = i;
+            }
+        }
+    }
+}
+
+/// Convert the cubic curves of a path to quadratic ones.
+pub fn path_to_quadratic(path: &BezPath, max_err: f64) -> Result<BezPath, Cu2QuError> {
+    Ok(paths_to_quadratic(std::slice::from_ref(path), max_err)?
+        .pop()
+        .unwrap())
+}
+
+/// Convert the cubic curves of several paths to quadratic ones, keeping
+/// the paths compatible.
+///
+/// The paths must have the same elements; corresponding cubic curves are
+/// converted with [`cubics_to_quadratic`], so the results have the same
+/// number of points.
+pub fn paths_to_quadratic(paths: &[BezPath], max_err: f64) -> Result<Vec<BezPath>, Cu2QuError> {
+    let mut results = vec![BezPath::new(); paths.len()];
+    let num_elements = match paths.first() {
+        Some(path) => path.elements().len(),
+        None => return Ok(results),
+    };
+    if let Some(path) = paths
+        .iter()
+        .find(|path| path.elements().len() != num_elements)
+    {
+        return Err(Cu2QuError::IncompatiblePaths(
+            num_elements.min(path.elements().len()),
+        ));
+    }
+    let mut current = vec![Point::ZERO; paths.len()];
+    let mut start = vec![Point::ZERO; paths.len()];
+    for i in 0..num_elements {
+        let elements = paths
+            .iter()
+            .map(|path| path.elements()[i])
+            .collect::<Vec<_>>();
+        let kind = std::mem::discriminant(&elements[0]);
+        if elements.iter().any(|el| std::mem::discriminant(el) != kind) {
+            return Err(Cu2QuError::IncompatiblePaths(i));
+        }
+        if let PathEl::CurveTo(..) = elements[0] {
+            let cubics = elements
+                .iter()
+                .zip(&current)
+                .map(|(el, p0)| match *el {
+                    PathEl::CurveTo(p1, p2, p3) => CubicBez::new(*p0, p1, p2, p3),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            let splines = cubics_to_quadratic(&cubics, max_err)?;
+            for ((result, spline), current) in results.iter_mut().zip(splines).zip(&mut current) {
+                append_spline(result, &spline);
+                *current = *spline.last().unwrap();
+            }
+            continue;
+        }
+        for (((result, el), current), start) in results
+            .iter_mut()
+            .zip(elements)
+            .zip(&mut current)
+            .zip(&mut start)
+        {
+            match el {
+                PathEl::MoveTo(p) => {
+                    *start = p;
+                    *current = p;
+                }
+                PathEl::LineTo(p) | PathEl::QuadTo(_, p) => *current = p,
+                PathEl::ClosePath => *current = *start,
+                PathEl::CurveTo(..) => unreachable!(),
+            }
+            result.push(el);
+        }
+    }
+    Ok(results)
+}
+
+/// Add a spline to a path, as quadratic segments with explicit on-curve
+/// points.
+fn append_spline(path: &mut BezPath, spline: &[Point]) {
+    for (control, end) in spline_segments(spline) {
+        path.quad_to(control, end);
+    }
+}
+
+/// The control and end points of each quadratic segment of a spline.
+fn spline_segments(spline: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    let off_curves = &spline[1..spline.len() - 1];
+    off_curves.iter().enumerate().map(move |(i, off_curve)| {
+        let end = match off_curves.get(i + 1) {
+            Some(next) => off_curve.midpoint(*next),
+            None => spline[spline.len() - 1],
+        };
+        (*off_curve, end)
+    })
+}
+
+/// A pen that converts cubic curves to quadratic ones before passing them
+/// to the inner pen.
+///
+/// Each curve is converted on its own; use [`paths_to_quadratic`] to
+/// convert interpolatable outlines.
+pub struct Cu2QuPen<'a, T: Pen> {
+    inner_pen: &'a mut T,
+    max_err: f64,
+    current: Point,
+    start: Point,
+    error: Option<Cu2QuError>,
+}
+
+impl<'a, T: Pen> Cu2QuPen<'a, T> {
+    pub fn new(inner_pen: &'a mut T, max_err: f64) -> Cu2QuPen<'a, T> {
+        Cu2QuPen {
+            inner_pen,
+            max_err,
+            current: Point::ZERO,
+            start: Point::ZERO,
+            error: None,
+        }
+    }
+
+    /// The first error that occurred, if any.
+    ///
+    /// Curves that could not be converted are passed to the inner pen
+    /// unchanged.
+    pub fn error(&self) -> Option<&Cu2QuError> {
+        self.error.as_ref()
+    }
+}
+
+impl<T: Pen> Pen for Cu2QuPen<'_, T> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.inner_pen.move_to(x, y);
+        self.current = point(x, y);
+        self.start = self.current;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.inner_pen.line_to(x, y);
+        self.current = point(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.inner_pen.quad_to(cx0, cy0, x, y);
+        self.current = point(x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let cubic = CubicBez::new(self.current, point(cx0, cy0), point(cx1, cy1), point(x, y));
+        self.current = cubic.p3;
+        match cubic_to_quadratic(cubic, self.max_err) {
+            Ok(spline) => {
+                for (c, p) in spline_segments(&spline) {
+                    self.inner_pen
+                        .quad_to(c.x as f32, c.y as f32, p.x as f32, p.y as f32);
+                }
+            }
+            Err(err) => {
+                self.inner_pen.curve_to(cx0, cy0, cx1, cy1, x, y);
+                self.error.get_or_insert(err);
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        self.inner_pen.close();
+        self.current = self.start;
+    }
+}
+
+fn point(x: f32, y: f32) -> Point {
+    Point::new(x as f64, y as f64)
+}
+
+/// Approximate a cubic with a spline of `n` quadratic segments, if one is
+/// within the tolerance.
+fn cubic_approx_spline(cubic: CubicBez, n: usize, tolerance: f64) -> Option<Vec<Point>> {
+    if n == 1 {
+        return cubic_approx_quadratic(cubic, tolerance);
+    }
+    let pieces = (0..n)
+        .map(|i| cubic.subsegment(i as f64 / n as f64..(i + 1) as f64 / n as f64))
+        .collect::<Vec<_>>();
+    let controls = pieces
+        .iter()
+        .enumerate()
+        .map(|(i, piece)| cubic_approx_control(i as f64 / (n - 1) as f64, piece))
+        .collect::<Vec<_>>();
+
+    // check each quadratic segment against its piece of the cubic
+    let mut d1 = Vec2::ZERO;
+    let mut q2 = cubic.p0;
+    for (i, piece) in pieces.iter().enumerate() {
+        let q0 = q2;
+        let q1 = controls[i];
+        q2 = match controls.get(i + 1) {
+            Some(next) => q1.midpoint(*next),
+            None => piece.p3,
+        };
+        let d0 = d1;
+        d1 = q2 - piece.p3;
+        if d1.hypot() > tolerance
+            || !cubic_farthest_fit_inside(
+                d0,
+                q0 + (q1 - q0) * (2.0 / 3.0) - piece.p1,
+                q2 + (q1 - q2) * (2.0 / 3.0) - piece.p2,
+                d1,
+                tolerance,
+            )
+        {
+            return None;
+        }
+    }
+    let mut spline = Vec::with_capacity(n + 2);
+    spline.push(cubic.p0);
+    spline.extend(controls);
+    spline.push(cubic.p3);
+    Some(spline)
+}
+
+/// Approximate a cubic with a single quadratic, if one is within the
+/// tolerance.
+fn cubic_approx_quadratic(cubic: CubicBez, tolerance: f64) -> Option<Vec<Point>> {
+    // the control point is where the tangents at the ends meet
+    let q1 = calc_intersect(cubic.p0, cubic.p1, cubic.p2, cubic.p3)?;
+    let c1 = cubic.p0 + (q1 - cubic.p0) * (2.0 / 3.0);
+    let c2 = cubic.p3 + (q1 - cubic.p3) * (2.0 / 3.0);
+    cubic_farthest_fit_inside(
+        Vec2::ZERO,
+        c1 - cubic.p1,
+        c2 - cubic.p2,
+        Vec2::ZERO,
+        tolerance,
+    )
+    .then(|| vec![cubic.p0, q1, cubic.p3])
+}
+
+/// The quadratic control point for the piece of a cubic at `t` along the
+/// spline.
+fn cubic_approx_control(t: f64, cubic: &CubicBez) -> Point {
+    let p1 = cubic.p0 + (cubic.p1 - cubic.p0) * 1.5;
+    let p2 = cubic.p3 + (cubic.p2 - cubic.p3) * 1.5;
+    p1.lerp(p2, t)
+}
+
+/// The intersection of the lines ab and cd, if they are not parallel.
+fn calc_intersect(a: Point, b: Point, c: Point, d: Point) -> Option<Point> {
+    let ab = b - a;
+    let cd = d - c;
+    let perpendicular = Vec2::new(-ab.y, ab.x);
+    let denominator = perpendicular.dot(cd);
+    if denominator == 0.0 {
+        return None;
+    }
+    let h = perpendicular.dot(a - c) / denominator;
+    let result = c + cd * h;
+    (result.x.is_finite() && result.y.is_finite()).then_some(result)
+}
+
+/// Check whether a cubic, given as its difference from the approximating
+/// curve, stays within the tolerance of zero.
+///
+/// The cubic is subdivided until its control points are close enough.
+fn cubic_farthest_fit_inside(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, tolerance: f64) -> bool {
+    if p2.hypot() <= tolerance && p1.hypot() <= tolerance {
+        return true;
+    }
+    let mid = (p0 + (p1 + p2) * 3.0 + p3) * 0.125;
+    if mid.hypot() > tolerance {
+        return false;
+    }
+    let deriv3 = (p3 + p2 - p1 - p0) * 0.125;
+    cubic_farthest_fit_inside(p0, (p0 + p1) * 0.5, mid - deriv3, mid, tolerance)
+        && cubic_farthest_fit_inside(mid, mid + deriv3, (p2 + p3) * 0.5, p3, tolerance)
+}
+
+impl std::fmt::Display for Cu2QuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cu2QuError::ApproxNotFound(cubics) => write!(
+                f,
+                "no quadratic approximation found for {} cubic curve(s)",
+                cubics.len()
+            ),
+            Cu2QuError::IncompatiblePaths(index) => {
+                write!(f, "paths have different elements at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Cu2QuError {}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::{ParamCurveNearest, QuadBez};
+
+    use super::*;
+    use crate::pens::{write_to_pen, BezPathPen};
+
+    /// The largest distance from points on the spline to the cubic.
+    fn max_distance(cubic: CubicBez, spline: &[Point]) -> f64 {
+        let mut path = BezPath::new();
+        path.move_to(spline[0]);
+        append_spline(&mut path, spline);
+        path.segments()
+            .flat_map(|seg| (0..=10).map(move |i| seg.eval(i as f64 / 10.0)))
+            .map(|p| cubic.nearest(p, 1e-9).distance_sq.sqrt())
+            .fold(0.0, f64::max)
+    }
+
+    fn quarter_circle() -> CubicBez {
+        CubicBez::new((0.0, 100.0), (55.0, 100.0), (100.0, 55.0), (100.0, 0.0))
+    }
+
+    #[test]
+    fn quadratic_cubic_is_exact() {
+        // a cubic that is exactly a quadratic with control point (50, 100)
+        let quad = QuadBez::new((0.0, 0.0), (50.0, 100.0), (100.0, 0.0));
+        let cubic = quad.raise();
+        let spline = cubic_to_quadratic(cubic, 0.001).unwrap();
+        assert_eq!(spline.len(), 3);
+        assert!((spline[1] - Point::new(50.0, 100.0)).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn error_is_respected() {
+        let cubic = quarter_circle();
+        let coarse = cubic_to_quadratic(cubic, 1.0).unwrap();
+        let fine = cubic_to_quadratic(cubic, 0.01).unwrap();
+        assert!(fine.len() > coarse.len());
+        assert!(max_distance(cubic, &coarse) <= 1.0);
+        assert!(max_distance(cubic, &fine) <= 0.01);
+        assert_eq!(coarse[0], cubic.p0);
+        assert_eq!(*coarse.last().unwrap(), cubic.p3);
+    }
+
+    #[test]
+    fn compatible_splines() {
+        let flat = CubicBez::new((0.0, 0.0), (30.0, 5.0), (70.0, 5.0), (100.0, 0.0));
+        let cubics = [flat, quarter_circle()];
+        let splines = cubics_to_quadratic(&cubics, 0.1).unwrap();
+        // the flat curve alone needs fewer segments, but is given as many
+        // as the curved one
+        assert!(cubic_to_quadratic(flat, 0.1).unwrap().len() < splines[0].len());
+        assert_eq!(splines[0].len(), splines[1].len());
+        for (cubic, spline) in cubics.iter().zip(&splines) {
+            assert!(max_distance(*cubic, spline) <= 0.1);
+        }
+    }
+
+    #[test]
+    fn compatible_paths() {
+        let mut light = BezPath::new();
+        light.move_to((0.0, 0.0));
+        light.line_to((0.0, 100.0));
+        light.curve_to((20.0, 110.0), (80.0, 110.0), (100.0, 100.0));
+        light.close_path();
+        let mut bold = BezPath::new();
+        bold.move_to((0.0, 0.0));
+        bold.line_to((0.0, 100.0));
+        bold.curve_to((55.0, 200.0), (200.0, 155.0), (200.0, 100.0));
+        bold.close_path();
+        let quads = paths_to_quadratic(&[light.clone(), bold], 0.5).unwrap();
+        assert_eq!(quads[0].elements().len(), quads[1].elements().len());
+        assert!(quads[0].elements().len() > light.elements().len());
+        for path in &quads {
+            assert!(!path
+                .elements()
+                .iter()
+                .any(|el| matches!(el, PathEl::CurveTo(..))));
+        }
+
+        let mut other = BezPath::new();
+        other.move_to((0.0, 0.0));
+        other.line_to((0.0, 100.0));
+        other.quad_to((100.0, 200.0), (200.0, 100.0));
+        other.close_path();
+        assert_eq!(
+            paths_to_quadratic(&[light, other], 0.5),
+            Err(Cu2QuError::IncompatiblePaths(2))
+        );
+    }
+
+    #[test]
+    fn pen() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 100.0));
+        path.curve_to((55.0, 100.0), (100.0, 55.0), (100.0, 0.0));
+        path.line_to((0.0, 0.0));
+        path.close_path();
+        let mut bez_pen = BezPathPen::new();
+        let mut cu2qu_pen = Cu2QuPen::new(&mut bez_pen, 1.0);
+        write_to_pen(&path, &mut cu2qu_pen);
+        assert!(cu2qu_pen.error().is_none());
+        let expected = path_to_quadratic(&path, 1.0).unwrap();
+        let converted = bez_pen.into_inner();
+        assert_eq!(converted.elements().len(), expected.elements().len());
+        for (a, b) in converted.segments().zip(expected.segments()) {
+            assert!((a.end() - b.end()).hypot() < 1e-3);
+        }
+    }
+}
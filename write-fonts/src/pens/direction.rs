@@ -0,0 +1,512 @@
+//! Correcting the direction of contours.
+//!
+//! Outlines drawn with a mix of clockwise and counter-clockwise outer
+//! contours fill the same way under the non-zero winding rule only as long
+//! as their holes go the opposite way to their own outer contour. Rewriting
+//! every contour to follow one convention, based on how deeply it is nested,
+//! makes the outline render identically across rasterizers, including those
+//! using the even-odd rule.
+//!
+//! Contours that cross each other don't enclose one another, so overlapping
+//! outer contours all go the same way. These only render as intended with
+//! the non-zero rule; [`remove_overlaps`](super::remove_overlaps) merges them.
+
+use font_types::Pen;
+use kurbo::{
+    BezPath, Line, ParamCurve, ParamCurveArea, ParamCurveNearest, PathEl, PathSeg, Point, Shape,
+};
+
+use super::{
+    overlap::{bounds, intersections, overlaps, VERTEX_TOLERANCE},
+    write_to_pen, BezPathPen, Direction,
+};
+
+/// An error that occurs when correcting contour directions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DirectionError {
+    /// The contours at these indices cross themselves, so have no single
+    /// direction.
+    ///
+    /// Such outlines can be fixed with [`remove_overlaps`](super::remove_overlaps).
+    SelfIntersecting(Vec<usize>),
+}
+
+impl std::fmt::Display for DirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DirectionError::SelfIntersecting(contours) => {
+                write!(f, "self-intersecting contours {contours:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DirectionError {}
+
+/// The direction and nesting of a single contour.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContourInfo {
+    /// The signed area of the contour: positive if it is counter-clockwise,
+    /// negative if it is clockwise.
+    ///
+    /// Open contours are treated as closed by a straight line.
+    pub area: f64,
+    /// The number of other contours that enclose this one.
+    ///
+    /// Contours that cross this one are not counted. Contours at an even depth
+    /// are outer contours, and those at an odd depth are holes.
+    pub depth: usize,
+    /// `true` if the contour crosses itself.
+    pub self_intersecting: bool,
+}
+
+impl ContourInfo {
+    /// The direction of this contour, or `None` if it encloses no area.
+    pub fn direction(&self) -> Option<Direction> {
+        if self.area > 0.0 {
+            Some(Direction::CounterClockwise)
+        } else if self.area < 0.0 {
+            Some(Direction::Clockwise)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single subpath of a [`BezPath`].
+struct Contour<'a> {
+    elements: &'a [PathEl],
+    /// The segments of the contour, including any closing line.
+    segments: Vec<PathSeg>,
+    closed: bool,
+    /// `true` if the last segment is a closing line added to an open contour
+    implied_close: bool,
+}
+
+impl<'a> Contour<'a> {
+    fn new(elements: &'a [PathEl]) -> Self {
+        let mut segments = kurbo::segments(elements.iter().copied()).collect::<Vec<_>>();
+        let closed = matches!(elements.last(), Some(PathEl::ClosePath));
+        let mut implied_close = false;
+        if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+            if !closed && first.start() != last.end() {
+                segments.push(Line::new(last.end(), first.start()).into());
+                implied_close = true;
+            }
+        }
+        Contour {
+            elements,
+            segments,
+            closed,
+            implied_close,
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.segments.iter().map(|seg| seg.signed_area()).sum()
+    }
+
+    fn winding(&self, pt: Point) -> i32 {
+        let mut path = BezPath::new();
+        if let Some(first) = self.segments.first() {
+            path.move_to(first.start());
+            self.segments
+                .iter()
+                .for_each(|seg| path.push(seg.as_path_el()));
+        }
+        path.winding(pt)
+    }
+
+    fn distance_to(&self, pt: Point) -> f64 {
+        self.segments
+            .iter()
+            .map(|seg| seg.nearest(pt, 1e-9).distance_sq)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt()
+    }
+
+    /// `true` if this contour is inside `other`.
+    ///
+    /// This is tested at the first point on this contour that is not also on
+    /// the other one.
+    fn is_inside(&self, other: &Contour) -> bool {
+        self.segments
+            .iter()
+            .flat_map(|seg| [seg.start(), seg.eval(0.5)])
+            .find(|pt| other.distance_to(*pt) > VERTEX_TOLERANCE)
+            .map(|pt| other.winding(pt) != 0)
+            .unwrap_or(false)
+    }
+
+    fn vertices(&self) -> impl Iterator<Item = Point> + '_ {
+        self.segments
+            .iter()
+            .map(|seg| seg.start())
+            .chain(self.segments.last().map(|seg| seg.end()))
+    }
+
+    fn is_self_intersecting(&self) -> bool {
+        let segments = &self.segments;
+        let vertices = self.vertices().collect::<Vec<_>>();
+        (0..segments.len())
+            .any(|i| (i + 1..segments.len()).any(|j| crosses(segments[i], segments[j], &vertices)))
+    }
+
+    /// `true` if this contour crosses `other`.
+    fn crosses(&self, other: &Contour) -> bool {
+        let vertices = self.vertices().chain(other.vertices()).collect::<Vec<_>>();
+        self.segments
+            .iter()
+            .any(|a| other.segments.iter().any(|b| crosses(*a, *b, &vertices)))
+    }
+
+    /// Write the contour reversed, keeping the start point of closed contours.
+    fn reverse_into(&self, path: &mut BezPath) {
+        // the implied closing line of an open contour is not part of its output
+        let segments = if self.implied_close {
+            &self.segments[..self.segments.len() - 1]
+        } else {
+            &self.segments[..]
+        };
+        let start = if self.closed {
+            segments[0].start()
+        } else {
+            segments.last().unwrap().end()
+        };
+        path.move_to(start);
+        let mut reversed = segments.iter().rev().map(|seg| seg.reverse()).peekable();
+        while let Some(seg) = reversed.next() {
+            // a closing line is implied by the close
+            let is_closing_line = self.closed
+                && reversed.peek().is_none()
+                && matches!(seg, PathSeg::Line(line) if line.p1 == start);
+            if !is_closing_line {
+                path.push(seg.as_path_el());
+            }
+        }
+        if self.closed {
+            path.close_path();
+        }
+    }
+}
+
+/// `true` if two segments cross away from any of the given vertices.
+///
+/// Segments that only meet at a vertex don't cross.
+fn crosses(a: PathSeg, b: PathSeg, vertices: &[Point]) -> bool {
+    overlaps(bounds(&a), bounds(&b))
+        && intersections(a, b)
+            .into_iter()
+            .map(|(t, _)| a.eval(t))
+            .any(|pt| vertices.iter().all(|v| v.distance(pt) > VERTEX_TOLERANCE))
+}
+
+fn subpaths(path: &BezPath) -> Vec<&[PathEl]> {
+    let elements = path.elements();
+    let mut starts = elements
+        .iter()
+        .enumerate()
+        .filter_map(|(i, el)| matches!(el, PathEl::MoveTo(_)).then_some(i))
+        .collect::<Vec<_>>();
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+    starts.push(elements.len());
+    starts
+        .windows(2)
+        .map(|range| &elements[range[0]..range[1]])
+        .filter(|elements| !elements.is_empty())
+        .collect()
+}
+
+/// Compute the area and nesting of each contour in a path.
+pub fn contour_info(path: &BezPath) -> Vec<ContourInfo> {
+    let contours = subpaths(path)
+        .into_iter()
+        .map(Contour::new)
+        .collect::<Vec<_>>();
+    contour_info_impl(&contours)
+}
+
+fn contour_info_impl(contours: &[Contour]) -> Vec<ContourInfo> {
+    let areas = contours.iter().map(Contour::area).collect::<Vec<_>>();
+    contours
+        .iter()
+        .enumerate()
+        .map(|(i, contour)| {
+            let depth = contours
+                .iter()
+                .enumerate()
+                // only a larger contour can enclose this one, and contours
+                // that cross don't enclose each other
+                .filter(|(j, other)| {
+                    *j != i
+                        && areas[*j].abs() > areas[i].abs()
+                        && contour.is_inside(other)
+                        && !contour.crosses(other)
+                })
+                .count();
+            ContourInfo {
+                area: areas[i],
+                depth,
+                self_intersecting: contour.is_self_intersecting(),
+            }
+        })
+        .collect()
+}
+
+/// Rewrite the contours of a path to follow a direction convention.
+///
+/// Outer contours are made to go in the given direction, and holes the
+/// opposite way, reversing contours where needed. The start point of each
+/// closed contour is kept, as are contours that enclose no area.
+///
+/// Returns an error if any contour intersects itself, since it is then
+/// unclear which parts of it are holes.
+pub fn correct_direction(path: &BezPath, direction: Direction) -> Result<BezPath, DirectionError> {
+    let contours = subpaths(path)
+        .into_iter()
+        .map(Contour::new)
+        .collect::<Vec<_>>();
+    let info = contour_info_impl(&contours);
+    let self_intersecting = info
+        .iter()
+        .enumerate()
+        .filter_map(|(i, info)| info.self_intersecting.then_some(i))
+        .collect::<Vec<_>>();
+    if !self_intersecting.is_empty() {
+        return Err(DirectionError::SelfIntersecting(self_intersecting));
+    }
+    let mut result = BezPath::new();
+    for (contour, info) in contours.iter().zip(&info) {
+        let wanted = match (info.depth % 2 == 0, direction) {
+            (true, direction) => direction,
+            (false, Direction::Clockwise) => Direction::CounterClockwise,
+            (false, Direction::CounterClockwise) => Direction::Clockwise,
+        };
+        match info.direction() {
+            Some(current) if current != wanted => contour.reverse_into(&mut result),
+            _ => contour.elements.iter().for_each(|el| result.push(*el)),
+        }
+    }
+    Ok(result)
+}
+
+/// Buffers commands until flushed, then plays them on the inner pen with
+/// each contour going in the given direction.
+///
+/// Unlike [`ReverseContourPen`](super::ReverseContourPen), which reverses
+/// every contour, this only reverses those that don't already follow the
+/// convention for their nesting depth; see [`correct_direction`].
+pub struct ContourDirectionPen<'a, T: Pen> {
+    inner_pen: &'a mut T,
+    direction: Direction,
+    pending: BezPathPen,
+}
+
+impl<'a, T: Pen> ContourDirectionPen<'a, T> {
+    pub fn new(inner_pen: &'a mut T, direction: Direction) -> ContourDirectionPen<'a, T> {
+        ContourDirectionPen {
+            inner_pen,
+            direction,
+            pending: BezPathPen::new(),
+        }
+    }
+
+    /// Flush buffer into inner, correcting contour directions.
+    ///
+    /// Requires an explicit call to afford the client the opportunity to receive errors.
+    /// Nothing is written to the inner pen if any contour is self-intersecting.
+    pub fn flush(&mut self) -> Result<(), DirectionError> {
+        let path = std::mem::take(&mut self.pending).into_inner();
+        let corrected = correct_direction(&path, self.direction)?;
+        write_to_pen(&corrected, self.inner_pen);
+        Ok(())
+    }
+}
+
+impl<'a, T: Pen> Pen for ContourDirectionPen<'a, T> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.pending.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.pending.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.pending.quad_to(cx0, cy0, x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.pending.curve_to(cx0, cy0, cx1, cy1, x, y);
+    }
+
+    fn close(&mut self) {
+        self.pending.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::{Rect, Shape};
+
+    use super::*;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BezPath {
+        // counter-clockwise
+        Rect::new(x0, y0, x1, y1).to_path(0.1)
+    }
+
+    fn triangle() -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.line_to((100., 0.));
+        path.line_to((0., 100.));
+        path.close_path();
+        path
+    }
+
+    #[test]
+    fn nesting() {
+        let mut path = rect(0., 0., 100., 100.);
+        path.extend(rect(25., 25., 75., 75.));
+        path.extend(rect(40., 40., 60., 60.));
+        path.extend(rect(200., 0., 300., 100.));
+        let info = contour_info(&path);
+        assert_eq!(
+            info.iter().map(|info| info.depth).collect::<Vec<_>>(),
+            [0, 1, 2, 0]
+        );
+        assert_eq!(
+            info.iter().map(|info| info.area).collect::<Vec<_>>(),
+            [10000., 2500., 400., 10000.]
+        );
+        assert!(info.iter().all(|info| !info.self_intersecting));
+        assert_eq!(info[0].direction(), Some(Direction::CounterClockwise));
+    }
+
+    #[test]
+    fn mixed_winding() {
+        // both contours counter-clockwise, so the hole would be filled
+        let mut path = rect(0., 0., 100., 100.);
+        path.extend(rect(25., 25., 75., 75.));
+
+        let truetype = correct_direction(&path, Direction::Clockwise).unwrap();
+        let info = contour_info(&truetype);
+        assert_eq!(info[0].direction(), Some(Direction::Clockwise));
+        assert_eq!(info[1].direction(), Some(Direction::CounterClockwise));
+        assert_eq!(truetype.winding(Point::new(50., 50.)), 0);
+        assert_eq!(truetype.elements().len(), path.elements().len());
+
+        let postscript = correct_direction(&path, Direction::CounterClockwise).unwrap();
+        let info = contour_info(&postscript);
+        assert_eq!(info[0].direction(), Some(Direction::CounterClockwise));
+        assert_eq!(info[1].direction(), Some(Direction::Clockwise));
+        // the outer contour is unchanged
+        assert_eq!(postscript.elements()[..6], path.elements()[..6]);
+    }
+
+    #[test]
+    fn overlapping_contours() {
+        // the second square overlaps the first without being inside it,
+        // and has a hole of its own
+        let mut path = rect(0., 0., 100., 100.);
+        path.extend(rect(50., 50., 140., 140.));
+        path.extend(rect(110., 110., 130., 130.));
+        let info = contour_info(&path);
+        assert_eq!(
+            info.iter().map(|info| info.depth).collect::<Vec<_>>(),
+            [0, 0, 1]
+        );
+
+        let corrected = correct_direction(&path, Direction::Clockwise).unwrap();
+        let info = contour_info(&corrected);
+        assert_eq!(info[0].direction(), Some(Direction::Clockwise));
+        assert_eq!(info[1].direction(), Some(Direction::Clockwise));
+        assert_eq!(info[2].direction(), Some(Direction::CounterClockwise));
+        // the overlap is still filled, and the hole is still a hole
+        assert_eq!(path.winding(Point::new(75., 75.)), 2);
+        assert_eq!(corrected.winding(Point::new(75., 75.)), -2);
+        assert_eq!(corrected.winding(Point::new(25., 25.)), -1);
+        assert_eq!(corrected.winding(Point::new(120., 120.)), 0);
+    }
+
+    #[test]
+    fn reverse_keeps_start_point() {
+        let reversed = correct_direction(&triangle(), Direction::Clockwise).unwrap();
+        assert_eq!(
+            reversed.elements(),
+            [
+                PathEl::MoveTo((0., 0.).into()),
+                PathEl::LineTo((0., 100.).into()),
+                PathEl::LineTo((100., 0.).into()),
+                PathEl::ClosePath,
+            ]
+        );
+
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.curve_to((50., 0.), (100., 50.), (100., 100.));
+        path.quad_to((0., 100.), (0., 0.));
+        path.close_path();
+        let reversed = correct_direction(&path, Direction::Clockwise).unwrap();
+        assert_eq!(
+            reversed.elements(),
+            [
+                PathEl::MoveTo((0., 0.).into()),
+                PathEl::QuadTo((0., 100.).into(), (100., 100.).into()),
+                PathEl::CurveTo((100., 50.).into(), (50., 0.).into(), (0., 0.).into()),
+                PathEl::ClosePath,
+            ]
+        );
+    }
+
+    #[test]
+    fn open_contour() {
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.line_to((100., 0.));
+        path.line_to((0., 100.));
+        let info = contour_info(&path);
+        assert_eq!(info[0].area, 5000.);
+        let reversed = correct_direction(&path, Direction::Clockwise).unwrap();
+        assert_eq!(
+            reversed.elements(),
+            [
+                PathEl::MoveTo((0., 100.).into()),
+                PathEl::LineTo((100., 0.).into()),
+                PathEl::LineTo((0., 0.).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn self_intersecting() {
+        let mut path = rect(200., 0., 300., 100.);
+        path.move_to((0., 0.));
+        path.line_to((100., 100.));
+        path.line_to((100., 0.));
+        path.line_to((0., 100.));
+        path.close_path();
+        let info = contour_info(&path);
+        assert!(!info[0].self_intersecting);
+        assert!(info[1].self_intersecting);
+        assert_eq!(
+            correct_direction(&path, Direction::Clockwise),
+            Err(DirectionError::SelfIntersecting(vec![1]))
+        );
+    }
+
+    #[test]
+    fn pen() {
+        let mut inner = BezPathPen::new();
+        let mut pen = ContourDirectionPen::new(&mut inner, Direction::Clockwise);
+        write_to_pen(&triangle(), &mut pen);
+        pen.flush().unwrap();
+        assert_eq!(
+            inner.into_inner(),
+            correct_direction(&triangle(), Direction::Clockwise).unwrap()
+        );
+    }
+}
@@ -0,0 +1,603 @@
+//! Removing overlaps between contours.
+//!
+//! The union of a set of contours is computed by splitting every segment
+//! where it meets another, and keeping the pieces that have the filled
+//! (non-zero winding) region on exactly one side. The kept pieces are pieces
+//! of the original segments, so lines, quadratic and cubic curves are
+//! preserved, and are then joined back into closed contours.
+
+use kurbo::{
+    BezPath, Line, ParamCurve, ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest, PathEl,
+    PathSeg, Point, Rect, Shape, Vec2,
+};
+
+use super::Direction;
+
+/// Points closer than this, in font units, are treated as the same point.
+pub(super) const VERTEX_TOLERANCE: f64 = 1e-3;
+
+/// How far either side of a segment we test whether we are inside the outline.
+const SAMPLE_DISTANCE: f64 = 1e-4;
+
+/// Curves are subdivided until their control points are this close to the
+/// chord, when looking for intersections.
+const FLATNESS: f64 = 1e-2;
+
+/// The maximum depth of subdivision when looking for intersections.
+const MAX_DEPTH: usize = 32;
+
+/// An error that occurs when removing overlaps.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OverlapError {
+    /// The input contains a coordinate that is infinite or NaN.
+    NotFinite,
+    /// The outline of the union could not be joined into closed contours at
+    /// the given point.
+    ///
+    /// This happens when curves are nearly, but not exactly, coincident, and
+    /// means the result would be unreliable.
+    OpenContour(Point),
+    /// The union would be empty, although the input fills some area.
+    ///
+    /// Like [`OpenContour`](Self::OpenContour), this happens when curves are
+    /// nearly, but not exactly, coincident.
+    Collapsed,
+}
+
+impl std::fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverlapError::NotFinite => write!(f, "path has a non-finite coordinate"),
+            OverlapError::OpenContour(pt) => {
+                write!(f, "could not close contour at ({}, {})", pt.x, pt.y)
+            }
+            OverlapError::Collapsed => write!(f, "the outline of the union is empty"),
+        }
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+/// Remove the overlaps between the contours of a path.
+///
+/// This is the same as [`union`] with a single path.
+pub fn remove_overlaps(path: &BezPath, direction: Direction) -> Result<BezPath, OverlapError> {
+    union(std::slice::from_ref(path), direction)
+}
+
+/// Compute the union of the areas filled by several paths.
+///
+/// Paths are filled using the non-zero winding rule, as in both TrueType
+/// and CFF outlines, and any subpaths that are not closed are closed with a
+/// straight line. The paths could be the output of a
+/// [`BezPathPen`](super::BezPathPen), for instance for each component of a
+/// composite glyph.
+///
+/// The result has no overlapping contours, with outer contours following
+/// the given [`Direction`] and holes going the opposite way. Every contour
+/// is closed, and ends with an explicit segment back to its start point.
+///
+/// Points closer than 1/1000 of a unit are merged, so any piece of the
+/// outline shorter than that is dropped. Curves that are closer than that
+/// without coinciding can't be resolved reliably: this is reported as an
+/// error when it is detected, but may also leave small distortions in the
+/// result.
+pub fn union(paths: &[BezPath], direction: Direction) -> Result<BezPath, OverlapError> {
+    let contours = paths
+        .iter()
+        .map(contours)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let mut filled = BezPath::new();
+    for contour in &contours {
+        filled.move_to(contour[0].start());
+        contour.iter().for_each(|seg| filled.push(seg.as_path_el()));
+        filled.close_path();
+    }
+
+    let segments = contours.into_iter().flatten().collect::<Vec<_>>();
+    let mut vertices = Vec::new();
+    let mut pieces: Vec<Piece> = Vec::new();
+    let mut any_filled = false;
+    for (seg, splits) in segments.iter().zip(split_points(&segments)) {
+        for range in splits.windows(2) {
+            let sub = seg.subsegment(range[0]..range[1]);
+            let start = vertex_id(&mut vertices, sub.start());
+            let end = vertex_id(&mut vertices, sub.end());
+            // pieces shorter than VERTEX_TOLERANCE collapse onto a vertex
+            if start == end {
+                continue;
+            }
+            let mut piece = match Piece::new(sub, start, end, &vertices) {
+                Some(piece) => piece,
+                None => continue,
+            };
+            let (left, right) = piece.sides();
+            let (left, right) = (filled.winding(left) != 0, filled.winding(right) != 0);
+            any_filled |= left || right;
+            if left == right {
+                continue;
+            }
+            // we keep the filled region on the left of each piece
+            if right {
+                piece = piece.reverse();
+            }
+            if !pieces.iter().any(|other| piece.coincides_with(other)) {
+                pieces.push(piece);
+            }
+        }
+    }
+    // a bounded region always has some edge with the outside
+    if pieces.is_empty() && any_filled {
+        return Err(OverlapError::Collapsed);
+    }
+    join_pieces(&pieces, &vertices, direction)
+}
+
+/// Split a path into closed contours of segments, dropping empty segments.
+fn contours(path: &BezPath) -> Result<Vec<Vec<PathSeg>>, OverlapError> {
+    if path.elements().iter().any(|el| !el.is_finite()) {
+        return Err(OverlapError::NotFinite);
+    }
+    let mut contours = Vec::new();
+    let mut current: Vec<PathSeg> = Vec::new();
+    let mut start = Point::ZERO;
+    let mut last = Point::ZERO;
+    let mut close = |current: &mut Vec<PathSeg>, start: Point, last: Point| {
+        if !current.is_empty() {
+            if last != start {
+                current.push(Line::new(last, start).into());
+            }
+            contours.push(std::mem::take(current));
+        }
+    };
+    for el in path.elements() {
+        let seg: PathSeg = match *el {
+            PathEl::MoveTo(pt) => {
+                close(&mut current, start, last);
+                start = pt;
+                last = pt;
+                continue;
+            }
+            PathEl::ClosePath => {
+                close(&mut current, start, last);
+                last = start;
+                continue;
+            }
+            PathEl::LineTo(p1) => Line::new(last, p1).into(),
+            PathEl::QuadTo(p1, p2) => kurbo::QuadBez::new(last, p1, p2).into(),
+            PathEl::CurveTo(p1, p2, p3) => kurbo::CubicBez::new(last, p1, p2, p3).into(),
+        };
+        last = seg.end();
+        if bounds(&seg).area() > 0.0 || seg.start() != seg.end() {
+            current.push(seg);
+        }
+    }
+    close(&mut current, start, last);
+    Ok(contours)
+}
+
+/// For each segment, the sorted parameters at which it meets other segments,
+/// including 0 and 1.
+fn split_points(segments: &[PathSeg]) -> Vec<Vec<f64>> {
+    let bounds = segments
+        .iter()
+        .map(|seg| bounds(seg).inflate(VERTEX_TOLERANCE, VERTEX_TOLERANCE))
+        .collect::<Vec<_>>();
+    let mut splits = vec![vec![0.0, 1.0]; segments.len()];
+    for i in 0..segments.len() {
+        for j in i + 1..segments.len() {
+            if !overlaps(bounds[i], bounds[j]) {
+                continue;
+            }
+            for (ti, tj) in intersections(segments[i], segments[j]) {
+                splits[i].push(ti);
+                splits[j].push(tj);
+            }
+        }
+    }
+    // points where one segment ends on another; this also catches segments
+    // that overlap along some of their length
+    for (i, seg) in segments.iter().enumerate() {
+        for (j, other) in segments.iter().enumerate() {
+            if i == j || !overlaps(bounds[i], bounds[j]) {
+                continue;
+            }
+            for pt in [other.start(), other.end()] {
+                let nearest = seg.nearest(pt, 1e-9);
+                if nearest.distance_sq < VERTEX_TOLERANCE * VERTEX_TOLERANCE {
+                    splits[i].push(nearest.t);
+                }
+            }
+        }
+    }
+    for (seg, splits) in segments.iter().zip(splits.iter_mut()) {
+        splits.retain(|t| (0.0..=1.0).contains(t));
+        splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // drop splits too close to the previous one, keeping the end point
+        let mut kept: Vec<f64> = Vec::with_capacity(splits.len());
+        for &t in splits.iter() {
+            match kept.last() {
+                Some(&prev) if seg.eval(prev).distance(seg.eval(t)) < VERTEX_TOLERANCE => {
+                    if t == 1.0 {
+                        *kept.last_mut().unwrap() = t;
+                    }
+                }
+                _ => kept.push(t),
+            }
+        }
+        *splits = kept;
+    }
+    splits
+}
+
+pub(super) fn bounds(seg: &PathSeg) -> Rect {
+    ParamCurveExtrema::bounding_box(seg)
+}
+
+pub(super) fn overlaps(a: Rect, b: Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+/// The parameters on each segment of the points where they cross.
+pub(super) fn intersections(a: PathSeg, b: PathSeg) -> Vec<(f64, f64)> {
+    // coincident segments don't cross; they are split where they end
+    if a == b || a == b.reverse() {
+        return Vec::new();
+    }
+    match (a, b) {
+        (_, PathSeg::Line(line)) => a
+            .intersect_line(line)
+            .into_iter()
+            .map(|hit| (hit.segment_t, hit.line_t))
+            .collect(),
+        (PathSeg::Line(line), _) => b
+            .intersect_line(line)
+            .into_iter()
+            .map(|hit| (hit.line_t, hit.segment_t))
+            .collect(),
+        _ => {
+            let mut result = Vec::new();
+            curve_intersections(a, 0.0..1.0, b, 0.0..1.0, 0, &mut result);
+            result
+        }
+    }
+}
+
+/// Find the intersections of two curves by subdividing them until they are
+/// flat, then refining the intersections of the chords.
+fn curve_intersections(
+    a: PathSeg,
+    a_range: std::ops::Range<f64>,
+    b: PathSeg,
+    b_range: std::ops::Range<f64>,
+    depth: usize,
+    result: &mut Vec<(f64, f64)>,
+) {
+    let a_sub = a.subsegment(a_range.clone());
+    let b_sub = b.subsegment(b_range.clone());
+    if !overlaps(bounds(&a_sub), bounds(&b_sub)) {
+        return;
+    }
+    let a_flat = is_flat(a_sub);
+    let b_flat = is_flat(b_sub);
+    if (a_flat && b_flat) || depth == MAX_DEPTH {
+        let a_chord = Line::new(a_sub.start(), a_sub.end());
+        let b_chord = Line::new(b_sub.start(), b_sub.end());
+        if let Some(hit) = PathSeg::Line(a_chord).intersect_line(b_chord).first() {
+            let s = lerp(&a_range, hit.segment_t);
+            let t = lerp(&b_range, hit.line_t);
+            result.push(refine(a, b, s, t));
+        }
+        return;
+    }
+    if !a_flat {
+        let mid = (a_range.start + a_range.end) / 2.0;
+        for a_range in [a_range.start..mid, mid..a_range.end] {
+            curve_intersections(a, a_range, b, b_range.clone(), depth + 1, result);
+        }
+    } else {
+        let mid = (b_range.start + b_range.end) / 2.0;
+        for b_range in [b_range.start..mid, mid..b_range.end] {
+            curve_intersections(a, a_range.clone(), b, b_range, depth + 1, result);
+        }
+    }
+}
+
+fn lerp(range: &std::ops::Range<f64>, t: f64) -> f64 {
+    range.start + (range.end - range.start) * t
+}
+
+fn is_flat(seg: PathSeg) -> bool {
+    let chord = Line::new(seg.start(), seg.end());
+    match seg {
+        PathSeg::Line(_) => true,
+        PathSeg::Quad(quad) => distance_to_line(quad.p1, chord) <= FLATNESS,
+        PathSeg::Cubic(cubic) => {
+            distance_to_line(cubic.p1, chord) <= FLATNESS
+                && distance_to_line(cubic.p2, chord) <= FLATNESS
+        }
+    }
+}
+
+fn distance_to_line(pt: Point, line: Line) -> f64 {
+    line.nearest(pt, 0.0).distance_sq.sqrt()
+}
+
+/// Refine an intersection with Newton's method, keeping the estimate if
+/// that doesn't converge.
+fn refine(a: PathSeg, b: PathSeg, s: f64, t: f64) -> (f64, f64) {
+    let (mut s1, mut t1) = (s, t);
+    for _ in 0..8 {
+        let delta = a.eval(s1) - b.eval(t1);
+        let da = deriv(a, s1);
+        let db = deriv(b, t1);
+        // solve da * ds - db * dt = -delta
+        let det = da.cross(-db);
+        if det.abs() < 1e-12 {
+            break;
+        }
+        s1 -= delta.cross(-db) / det;
+        t1 -= da.cross(delta) / det;
+    }
+    let valid = (0.0..=1.0).contains(&s1) && (0.0..=1.0).contains(&t1);
+    if valid && a.eval(s1).distance(b.eval(t1)) <= a.eval(s).distance(b.eval(t)) {
+        (s1, t1)
+    } else {
+        (s, t)
+    }
+}
+
+fn deriv(seg: PathSeg, t: f64) -> Vec2 {
+    match seg {
+        PathSeg::Line(line) => line.p1 - line.p0,
+        PathSeg::Quad(quad) => quad.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(cubic) => cubic.deriv().eval(t).to_vec2(),
+    }
+}
+
+/// The index of the vertex at this point, adding it if it is new.
+fn vertex_id(vertices: &mut Vec<Point>, pt: Point) -> usize {
+    match vertices
+        .iter()
+        .position(|v| v.distance(pt) < VERTEX_TOLERANCE)
+    {
+        Some(idx) => idx,
+        None => {
+            vertices.push(pt);
+            vertices.len() - 1
+        }
+    }
+}
+
+/// A piece of an input segment, between two vertices.
+#[derive(Clone, Copy, Debug)]
+struct Piece {
+    seg: PathSeg,
+    start: usize,
+    end: usize,
+}
+
+impl Piece {
+    /// Create a piece, moving its end points onto its vertices.
+    ///
+    /// Returns `None` if the piece is degenerate.
+    fn new(seg: PathSeg, start: usize, end: usize, vertices: &[Point]) -> Option<Self> {
+        let (p0, p1) = (vertices[start], vertices[end]);
+        let seg = match seg {
+            PathSeg::Line(_) => Line::new(p0, p1).into(),
+            PathSeg::Quad(quad) => kurbo::QuadBez::new(p0, quad.p1, p1).into(),
+            PathSeg::Cubic(cubic) => kurbo::CubicBez::new(p0, cubic.p1, cubic.p2, p1).into(),
+        };
+        let piece = Piece { seg, start, end };
+        piece.normal().map(|_| piece)
+    }
+
+    fn normal(&self) -> Option<Vec2> {
+        let mut tangent = deriv(self.seg, 0.5);
+        if tangent.hypot() < 1e-9 {
+            tangent = self.seg.end() - self.seg.start();
+        }
+        let len = tangent.hypot();
+        (len >= 1e-9).then(|| Vec2::new(-tangent.y, tangent.x) / len)
+    }
+
+    /// Points just to the left and right of the middle of the piece.
+    fn sides(&self) -> (Point, Point) {
+        let mid = self.seg.eval(0.5);
+        let normal = self.normal().unwrap_or_default() * SAMPLE_DISTANCE;
+        (mid + normal, mid - normal)
+    }
+
+    fn reverse(self) -> Self {
+        Piece {
+            seg: self.seg.reverse(),
+            start: self.end,
+            end: self.start,
+        }
+    }
+
+    fn coincides_with(&self, other: &Piece) -> bool {
+        self.start == other.start
+            && self.end == other.end
+            && self.seg.eval(0.5).distance(other.seg.eval(0.5)) < VERTEX_TOLERANCE
+    }
+}
+
+/// Join pieces end to end into closed contours.
+fn join_pieces(
+    pieces: &[Piece],
+    vertices: &[Point],
+    direction: Direction,
+) -> Result<BezPath, OverlapError> {
+    let mut outgoing = vec![Vec::new(); vertices.len()];
+    for (i, piece) in pieces.iter().enumerate() {
+        outgoing[piece.start].push(i);
+    }
+    let mut used = vec![false; pieces.len()];
+    let mut path = BezPath::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut contour = vec![pieces[first]];
+        let start = pieces[first].start;
+        let mut current = pieces[first].end;
+        while current != start {
+            let next = outgoing[current]
+                .iter()
+                .copied()
+                .find(|i| !used[*i])
+                .ok_or(OverlapError::OpenContour(vertices[current]))?;
+            used[next] = true;
+            contour.push(pieces[next]);
+            current = pieces[next].end;
+        }
+        // pieces have the filled region on their left, so outer contours
+        // are counter-clockwise
+        if direction == Direction::Clockwise {
+            contour = contour.into_iter().rev().map(Piece::reverse).collect();
+        }
+        path.move_to(contour[0].seg.start());
+        contour
+            .iter()
+            .for_each(|piece| path.push(piece.seg.as_path_el()));
+        path.close_path();
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::Circle;
+
+    use super::*;
+    use crate::pens::path_to_quadratic;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BezPath {
+        Rect::new(x0, y0, x1, y1).to_path(0.1)
+    }
+
+    fn num_contours(path: &BezPath) -> usize {
+        path.elements()
+            .iter()
+            .filter(|el| matches!(el, PathEl::MoveTo(_)))
+            .count()
+    }
+
+    #[test]
+    fn overlapping_rects() {
+        let mut path = rect(0., 0., 100., 100.);
+        path.extend(rect(50., 50., 150., 150.));
+        let result = remove_overlaps(&path, Direction::CounterClockwise).unwrap();
+        assert_eq!(num_contours(&result), 1);
+        assert!((result.area() - 17500.).abs() < 1e-6);
+        assert_eq!(result.winding(Point::new(75., 75.)), 1);
+        assert_eq!(result.winding(Point::new(25., 125.)), 0);
+
+        let result = remove_overlaps(&path, Direction::Clockwise).unwrap();
+        assert!((result.area() + 17500.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn separate_contours_are_kept() {
+        let mut path = rect(0., 0., 100., 100.);
+        path.extend(rect(200., 0., 300., 100.));
+        let result = remove_overlaps(&path, Direction::CounterClockwise).unwrap();
+        assert_eq!(num_contours(&result), 2);
+        assert!((result.area() - 20000.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn holes_are_kept() {
+        // a counter-clockwise outer contour and a clockwise inner one
+        let mut path = rect(0., 0., 100., 100.);
+        let mut hole = BezPath::new();
+        hole.move_to((25., 25.));
+        hole.line_to((25., 75.));
+        hole.line_to((75., 75.));
+        hole.line_to((75., 25.));
+        hole.close_path();
+        path.extend(hole);
+        let result = remove_overlaps(&path, Direction::Clockwise).unwrap();
+        assert_eq!(num_contours(&result), 2);
+        assert!((result.area() + 7500.).abs() < 1e-6);
+        assert_eq!(result.winding(Point::new(50., 50.)), 0);
+    }
+
+    #[test]
+    fn coincident_contours() {
+        let path = rect(0., 0., 100., 100.);
+        let result = union(&[path.clone(), path], Direction::CounterClockwise).unwrap();
+        assert_eq!(num_contours(&result), 1);
+        assert!((result.area() - 10000.).abs() < 1e-6);
+
+        // sharing an edge
+        let result = union(
+            &[rect(0., 0., 100., 100.), rect(100., 0., 200., 100.)],
+            Direction::CounterClockwise,
+        )
+        .unwrap();
+        assert_eq!(num_contours(&result), 1);
+        assert!((result.area() - 20000.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn overlapping_circles() {
+        let a = Circle::new((0., 0.), 100.).to_path(0.01);
+        let b = Circle::new((100., 0.), 100.).to_path(0.01);
+        // two circles of radius r, d apart, less the lens between them
+        let (r, d) = (100f64, 100f64);
+        let lens = 2. * r * r * (d / (2. * r)).acos() - d / 2. * (4. * r * r - d * d).sqrt();
+        let expected = 2. * std::f64::consts::PI * r * r - lens;
+
+        let result = union(&[a.clone(), b.clone()], Direction::CounterClockwise).unwrap();
+        assert_eq!(num_contours(&result), 1);
+        assert!((result.area() - expected).abs() / expected < 1e-3);
+        assert!(result
+            .elements()
+            .iter()
+            .all(|el| !matches!(el, PathEl::LineTo(_) | PathEl::QuadTo(..))));
+
+        // and with quadratic curves
+        let a = path_to_quadratic(&a, 0.1).unwrap();
+        let b = path_to_quadratic(&b, 0.1).unwrap();
+        let result = union(&[a, b], Direction::Clockwise).unwrap();
+        assert_eq!(num_contours(&result), 1);
+        assert!((result.area() + expected).abs() / expected < 1e-3);
+        assert!(result
+            .elements()
+            .iter()
+            .all(|el| !matches!(el, PathEl::LineTo(_) | PathEl::CurveTo(..))));
+    }
+
+    #[test]
+    fn nearly_coincident_curves() {
+        let a = Circle::new((0., 0.), 100.).to_path(0.01);
+        let b = Circle::new((5e-4, 0.), 100.).to_path(0.01);
+        assert!(matches!(
+            union(&[a.clone(), b], Direction::CounterClockwise),
+            Err(OverlapError::OpenContour(_))
+        ));
+
+        let b = Circle::new((0., 0.), 100. + 5e-4).to_path(0.01);
+        assert_eq!(
+            union(&[a, b], Direction::CounterClockwise),
+            Err(OverlapError::Collapsed)
+        );
+    }
+
+    #[test]
+    fn not_finite() {
+        let mut path = rect(0., 0., 100., 100.);
+        path.move_to((0., 0.));
+        path.line_to((f64::NAN, 0.));
+        assert_eq!(
+            remove_overlaps(&path, Direction::Clockwise),
+            Err(OverlapError::NotFinite)
+        );
+    }
+}
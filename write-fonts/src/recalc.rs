@@ -0,0 +1,1211 @@
+//! Recalculating derived fields.
+//!
+//! A number of fields in the head, hhea, maxp and OS/2 tables summarize the
+//! contents of other tables: bounding boxes, metric extremes, limits on glyph
+//! complexity and the set of supported characters. The functions in this
+//! module recompute these fields from a compiled font's glyf, loca, hmtx and
+//! cmap tables, following what fontTools does when saving a font.
+//!
+//! Glyph bounds are computed from the points of each glyph's outline, with the
+//! components of composite glyphs transformed into place, rather than taken
+//! from the (possibly stale) bounding box in the glyph header. Fonts without a
+//! glyf table keep their existing bounds-based fields.
+
+use std::collections::BTreeSet;
+
+use kurbo::{Affine, Point, Rect};
+use read::{
+    tables::{
+        cmap::{CmapSubtable, PlatformId},
+        glyf::{Anchor, Component, CompositeGlyphFlags, Glyph},
+    },
+    types::GlyphId,
+    FontRef, ReadError, TableProvider, TopLevelTable,
+};
+use types::{FWord, UfWord};
+
+use crate::{
+    error::Error,
+    tables::{head::Head, hhea::Hhea, maxp::Maxp, os2::Os2},
+    FontBuilder, OtRound,
+};
+
+// the spec doesn't give a limit, but anything deeper is almost certainly a cycle
+const MAX_COMPONENT_DEPTH: u16 = 64;
+
+/// An error that occurs while recalculating a font.
+#[derive(Debug)]
+pub enum RecalcError {
+    /// A table needed for the calculation could not be read.
+    Read(ReadError),
+    /// An updated table could not be written.
+    Write(Error),
+}
+
+/// Recompute the derived fields of the head, hhea, maxp and OS/2 tables.
+///
+/// Returns the data for a new font with the updated tables, and all other
+/// tables unchanged. Tables that are missing from the font are skipped.
+pub fn recalc_font(font: &FontRef) -> Result<Vec<u8>, RecalcError> {
+    let glyphs = GlyphSummary::new(font)?;
+    let mut builder = FontBuilder::default();
+    if font.table_data(Head::TAG).is_some() {
+        let mut head = font.expect_table::<Head>()?;
+        update_head(glyphs.as_ref(), &mut head);
+        builder.add_table(Head::TAG, crate::dump_table(&head)?);
+    }
+    if font.table_data(Hhea::TAG).is_some() {
+        let mut hhea = font.expect_table::<Hhea>()?;
+        update_hhea(font, glyphs.as_ref(), &mut hhea)?;
+        builder.add_table(Hhea::TAG, crate::dump_table(&hhea)?);
+    }
+    if font.table_data(Maxp::TAG).is_some() {
+        let mut maxp = font.expect_table::<Maxp>()?;
+        update_maxp(glyphs.as_ref(), &mut maxp);
+        builder.add_table(Maxp::TAG, crate::dump_table(&maxp)?);
+    }
+    if font.table_data(Os2::TAG).is_some() {
+        let mut os2 = font.expect_table::<Os2>()?;
+        recalc_os2(font, &mut os2)?;
+        builder.add_table(Os2::TAG, crate::dump_table(&os2)?);
+    }
+    for record in font.table_directory.table_records() {
+        let tag = record.tag();
+        if builder.contains(tag) {
+            continue;
+        }
+        if let Some(data) = font.table_data(tag) {
+            builder.add_table(tag, data.as_ref().to_vec());
+        }
+    }
+    Ok(builder.build())
+}
+
+/// Recompute the bounding box of all glyphs in the head table.
+pub fn recalc_head(font: &FontRef, head: &mut Head) -> Result<(), ReadError> {
+    update_head(GlyphSummary::new(font)?.as_ref(), head);
+    Ok(())
+}
+
+/// Recompute `advanceWidthMax`, `minLeftSideBearing`, `minRightSideBearing`
+/// and `xMaxExtent` in the hhea table.
+///
+/// The `numberOfHMetrics` field is used to read the hmtx table and so is not
+/// changed.
+pub fn recalc_hhea(font: &FontRef, hhea: &mut Hhea) -> Result<(), ReadError> {
+    update_hhea(font, GlyphSummary::new(font)?.as_ref(), hhea)
+}
+
+/// Recompute the glyph count and, for version 1.0 tables, the limits on
+/// glyph complexity in the maxp table.
+///
+/// The fields that depend on the hinting instructions (such as
+/// `maxStackElements` and `maxSizeOfInstructions`) are not changed, since they
+/// also account for the instructions in the fpgm and prep tables.
+pub fn recalc_maxp(font: &FontRef, maxp: &mut Maxp) -> Result<(), ReadError> {
+    update_maxp(GlyphSummary::new(font)?.as_ref(), maxp);
+    Ok(())
+}
+
+/// Recompute `xAvgCharWidth`, `usFirstCharIndex`, `usLastCharIndex` and the
+/// Unicode and code page range bits in the OS/2 table.
+///
+/// Before version 3, `xAvgCharWidth` is the weighted average of the lowercase
+/// Latin letters and the space; if any of those are unmapped we fall back to
+/// the average of all non-zero advances, as in later versions.
+///
+/// Code page ranges are only updated if the table is recent enough to
+/// contain them.
+pub fn recalc_os2(font: &FontRef, os2: &mut Os2) -> Result<(), ReadError> {
+    let metrics = h_metrics(font)?;
+    let weighted = match os2.compute_version() < 3 {
+        true => weighted_avg_char_width(font, &metrics)?,
+        false => None,
+    };
+    os2.x_avg_char_width = weighted.unwrap_or_else(|| {
+        let advances = metrics.iter().map(|(advance, _)| *advance);
+        let advances = advances.filter(|advance| *advance > 0).collect::<Vec<_>>();
+        match advances.len() {
+            0 => 0,
+            n => (advances.iter().map(|x| *x as f64).sum::<f64>() / n as f64).ot_round(),
+        }
+    });
+
+    let codepoints = unicode_codepoints(font)?;
+    if let (Some(first), Some(last)) = (codepoints.first(), codepoints.last()) {
+        // these fields can't represent supplementary plane characters
+        os2.us_first_char_index = (*first).min(0xFFFF) as u16;
+        os2.us_last_char_index = (*last).min(0xFFFF) as u16;
+    }
+
+    let unicode_ranges = unicode_range_bits(&codepoints);
+    os2.ul_unicode_range_1 = unicode_ranges as u32;
+    os2.ul_unicode_range_2 = (unicode_ranges >> 32) as u32;
+    os2.ul_unicode_range_3 = (unicode_ranges >> 64) as u32;
+    os2.ul_unicode_range_4 = (unicode_ranges >> 96) as u32;
+
+    if os2.ul_code_page_range_1.is_some() {
+        let code_pages = code_page_range_bits(&codepoints);
+        os2.ul_code_page_range_1 = Some(code_pages as u32);
+        os2.ul_code_page_range_2 = Some((code_pages >> 32) as u32);
+    }
+    Ok(())
+}
+
+fn update_head(glyphs: Option<&GlyphSummary>, head: &mut Head) {
+    let Some(glyphs) = glyphs else {
+        return;
+    };
+    let bbox = glyphs.bboxes.iter().flatten().copied().reduce(union);
+    let [x_min, y_min, x_max, y_max] = bbox.unwrap_or_default();
+    head.x_min = x_min;
+    head.y_min = y_min;
+    head.x_max = x_max;
+    head.y_max = y_max;
+}
+
+fn update_hhea(
+    font: &FontRef,
+    glyphs: Option<&GlyphSummary>,
+    hhea: &mut Hhea,
+) -> Result<(), ReadError> {
+    let metrics = h_metrics(font)?;
+    let advance_width_max = metrics.iter().map(|(advance, _)| *advance).max();
+    hhea.advance_width_max = UfWord::new(advance_width_max.unwrap_or_default());
+
+    let Some(glyphs) = glyphs else {
+        return Ok(());
+    };
+    // (lsb, rsb, extent) of each glyph; empty glyphs don't contribute
+    let bounds = metrics
+        .iter()
+        .zip(&glyphs.bboxes)
+        .filter_map(|((advance, lsb), bbox)| {
+            let [x_min, _, x_max, _] = (*bbox)?;
+            let (advance, lsb) = (*advance as i32, *lsb as i32);
+            let width = x_max as i32 - x_min as i32;
+            Some((lsb, advance - lsb - width, lsb + width))
+        })
+        .collect::<Vec<_>>();
+    let min_lsb = bounds.iter().map(|(lsb, _, _)| *lsb).min();
+    let min_rsb = bounds.iter().map(|(_, rsb, _)| *rsb).min();
+    let max_extent = bounds.iter().map(|(_, _, extent)| *extent).max();
+    let clamp = |val: Option<i32>| val.unwrap_or_default().clamp(i16::MIN as _, i16::MAX as _);
+    hhea.min_left_side_bearing = FWord::new(clamp(min_lsb) as i16);
+    hhea.min_right_side_bearing = FWord::new(clamp(min_rsb) as i16);
+    hhea.x_max_extent = FWord::new(clamp(max_extent) as i16);
+    Ok(())
+}
+
+fn update_maxp(glyphs: Option<&GlyphSummary>, maxp: &mut Maxp) {
+    let Some(glyphs) = glyphs else {
+        return;
+    };
+    maxp.num_glyphs = glyphs.bboxes.len().min(u16::MAX as usize) as u16;
+    // version 0.5 tables don't have these fields
+    if maxp.max_points.is_none() {
+        return;
+    }
+    maxp.max_points = Some(glyphs.max_points);
+    maxp.max_contours = Some(glyphs.max_contours);
+    maxp.max_composite_points = Some(glyphs.max_composite_points);
+    maxp.max_composite_contours = Some(glyphs.max_composite_contours);
+    maxp.max_component_elements = Some(glyphs.max_component_elements);
+    maxp.max_component_depth = Some(glyphs.max_component_depth);
+}
+
+/// Information collected from the glyf table.
+#[derive(Default)]
+struct GlyphSummary {
+    /// The bounding box of each glyph, or `None` if the glyph is empty.
+    bboxes: Vec<Option<[i16; 4]>>,
+    max_points: u16,
+    max_contours: u16,
+    max_composite_points: u16,
+    max_composite_contours: u16,
+    max_component_elements: u16,
+    max_component_depth: u16,
+}
+
+/// The number of points, contours and nesting depth of a glyph.
+#[derive(Clone, Copy, Default)]
+struct GlyphCounts {
+    points: u32,
+    contours: u32,
+    depth: u16,
+}
+
+impl GlyphSummary {
+    /// Returns `None` if the font has no glyf table.
+    fn new(font: &FontRef) -> Result<Option<Self>, ReadError> {
+        if font.table_data(read::tables::glyf::Glyf::TAG).is_none() {
+            return Ok(None);
+        }
+        let loca = font.loca(None)?;
+        let glyf = font.glyf()?;
+        let glyphs = (0..loca.len())
+            .map(|gid| loca.get_glyf(GlyphId::new(gid as u16), &glyf))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut summary = GlyphSummary::default();
+        let mut counts = vec![None; glyphs.len()];
+        let mut outlines = vec![None; glyphs.len()];
+        for (gid, glyph) in glyphs.iter().enumerate() {
+            let Some(glyph) = glyph else {
+                summary.bboxes.push(None);
+                continue;
+            };
+            summary
+                .bboxes
+                .push(bbox(&glyph_points(&glyphs, gid, &mut outlines, 0)?));
+            let glyph_counts = glyph_counts(&glyphs, gid, &mut counts, 0)?;
+            let (points, contours) = (
+                saturate(glyph_counts.points),
+                saturate(glyph_counts.contours),
+            );
+            match glyph {
+                Glyph::Simple(_) => {
+                    summary.max_points = summary.max_points.max(points);
+                    summary.max_contours = summary.max_contours.max(contours);
+                }
+                Glyph::Composite(composite) => {
+                    summary.max_composite_points = summary.max_composite_points.max(points);
+                    summary.max_composite_contours = summary.max_composite_contours.max(contours);
+                    let num_components = saturate(composite.components().count() as u32);
+                    summary.max_component_elements =
+                        summary.max_component_elements.max(num_components);
+                    summary.max_component_depth =
+                        summary.max_component_depth.max(glyph_counts.depth);
+                }
+            }
+        }
+        Ok(Some(summary))
+    }
+}
+
+/// Compute the number of points and contours in a glyph, including those
+/// of its components.
+fn glyph_counts(
+    glyphs: &[Option<Glyph>],
+    gid: usize,
+    memo: &mut [Option<GlyphCounts>],
+    depth: u16,
+) -> Result<GlyphCounts, ReadError> {
+    if let Some(counts) = memo.get(gid).copied().flatten() {
+        return Ok(counts);
+    }
+    if depth > MAX_COMPONENT_DEPTH {
+        return Err(ReadError::MalformedData("components are nested too deeply"));
+    }
+    let counts = match glyphs.get(gid) {
+        Some(Some(Glyph::Simple(simple))) => GlyphCounts {
+            points: simple.num_points() as u32,
+            contours: simple.end_pts_of_contours().len() as u32,
+            depth: 0,
+        },
+        Some(Some(Glyph::Composite(composite))) => {
+            let mut counts = GlyphCounts {
+                depth: 1,
+                ..Default::default()
+            };
+            for component in composite.components() {
+                let component_gid = component.glyph.to_u16() as usize;
+                let component = glyph_counts(glyphs, component_gid, memo, depth + 1)?;
+                counts.points += component.points;
+                counts.contours += component.contours;
+                counts.depth = counts.depth.max(component.depth + 1);
+            }
+            counts
+        }
+        // empty or missing glyphs have no points
+        _ => GlyphCounts::default(),
+    };
+    if let Some(slot) = memo.get_mut(gid) {
+        *slot = Some(counts);
+    }
+    Ok(counts)
+}
+
+/// Compute the points of a glyph's outline, with the points of any
+/// components transformed into place.
+fn glyph_points(
+    glyphs: &[Option<Glyph>],
+    gid: usize,
+    memo: &mut [Option<Vec<Point>>],
+    depth: u16,
+) -> Result<Vec<Point>, ReadError> {
+    if let Some(points) = memo.get(gid).cloned().flatten() {
+        return Ok(points);
+    }
+    if depth > MAX_COMPONENT_DEPTH {
+        return Err(ReadError::MalformedData("components are nested too deeply"));
+    }
+    let points = match glyphs.get(gid) {
+        Some(Some(Glyph::Simple(simple))) => simple
+            .points()
+            .map(|point| Point::new(point.x as f64, point.y as f64))
+            .collect(),
+        Some(Some(Glyph::Composite(composite))) => {
+            let mut points = Vec::new();
+            for component in composite.components() {
+                let component_gid = component.glyph.to_u16() as usize;
+                let component_points = glyph_points(glyphs, component_gid, memo, depth + 1)?;
+                place_component(&component, &component_points, &mut points)?;
+            }
+            points
+        }
+        // empty or missing glyphs have no points
+        _ => Vec::new(),
+    };
+    if let Some(slot) = memo.get_mut(gid) {
+        *slot = Some(points.clone());
+    }
+    Ok(points)
+}
+
+/// Transform the points of a component and add them to the points of the
+/// composite glyph, as fontTools does.
+fn place_component(
+    component: &Component,
+    component_points: &[Point],
+    points: &mut Vec<Point>,
+) -> Result<(), ReadError> {
+    let transform = &component.transform;
+    let transform = Affine::new([
+        transform.xx.to_f32() as f64,
+        transform.yx.to_f32() as f64,
+        transform.xy.to_f32() as f64,
+        transform.yy.to_f32() as f64,
+        0.0,
+        0.0,
+    ]);
+    let transformed = component_points
+        .iter()
+        .map(|point| transform * *point)
+        .collect::<Vec<_>>();
+    let offset = match component.anchor {
+        Anchor::Offset { x, y } => {
+            let offset = Point::new(x as f64, y as f64);
+            // offsets are unscaled unless the component asks otherwise
+            let scaled = component
+                .flags
+                .contains(CompositeGlyphFlags::SCALED_COMPONENT_OFFSET)
+                && !component
+                    .flags
+                    .contains(CompositeGlyphFlags::UNSCALED_COMPONENT_OFFSET);
+            if scaled {
+                (transform * offset).to_vec2()
+            } else {
+                offset.to_vec2()
+            }
+        }
+        // move the component so that its point lands on the point of the
+        // glyph so far
+        Anchor::Point { base, component } => {
+            match (
+                points.get(base as usize),
+                transformed.get(component as usize),
+            ) {
+                (Some(base), Some(component)) => *base - *component,
+                _ => {
+                    return Err(ReadError::MalformedData(
+                        "component anchor point out of range",
+                    ))
+                }
+            }
+        }
+    };
+    points.extend(transformed.iter().map(|point| *point + offset));
+    Ok(())
+}
+
+fn saturate(val: u32) -> u16 {
+    val.min(u16::MAX as u32) as u16
+}
+
+/// The rounded bounds of a glyph's points, or `None` if it has none.
+fn bbox(points: &[Point]) -> Option<[i16; 4]> {
+    let first = points.first()?;
+    let rect = points
+        .iter()
+        .fold(Rect::from_points(*first, *first), |rect, point| {
+            rect.union_pt(*point)
+        });
+    Some([
+        rect.x0.ot_round(),
+        rect.y0.ot_round(),
+        rect.x1.ot_round(),
+        rect.y1.ot_round(),
+    ])
+}
+
+fn union(a: [i16; 4], b: [i16; 4]) -> [i16; 4] {
+    [
+        a[0].min(b[0]),
+        a[1].min(b[1]),
+        a[2].max(b[2]),
+        a[3].max(b[3]),
+    ]
+}
+
+/// The advance and left side bearing of each glyph.
+fn h_metrics(font: &FontRef) -> Result<Vec<(u16, i16)>, ReadError> {
+    let hmtx = font.hmtx()?;
+    let long_metrics = hmtx.h_metrics();
+    let last_advance = long_metrics.last().map(|metric| metric.advance());
+    let long = long_metrics
+        .iter()
+        .map(|metric| (metric.advance(), metric.side_bearing()));
+    let short = hmtx
+        .left_side_bearings()
+        .iter()
+        .map(|lsb| (last_advance.unwrap_or_default(), lsb.get()));
+    Ok(long.chain(short).collect())
+}
+
+/// Weights used to compute `xAvgCharWidth` before OS/2 version 3.
+const AVG_CHAR_WEIGHTS: [(char, u32); 27] = [
+    ('a', 64),
+    ('b', 14),
+    ('c', 27),
+    ('d', 35),
+    ('e', 100),
+    ('f', 20),
+    ('g', 14),
+    ('h', 42),
+    ('i', 63),
+    ('j', 3),
+    ('k', 6),
+    ('l', 35),
+    ('m', 20),
+    ('n', 56),
+    ('o', 56),
+    ('p', 17),
+    ('q', 4),
+    ('r', 49),
+    ('s', 56),
+    ('t', 71),
+    ('u', 31),
+    ('v', 10),
+    ('w', 18),
+    ('x', 3),
+    ('y', 18),
+    ('z', 2),
+    (' ', 166),
+];
+
+/// The weighted average width of the lowercase Latin letters and the space,
+/// or `None` if any of them isn't mapped.
+fn weighted_avg_char_width(
+    font: &FontRef,
+    metrics: &[(u16, i16)],
+) -> Result<Option<i16>, ReadError> {
+    let cmap = font.cmap()?;
+    let mut total = 0;
+    for (ch, weight) in AVG_CHAR_WEIGHTS {
+        let advance = cmap
+            .map_codepoint(ch)
+            .filter(|gid| *gid != GlyphId::NOTDEF)
+            .and_then(|gid| metrics.get(gid.to_u16() as usize));
+        let Some((advance, _)) = advance else {
+            return Ok(None);
+        };
+        total += *advance as u32 * weight;
+    }
+    Ok(Some((total as f64 / 1000.0).ot_round()))
+}
+
+/// All of the codepoints mapped by Unicode subtables in the cmap table.
+fn unicode_codepoints(font: &FontRef) -> Result<Vec<u32>, ReadError> {
+    let cmap = font.cmap()?;
+    let mut codepoints = BTreeSet::new();
+    for record in cmap.encoding_records() {
+        let is_unicode = match record.platform_id() {
+            PlatformId::Unicode => true,
+            PlatformId::Windows => matches!(record.encoding_id(), 1 | 10),
+            _ => false,
+        };
+        if !is_unicode {
+            continue;
+        }
+        match record.subtable(cmap.offset_data())? {
+            CmapSubtable::Format4(subtable) => {
+                codepoints.extend(subtable.iter().map(|(codepoint, _)| codepoint))
+            }
+            CmapSubtable::Format12(subtable) => {
+                codepoints.extend(subtable.iter().map(|(codepoint, _)| codepoint))
+            }
+            _ => (),
+        }
+    }
+    Ok(codepoints.into_iter().collect())
+}
+
+/// The `ulUnicodeRange` bits for a sorted set of codepoints.
+fn unicode_range_bits(codepoints: &[u32]) -> u128 {
+    // bit 57 is set for any character outside of the BMP
+    const NON_PLANE_0_BIT: u32 = 57;
+    let mut bits = 0u128;
+    for codepoint in codepoints {
+        if *codepoint >= 0x10000 {
+            bits |= 1 << NON_PLANE_0_BIT;
+        }
+        let idx = UNICODE_RANGES.partition_point(|(_, end, _)| end < codepoint);
+        if let Some((start, _, bit)) = UNICODE_RANGES.get(idx) {
+            if start <= codepoint {
+                bits |= 1 << bit;
+            }
+        }
+    }
+    bits
+}
+
+/// The `ulCodePageRange` bits for a sorted set of codepoints.
+///
+/// Each code page is detected by the presence of characteristic characters,
+/// using the same heuristics as fontTools.
+fn code_page_range_bits(codepoints: &[u32]) -> u64 {
+    let has = |c: char| codepoints.binary_search(&(c as u32)).is_ok();
+    let has_ascii = (0x20..0x7E).all(|c| codepoints.binary_search(&c).is_ok());
+    let has_lineart = has('┤');
+    let has_radical = has('√');
+
+    let mut bits = 0u64;
+    let mut set = |bit: u32| bits |= 1 << bit;
+    for c in codepoints.iter().filter_map(|c| char::from_u32(*c)) {
+        match c {
+            'Þ' if has_ascii => set(0), // Latin 1
+            'Ľ' if has_ascii => {
+                set(1); // Latin 2: Eastern Europe
+                if has_lineart {
+                    set(58); // Latin 2
+                }
+            }
+            'Б' => {
+                set(2); // Cyrillic
+                if has('Ѕ') && has_lineart {
+                    set(57); // IBM Cyrillic
+                }
+                if has('╜') && has_lineart {
+                    set(49); // MS-DOS Russian
+                }
+            }
+            'Ά' => {
+                set(3); // Greek
+                if has_lineart && has('½') {
+                    set(48); // IBM Greek
+                }
+                if has_lineart && has_radical {
+                    set(60); // Greek, former 437 G
+                }
+            }
+            'İ' if has_ascii => {
+                set(4); // Turkish
+                if has_lineart {
+                    set(56); // IBM Turkish
+                }
+            }
+            'א' => {
+                set(5); // Hebrew
+                if has_lineart && has_radical {
+                    set(53); // Hebrew
+                }
+            }
+            'ر' => {
+                set(6); // Arabic
+                if has_radical {
+                    set(51); // Arabic
+                }
+                if has_lineart {
+                    set(61); // Arabic; ASMO 708
+                }
+            }
+            'ŗ' if has_ascii => {
+                set(7); // Windows Baltic
+                if has_lineart {
+                    set(59); // MS-DOS Baltic
+                }
+            }
+            '₫' if has_ascii => set(8),                 // Vietnamese
+            'ๅ' => set(16),                             // Thai
+            'エ' => set(17),                            // JIS/Japan
+            'ㄅ' => set(18),                            // Chinese: Simplified
+            'ㄱ' => set(19),                            // Korean Wansung
+            '央' => set(20),                            // Chinese: Traditional
+            '곴' => set(21),                            // Korean Johab
+            '♥' if has_ascii => set(30),                // OEM Character Set
+            'þ' if has_ascii && has_lineart => set(54), // MS-DOS Icelandic
+            '╚' if has_ascii => {
+                set(62); // WE/Latin 1
+                set(63); // US
+            }
+            'Å' if has_ascii && has_lineart && has_radical => set(50), // MS-DOS Nordic
+            'é' if has_ascii && has_lineart && has_radical => set(52), // MS-DOS Canadian French
+            'õ' if has_ascii && has_lineart && has_radical => set(55), // MS-DOS Portuguese
+            _ => (),
+        }
+    }
+    if has_ascii && has('‰') && has('∑') {
+        set(29); // Macintosh Character Set (US Roman)
+    }
+    // with no other bits set, fall back to Latin 1 so that the font is
+    // still usable in applications that check these bits
+    if bits == 0 {
+        bits = 1; // Latin 1
+    }
+    bits
+}
+
+/// The `ulUnicodeRange` blocks, as (first, last, bit), sorted by codepoint.
+#[rustfmt::skip]
+static UNICODE_RANGES: &[(u32, u32, u32)] = &[
+    (0x0000, 0x007F, 0),
+    (0x0080, 0x00FF, 1),
+    (0x0100, 0x017F, 2),
+    (0x0180, 0x024F, 3),
+    (0x0250, 0x02AF, 4),
+    (0x02B0, 0x02FF, 5),
+    (0x0300, 0x036F, 6),
+    (0x0370, 0x03FF, 7),
+    (0x0400, 0x04FF, 9),
+    (0x0500, 0x052F, 9),
+    (0x0530, 0x058F, 10),
+    (0x0590, 0x05FF, 11),
+    (0x0600, 0x06FF, 13),
+    (0x0700, 0x074F, 71),
+    (0x0750, 0x077F, 13),
+    (0x0780, 0x07BF, 72),
+    (0x07C0, 0x07FF, 14),
+    (0x0900, 0x097F, 15),
+    (0x0980, 0x09FF, 16),
+    (0x0A00, 0x0A7F, 17),
+    (0x0A80, 0x0AFF, 18),
+    (0x0B00, 0x0B7F, 19),
+    (0x0B80, 0x0BFF, 20),
+    (0x0C00, 0x0C7F, 21),
+    (0x0C80, 0x0CFF, 22),
+    (0x0D00, 0x0D7F, 23),
+    (0x0D80, 0x0DFF, 73),
+    (0x0E00, 0x0E7F, 24),
+    (0x0E80, 0x0EFF, 25),
+    (0x0F00, 0x0FFF, 70),
+    (0x1000, 0x109F, 74),
+    (0x10A0, 0x10FF, 26),
+    (0x1100, 0x11FF, 28),
+    (0x1200, 0x137F, 75),
+    (0x1380, 0x139F, 75),
+    (0x13A0, 0x13FF, 76),
+    (0x1400, 0x167F, 77),
+    (0x1680, 0x169F, 78),
+    (0x16A0, 0x16FF, 79),
+    (0x1700, 0x171F, 84),
+    (0x1720, 0x173F, 84),
+    (0x1740, 0x175F, 84),
+    (0x1760, 0x177F, 84),
+    (0x1780, 0x17FF, 80),
+    (0x1800, 0x18AF, 81),
+    (0x1900, 0x194F, 93),
+    (0x1950, 0x197F, 94),
+    (0x1980, 0x19DF, 95),
+    (0x19E0, 0x19FF, 80),
+    (0x1A00, 0x1A1F, 96),
+    (0x1B00, 0x1B7F, 27),
+    (0x1B80, 0x1BBF, 112),
+    (0x1C00, 0x1C4F, 113),
+    (0x1C50, 0x1C7F, 114),
+    (0x1D00, 0x1D7F, 4),
+    (0x1D80, 0x1DBF, 4),
+    (0x1DC0, 0x1DFF, 6),
+    (0x1E00, 0x1EFF, 29),
+    (0x1F00, 0x1FFF, 30),
+    (0x2000, 0x206F, 31),
+    (0x2070, 0x209F, 32),
+    (0x20A0, 0x20CF, 33),
+    (0x20D0, 0x20FF, 34),
+    (0x2100, 0x214F, 35),
+    (0x2150, 0x218F, 36),
+    (0x2190, 0x21FF, 37),
+    (0x2200, 0x22FF, 38),
+    (0x2300, 0x23FF, 39),
+    (0x2400, 0x243F, 40),
+    (0x2440, 0x245F, 41),
+    (0x2460, 0x24FF, 42),
+    (0x2500, 0x257F, 43),
+    (0x2580, 0x259F, 44),
+    (0x25A0, 0x25FF, 45),
+    (0x2600, 0x26FF, 46),
+    (0x2700, 0x27BF, 47),
+    (0x27C0, 0x27EF, 38),
+    (0x27F0, 0x27FF, 37),
+    (0x2800, 0x28FF, 82),
+    (0x2900, 0x297F, 37),
+    (0x2980, 0x29FF, 38),
+    (0x2A00, 0x2AFF, 38),
+    (0x2B00, 0x2BFF, 37),
+    (0x2C00, 0x2C5F, 97),
+    (0x2C60, 0x2C7F, 29),
+    (0x2C80, 0x2CFF, 8),
+    (0x2D00, 0x2D2F, 26),
+    (0x2D30, 0x2D7F, 98),
+    (0x2D80, 0x2DDF, 75),
+    (0x2DE0, 0x2DFF, 9),
+    (0x2E00, 0x2E7F, 31),
+    (0x2E80, 0x2EFF, 59),
+    (0x2F00, 0x2FDF, 59),
+    (0x2FF0, 0x2FFF, 59),
+    (0x3000, 0x303F, 48),
+    (0x3040, 0x309F, 49),
+    (0x30A0, 0x30FF, 50),
+    (0x3100, 0x312F, 51),
+    (0x3130, 0x318F, 52),
+    (0x3190, 0x319F, 59),
+    (0x31A0, 0x31BF, 51),
+    (0x31C0, 0x31EF, 61),
+    (0x31F0, 0x31FF, 50),
+    (0x3200, 0x32FF, 54),
+    (0x3300, 0x33FF, 55),
+    (0x3400, 0x4DBF, 59),
+    (0x4DC0, 0x4DFF, 99),
+    (0x4E00, 0x9FFF, 59),
+    (0xA000, 0xA48F, 83),
+    (0xA490, 0xA4CF, 83),
+    (0xA500, 0xA63F, 12),
+    (0xA640, 0xA69F, 9),
+    (0xA700, 0xA71F, 5),
+    (0xA720, 0xA7FF, 29),
+    (0xA800, 0xA82F, 100),
+    (0xA840, 0xA87F, 53),
+    (0xA880, 0xA8DF, 115),
+    (0xA900, 0xA92F, 116),
+    (0xA930, 0xA95F, 117),
+    (0xAA00, 0xAA5F, 118),
+    (0xAC00, 0xD7AF, 56),
+    (0xE000, 0xF8FF, 60),
+    (0xF900, 0xFAFF, 61),
+    (0xFB00, 0xFB4F, 62),
+    (0xFB50, 0xFDFF, 63),
+    (0xFE00, 0xFE0F, 91),
+    (0xFE10, 0xFE1F, 65),
+    (0xFE20, 0xFE2F, 64),
+    (0xFE30, 0xFE4F, 65),
+    (0xFE50, 0xFE6F, 66),
+    (0xFE70, 0xFEFF, 67),
+    (0xFF00, 0xFFEF, 68),
+    (0xFFF0, 0xFFFF, 69),
+    (0x10000, 0x1007F, 101),
+    (0x10080, 0x100FF, 101),
+    (0x10100, 0x1013F, 101),
+    (0x10140, 0x1018F, 102),
+    (0x10190, 0x101CF, 119),
+    (0x101D0, 0x101FF, 120),
+    (0x10280, 0x1029F, 121),
+    (0x102A0, 0x102DF, 121),
+    (0x10300, 0x1032F, 85),
+    (0x10330, 0x1034F, 86),
+    (0x10380, 0x1039F, 103),
+    (0x103A0, 0x103DF, 104),
+    (0x10400, 0x1044F, 87),
+    (0x10450, 0x1047F, 105),
+    (0x10480, 0x104AF, 106),
+    (0x10800, 0x1083F, 107),
+    (0x10900, 0x1091F, 58),
+    (0x10920, 0x1093F, 121),
+    (0x10A00, 0x10A5F, 108),
+    (0x12000, 0x123FF, 110),
+    (0x12400, 0x1247F, 110),
+    (0x1D000, 0x1D0FF, 88),
+    (0x1D100, 0x1D1FF, 88),
+    (0x1D200, 0x1D24F, 88),
+    (0x1D300, 0x1D35F, 109),
+    (0x1D360, 0x1D37F, 111),
+    (0x1D400, 0x1D7FF, 89),
+    (0x1F000, 0x1F02F, 122),
+    (0x1F030, 0x1F09F, 122),
+    (0x20000, 0x2A6DF, 59),
+    (0x2F800, 0x2FA1F, 61),
+    (0xE0000, 0xE007F, 92),
+    (0xE0100, 0xE01EF, 91),
+    (0xF0000, 0xFFFFD, 90),
+    (0x100000, 0x10FFFD, 90),
+];
+
+impl From<ReadError> for RecalcError {
+    fn from(value: ReadError) -> Self {
+        RecalcError::Read(value)
+    }
+}
+
+impl From<Error> for RecalcError {
+    fn from(value: Error) -> Self {
+        RecalcError::Write(value)
+    }
+}
+
+impl std::fmt::Display for RecalcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecalcError::Read(error) => write!(f, "failed to read font: {error}"),
+            RecalcError::Write(error) => write!(f, "failed to write table: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RecalcError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SIMPLE_GLYF, which is missing the hhea table needed to read hmtx.
+    fn simple_glyf_with_hhea() -> Vec<u8> {
+        let font = FontRef::new(font_test_data::SIMPLE_GLYF).unwrap();
+        let hhea = Hhea {
+            number_of_long_metrics: 3,
+            ..Default::default()
+        };
+        let mut builder = FontBuilder::default();
+        builder.add_table(Hhea::TAG, crate::dump_table(&hhea).unwrap());
+        for record in font.table_directory.table_records() {
+            let data = font.table_data(record.tag()).unwrap();
+            builder.add_table(record.tag(), data.as_ref().to_vec());
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn matches_compiled_fonts() {
+        for data in [
+            font_test_data::VAZIRMATN_VAR,
+            font_test_data::LAYOUT_FEATURES,
+        ] {
+            let font = FontRef::new(data).unwrap();
+            let expected_head = font.expect_table::<Head>().unwrap();
+            let expected_hhea = font.expect_table::<Hhea>().unwrap();
+            let expected_maxp = font.expect_table::<Maxp>().unwrap();
+
+            let mut head = expected_head.clone();
+            (head.x_min, head.y_min, head.x_max, head.y_max) = (0, 0, 0, 0);
+            recalc_head(&font, &mut head).unwrap();
+            assert_eq!(
+                (head.x_min, head.y_min, head.x_max, head.y_max),
+                (
+                    expected_head.x_min,
+                    expected_head.y_min,
+                    expected_head.x_max,
+                    expected_head.y_max
+                )
+            );
+
+            let mut hhea = Hhea {
+                number_of_long_metrics: expected_hhea.number_of_long_metrics,
+                ..Default::default()
+            };
+            recalc_hhea(&font, &mut hhea).unwrap();
+            assert_eq!(hhea.advance_width_max, expected_hhea.advance_width_max);
+            assert_eq!(
+                hhea.min_left_side_bearing,
+                expected_hhea.min_left_side_bearing
+            );
+            assert_eq!(
+                hhea.min_right_side_bearing,
+                expected_hhea.min_right_side_bearing
+            );
+            assert_eq!(hhea.x_max_extent, expected_hhea.x_max_extent);
+
+            let mut maxp = Maxp {
+                max_points: Some(0),
+                ..Default::default()
+            };
+            recalc_maxp(&font, &mut maxp).unwrap();
+            assert_eq!(maxp.num_glyphs, expected_maxp.num_glyphs);
+            assert_eq!(maxp.max_points, expected_maxp.max_points);
+            assert_eq!(maxp.max_contours, expected_maxp.max_contours);
+            assert_eq!(
+                maxp.max_composite_points,
+                expected_maxp.max_composite_points
+            );
+            assert_eq!(
+                maxp.max_composite_contours,
+                expected_maxp.max_composite_contours
+            );
+            assert_eq!(
+                maxp.max_component_elements,
+                expected_maxp.max_component_elements
+            );
+            assert_eq!(maxp.max_component_depth, expected_maxp.max_component_depth);
+            // hinting fields are left alone
+            assert_eq!(maxp.max_stack_elements, None);
+        }
+    }
+
+    #[test]
+    fn maxp_version_0_5() {
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let mut maxp = Maxp::default();
+        recalc_maxp(&font, &mut maxp).unwrap();
+        assert_eq!(maxp.num_glyphs, 4);
+        assert_eq!(maxp.max_points, None);
+    }
+
+    #[test]
+    fn updates_stale_values() {
+        // the head and maxp tables in this font were not updated after the
+        // glyphs were edited
+        let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+        let mut head = font.expect_table::<Head>().unwrap();
+        recalc_head(&font, &mut head).unwrap();
+        assert_eq!(
+            (head.x_min, head.y_min, head.x_max, head.y_max),
+            (5, 0, 80, 100)
+        );
+        let mut maxp = font.expect_table::<Maxp>().unwrap();
+        recalc_maxp(&font, &mut maxp).unwrap();
+        assert_eq!(maxp.max_points, Some(8));
+        assert_eq!(maxp.max_contours, Some(2));
+    }
+
+    #[test]
+    fn bounds_from_outlines() {
+        use crate::tables::{
+            glyf::{Bbox, Component, ComponentFlags, CompositeGlyph, GlyfLocaBuilder, SimpleGlyph},
+            loca::Loca,
+        };
+        use read::tables::glyf::{Anchor, Transform};
+        use types::F2Dot14;
+
+        let mut square = kurbo::BezPath::new();
+        square.move_to((0., 0.));
+        square.line_to((100., 0.));
+        square.line_to((100., 100.));
+        square.line_to((0., 100.));
+        square.close_path();
+        let mut simple = SimpleGlyph::from_kurbo(&square).unwrap();
+        // bounds in the glyph headers are ignored
+        simple.bbox = Bbox::default();
+        let stale = Bbox::default();
+        let scale = Transform {
+            xx: F2Dot14::from_f32(1.5),
+            yy: F2Dot14::from_f32(1.5),
+            ..Default::default()
+        };
+        let scaled = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(0),
+                Anchor::Offset { x: 10, y: 20 },
+                scale,
+                ComponentFlags::default(),
+            ),
+            stale,
+        );
+        let mut matched = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(0),
+                Anchor::Offset { x: 0, y: 0 },
+                Default::default(),
+                ComponentFlags::default(),
+            ),
+            stale,
+        );
+        // put the first point of the second square on the third point
+        // (the top right corner) of the first
+        matched.add_component(
+            Component::new(
+                GlyphId::new(0),
+                Anchor::Point {
+                    base: 2,
+                    component: 0,
+                },
+                Default::default(),
+                ComponentFlags::default(),
+            ),
+            stale,
+        );
+
+        let mut builder = GlyfLocaBuilder::new();
+        builder
+            .add_glyph(&simple)
+            .unwrap()
+            .add_glyph(&scaled)
+            .unwrap()
+            .add_glyph(&matched)
+            .unwrap();
+        let (glyf, loca, loca_format) = builder.build();
+        let head = Head {
+            index_to_loc_format: loca_format as i16,
+            ..Default::default()
+        };
+        let mut font = FontBuilder::default();
+        font.add_table(Head::TAG, crate::dump_table(&head).unwrap())
+            .add_table(Loca::TAG, crate::dump_table(&loca).unwrap())
+            .add_table(
+                read::tables::glyf::Glyf::TAG,
+                crate::dump_table(&glyf).unwrap(),
+            );
+        let data = font.build();
+        let font = FontRef::new(&data).unwrap();
+
+        let glyphs = GlyphSummary::new(&font).unwrap().unwrap();
+        assert_eq!(
+            glyphs.bboxes,
+            [
+                Some([0, 0, 100, 100]),
+                Some([10, 20, 160, 170]),
+                Some([0, 0, 200, 200]),
+            ]
+        );
+        let mut head = font.expect_table::<Head>().unwrap();
+        recalc_head(&font, &mut head).unwrap();
+        assert_eq!(
+            (head.x_min, head.y_min, head.x_max, head.y_max),
+            (0, 0, 200, 200)
+        );
+    }
+
+    #[test]
+    fn no_glyf() {
+        let font = FontRef::new(font_test_data::CMAP12_FONT1).unwrap();
+        let mut head = font.expect_table::<Head>().unwrap();
+        let expected = (head.x_min, head.y_min, head.x_max, head.y_max);
+        recalc_head(&font, &mut head).unwrap();
+        assert_eq!((head.x_min, head.y_min, head.x_max, head.y_max), expected);
+    }
+
+    #[test]
+    fn os2() {
+        let data = simple_glyf_with_hhea();
+        let font = FontRef::new(&data).unwrap();
+        let mut os2 = Os2 {
+            ul_code_page_range_1: Some(0),
+            ..Default::default()
+        };
+        recalc_os2(&font, &mut os2).unwrap();
+        let expected = font.expect_table::<Os2>().unwrap();
+        assert_eq!(os2.x_avg_char_width, expected.x_avg_char_width);
+        assert_eq!(os2.us_first_char_index, 14);
+        assert_eq!(os2.us_last_char_index, 32);
+        assert_eq!(os2.ul_unicode_range_1, 1);
+        assert_eq!(os2.ul_code_page_range_1, Some(1));
+        assert_eq!(os2.ul_code_page_range_2, Some(0));
+
+        // no code page fields in version 0
+        let mut os2 = Os2::default();
+        recalc_os2(&font, &mut os2).unwrap();
+        assert_eq!(os2.ul_code_page_range_1, None);
+    }
+
+    #[test]
+    fn os2_weighted_avg_char_width() {
+        use crate::tables::{
+            cmap::Cmap,
+            hmtx::{Hmtx, LongMetric},
+        };
+
+        // .notdef, then each of the weighted characters in order
+        let chars = AVG_CHAR_WEIGHTS.map(|(ch, _)| ch);
+        let build = |chars: &[char]| {
+            let mappings = (1..).zip(chars).map(|(gid, ch)| (*ch, GlyphId::new(gid)));
+            let metrics = std::iter::once(500)
+                .chain(chars.iter().map(|ch| if *ch == ' ' { 250 } else { 500 }))
+                .map(|advance| LongMetric::new(advance, 0))
+                .collect::<Vec<_>>();
+            let hhea = Hhea {
+                number_of_long_metrics: metrics.len() as u16,
+                ..Default::default()
+            };
+            let maxp = Maxp::new(metrics.len() as u16);
+            let mut builder = FontBuilder::default();
+            builder.add_table(
+                Cmap::TAG,
+                crate::dump_table(&Cmap::from_mappings(mappings)).unwrap(),
+            );
+            builder.add_table(Hhea::TAG, crate::dump_table(&hhea).unwrap());
+            builder.add_table(Maxp::TAG, crate::dump_table(&maxp).unwrap());
+            let hmtx = Hmtx::new(metrics, Vec::new());
+            builder.add_table(Hmtx::TAG, crate::dump_table(&hmtx).unwrap());
+            builder.build()
+        };
+
+        let data = build(&chars);
+        let font = FontRef::new(&data).unwrap();
+        // (834 * 500 + 166 * 250) / 1000
+        let mut os2 = Os2::default();
+        recalc_os2(&font, &mut os2).unwrap();
+        assert_eq!(os2.x_avg_char_width, 459);
+
+        // version 3 and later always use the average of all advances
+        let mut os2 = Os2 {
+            sx_height: Some(0),
+            ..Default::default()
+        };
+        recalc_os2(&font, &mut os2).unwrap();
+        assert_eq!(os2.x_avg_char_width, 491);
+
+        // missing 'z', so fall back to the average of all advances
+        let data = build(
+            &chars[..25]
+                .iter()
+                .chain([&' '])
+                .copied()
+                .collect::<Vec<_>>(),
+        );
+        let font = FontRef::new(&data).unwrap();
+        let mut os2 = Os2::default();
+        recalc_os2(&font, &mut os2).unwrap();
+        assert_eq!(os2.x_avg_char_width, (13250.0f64 / 27.0).ot_round());
+    }
+
+    #[test]
+    fn unicode_ranges() {
+        assert_eq!(unicode_range_bits(&[]), 0);
+        // Basic Latin, Latin-1 Supplement
+        assert_eq!(unicode_range_bits(&[0x41, 0xE9]), 0b11);
+        // Arabic, CJK Unified Ideographs
+        assert_eq!(unicode_range_bits(&[0x628, 0x4E2D]), 1 << 13 | 1 << 59);
+        // unassigned
+        assert_eq!(unicode_range_bits(&[0x0870]), 0);
+        // supplementary planes also set the non-plane 0 bit
+        assert_eq!(unicode_range_bits(&[0x1D400]), 1 << 57 | 1 << 89);
+        assert_eq!(unicode_range_bits(&[0x1F600]), 1 << 57);
+    }
+
+    #[test]
+    fn code_page_ranges() {
+        let ascii = (0x20..0x7E).collect::<Vec<u32>>();
+        assert_eq!(code_page_range_bits(&ascii), 1);
+        let mut latin = ascii.clone();
+        latin.extend(['Þ' as u32, 'Ľ' as u32]);
+        latin.sort();
+        assert_eq!(code_page_range_bits(&latin), 0b11);
+        // Cyrillic doesn't need ASCII
+        assert_eq!(code_page_range_bits(&['Б' as u32]), 1 << 2);
+        // Latin 1 also needs ASCII, so this falls back to it anyway
+        assert_eq!(code_page_range_bits(&['Ľ' as u32]), 1);
+    }
+
+    #[test]
+    fn recalc_whole_font() {
+        let data = simple_glyf_with_hhea();
+        let font = FontRef::new(&data).unwrap();
+        let recalced = recalc_font(&font).unwrap();
+        let recalced = FontRef::new(&recalced).unwrap();
+        assert!(recalced.has_valid_checksum_adjustment());
+        let tags = |font: &FontRef| {
+            let records = font.table_directory.table_records();
+            records
+                .iter()
+                .map(|record| record.tag())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tags(&recalced), tags(&font));
+        // glyf is copied unchanged
+        assert_eq!(
+            recalced.glyf().unwrap().offset_data().as_ref(),
+            font.glyf().unwrap().offset_data().as_ref()
+        );
+        let hhea = recalced.hhea().unwrap();
+        assert_eq!(hhea.advance_width_max().to_u16(), 1275);
+        assert_eq!(hhea.number_of_long_metrics(), 3);
+        let os2 = recalced.os2().unwrap();
+        assert_eq!(os2.us_last_char_index(), 32);
+    }
+}
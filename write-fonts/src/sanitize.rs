@@ -0,0 +1,169 @@
+//! Rebuilding untrusted fonts.
+//!
+//! This builds on the structural checks in [`read_fonts::traversal::sanitize`],
+//! copying the tables that pass into a freshly laid out font.
+
+use read::{
+    traversal::{self, IssueKind, SanitizeReport},
+    FontRef,
+};
+use types::Tag;
+
+use crate::FontBuilder;
+
+/// Tables without which a font can't be used.
+const REQUIRED_TABLES: &[Tag] = &[
+    Tag::new(b"cmap"),
+    Tag::new(b"head"),
+    Tag::new(b"hhea"),
+    Tag::new(b"hmtx"),
+    Tag::new(b"maxp"),
+    Tag::new(b"name"),
+    Tag::new(b"OS/2"),
+    Tag::new(b"post"),
+    Tag::new(b"glyf"),
+    Tag::new(b"loca"),
+    Tag::new(b"CFF "),
+    Tag::new(b"CFF2"),
+];
+
+/// Options for [`sanitize`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SanitizeOptions {
+    /// If `true`, optional tables with problems are removed from the output
+    /// rather than causing sanitization to fail.
+    pub drop_failing_tables: bool,
+}
+
+/// A font that has been sanitized.
+#[derive(Clone, Debug)]
+pub struct Sanitized {
+    /// The rebuilt font.
+    pub data: Vec<u8>,
+    /// The problems found in the original font.
+    pub report: SanitizeReport,
+    /// The tables that were removed because of those problems.
+    pub dropped_tables: Vec<Tag>,
+}
+
+/// Checks the structure of a font and rebuilds it from the tables that pass.
+///
+/// Problems with the table directory itself, such as overlapping tables and
+/// incorrect checksums, are repaired by the rebuild. Any other problem in a
+/// table causes sanitization to fail unless the table is optional and
+/// [`SanitizeOptions::drop_failing_tables`] is set, in which case the table
+/// is omitted.
+///
+/// On failure, the report describing the problems is returned.
+pub fn sanitize(font: &FontRef, options: SanitizeOptions) -> Result<Sanitized, SanitizeReport> {
+    let report = traversal::sanitize(font);
+    let mut dropped_tables = Vec::new();
+    for issue in &report.issues {
+        if is_repaired_by_rebuild(&issue.kind) || dropped_tables.contains(&issue.table) {
+            continue;
+        }
+        if !options.drop_failing_tables || REQUIRED_TABLES.contains(&issue.table) {
+            return Err(report);
+        }
+        dropped_tables.push(issue.table);
+    }
+    let mut builder = FontBuilder::default();
+    for record in font.table_directory.table_records() {
+        let tag = record.tag();
+        if dropped_tables.contains(&tag) {
+            continue;
+        }
+        // tables that are out of bounds are never repaired, so this is
+        // always present
+        let Some(data) = font.table_data(tag) else {
+            continue;
+        };
+        builder.add_table(tag, data.as_ref().to_vec());
+    }
+    Ok(Sanitized {
+        data: builder.build(),
+        report,
+        dropped_tables,
+    })
+}
+
+fn is_repaired_by_rebuild(kind: &IssueKind) -> bool {
+    matches!(
+        kind,
+        IssueKind::ChecksumMismatch { .. } | IssueKind::OverlappingTables(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_pos(data: &[u8], tag: Tag) -> usize {
+        let num_tables = u16::from_be_bytes([data[4], data[5]]) as usize;
+        (0..num_tables)
+            .map(|i| 12 + i * 16)
+            .find(|pos| data[*pos..*pos + 4] == tag.to_be_bytes())
+            .unwrap()
+    }
+
+    fn table_start(data: &[u8], tag: Tag) -> usize {
+        let pos = record_pos(data, tag) + 8;
+        u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize
+    }
+
+    fn tags(data: &[u8]) -> Vec<Tag> {
+        let font = FontRef::new(data).unwrap();
+        let records = font.table_directory.table_records();
+        records.iter().map(|record| record.tag()).collect()
+    }
+
+    #[test]
+    fn repairs_checksums() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        let record = record_pos(&data, Tag::new(b"GSUB"));
+        data[record + 4..record + 8].copy_from_slice(&[0; 4]);
+        let font = FontRef::new(&data).unwrap();
+        let sanitized = sanitize(&font, SanitizeOptions::default()).unwrap();
+        assert_eq!(sanitized.report.issues.len(), 1);
+        assert!(sanitized.dropped_tables.is_empty());
+        assert_eq!(tags(&sanitized.data), tags(&data));
+        let rebuilt = FontRef::new(&sanitized.data).unwrap();
+        assert!(traversal::sanitize(&rebuilt).is_clean());
+        assert!(rebuilt.has_valid_checksum_adjustment());
+    }
+
+    #[test]
+    fn drops_failing_optional_tables() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        // GPOS lookupListOffset
+        let gpos = table_start(&data, Tag::new(b"GPOS"));
+        data[gpos + 8..gpos + 10].copy_from_slice(&0xFFF0u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        assert!(sanitize(&font, SanitizeOptions::default()).is_err());
+
+        let options = SanitizeOptions {
+            drop_failing_tables: true,
+        };
+        let sanitized = sanitize(&font, options).unwrap();
+        assert_eq!(sanitized.dropped_tables, [Tag::new(b"GPOS")]);
+        let mut expected = tags(&data);
+        expected.retain(|tag| *tag != Tag::new(b"GPOS"));
+        assert_eq!(tags(&sanitized.data), expected);
+        let rebuilt = FontRef::new(&sanitized.data).unwrap();
+        assert!(traversal::sanitize(&rebuilt).is_clean());
+    }
+
+    #[test]
+    fn rejects_failing_required_tables() {
+        let mut data = font_test_data::LAYOUT_FEATURES.to_vec();
+        // maxp numGlyphs no longer matches hmtx and loca
+        let maxp = table_start(&data, Tag::new(b"maxp"));
+        data[maxp + 4..maxp + 6].copy_from_slice(&20u16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let options = SanitizeOptions {
+            drop_failing_tables: true,
+        };
+        let report = sanitize(&font, options).unwrap_err();
+        assert!(report.failing_tables().contains(&Tag::new(b"hmtx")));
+    }
+}
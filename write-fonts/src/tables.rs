@@ -2,7 +2,10 @@
 
 pub mod avar;
 pub mod base;
+pub mod cff;
+pub mod cff2;
 pub mod cmap;
+pub mod colr;
 pub mod fvar;
 pub mod gdef;
 pub mod glyf;
@@ -14,10 +17,12 @@ pub mod hhea;
 pub mod hmtx;
 pub mod hvar;
 pub mod layout;
+pub mod loca;
 pub mod maxp;
 pub mod name;
 pub mod os2;
 pub mod post;
+pub mod postscript;
 pub mod stat;
 pub mod variations;
 pub mod vhea;
@@ -0,0 +1,925 @@
+//! The [CFF](https://learn.microsoft.com/en-us/typography/opentype/spec/cff) table
+
+use std::collections::HashMap;
+
+use kurbo::{BezPath, Rect, Shape};
+use read::TopLevelTable;
+use types::Tag;
+
+use super::postscript::{
+    encode_fd_select, encode_path, op, subroutinize, write_index, DictWriter, Flavor, StringTable,
+    Subroutinized,
+};
+
+pub use super::postscript::{CffError, PrivateDict};
+
+/// The maximum number of glyphs in a CFF font; the count of the CharStrings
+/// INDEX is 16 bits.
+const MAX_GLYPHS: usize = u16::MAX as usize;
+
+/// The maximum number of Font DICTs, which are referenced by a byte in
+/// the FDSelect.
+const MAX_FONT_DICTS: usize = 256;
+
+/// The [Compact Font Format](https://learn.microsoft.com/en-us/typography/opentype/spec/cff) table.
+///
+/// This is a complete CFF font program, and is constructed with a
+/// [`CffBuilder`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cff(Vec<u8>);
+
+/// Descriptive values stored in the Top DICT.
+///
+/// Strings that are `None` are omitted; the other values are only written
+/// if they differ from the defaults in the specification.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FontInfo {
+    pub version: Option<String>,
+    pub notice: Option<String>,
+    pub copyright: Option<String>,
+    pub full_name: Option<String>,
+    pub family_name: Option<String>,
+    pub weight: Option<String>,
+    pub is_fixed_pitch: bool,
+    pub italic_angle: f64,
+    pub underline_position: Option<f64>,
+    pub underline_thickness: Option<f64>,
+    /// The transformation from glyph space to text space.
+    ///
+    /// This only needs to be set for fonts where the units per em is not
+    /// 1000, in which case it is `[1/upem, 0, 0, 1/upem, 0, 0]`.
+    pub font_matrix: Option<[f64; 6]>,
+}
+
+/// The character collection of a CID-keyed font.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ros {
+    pub registry: String,
+    pub ordering: String,
+    pub supplement: i32,
+}
+
+/// A Font DICT of a CID-keyed font, which gives a group of glyphs their
+/// own hinting values.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FontDict {
+    pub name: Option<String>,
+    pub private: PrivateDict,
+}
+
+/// A builder for the [`Cff`] table.
+///
+/// Fonts are either name-keyed, where each glyph is added with
+/// [`add_glyph`](Self::add_glyph), or CID-keyed, where glyphs are added
+/// with [`add_cid_glyph`](Self::add_cid_glyph). Glyphs are added in glyph
+/// id order, and the first must be `.notdef` (or CID 0).
+///
+/// Outlines are rounded to integer coordinates, and quadratic curves are
+/// converted to cubic ones.
+///
+/// ```
+/// # use kurbo::{BezPath, Shape};
+/// # use write_fonts::tables::cff::{CffBuilder, FontInfo, PrivateDict};
+/// let mut builder = CffBuilder::new("ExampleSans-Regular", FontInfo::default(), PrivateDict::default());
+/// builder.add_glyph(".notdef", &BezPath::new(), 500);
+/// let bar = kurbo::Rect::new(100.0, 0.0, 200.0, 700.0).to_path(0.1);
+/// builder.add_glyph("bar", &bar, 300);
+/// let cff = builder.subroutinize(true).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct CffBuilder {
+    font_name: String,
+    info: FontInfo,
+    keying: Keying,
+    glyphs: Vec<Glyph>,
+    subroutinize: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Keying {
+    Names(PrivateDict),
+    Cids { ros: Ros, font_dicts: Vec<FontDict> },
+}
+
+#[derive(Clone, Debug)]
+struct Glyph {
+    key: GlyphKey,
+    font_dict: usize,
+    path: BezPath,
+    advance: u16,
+}
+
+#[derive(Clone, Debug)]
+enum GlyphKey {
+    Name(String),
+    Cid(u16),
+}
+
+/// The position of each part of the table, which are referenced from the
+/// Top DICT.
+#[derive(Clone, Copy, Debug, Default)]
+struct Offsets {
+    charset: usize,
+    fd_select: usize,
+    char_strings: usize,
+    fd_array: usize,
+    private: usize,
+    private_len: usize,
+}
+
+impl CffBuilder {
+    /// Create a builder for a name-keyed font.
+    pub fn new(font_name: impl Into<String>, info: FontInfo, private: PrivateDict) -> Self {
+        Self {
+            font_name: font_name.into(),
+            info,
+            keying: Keying::Names(private),
+            glyphs: Vec::new(),
+            subroutinize: false,
+        }
+    }
+
+    /// Create a builder for a CID-keyed font.
+    pub fn new_cid(
+        font_name: impl Into<String>,
+        info: FontInfo,
+        ros: Ros,
+        font_dicts: Vec<FontDict>,
+    ) -> Self {
+        Self {
+            font_name: font_name.into(),
+            info,
+            keying: Keying::Cids { ros, font_dicts },
+            glyphs: Vec::new(),
+            subroutinize: false,
+        }
+    }
+
+    /// Add a glyph to a name-keyed font.
+    pub fn add_glyph(
+        &mut self,
+        name: impl Into<String>,
+        path: &BezPath,
+        advance: u16,
+    ) -> &mut Self {
+        self.glyphs.push(Glyph {
+            key: GlyphKey::Name(name.into()),
+            font_dict: 0,
+            path: path.clone(),
+            advance,
+        });
+        self
+    }
+
+    /// Add a glyph to a CID-keyed font, using the Font DICT at the given
+    /// index.
+    pub fn add_cid_glyph(
+        &mut self,
+        cid: u16,
+        font_dict: usize,
+        path: &BezPath,
+        advance: u16,
+    ) -> &mut Self {
+        self.glyphs.push(Glyph {
+            key: GlyphKey::Cid(cid),
+            font_dict,
+            path: path.clone(),
+            advance,
+        });
+        self
+    }
+
+    /// Whether to move repeated parts of charstrings into subroutines.
+    ///
+    /// This makes the table smaller, but takes longer to build.
+    pub fn subroutinize(&mut self, subroutinize: bool) -> &mut Self {
+        self.subroutinize = subroutinize;
+        self
+    }
+
+    /// Build the table.
+    pub fn build(&self) -> Result<Cff, CffError> {
+        self.validate()?;
+        let num_font_dicts = match &self.keying {
+            Keying::Names(_) => 1,
+            Keying::Cids { font_dicts, .. } => font_dicts.len(),
+        };
+        let font_dict_indices = self
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.font_dict)
+            .collect::<Vec<_>>();
+        let charstrings = self
+            .glyphs
+            .iter()
+            .enumerate()
+            .map(|(gid, glyph)| encode_path(&glyph.path, &[], Flavor::Cff, gid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Subroutinized {
+            charstrings,
+            global_subrs,
+            local_subrs,
+        } = if self.subroutinize {
+            subroutinize(
+                &charstrings,
+                &font_dict_indices,
+                num_font_dicts,
+                Flavor::Cff,
+            )
+        } else {
+            Subroutinized {
+                charstrings,
+                global_subrs: Vec::new(),
+                local_subrs: vec![Vec::new(); num_font_dicts],
+            }
+        };
+
+        // each Private DICT has its own default and nominal widths
+        let widths = (0..num_font_dicts)
+            .map(|font_dict| {
+                let advances = self
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| glyph.font_dict == font_dict)
+                    .map(|glyph| glyph.advance);
+                let default = most_common(advances).unwrap_or_default() as i32;
+                (default, default)
+            })
+            .collect::<Vec<_>>();
+        let charstrings = charstrings
+            .iter()
+            .zip(&self.glyphs)
+            .map(|(charstring, glyph)| {
+                let (default, nominal) = widths[glyph.font_dict];
+                let advance = glyph.advance as i32;
+                charstring.to_cff_bytes((advance != default).then_some(advance - nominal))
+            })
+            .collect::<Vec<_>>();
+
+        let mut strings = StringTable::default();
+        let top_dict_sids = self.top_dict_sids(&mut strings);
+        let charset = self.encode_charset(&mut strings);
+        let font_dict_sids = match &self.keying {
+            Keying::Names(_) => Vec::new(),
+            Keying::Cids { font_dicts, .. } => font_dicts
+                .iter()
+                .map(|font_dict| font_dict.name.as_ref().map(|name| strings.sid(name)))
+                .collect(),
+        };
+
+        let private_dicts = self
+            .private_dicts()
+            .zip(&widths)
+            .zip(&local_subrs)
+            .map(|((private, widths), subrs)| {
+                let dict = private.encode(Some(*widths), !subrs.is_empty());
+                let subrs = if subrs.is_empty() {
+                    Vec::new()
+                } else {
+                    write_index(subrs, Flavor::Cff)
+                };
+                (dict, subrs)
+            })
+            .collect::<Vec<_>>();
+
+        let name_index = write_index(&[self.font_name.as_bytes()], Flavor::Cff);
+        let string_index = write_index(strings.custom_strings(), Flavor::Cff);
+        let global_subrs_index = write_index(&global_subrs, Flavor::Cff);
+        let fd_select = match self.keying {
+            Keying::Names(_) => Vec::new(),
+            Keying::Cids { .. } => encode_fd_select(&font_dict_indices),
+        };
+        let char_strings_index = write_index(&charstrings, Flavor::Cff);
+        let bbox = self.font_bbox();
+
+        // offsets have a fixed size, so the DICTs can be measured before
+        // the offsets are known
+        let top_dict_len = self
+            .top_dict(&top_dict_sids, bbox, Offsets::default())
+            .len();
+        let fd_array_len = match self.keying {
+            Keying::Names(_) => 0,
+            Keying::Cids { .. } => self.fd_array(&font_dict_sids, &private_dicts, 0).len(),
+        };
+        let mut offsets = Offsets::default();
+        let mut pos = HEADER.len()
+            + name_index.len()
+            + write_index(&[vec![0u8; top_dict_len]], Flavor::Cff).len()
+            + string_index.len()
+            + global_subrs_index.len();
+        offsets.charset = pos;
+        pos += charset.len();
+        offsets.fd_select = pos;
+        pos += fd_select.len();
+        offsets.char_strings = pos;
+        pos += char_strings_index.len();
+        offsets.fd_array = pos;
+        pos += fd_array_len;
+        offsets.private = pos;
+        offsets.private_len = private_dicts.first().map_or(0, |(dict, _)| dict.len());
+
+        let top_dict = self.top_dict(&top_dict_sids, bbox, offsets);
+        debug_assert_eq!(top_dict.len(), top_dict_len);
+        let mut data = HEADER.to_vec();
+        data.extend(name_index);
+        data.extend(write_index(&[top_dict], Flavor::Cff));
+        data.extend(string_index);
+        data.extend(global_subrs_index);
+        data.extend(charset);
+        data.extend(fd_select);
+        data.extend(char_strings_index);
+        if let Keying::Cids { .. } = self.keying {
+            data.extend(self.fd_array(&font_dict_sids, &private_dicts, offsets.private));
+        }
+        for (dict, subrs) in private_dicts {
+            data.extend(dict);
+            data.extend(subrs);
+        }
+        Ok(Cff(data))
+    }
+
+    fn validate(&self) -> Result<(), CffError> {
+        if self.glyphs.len() > MAX_GLYPHS {
+            return Err(CffError::TooManyGlyphs);
+        }
+        match self.glyphs.first().map(|glyph| &glyph.key) {
+            Some(GlyphKey::Name(name)) if name == ".notdef" => (),
+            Some(GlyphKey::Cid(0)) => (),
+            _ => return Err(CffError::MissingNotdef),
+        }
+        for (gid, glyph) in self.glyphs.iter().enumerate() {
+            match (&self.keying, &glyph.key) {
+                (Keying::Names(_), GlyphKey::Name(_)) => (),
+                (Keying::Cids { font_dicts, .. }, GlyphKey::Cid(_)) => {
+                    if glyph.font_dict >= font_dicts.len().min(MAX_FONT_DICTS) {
+                        return Err(CffError::InvalidFontDict {
+                            glyph: gid,
+                            font_dict: glyph.font_dict,
+                        });
+                    }
+                }
+                _ => return Err(CffError::MixedGlyphKeys { glyph: gid }),
+            }
+        }
+        let info = &self.info;
+        let mut numbers = [info.underline_position, info.underline_thickness]
+            .into_iter()
+            .flatten()
+            .chain(info.font_matrix.into_iter().flatten())
+            .chain([info.italic_angle]);
+        if !numbers.all(f64::is_finite)
+            || !self.private_dicts().all(PrivateDict::is_finite)
+            || !self.font_bbox().is_finite()
+        {
+            return Err(CffError::NonFiniteNumber);
+        }
+        Ok(())
+    }
+
+    fn private_dicts(&self) -> impl Iterator<Item = &PrivateDict> {
+        let (single, multiple) = match &self.keying {
+            Keying::Names(private) => (Some(private), None),
+            Keying::Cids { font_dicts, .. } => (None, Some(font_dicts)),
+        };
+        single.into_iter().chain(
+            multiple
+                .into_iter()
+                .flatten()
+                .map(|font_dict| &font_dict.private),
+        )
+    }
+
+    /// Assign ids to the strings in the Top DICT.
+    fn top_dict_sids(&self, strings: &mut StringTable) -> Vec<(u16, u16)> {
+        let info = &self.info;
+        let mut sids = Vec::new();
+        if let Keying::Cids { ros, .. } = &self.keying {
+            sids.push((op::ROS, strings.sid(&ros.registry)));
+            sids.push((op::ROS, strings.sid(&ros.ordering)));
+        }
+        for (op, string) in [
+            (op::VERSION, &info.version),
+            (op::NOTICE, &info.notice),
+            (op::COPYRIGHT, &info.copyright),
+            (op::FULL_NAME, &info.full_name),
+            (op::FAMILY_NAME, &info.family_name),
+            (op::WEIGHT, &info.weight),
+        ] {
+            if let Some(string) = string {
+                sids.push((op, strings.sid(string)));
+            }
+        }
+        sids
+    }
+
+    fn top_dict(&self, sids: &[(u16, u16)], bbox: Rect, offsets: Offsets) -> Vec<u8> {
+        let info = &self.info;
+        let mut dict = DictWriter::default();
+        let mut sids = sids.iter().peekable();
+        if let Keying::Cids { ros, .. } = &self.keying {
+            let registry = sids.next().unwrap().1;
+            let ordering = sids.next().unwrap().1;
+            dict.int(registry as i32)
+                .int(ordering as i32)
+                .int(ros.supplement)
+                .op(op::ROS);
+        }
+        for (op, sid) in sids {
+            dict.int(*sid as i32).op(*op);
+        }
+        if info.is_fixed_pitch {
+            dict.int(1).op(op::IS_FIXED_PITCH);
+        }
+        if info.italic_angle != 0.0 {
+            dict.number(info.italic_angle).op(op::ITALIC_ANGLE);
+        }
+        dict.maybe_number(op::UNDERLINE_POSITION, info.underline_position)
+            .maybe_number(op::UNDERLINE_THICKNESS, info.underline_thickness);
+        if let Some(matrix) = &info.font_matrix {
+            dict.array(op::FONT_MATRIX, matrix);
+        }
+        if bbox != Rect::ZERO {
+            dict.array(op::FONT_BBOX, &[bbox.x0, bbox.y0, bbox.x1, bbox.y1]);
+        }
+        dict.offset(offsets.charset).op(op::CHARSET);
+        match &self.keying {
+            Keying::Names(_) => {
+                dict.offset(offsets.char_strings).op(op::CHAR_STRINGS);
+                dict.offset(offsets.private_len)
+                    .offset(offsets.private)
+                    .op(op::PRIVATE);
+            }
+            Keying::Cids { .. } => {
+                let cid_count = self
+                    .glyphs
+                    .iter()
+                    .filter_map(|glyph| match glyph.key {
+                        GlyphKey::Cid(cid) => Some(cid as i32 + 1),
+                        GlyphKey::Name(_) => None,
+                    })
+                    .max()
+                    .unwrap_or_default();
+                dict.int(cid_count).op(op::CID_COUNT);
+                dict.offset(offsets.char_strings).op(op::CHAR_STRINGS);
+                dict.offset(offsets.fd_array).op(op::FD_ARRAY);
+                dict.offset(offsets.fd_select).op(op::FD_SELECT);
+            }
+        }
+        dict.into_bytes()
+    }
+
+    /// Encode the FDArray INDEX, given the position of the first Private
+    /// DICT.
+    fn fd_array(
+        &self,
+        sids: &[Option<u16>],
+        private_dicts: &[(Vec<u8>, Vec<u8>)],
+        mut private_pos: usize,
+    ) -> Vec<u8> {
+        let font_dicts = sids
+            .iter()
+            .zip(private_dicts)
+            .map(|(sid, (private, subrs))| {
+                let mut dict = DictWriter::default();
+                if let Some(sid) = sid {
+                    dict.int(*sid as i32).op(op::FONT_NAME);
+                }
+                dict.offset(private.len())
+                    .offset(private_pos)
+                    .op(op::PRIVATE);
+                private_pos += private.len() + subrs.len();
+                dict.into_bytes()
+            })
+            .collect::<Vec<_>>();
+        write_index(&font_dicts, Flavor::Cff)
+    }
+
+    /// Encode the charset, in whichever format is smallest.
+    fn encode_charset(&self, strings: &mut StringTable) -> Vec<u8> {
+        // .notdef is implied
+        let ids = self.glyphs[1..]
+            .iter()
+            .map(|glyph| match &glyph.key {
+                GlyphKey::Name(name) => strings.sid(name),
+                GlyphKey::Cid(cid) => *cid,
+            })
+            .collect::<Vec<_>>();
+        let mut ranges: Vec<(u16, u16)> = Vec::new();
+        for id in &ids {
+            match ranges.last_mut() {
+                Some((first, n_left)) if first.checked_add(*n_left + 1) == Some(*id) => {
+                    *n_left += 1
+                }
+                _ => ranges.push((*id, 0)),
+            }
+        }
+        // format 1 can only store up to 256 ids in a range
+        let format_1_ranges = ranges
+            .iter()
+            .map(|(_, n_left)| *n_left as usize / 256 + 1)
+            .sum::<usize>();
+        let format_0_len = ids.len() * 2;
+        let format_1_len = format_1_ranges * 3;
+        let format_2_len = ranges.len() * 4;
+        let mut data = Vec::new();
+        if format_0_len <= format_1_len && format_0_len <= format_2_len {
+            data.push(0);
+            ids.iter().for_each(|id| data.extend(id.to_be_bytes()));
+        } else if format_1_len <= format_2_len {
+            data.push(1);
+            for (first, n_left) in ranges {
+                let mut first = first;
+                let mut remaining = n_left as usize + 1;
+                while remaining > 0 {
+                    let len = remaining.min(256);
+                    data.extend(first.to_be_bytes());
+                    data.push((len - 1) as u8);
+                    first = first.wrapping_add(len as u16);
+                    remaining -= len;
+                }
+            }
+        } else {
+            data.push(2);
+            for (first, n_left) in ranges {
+                data.extend(first.to_be_bytes());
+                data.extend(n_left.to_be_bytes());
+            }
+        }
+        data
+    }
+
+    /// The union of the glyph bounding boxes, rounded outwards.
+    fn font_bbox(&self) -> Rect {
+        self.glyphs
+            .iter()
+            .filter(|glyph| !glyph.path.elements().is_empty())
+            .map(|glyph| glyph.path.bounding_box())
+            .reduce(|a, b| a.union(b))
+            .map(Rect::expand)
+            .unwrap_or_default()
+    }
+}
+
+/// The header: major and minor version, header size, and the offset size
+/// used for absolute offsets (which is unused by readers).
+const HEADER: [u8; 4] = [1, 0, 4, 4];
+
+/// The most common value, preferring the smallest in a tie.
+fn most_common(values: impl Iterator<Item = u16>) -> Option<u16> {
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0usize) += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(value, _)| value)
+}
+
+impl Cff {
+    /// The raw bytes of the table.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TopLevelTable for Cff {
+    const TAG: Tag = Tag::new(b"CFF ");
+}
+
+impl crate::FontWrite for Cff {
+    fn write_into(&self, writer: &mut crate::TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl crate::validate::Validate for Cff {
+    fn validate_impl(&self, _ctx: &mut crate::codegen_prelude::ValidationCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::Rect;
+
+    use super::*;
+    use crate::tables::postscript::test_utils::{
+        read_dict, read_index, run_charstring, svg, ttf_parser_outlines,
+    };
+
+    type Dict = Vec<(u16, Vec<f64>)>;
+
+    /// The parts of a CFF table, decoded.
+    struct Parsed {
+        data: Vec<u8>,
+        names: Vec<Vec<u8>>,
+        top_dict: Dict,
+        strings: Vec<Vec<u8>>,
+        global_subrs: Vec<Vec<u8>>,
+        charstrings: Vec<Vec<u8>>,
+    }
+
+    impl Parsed {
+        fn new(cff: &Cff) -> Self {
+            let data = cff.as_bytes().to_vec();
+            assert_eq!(data[..4], HEADER);
+            let mut pos = 4;
+            let mut next_index = || {
+                let (items, len) = read_index(&data[pos..], 2);
+                pos += len;
+                items
+            };
+            let names = next_index();
+            let top_dict = read_dict(&next_index()[0]);
+            let strings = next_index();
+            let global_subrs = next_index();
+            let char_strings_pos = get(&top_dict, op::CHAR_STRINGS)[0] as usize;
+            let charstrings = read_index(&data[char_strings_pos..], 2).0;
+            Parsed {
+                data,
+                names,
+                top_dict,
+                strings,
+                global_subrs,
+                charstrings,
+            }
+        }
+
+        /// Decode the Private DICT at the given size and offset, and its
+        /// local subrs.
+        fn private(&self, size_and_offset: &[f64]) -> (Dict, Vec<Vec<u8>>) {
+            let (size, offset) = (size_and_offset[0] as usize, size_and_offset[1] as usize);
+            let dict = read_dict(&self.data[offset..offset + size]);
+            let subrs = dict
+                .iter()
+                .find(|(op, _)| *op == op::SUBRS)
+                .map(|(_, args)| read_index(&self.data[offset + args[0] as usize..], 2).0)
+                .unwrap_or_default();
+            (dict, subrs)
+        }
+
+        fn string(&self, sid: f64) -> &[u8] {
+            &self.strings[sid as usize - 391]
+        }
+    }
+
+    fn get(dict: &[(u16, Vec<f64>)], op: u16) -> Vec<f64> {
+        dict.iter()
+            .find(|(dict_op, _)| *dict_op == op)
+            .map(|(_, args)| args.clone())
+            .unwrap_or_else(|| panic!("missing operator {op}"))
+    }
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BezPath {
+        Rect::new(x0, y0, x1, y1).to_path(0.1)
+    }
+
+    /// Outlines with several contours, and curves in every direction.
+    fn outlines() -> Vec<BezPath> {
+        (0..20)
+            .map(|i| {
+                let x = 10.0 * i as f64;
+                let mut path = rect(x, 0.0, 400.0, 700.0);
+                path.move_to((100.0, 100.0));
+                path.curve_to((100.0, 200.0), (200.0 + x, 300.0), (300.0, 300.0));
+                path.curve_to((350.0, 300.0), (350.0, 250.0 - x), (350.0, 200.0));
+                path.curve_to((350.0, 150.0), (300.0, 100.0), (250.0, 100.0));
+                path.close_path();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn name_keyed() {
+        let info = FontInfo {
+            full_name: Some("Example Sans Regular".into()),
+            weight: Some("Regular".into()),
+            italic_angle: -12.5,
+            ..Default::default()
+        };
+        let private = PrivateDict {
+            blue_values: vec![-10.0, 0.0, 500.0, 510.0],
+            std_vw: Some(80.0),
+            ..Default::default()
+        };
+        let mut builder = CffBuilder::new("ExampleSans-Regular", info, private);
+        builder
+            .add_glyph(".notdef", &rect(50.0, 0.0, 450.0, 700.0), 500)
+            .add_glyph("A", &rect(0.0, -5.25, 600.0, 700.0), 600)
+            .add_glyph("uni0414", &rect(10.0, 0.0, 590.0, 700.0), 600);
+        let cff = builder.build().unwrap();
+        let parsed = Parsed::new(&cff);
+        assert_eq!(parsed.names, [b"ExampleSans-Regular".to_vec()]);
+        assert_eq!(
+            parsed.string(get(&parsed.top_dict, op::FULL_NAME)[0]),
+            b"Example Sans Regular"
+        );
+        // a standard string
+        assert_eq!(get(&parsed.top_dict, op::WEIGHT), [388.0]);
+        assert_eq!(get(&parsed.top_dict, op::ITALIC_ANGLE), [-12.5]);
+        assert_eq!(
+            get(&parsed.top_dict, op::FONT_BBOX),
+            [0.0, -6.0, 600.0, 700.0]
+        );
+        assert!(parsed.global_subrs.is_empty());
+
+        let charset_pos = get(&parsed.top_dict, op::CHARSET)[0] as usize;
+        let uni0414 = parsed.strings.iter().position(|s| s == b"uni0414").unwrap() as u8;
+        assert_eq!(
+            parsed.data[charset_pos..charset_pos + 5],
+            [0, 0, 34, 1, 135 + uni0414]
+        );
+
+        let (private, subrs) = parsed.private(&get(&parsed.top_dict, op::PRIVATE));
+        assert_eq!(get(&private, op::BLUE_VALUES), [-10.0, 10.0, 500.0, 10.0]);
+        assert_eq!(get(&private, op::STD_VW), [80.0]);
+        assert_eq!(get(&private, op::DEFAULT_WIDTH_X), [600.0]);
+        assert_eq!(get(&private, op::NOMINAL_WIDTH_X), [600.0]);
+        assert!(subrs.is_empty());
+
+        assert_eq!(parsed.charstrings.len(), 3);
+        let notdef = run_charstring(&parsed.charstrings[0], &[], &[], &[]);
+        assert_eq!(notdef.width, Some(-100.0));
+        assert_eq!(notdef.ops[0], (22 /* hmoveto */, vec![50.0]));
+        let a = run_charstring(&parsed.charstrings[1], &[], &[], &[]);
+        assert_eq!(a.width, None);
+    }
+
+    #[test]
+    fn missing_notdef() {
+        let mut builder = CffBuilder::new("Test", FontInfo::default(), PrivateDict::default());
+        assert!(matches!(builder.build(), Err(CffError::MissingNotdef)));
+        builder.add_glyph("A", &BezPath::new(), 500);
+        assert!(matches!(builder.build(), Err(CffError::MissingNotdef)));
+    }
+
+    #[test]
+    fn non_finite_numbers() {
+        let info = FontInfo {
+            underline_position: Some(f64::NEG_INFINITY),
+            ..Default::default()
+        };
+        let mut builder = CffBuilder::new("Test", info, PrivateDict::default());
+        builder.add_glyph(".notdef", &BezPath::new(), 500);
+        assert!(matches!(builder.build(), Err(CffError::NonFiniteNumber)));
+
+        let private = PrivateDict {
+            std_hw: Some(f64::NAN),
+            ..Default::default()
+        };
+        let mut builder = CffBuilder::new("Test", FontInfo::default(), private);
+        builder.add_glyph(".notdef", &BezPath::new(), 500);
+        assert!(matches!(builder.build(), Err(CffError::NonFiniteNumber)));
+
+        // the font bounding box is written to the Top DICT
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((f64::NAN, 100.0));
+        path.close_path();
+        let mut builder = CffBuilder::new("Test", FontInfo::default(), PrivateDict::default());
+        builder.add_glyph(".notdef", &path, 500);
+        assert!(matches!(builder.build(), Err(CffError::NonFiniteNumber)));
+    }
+
+    #[test]
+    fn cid_keyed() {
+        let ros = Ros {
+            registry: "Adobe".into(),
+            ordering: "Identity".into(),
+            supplement: 0,
+        };
+        let font_dicts = vec![
+            FontDict {
+                name: Some("Example-Alphabetic".into()),
+                private: PrivateDict::default(),
+            },
+            FontDict {
+                name: Some("Example-Ideographs".into()),
+                private: PrivateDict {
+                    language_group: Some(1),
+                    ..Default::default()
+                },
+            },
+        ];
+        let mut builder = CffBuilder::new_cid("Example", FontInfo::default(), ros, font_dicts);
+        builder.add_cid_glyph(0, 0, &BezPath::new(), 500);
+        for cid in 1..=5 {
+            builder.add_cid_glyph(cid, 0, &rect(0.0, 0.0, 100.0, 100.0), 500);
+        }
+        for cid in 1000..1010 {
+            builder.add_cid_glyph(cid, 1, &rect(0.0, 0.0, 900.0, 900.0), 1000);
+        }
+        let cff = builder.build().unwrap();
+        let parsed = Parsed::new(&cff);
+        // ROS must be the first operator
+        let (ros_op, ros) = &parsed.top_dict[0];
+        assert_eq!(*ros_op, op::ROS);
+        assert_eq!(parsed.string(ros[0]), b"Adobe");
+        assert_eq!(parsed.string(ros[1]), b"Identity");
+        assert_eq!(get(&parsed.top_dict, op::CID_COUNT), [1010.0]);
+
+        // two short ranges of CIDs, in format 1
+        let charset_pos = get(&parsed.top_dict, op::CHARSET)[0] as usize;
+        assert_eq!(
+            parsed.data[charset_pos..charset_pos + 7],
+            [1, 0, 1, 4, 0x03, 0xe8, 9]
+        );
+
+        // format 3, with two ranges and a sentinel
+        let fd_select_pos = get(&parsed.top_dict, op::FD_SELECT)[0] as usize;
+        assert_eq!(
+            parsed.data[fd_select_pos..fd_select_pos + 11],
+            [3, 0, 2, 0, 0, 0, 0, 6, 1, 0, 16]
+        );
+
+        let fd_array_pos = get(&parsed.top_dict, op::FD_ARRAY)[0] as usize;
+        let fd_array = read_index(&parsed.data[fd_array_pos..], 2).0;
+        assert_eq!(fd_array.len(), 2);
+        let font_dict = read_dict(&fd_array[1]);
+        assert_eq!(
+            parsed.string(get(&font_dict, op::FONT_NAME)[0]),
+            b"Example-Ideographs"
+        );
+        let (private, _) = parsed.private(&get(&font_dict, op::PRIVATE));
+        assert_eq!(get(&private, op::LANGUAGE_GROUP), [1.0]);
+        assert_eq!(get(&private, op::DEFAULT_WIDTH_X), [1000.0]);
+    }
+
+    #[test]
+    fn subroutinized_outlines_are_unchanged() {
+        let mut builder = CffBuilder::new("Test", FontInfo::default(), PrivateDict::default());
+        builder.add_glyph(".notdef", &BezPath::new(), 500);
+        for i in 0..20 {
+            let mut path = rect(0.0, 0.0, 400.0, 700.0);
+            path.extend(rect(100.0 + i as f64, 100.0, 300.0, 600.0));
+            builder.add_glyph(format!("glyph{i}"), &path, 500 + i);
+        }
+        let plain = builder.build().unwrap();
+        let subroutinized = builder.subroutinize(true).build().unwrap();
+        assert!(subroutinized.as_bytes().len() < plain.as_bytes().len());
+
+        let plain = Parsed::new(&plain);
+        let subroutinized = Parsed::new(&subroutinized);
+        let (_, local_subrs) = subroutinized.private(&get(&subroutinized.top_dict, op::PRIVATE));
+        assert!(!local_subrs.is_empty());
+        for (before, after) in plain.charstrings.iter().zip(&subroutinized.charstrings) {
+            assert_eq!(
+                run_charstring(before, &[], &[], &[]),
+                run_charstring(after, &subroutinized.global_subrs, &local_subrs, &[])
+            );
+        }
+    }
+
+    #[test]
+    fn outlines_read_by_ttf_parser() {
+        let outlines = outlines();
+        let mut builder = CffBuilder::new("Test", FontInfo::default(), PrivateDict::default());
+        builder.add_glyph(".notdef", &BezPath::new(), 500);
+        for (i, path) in outlines.iter().enumerate() {
+            builder.add_glyph(format!("glyph{i}"), path, 500);
+        }
+        let expected = std::iter::once(String::new())
+            .chain(outlines.iter().map(svg))
+            .collect::<Vec<_>>();
+        for subroutinize in [false, true] {
+            let cff = builder.subroutinize(subroutinize).build().unwrap();
+            assert_eq!(
+                ttf_parser_outlines(Cff::TAG, cff.as_bytes(), 21, None),
+                expected,
+                "subroutinize: {subroutinize}"
+            );
+        }
+    }
+
+    #[test]
+    fn cid_outlines_read_by_ttf_parser() {
+        let ros = Ros {
+            registry: "Adobe".into(),
+            ordering: "Identity".into(),
+            supplement: 0,
+        };
+        let font_dict = FontDict {
+            name: None,
+            private: PrivateDict::default(),
+        };
+        let mut builder = CffBuilder::new_cid(
+            "Test",
+            FontInfo::default(),
+            ros,
+            vec![font_dict.clone(), font_dict],
+        );
+        builder.add_cid_glyph(0, 0, &BezPath::new(), 500);
+        let outlines = outlines();
+        for (i, path) in outlines.iter().enumerate() {
+            builder.add_cid_glyph(100 + i as u16, i % 2, path, 500);
+        }
+        let cff = builder.subroutinize(true).build().unwrap();
+        let expected = std::iter::once(String::new())
+            .chain(outlines.iter().map(svg))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ttf_parser_outlines(Cff::TAG, cff.as_bytes(), 21, None),
+            expected
+        );
+    }
+}
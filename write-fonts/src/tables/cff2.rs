@@ -0,0 +1,548 @@
+//! The [CFF2](https://learn.microsoft.com/en-us/typography/opentype/spec/cff2) table
+
+use kurbo::BezPath;
+use read::TopLevelTable;
+use types::Tag;
+
+use super::{
+    postscript::{
+        encode_fd_select, encode_path, op, subroutinize, write_index, DictWriter, Flavor,
+        Subroutinized,
+    },
+    variations::ItemVariationStore,
+};
+
+pub use super::postscript::{CffError, PrivateDict};
+
+/// The maximum number of glyphs in a font.
+const MAX_GLYPHS: usize = u16::MAX as usize;
+
+/// The maximum number of Font DICTs, which are referenced by a byte in
+/// the FDSelect.
+const MAX_FONT_DICTS: usize = 256;
+
+/// The [Compact Font Format version 2](https://learn.microsoft.com/en-us/typography/opentype/spec/cff2) table.
+///
+/// This is constructed with a [`Cff2Builder`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cff2(Vec<u8>);
+
+/// A builder for the [`Cff2`] table.
+///
+/// Glyphs are added in glyph id order. Each glyph uses one of the Private
+/// DICTs passed to [`new`](Self::new); most fonts have only one.
+///
+/// Variable glyphs are added with
+/// [`add_variable_glyph`](Self::add_variable_glyph), along with the deltas
+/// of each point for each region of the first [`ItemVariationData`] of the
+/// variation store, which are written with `blend` operators.
+///
+/// [`ItemVariationData`]: super::variations::ItemVariationData
+#[derive(Clone, Debug)]
+pub struct Cff2Builder {
+    font_dicts: Vec<PrivateDict>,
+    font_matrix: Option<[f64; 6]>,
+    variation_store: Option<ItemVariationStore>,
+    glyphs: Vec<Glyph>,
+    subroutinize: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Glyph {
+    font_dict: usize,
+    path: BezPath,
+    deltas: Vec<BezPath>,
+}
+
+/// The position of each part of the table, which are referenced from the
+/// Top DICT.
+#[derive(Clone, Copy, Debug, Default)]
+struct Offsets {
+    vstore: usize,
+    fd_select: usize,
+    char_strings: usize,
+    fd_array: usize,
+}
+
+impl Cff2Builder {
+    /// Create a builder with the given Private DICTs.
+    pub fn new(font_dicts: Vec<PrivateDict>) -> Self {
+        Self {
+            font_dicts,
+            font_matrix: None,
+            variation_store: None,
+            glyphs: Vec::new(),
+            subroutinize: false,
+        }
+    }
+
+    /// Set the transformation from glyph space to text space.
+    ///
+    /// This only needs to be set for fonts where the units per em is not
+    /// 1000.
+    pub fn set_font_matrix(&mut self, font_matrix: [f64; 6]) -> &mut Self {
+        self.font_matrix = Some(font_matrix);
+        self
+    }
+
+    /// Set the variation store whose regions are used by variable glyphs.
+    pub fn set_variation_store(&mut self, variation_store: ItemVariationStore) -> &mut Self {
+        self.variation_store = Some(variation_store);
+        self
+    }
+
+    /// Add a glyph with no variations.
+    pub fn add_glyph(&mut self, font_dict: usize, path: &BezPath) -> &mut Self {
+        self.add_variable_glyph(font_dict, path, Vec::new())
+    }
+
+    /// Add a glyph with variations.
+    ///
+    /// `deltas` has a path for each region, with the same structure as
+    /// `default`, whose points are the deltas of the default's points.
+    pub fn add_variable_glyph(
+        &mut self,
+        font_dict: usize,
+        default: &BezPath,
+        deltas: Vec<BezPath>,
+    ) -> &mut Self {
+        self.glyphs.push(Glyph {
+            font_dict,
+            path: default.clone(),
+            deltas,
+        });
+        self
+    }
+
+    /// Whether to move repeated parts of charstrings into subroutines.
+    ///
+    /// This makes the table smaller, but takes longer to build.
+    pub fn subroutinize(&mut self, subroutinize: bool) -> &mut Self {
+        self.subroutinize = subroutinize;
+        self
+    }
+
+    /// Build the table.
+    pub fn build(&self) -> Result<Cff2, CffError> {
+        self.validate()?;
+        let num_font_dicts = self.font_dicts.len();
+        let font_dict_indices = self
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.font_dict)
+            .collect::<Vec<_>>();
+        let charstrings = self
+            .glyphs
+            .iter()
+            .enumerate()
+            .map(|(gid, glyph)| encode_path(&glyph.path, &glyph.deltas, Flavor::Cff2, gid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Subroutinized {
+            charstrings,
+            global_subrs,
+            local_subrs,
+        } = if self.subroutinize {
+            subroutinize(
+                &charstrings,
+                &font_dict_indices,
+                num_font_dicts,
+                Flavor::Cff2,
+            )
+        } else {
+            Subroutinized {
+                charstrings,
+                global_subrs: Vec::new(),
+                local_subrs: vec![Vec::new(); num_font_dicts],
+            }
+        };
+        let charstrings = charstrings
+            .iter()
+            .map(|charstring| charstring.to_cff2_bytes())
+            .collect::<Vec<_>>();
+
+        let private_dicts = self
+            .font_dicts
+            .iter()
+            .zip(&local_subrs)
+            .map(|(private, subrs)| {
+                let dict = private.encode(None, !subrs.is_empty());
+                let subrs = if subrs.is_empty() {
+                    Vec::new()
+                } else {
+                    write_index(subrs, Flavor::Cff2)
+                };
+                (dict, subrs)
+            })
+            .collect::<Vec<_>>();
+
+        let global_subrs_index = write_index(&global_subrs, Flavor::Cff2);
+        let vstore = match &self.variation_store {
+            Some(store) => {
+                let data = crate::dump_table(store).map_err(CffError::VariationStore)?;
+                let len =
+                    u16::try_from(data.len()).map_err(|_| CffError::VariationStoreTooLarge)?;
+                let mut vstore = len.to_be_bytes().to_vec();
+                vstore.extend(data);
+                vstore
+            }
+            None => Vec::new(),
+        };
+        let fd_select = if num_font_dicts > 1 {
+            encode_fd_select(&font_dict_indices)
+        } else {
+            Vec::new()
+        };
+        let char_strings_index = write_index(&charstrings, Flavor::Cff2);
+
+        // offsets have a fixed size, so the DICTs can be measured before
+        // the offsets are known
+        let top_dict_len = self.top_dict(Offsets::default()).len();
+        let fd_array_len = fd_array(&private_dicts, 0).len();
+        let mut offsets = Offsets::default();
+        let mut pos = HEADER_LEN + top_dict_len + global_subrs_index.len();
+        offsets.vstore = pos;
+        pos += vstore.len();
+        offsets.fd_select = pos;
+        pos += fd_select.len();
+        offsets.char_strings = pos;
+        pos += char_strings_index.len();
+        offsets.fd_array = pos;
+        pos += fd_array_len;
+
+        let top_dict = self.top_dict(offsets);
+        debug_assert_eq!(top_dict.len(), top_dict_len);
+        // major and minor version, header size, Top DICT length
+        let mut data = vec![2, 0, HEADER_LEN as u8];
+        data.extend((top_dict.len() as u16).to_be_bytes());
+        data.extend(top_dict);
+        data.extend(global_subrs_index);
+        data.extend(vstore);
+        data.extend(fd_select);
+        data.extend(char_strings_index);
+        data.extend(fd_array(&private_dicts, pos));
+        for (dict, subrs) in private_dicts {
+            data.extend(dict);
+            data.extend(subrs);
+        }
+        Ok(Cff2(data))
+    }
+
+    fn validate(&self) -> Result<(), CffError> {
+        if self.glyphs.len() > MAX_GLYPHS {
+            return Err(CffError::TooManyGlyphs);
+        }
+        let num_regions = self
+            .variation_store
+            .as_ref()
+            .and_then(|store| store.item_variation_datas.first())
+            .and_then(|data| data.as_ref())
+            .map(|data| data.region_indexes.len())
+            .unwrap_or_default();
+        for (gid, glyph) in self.glyphs.iter().enumerate() {
+            if glyph.font_dict >= self.font_dicts.len().min(MAX_FONT_DICTS) {
+                return Err(CffError::InvalidFontDict {
+                    glyph: gid,
+                    font_dict: glyph.font_dict,
+                });
+            }
+            if !glyph.deltas.is_empty() && glyph.deltas.len() != num_regions {
+                return Err(CffError::RegionCountMismatch {
+                    glyph: gid,
+                    expected: num_regions,
+                    found: glyph.deltas.len(),
+                });
+            }
+        }
+        if !self
+            .font_matrix
+            .iter()
+            .flatten()
+            .all(|value| value.is_finite())
+            || !self.font_dicts.iter().all(PrivateDict::is_finite)
+        {
+            return Err(CffError::NonFiniteNumber);
+        }
+        Ok(())
+    }
+
+    fn top_dict(&self, offsets: Offsets) -> Vec<u8> {
+        let mut dict = DictWriter::default();
+        if let Some(matrix) = &self.font_matrix {
+            dict.array(op::FONT_MATRIX, matrix);
+        }
+        dict.offset(offsets.char_strings).op(op::CHAR_STRINGS);
+        dict.offset(offsets.fd_array).op(op::FD_ARRAY);
+        if self.font_dicts.len() > 1 {
+            dict.offset(offsets.fd_select).op(op::FD_SELECT);
+        }
+        if self.variation_store.is_some() {
+            dict.offset(offsets.vstore).op(op::VSTORE);
+        }
+        dict.into_bytes()
+    }
+}
+
+/// The size of the header.
+const HEADER_LEN: usize = 5;
+
+/// Encode the FDArray INDEX, given the position of the first Private DICT.
+fn fd_array(private_dicts: &[(Vec<u8>, Vec<u8>)], mut private_pos: usize) -> Vec<u8> {
+    let font_dicts = private_dicts
+        .iter()
+        .map(|(private, subrs)| {
+            let mut dict = DictWriter::default();
+            dict.offset(private.len())
+                .offset(private_pos)
+                .op(op::PRIVATE);
+            private_pos += private.len() + subrs.len();
+            dict.into_bytes()
+        })
+        .collect::<Vec<_>>();
+    write_index(&font_dicts, Flavor::Cff2)
+}
+
+impl Cff2 {
+    /// The raw bytes of the table.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TopLevelTable for Cff2 {
+    const TAG: Tag = Tag::new(b"CFF2");
+}
+
+impl crate::FontWrite for Cff2 {
+    fn write_into(&self, writer: &mut crate::TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl crate::validate::Validate for Cff2 {
+    fn validate_impl(&self, _ctx: &mut crate::codegen_prelude::ValidationCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use font_types::F2Dot14;
+    use kurbo::{Rect, Shape};
+
+    use super::*;
+    use crate::tables::{
+        postscript::test_utils::{read_dict, read_index, run_charstring, svg, ttf_parser_outlines},
+        variations::{
+            ItemVariationData, RegionAxisCoordinates, VariationRegion, VariationRegionList,
+        },
+    };
+
+    fn get(dict: &[(u16, Vec<f64>)], op: u16) -> Option<Vec<f64>> {
+        dict.iter()
+            .find(|(dict_op, _)| *dict_op == op)
+            .map(|(_, args)| args.clone())
+    }
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BezPath {
+        Rect::new(x0, y0, x1, y1).to_path(0.1)
+    }
+
+    /// A variation store with a single region.
+    fn variation_store() -> ItemVariationStore {
+        let region = VariationRegion::new(vec![RegionAxisCoordinates::new(
+            F2Dot14::ZERO,
+            F2Dot14::ONE,
+            F2Dot14::ONE,
+        )]);
+        ItemVariationStore::new(
+            1,
+            VariationRegionList::new(vec![region]),
+            vec![Some(ItemVariationData::new(0, 0, vec![0], vec![]))],
+        )
+    }
+
+    /// Decode the Top DICT, the CharStrings and the FDArray.
+    #[allow(clippy::type_complexity)]
+    fn parse(cff2: &Cff2) -> (Vec<(u16, Vec<f64>)>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        let data = cff2.as_bytes();
+        assert_eq!(data[..3], [2, 0, 5]);
+        let top_dict_len = u16::from_be_bytes([data[3], data[4]]) as usize;
+        let top_dict = read_dict(&data[5..5 + top_dict_len]);
+        let char_strings_pos = get(&top_dict, op::CHAR_STRINGS).unwrap()[0] as usize;
+        let fd_array_pos = get(&top_dict, op::FD_ARRAY).unwrap()[0] as usize;
+        let charstrings = read_index(&data[char_strings_pos..], 4).0;
+        let fd_array = read_index(&data[fd_array_pos..], 4).0;
+        (top_dict, charstrings, fd_array)
+    }
+
+    #[test]
+    fn static_glyphs() {
+        let private = PrivateDict {
+            blue_values: vec![-10.0, 0.0],
+            force_bold: true,
+            ..Default::default()
+        };
+        let mut builder = Cff2Builder::new(vec![private]);
+        builder
+            .add_glyph(0, &BezPath::new())
+            .add_glyph(0, &rect(10.0, 0.0, 110.0, 100.0));
+        let cff2 = builder.build().unwrap();
+        let (top_dict, charstrings, fd_array) = parse(&cff2);
+        assert_eq!(get(&top_dict, op::FD_SELECT), None);
+        assert_eq!(get(&top_dict, op::VSTORE), None);
+        assert_eq!(charstrings.len(), 2);
+        // no endchar
+        assert!(charstrings[0].is_empty());
+        let outline = run_charstring(&charstrings[1], &[], &[], &[]);
+        assert_eq!(outline.ops[0], (22 /* hmoveto */, vec![10.0]));
+
+        assert_eq!(fd_array.len(), 1);
+        let private = get(&read_dict(&fd_array[0]), op::PRIVATE).unwrap();
+        let (size, offset) = (private[0] as usize, private[1] as usize);
+        let private = read_dict(&cff2.as_bytes()[offset..offset + size]);
+        // forceBold is not written to CFF2
+        assert_eq!(private, [(op::BLUE_VALUES, vec![-10.0, 10.0])]);
+    }
+
+    #[test]
+    fn variable_glyphs() {
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default()]);
+        builder.set_variation_store(variation_store());
+        let default = rect(0.0, 0.0, 100.0, 100.0);
+        let delta = Rect::new(0.0, 0.0, 50.0, 0.0).to_path(0.1);
+        builder
+            .add_glyph(0, &BezPath::new())
+            .add_variable_glyph(0, &default, vec![delta]);
+        let cff2 = builder.build().unwrap();
+        let (top_dict, charstrings, _) = parse(&cff2);
+
+        let vstore_pos = get(&top_dict, op::VSTORE).unwrap()[0] as usize;
+        let vstore = crate::dump_table(&variation_store()).unwrap();
+        let data = cff2.as_bytes();
+        assert_eq!(
+            u16::from_be_bytes([data[vstore_pos], data[vstore_pos + 1]]) as usize,
+            vstore.len()
+        );
+        assert_eq!(data[vstore_pos + 2..vstore_pos + 2 + vstore.len()], vstore);
+
+        let bbox = |scalar| {
+            let outline = run_charstring(&charstrings[1], &[], &[], &[scalar]);
+            let mut x = 0.0;
+            let mut max_x = 0.0f64;
+            for (op, args) in outline.ops {
+                if op == 22 || op == 6 {
+                    x += args.iter().step_by(2).sum::<f64>();
+                    max_x = max_x.max(x);
+                }
+            }
+            max_x
+        };
+        assert_eq!(bbox(0.0), 100.0);
+        assert_eq!(bbox(1.0), 150.0);
+    }
+
+    #[test]
+    fn region_count_mismatch() {
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default()]);
+        let default = rect(0.0, 0.0, 100.0, 100.0);
+        builder.add_variable_glyph(0, &default, vec![default.clone()]);
+        assert!(matches!(
+            builder.build(),
+            Err(CffError::RegionCountMismatch {
+                glyph: 0,
+                expected: 0,
+                found: 1
+            })
+        ));
+        builder.set_variation_store(variation_store());
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn variation_store_too_large() {
+        let mut store = variation_store();
+        store.item_variation_datas[0] = Some(ItemVariationData::new(
+            u16::MAX,
+            0,
+            vec![0],
+            vec![0; u16::MAX as usize],
+        ))
+        .into();
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default()]);
+        builder.set_variation_store(store);
+        assert!(matches!(
+            builder.build(),
+            Err(CffError::VariationStoreTooLarge)
+        ));
+    }
+
+    #[test]
+    fn non_finite_numbers() {
+        let private = PrivateDict {
+            blue_values: vec![-10.0, f64::NAN],
+            ..Default::default()
+        };
+        let builder = Cff2Builder::new(vec![private]);
+        assert!(matches!(builder.build(), Err(CffError::NonFiniteNumber)));
+
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default()]);
+        builder.set_font_matrix([0.001, 0.0, 0.0, f64::INFINITY, 0.0, 0.0]);
+        assert!(matches!(builder.build(), Err(CffError::NonFiniteNumber)));
+    }
+
+    #[test]
+    fn multiple_font_dicts() {
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default(); 2]);
+        for i in 0..10 {
+            builder.add_glyph(i / 5, &rect(0.0, 0.0, 100.0 + (i % 5) as f64, 700.0));
+        }
+        assert!(matches!(
+            builder.clone().add_glyph(2, &BezPath::new()).build(),
+            Err(CffError::InvalidFontDict {
+                glyph: 10,
+                font_dict: 2
+            })
+        ));
+        let cff2 = builder.subroutinize(true).build().unwrap();
+        let (top_dict, charstrings, fd_array) = parse(&cff2);
+        let fd_select_pos = get(&top_dict, op::FD_SELECT).unwrap()[0] as usize;
+        assert_eq!(
+            cff2.as_bytes()[fd_select_pos..fd_select_pos + 11],
+            [0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1]
+        );
+        assert_eq!(fd_array.len(), 2);
+        assert_eq!(charstrings.len(), 10);
+    }
+
+    #[test]
+    fn outlines_read_by_ttf_parser() {
+        let mut builder = Cff2Builder::new(vec![PrivateDict::default(); 2]);
+        builder.set_variation_store(variation_store());
+        let mut outlines = Vec::new();
+        for i in 0..10 {
+            let mut path = rect(0.0, 0.0, 400.0 + i as f64, 700.0);
+            path.move_to((100.0, 100.0));
+            path.curve_to((100.0, 200.0), (200.0, 300.0), (300.0, 300.0 - i as f64));
+            path.close_path();
+            outlines.push(path);
+        }
+        for (i, path) in outlines.iter().enumerate() {
+            builder.add_glyph(i % 2, path);
+        }
+        let default = outlines[0].clone();
+        let delta = kurbo::Affine::translate((50.0, 0.0)) * kurbo::Affine::scale(0.0) * &default;
+        builder.add_variable_glyph(0, &default, vec![delta]);
+
+        let cff2 = builder.subroutinize(true).build().unwrap();
+        outlines.push(default);
+        let expected = outlines.iter().map(svg).collect::<Vec<_>>();
+        assert_eq!(
+            ttf_parser_outlines(Cff2::TAG, cff2.as_bytes(), 11, Some(0.0)),
+            expected
+        );
+        let varied = ttf_parser_outlines(Cff2::TAG, cff2.as_bytes(), 11, Some(1.0));
+        assert_eq!(varied[..10], expected[..10]);
+        assert_eq!(
+            varied[10],
+            svg(&(kurbo::Affine::translate((50.0, 0.0)) * &outlines[0]))
+        );
+    }
+}
@@ -4,13 +4,41 @@
 
 include!("../../generated/generated_cmap.rs");
 
+use std::{
+    collections::{BTreeMap, HashSet},
+    ops::Range,
+};
+
 // https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#windows-platform-platform-id--3
 const WINDOWS_BMP_ENCODING: u16 = 1;
 
+// https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#windows-platform-platform-id--3
+const WINDOWS_FULL_REPERTOIRE_ENCODING: u16 = 10;
+
 // https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#unicode-platform-platform-id--0
 const UNICODE_BMP_ENCODING: u16 = 3;
 
-fn size_of_cmap4(seg_count: u16, gid_count: u16) -> u16 {
+// https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#unicode-platform-platform-id--0
+const UNICODE_FULL_REPERTOIRE_ENCODING: u16 = 4;
+
+// https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#unicode-platform-platform-id--0
+const UNICODE_VARIATION_SEQUENCES_ENCODING: u16 = 5;
+
+/// An error if the mappings in the Basic Multilingual Plane are too many or
+/// too scattered to fit in a format 4 subtable.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct Cmap4Overflow;
+
+impl std::fmt::Display for Cmap4Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The BMP mappings do not fit in a cmap format 4 subtable")
+    }
+}
+
+impl std::error::Error for Cmap4Overflow {}
+
+fn size_of_cmap4(seg_count: usize, gid_count: usize) -> usize {
     8 * 2  // 8 uint16's
     + 2 * seg_count * 4  // 4 parallel arrays of len seg_count, 2 bytes per entry
     + 2 * gid_count // 2 bytes per gid in glyphIdArray
@@ -22,28 +50,35 @@ impl CmapSubtable {
         end_code: Vec<u16>,
         start_code: Vec<u16>,
         id_deltas: Vec<i16>,
-    ) -> Self {
+        id_range_offsets: Vec<u16>,
+        glyph_id_array: Vec<u16>,
+    ) -> Result<Self, Cmap4Overflow> {
         assert!(
-            end_code.len() == start_code.len() && end_code.len() == id_deltas.len(),
+            end_code.len() == start_code.len()
+                && end_code.len() == id_deltas.len()
+                && end_code.len() == id_range_offsets.len(),
             "uneven parallel arrays, very bad. Very very bad."
         );
 
-        let seg_count: u16 = start_code.len().try_into().unwrap();
+        // the length is a u16, and everything else is smaller
+        let length: u16 = size_of_cmap4(start_code.len(), glyph_id_array.len())
+            .try_into()
+            .map_err(|_| Cmap4Overflow)?;
+        let seg_count = start_code.len() as u16;
         // Spec: Log2 of the maximum power of 2 less than or equal to segCount (log2(searchRange/2),
         // which is equal to floor(log2(segCount)))
         let entry_selector = (seg_count as f32).log2().floor();
 
         // Spec: Maximum power of 2 less than or equal to segCount, times 2
         // ((2**floor(log2(segCount))) * 2, where “**” is an exponentiation operator)
-        let search_range = 2u16.pow(entry_selector as u32).checked_mul(2).unwrap();
+        let search_range = 2u16.pow(entry_selector as u32) * 2;
 
         // if 2^entry_selector*2 is a u16 then so is entry_selector
         let entry_selector = entry_selector as u16;
         let range_shift = seg_count * 2 - search_range;
 
-        let id_range_offsets = vec![0; id_deltas.len()];
-        CmapSubtable::Format4(Cmap4::new(
-            size_of_cmap4(seg_count, 0),
+        Ok(CmapSubtable::Format4(Cmap4::new(
+            length,
             lang,
             seg_count * 2,
             search_range,
@@ -53,79 +88,361 @@ impl CmapSubtable {
             start_code,
             id_deltas,
             id_range_offsets,
-            vec![], // becauseour idRangeOffset's are 0 glyphIdArray is unused
-        ))
+            glyph_id_array,
+        )))
     }
-}
 
-impl Cmap {
-    /// Generates a [cmap](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap) that is expected to work in most modern environments.
+    /// Create a format 4 subtable from sorted mappings in the BMP.
     ///
-    /// For the time being just emits [format 4](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-4-segment-mapping-to-delta-values)
-    /// so we can drive towards compiling working fonts. In time we may wish to additionally emit format 12 to support
-    /// novel codepoints.
-    pub fn from_mappings(mappings: impl IntoIterator<Item = (char, GlyphId)>) -> Cmap {
-        let mut end_code = Vec::new();
-        let mut start_code = Vec::new();
-        let mut id_deltas = Vec::new();
-
-        let mut mappings: Vec<_> = mappings.into_iter().collect();
-        mappings.sort();
-
-        let mut prev = (u16::MAX - 1, u16::MAX - 1);
-        for (cp, gid) in mappings.into_iter() {
-            let gid = gid.to_u16();
-            let cp = (cp as u32).try_into().unwrap();
-            let next_in_run = (
-                prev.0.checked_add(1).unwrap(),
-                prev.1.checked_add(1).unwrap(),
-            );
-            let current = (cp, gid);
-            // Codepoint and gid need to be continuous
-            if current != next_in_run {
-                // Start a new run
-                start_code.push(cp);
-                end_code.push(cp);
+    /// Each run of consecutive codepoints is split into segments following
+    /// the approach of fontTools: sub-runs where the glyph ids are also
+    /// consecutive get their own segment (using idDelta) if they are long
+    /// enough to be worth it, and everything else is stored in the
+    /// glyphIdArray.
+    ///
+    /// Returns an error if the subtable would be larger than 64KiB.
+    fn create_format_4_from_mappings(
+        mappings: impl IntoIterator<Item = (char, GlyphId)>,
+    ) -> Result<Self, Cmap4Overflow> {
+        let mappings = mappings
+            .into_iter()
+            .map(|(cp, gid)| ((cp as u32).try_into().unwrap(), gid.to_u16()))
+            .collect::<Vec<(u16, u16)>>();
+
+        let mut segments = Vec::new();
+        let mut run_start = 0;
+        for i in 1..=mappings.len() {
+            if i == mappings.len() || mappings[i - 1].0 + 1 != mappings[i].0 {
+                let run = &mappings[run_start..i];
+                segments.extend(split_range(run).into_iter().map(|range| &run[range]));
+                run_start = i;
+            }
+        }
 
+        // the final segment, mapping 0xFFFF to glyph 0
+        let seg_count = segments.len() + 1;
+        let mut end_code = Vec::with_capacity(seg_count);
+        let mut start_code = Vec::with_capacity(seg_count);
+        let mut id_deltas = Vec::with_capacity(seg_count);
+        let mut id_range_offsets = Vec::with_capacity(seg_count);
+        let mut glyph_id_array = Vec::new();
+        for (i, segment) in segments.into_iter().enumerate() {
+            let (start, first_gid) = segment[0];
+            start_code.push(start);
+            end_code.push(segment[segment.len() - 1].0);
+            if is_consecutive(segment) {
                 // TIL Python % 0x10000 and Rust % 0x10000 do not mean the same thing.
                 // rem_euclid is almost what we want, except as applied to small values
                 // ex -10 rem_euclid 0x10000 = 65526
-                let delta: i32 = gid as i32 - cp as i32;
+                let delta: i32 = first_gid as i32 - start as i32;
                 let delta = if let Ok(delta) = TryInto::<i16>::try_into(delta) {
                     delta
                 } else {
-                    delta.rem_euclid(0x10000).try_into().unwrap()
+                    delta.rem_euclid(0x10000) as u16 as i16
                 };
                 id_deltas.push(delta);
+                id_range_offsets.push(0);
             } else {
-                // Continue the prior run
-                let last = end_code.last_mut().unwrap();
-                *last = cp;
+                // the offset is relative to this entry in idRangeOffsets
+                let offset = 2 * (seg_count - i + glyph_id_array.len());
+                id_deltas.push(0);
+                id_range_offsets.push(offset.try_into().map_err(|_| Cmap4Overflow)?);
+                glyph_id_array.extend(segment.iter().map(|(_, gid)| *gid));
             }
-            prev = current;
         }
 
         // close out
         start_code.push(0xFFFF);
         end_code.push(0xFFFF);
         id_deltas.push(1);
+        id_range_offsets.push(0);
+
+        CmapSubtable::create_format_4(
+            0, // set to zero for all 'cmap' subtables whose platform IDs are other than Macintosh
+            end_code,
+            start_code,
+            id_deltas,
+            id_range_offsets,
+            glyph_id_array,
+        )
+    }
+
+    fn create_format_12(mappings: &[(char, GlyphId)]) -> Self {
+        let mut groups: Vec<SequentialMapGroup> = Vec::new();
+        for (cp, gid) in mappings {
+            let (cp, gid) = (*cp as u32, gid.to_u16() as u32);
+            match groups.last_mut() {
+                // Codepoint and gid need to be continuous
+                Some(group)
+                    if group.end_char_code + 1 == cp
+                        && group.start_glyph_id + (cp - group.start_char_code) == gid =>
+                {
+                    group.end_char_code = cp;
+                }
+                _ => groups.push(SequentialMapGroup::new(cp, cp, gid)),
+            }
+        }
+        let num_groups: u32 = groups.len().try_into().unwrap();
+        CmapSubtable::Format12(Cmap12::new(
+            // 2 u16s and 3 u32s, then 3 u32s per group
+            16 + 12 * num_groups,
+            0, // set to zero for all 'cmap' subtables whose platform IDs are other than Macintosh
+            num_groups,
+            groups,
+        ))
+    }
+
+    /// Create a format 14 subtable from sorted (base, glyph) pairs for each
+    /// selector, where a glyph of `None` means the base character's default glyph.
+    fn create_format_14(sequences: BTreeMap<char, Vec<(char, Option<GlyphId>)>>) -> Self {
+        let mut records = Vec::new();
+        // the length includes the UVS tables, which are written immediately
+        // after the subtable
+        let mut length = 10;
+        let mut seen_default = HashSet::new();
+        let mut seen_non_default = HashSet::new();
+        for (selector, group) in sequences {
+            let selector = Uint24::new(selector as u32);
+            let mut ranges: Vec<UnicodeRange> = Vec::new();
+            let mut uvs_mappings = Vec::new();
+            for (base, glyph) in group {
+                let base = base as u32;
+                match glyph {
+                    Some(gid) => {
+                        uvs_mappings.push(UvsMapping::new(Uint24::new(base), gid.to_u16()))
+                    }
+                    None => match ranges.last_mut() {
+                        Some(range)
+                            if range.additional_count < u8::MAX
+                                && u32::from(range.start_unicode_value)
+                                    + range.additional_count as u32
+                                    + 1
+                                    == base =>
+                        {
+                            range.additional_count += 1;
+                        }
+                        _ => ranges.push(UnicodeRange::new(Uint24::new(base), 0)),
+                    },
+                }
+            }
+            // a 3-byte selector and two 4-byte offsets
+            length += 11;
+            // identical UVS tables are shared when the subtable is written
+            let default_uvs = (!ranges.is_empty()).then(|| {
+                let key = ranges
+                    .iter()
+                    .map(|range| (u32::from(range.start_unicode_value), range.additional_count));
+                if seen_default.insert(key.collect::<Vec<_>>()) {
+                    length += 4 + 4 * ranges.len() as u32;
+                }
+                DefaultUvs::new(ranges.len() as u32, ranges)
+            });
+            let non_default_uvs = (!uvs_mappings.is_empty()).then(|| {
+                let key = uvs_mappings
+                    .iter()
+                    .map(|mapping| (u32::from(mapping.unicode_value), mapping.glyph_id));
+                if seen_non_default.insert(key.collect::<Vec<_>>()) {
+                    length += 4 + 5 * uvs_mappings.len() as u32;
+                }
+                NonDefaultUvs::new(uvs_mappings.len() as u32, uvs_mappings)
+            });
+            records.push(VariationSelector::new(
+                selector,
+                default_uvs,
+                non_default_uvs,
+            ));
+        }
+        CmapSubtable::Format14(Cmap14::new(length, records.len() as u32, records))
+    }
+}
+
+/// Whether the glyph ids of a run of mappings are consecutive.
+fn is_consecutive(mappings: &[(u16, u16)]) -> bool {
+    mappings
+        .windows(2)
+        .all(|pair| pair[0].1.checked_add(1) == Some(pair[1].1))
+}
+
+/// Split a run of mappings with consecutive codepoints into format 4 segments.
+///
+/// A segment using idDelta costs 8 bytes, while storing a mapping in the
+/// glyphIdArray costs 2 bytes. Sub-runs with consecutive glyph ids are
+/// therefore split out when that saves space: if they are at one end of the
+/// run this adds one segment, and if they are in the middle it adds two.
+///
+/// This is a port of `splitRange` in fontTools' cmap format 4 compiler.
+fn split_range(run: &[(u16, u16)]) -> Vec<Range<usize>> {
+    let len = run.len();
+    let mut ordered = Vec::new();
+    let mut ordered_start = None;
+    for i in 1..len {
+        if run[i - 1].1.checked_add(1) == Some(run[i].1) {
+            ordered_start.get_or_insert(i - 1);
+        } else if let Some(start) = ordered_start.take() {
+            ordered.push(start..i);
+        }
+    }
+    if let Some(start) = ordered_start {
+        ordered.push(start..len);
+    }
+    // the whole run is ordered
+    if ordered.first() == Some(&(0..len)) {
+        return ordered;
+    }
+
+    ordered.retain(|range| {
+        let threshold = if range.start == 0 || range.end == len {
+            4
+        } else {
+            8
+        };
+        range.len() > threshold
+    });
+
+    // fill in the gaps between the ordered sub-runs
+    let mut segments = Vec::new();
+    let mut pos = 0;
+    for range in ordered {
+        if range.start > pos {
+            segments.push(pos..range.start);
+        }
+        pos = range.end;
+        segments.push(range);
+    }
+    if pos < len {
+        segments.push(pos..len);
+    }
+    segments
+}
+
+impl Cmap {
+    /// Generates a [cmap](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap) that is expected to work in most modern environments.
+    ///
+    /// This always emits a [format 4](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-4-segment-mapping-to-delta-values)
+    /// subtable for the Basic Multilingual Plane. If there are any mappings
+    /// outside of the BMP, a [format 12](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-12-segmented-coverage)
+    /// subtable containing all of the mappings is also emitted.
+    ///
+    /// U+FFFF is reserved for the final format 4 segment, so a mapping for it
+    /// is only stored in the format 12 subtable.
+    ///
+    /// If the mappings in the BMP don't fit in a format 4 subtable, which is
+    /// limited to 64KiB, only the format 12 subtable is emitted. Use
+    /// [`Cmap::try_from_mappings`] to treat this as an error instead.
+    pub fn from_mappings(mappings: impl IntoIterator<Item = (char, GlyphId)>) -> Cmap {
+        Cmap::from_mappings_and_uvs(mappings, [])
+    }
+
+    /// Generates a cmap as with [`Cmap::from_mappings`], as well as a
+    /// [format 14](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-14-unicode-variation-sequences)
+    /// subtable for the provided Unicode Variation Sequences.
+    ///
+    /// Each sequence is a (base character, variation selector, glyph) triple.
+    /// Sequences that map to the same glyph as the base character alone are
+    /// stored as default UVSes.
+    pub fn from_mappings_and_uvs(
+        mappings: impl IntoIterator<Item = (char, GlyphId)>,
+        uvs: impl IntoIterator<Item = (char, char, GlyphId)>,
+    ) -> Cmap {
+        match Cmap::build(mappings, uvs, true) {
+            Ok(cmap) => cmap,
+            Err(_) => unreachable!("format 4 overflow falls back to format 12"),
+        }
+    }
+
+    /// Generates a cmap as with [`Cmap::from_mappings`], returning an error
+    /// if the mappings in the BMP don't fit in a format 4 subtable.
+    pub fn try_from_mappings(
+        mappings: impl IntoIterator<Item = (char, GlyphId)>,
+    ) -> Result<Cmap, Cmap4Overflow> {
+        Cmap::try_from_mappings_and_uvs(mappings, [])
+    }
+
+    /// Generates a cmap as with [`Cmap::from_mappings_and_uvs`], returning an
+    /// error if the mappings in the BMP don't fit in a format 4 subtable.
+    pub fn try_from_mappings_and_uvs(
+        mappings: impl IntoIterator<Item = (char, GlyphId)>,
+        uvs: impl IntoIterator<Item = (char, char, GlyphId)>,
+    ) -> Result<Cmap, Cmap4Overflow> {
+        Cmap::build(mappings, uvs, false)
+    }
+
+    fn build(
+        mappings: impl IntoIterator<Item = (char, GlyphId)>,
+        uvs: impl IntoIterator<Item = (char, char, GlyphId)>,
+        fallback_to_format_12: bool,
+    ) -> Result<Cmap, Cmap4Overflow> {
+        let mut mappings: Vec<_> = mappings.into_iter().collect();
+        mappings.sort();
+        mappings.dedup();
+
+        // U+FFFF can't be mapped in format 4, it's taken by the final segment
+        let bmp_mappings = mappings.iter().filter(|(cp, _)| (*cp as u32) < 0xFFFF);
+        let cmap4 = match CmapSubtable::create_format_4_from_mappings(bmp_mappings.copied()) {
+            Ok(cmap4) => Some(cmap4),
+            Err(_) if fallback_to_format_12 => None,
+            Err(e) => return Err(e),
+        };
+        let cmap12 = mappings
+            .last()
+            .filter(|(cp, _)| cmap4.is_none() || (*cp as u32) >= 0xFFFF)
+            .map(|_| CmapSubtable::create_format_12(&mappings));
+
+        let mut sequences = BTreeMap::<_, Vec<_>>::new();
+        for (base, selector, gid) in uvs {
+            let default_gid = mappings
+                .binary_search_by_key(&base, |(cp, _)| *cp)
+                .ok()
+                .map(|idx| mappings[idx].1);
+            let gid = (default_gid != Some(gid)).then_some(gid);
+            sequences.entry(selector).or_default().push((base, gid));
+        }
+        for group in sequences.values_mut() {
+            group.sort();
+            group.dedup();
+        }
+        let cmap14 = (!sequences.is_empty()).then(|| CmapSubtable::create_format_14(sequences));
 
         // Absent a strong signal to do otherwise, match fontmake/fonttools
         // Since both tables use the same format4 subtable they are almost entirely byte-shared
         // See https://github.com/googlefonts/fontmake-rs/issues/251
-        let win_bmp = EncodingRecord::new(
-            PlatformId::Windows,
-            WINDOWS_BMP_ENCODING,
-            CmapSubtable::create_format_4(
-                0, // set to zero for all 'cmap' subtables whose platform IDs are other than Macintosh
-                end_code, start_code, id_deltas,
-            ),
-        );
-        let mut unicode = win_bmp.clone();
-        unicode.platform_id = PlatformId::Unicode;
-        unicode.encoding_id = UNICODE_BMP_ENCODING;
-
-        Cmap::new(vec![unicode, win_bmp])
+        let mut records = Vec::new();
+        if let Some(cmap4) = &cmap4 {
+            records.push(EncodingRecord::new(
+                PlatformId::Unicode,
+                UNICODE_BMP_ENCODING,
+                cmap4.clone(),
+            ));
+        }
+        if let Some(cmap12) = &cmap12 {
+            records.push(EncodingRecord::new(
+                PlatformId::Unicode,
+                UNICODE_FULL_REPERTOIRE_ENCODING,
+                cmap12.clone(),
+            ));
+        }
+        if let Some(cmap14) = cmap14 {
+            records.push(EncodingRecord::new(
+                PlatformId::Unicode,
+                UNICODE_VARIATION_SEQUENCES_ENCODING,
+                cmap14,
+            ));
+        }
+        if let Some(cmap4) = cmap4 {
+            records.push(EncodingRecord::new(
+                PlatformId::Windows,
+                WINDOWS_BMP_ENCODING,
+                cmap4,
+            ));
+        }
+        if let Some(cmap12) = cmap12 {
+            records.push(EncodingRecord::new(
+                PlatformId::Windows,
+                WINDOWS_FULL_REPERTOIRE_ENCODING,
+                cmap12,
+            ));
+        }
+        Ok(Cmap::new(records))
     }
 }
 
@@ -133,13 +450,17 @@ impl Cmap {
 mod tests {
     use font_types::GlyphId;
     use read::{
-        tables::cmap::{Cmap, CmapSubtable, PlatformId},
+        tables::cmap::{Cmap, CmapSubtable, MapVariant, PlatformId},
         FontData, FontRead,
     };
 
     use crate::{
         dump_table,
-        tables::cmap::{self as write, UNICODE_BMP_ENCODING, WINDOWS_BMP_ENCODING},
+        tables::cmap::{
+            self as write, UNICODE_BMP_ENCODING, UNICODE_FULL_REPERTOIRE_ENCODING,
+            UNICODE_VARIATION_SEQUENCES_ENCODING, WINDOWS_BMP_ENCODING,
+            WINDOWS_FULL_REPERTOIRE_ENCODING,
+        },
     };
 
     fn assert_generates_simple_cmap(mappings: Vec<(char, GlyphId)>) {
@@ -249,4 +570,347 @@ mod tests {
             assert_eq!(bytes_for_one + 8, bytes_for_both);
         }
     }
+
+    fn encodings(cmap: &Cmap) -> Vec<(PlatformId, u16)> {
+        cmap.encoding_records()
+            .iter()
+            .map(|er| (er.platform_id(), er.encoding_id()))
+            .collect()
+    }
+
+    #[test]
+    fn generate_cmap12_for_supplementary_planes() {
+        // iterating a subtable skips mappings to glyph 0
+        let mut mappings = simple_cmap_mappings()
+            .into_iter()
+            .map(|(cp, gid)| (cp, GlyphId::new(gid.to_u16() + 1)))
+            .collect::<Vec<_>>();
+        let emoji = [0x1F600, 0x1F601, 0x1F602, 0x1F680];
+        for (i, cp) in emoji.into_iter().enumerate() {
+            mappings.push((char::from_u32(cp).unwrap(), GlyphId::new(500 + i as u16)));
+        }
+        let cmap = write::Cmap::from_mappings(mappings.clone());
+
+        let bytes = dump_table(&cmap).unwrap();
+        let font_data = FontData::new(&bytes);
+        let cmap = Cmap::read(font_data).unwrap();
+        assert_eq!(
+            encodings(&cmap),
+            [
+                (PlatformId::Unicode, UNICODE_BMP_ENCODING),
+                (PlatformId::Unicode, UNICODE_FULL_REPERTOIRE_ENCODING),
+                (PlatformId::Windows, WINDOWS_BMP_ENCODING),
+                (PlatformId::Windows, WINDOWS_FULL_REPERTOIRE_ENCODING),
+            ]
+        );
+
+        let records = cmap.encoding_records();
+        let CmapSubtable::Format4(cmap4) = records[0].subtable(font_data).unwrap() else {
+            panic!("expected a cmap4 in {:?}", records[0]);
+        };
+        let CmapSubtable::Format12(cmap12) = records[1].subtable(font_data).unwrap() else {
+            panic!("expected a cmap12 in {:?}", records[1]);
+        };
+        // the format 4 subtable only covers the BMP
+        assert_eq!(cmap4.iter().count(), mappings.len() - emoji.len());
+        // the format 12 subtable covers everything
+        assert_eq!(
+            cmap12.iter().collect::<Vec<_>>(),
+            mappings
+                .iter()
+                .map(|(cp, gid)| (*cp as u32, *gid))
+                .collect::<Vec<_>>()
+        );
+        // three runs in the BMP, then one for the first three emoji
+        assert_eq!(cmap12.num_groups(), 5);
+        for (cp, gid) in mappings {
+            assert_eq!(cmap.map_codepoint(cp), Some(gid));
+        }
+        // both encodings share the same subtables
+        assert_eq!(records[0].subtable_offset(), records[2].subtable_offset());
+        assert_eq!(records[1].subtable_offset(), records[3].subtable_offset());
+    }
+
+    #[test]
+    fn generate_cmap14() {
+        let mappings = [
+            ('\u{82a6}', GlyphId::new(1)),
+            ('\u{845b}', GlyphId::new(2)),
+            ('\u{8fbb}', GlyphId::new(3)),
+            ('\u{8fbc}', GlyphId::new(4)),
+        ];
+        let uvs = [
+            // default
+            ('\u{82a6}', '\u{e0100}', GlyphId::new(1)),
+            ('\u{8fbb}', '\u{e0100}', GlyphId::new(3)),
+            ('\u{8fbc}', '\u{e0100}', GlyphId::new(4)),
+            // non-default
+            ('\u{82a6}', '\u{e0101}', GlyphId::new(5)),
+            ('\u{845b}', '\u{e0101}', GlyphId::new(6)),
+            // the base character isn't mapped
+            ('\u{8fbd}', '\u{e0101}', GlyphId::new(7)),
+        ];
+        let cmap = write::Cmap::from_mappings_and_uvs(mappings, uvs);
+
+        let bytes = dump_table(&cmap).unwrap();
+        let font_data = FontData::new(&bytes);
+        let cmap = Cmap::read(font_data).unwrap();
+        assert_eq!(
+            encodings(&cmap),
+            [
+                (PlatformId::Unicode, UNICODE_BMP_ENCODING),
+                (PlatformId::Unicode, UNICODE_VARIATION_SEQUENCES_ENCODING),
+                (PlatformId::Windows, WINDOWS_BMP_ENCODING),
+            ]
+        );
+        let record = &cmap.encoding_records()[1];
+        let CmapSubtable::Format14(cmap14) = record.subtable(font_data).unwrap() else {
+            panic!("expected a cmap14 in {record:?}");
+        };
+        assert_eq!(cmap14.num_var_selector_records(), 2);
+        let selectors = cmap14.var_selector();
+        // two consecutive default UVSes share a range
+        let default_uvs = selectors[0]
+            .default_uvs(cmap14.offset_data())
+            .unwrap()
+            .unwrap();
+        assert_eq!(default_uvs.num_unicode_value_ranges(), 2);
+        assert!(selectors[0].non_default_uvs(cmap14.offset_data()).is_none());
+        assert!(selectors[1].default_uvs(cmap14.offset_data()).is_none());
+
+        for (base, selector, gid) in uvs {
+            let expected = if mappings.contains(&(base, gid)) {
+                MapVariant::UseDefault
+            } else {
+                MapVariant::Variant(gid)
+            };
+            assert_eq!(cmap14.map_variant(base, selector), Some(expected));
+        }
+        assert_eq!(cmap14.map_variant('\u{845b}', '\u{e0100}'), None);
+
+        // the UVS tables are within the bounds of the subtable
+        let start = record.subtable_offset().to_u32();
+        let end = start + cmap14.length();
+        assert!(end as usize <= bytes.len());
+        for selector in selectors {
+            for offset in [
+                selector.default_uvs_offset().offset().to_u32(),
+                selector.non_default_uvs_offset().offset().to_u32(),
+            ] {
+                assert!(start + offset < end);
+            }
+        }
+    }
+
+    #[test]
+    fn identical_uvs_tables_are_shared() {
+        let mappings = [('a', GlyphId::new(1))];
+        let uvs = [
+            ('a', '\u{fe00}', GlyphId::new(1)),
+            ('a', '\u{fe01}', GlyphId::new(1)),
+        ];
+        let cmap = write::Cmap::from_mappings_and_uvs(mappings, uvs);
+        let bytes = dump_table(&cmap).unwrap();
+        let cmap = Cmap::read(FontData::new(&bytes)).unwrap();
+        let record = &cmap.encoding_records()[1];
+        let CmapSubtable::Format14(cmap14) = record.subtable(cmap.offset_data()).unwrap() else {
+            panic!("expected a cmap14 in {record:?}");
+        };
+        let selectors = cmap14.var_selector();
+        assert_eq!(
+            selectors[0].default_uvs_offset().offset(),
+            selectors[1].default_uvs_offset().offset()
+        );
+        // header, two selector records and one default UVS table
+        assert_eq!(cmap14.length(), 10 + 2 * 11 + 8);
+        assert_eq!(
+            record.subtable_offset().to_u32() + cmap14.length(),
+            bytes.len() as u32
+        );
+    }
+
+    fn compile_cmap4(mappings: &[(char, GlyphId)]) -> Vec<u8> {
+        let cmap = write::Cmap::from_mappings(mappings.iter().copied());
+        let bytes = dump_table(&cmap).unwrap();
+        let cmap = Cmap::read(FontData::new(&bytes)).unwrap();
+        for (cp, gid) in mappings {
+            assert_eq!(cmap.map_codepoint(*cp), Some(*gid), "{cp:?}");
+        }
+        bytes
+    }
+
+    fn consecutive_codepoints(first: u32, gids: &[u16]) -> Vec<(char, GlyphId)> {
+        gids.iter()
+            .enumerate()
+            .map(|(i, gid)| {
+                (
+                    char::from_u32(first + i as u32).unwrap(),
+                    GlyphId::new(*gid),
+                )
+            })
+            .collect()
+    }
+
+    fn read_cmap4(bytes: &[u8]) -> read::tables::cmap::Cmap4<'_> {
+        let cmap = Cmap::read(FontData::new(bytes)).unwrap();
+        match cmap.encoding_records()[0].subtable(cmap.offset_data()) {
+            Ok(CmapSubtable::Format4(cmap4)) => cmap4,
+            _ => panic!("expected a cmap4"),
+        }
+    }
+
+    #[test]
+    fn cmap4_scattered_glyph_ids() {
+        // consecutive codepoints with glyph ids in no particular order use a
+        // single segment with the glyphIdArray
+        let gids = [5u16, 3, 9, 1, 7, 2, 8];
+        let mappings = consecutive_codepoints(0x41, &gids);
+        let bytes = compile_cmap4(&mappings);
+        let cmap4 = read_cmap4(&bytes);
+        assert_eq!(cmap4.start_code(), &[0x41, 0xFFFF]);
+        assert_eq!(cmap4.end_code(), &[0x47, 0xFFFF]);
+        assert_eq!(cmap4.id_delta(), &[0, 1]);
+        assert_eq!(cmap4.id_range_offsets(), &[4, 0]);
+        assert_eq!(cmap4.glyph_id_array(), &gids);
+        assert_eq!(cmap4.length() as usize, 16 + 2 * 4 * 2 + 2 * gids.len());
+    }
+
+    #[test]
+    fn cmap4_splits_ordered_runs() {
+        // 10 scattered glyphs, 20 in order, 3 scattered, 5 in order
+        let gids = [50u16, 40, 60, 45, 55, 41, 61, 49, 59, 42]
+            .into_iter()
+            .chain(100..120)
+            .chain([70, 80, 75])
+            .chain(200..205)
+            .collect::<Vec<_>>();
+        let mappings = gids
+            .iter()
+            .enumerate()
+            .map(|(i, gid)| {
+                (
+                    char::from_u32(0x100 + i as u32).unwrap(),
+                    GlyphId::new(*gid),
+                )
+            })
+            .collect::<Vec<_>>();
+        let bytes = compile_cmap4(&mappings);
+        let cmap4 = read_cmap4(&bytes);
+        // the long ordered run in the middle gets its own segment, as does
+        // the one at the end; the short run of 3 is too short to be worth it
+        assert_eq!(cmap4.start_code(), &[0x100, 0x10A, 0x11E, 0x121, 0xFFFF]);
+        assert_eq!(cmap4.end_code(), &[0x109, 0x11D, 0x120, 0x125, 0xFFFF]);
+        assert_eq!(cmap4.id_range_offsets()[1], 0);
+        assert_eq!(cmap4.id_range_offsets()[3], 0);
+        assert_eq!(cmap4.glyph_id_array().len(), 13);
+    }
+
+    #[test]
+    fn cmap4_short_ordered_runs_are_not_split() {
+        // an ordered run at the end must be longer than 4 to be split out
+        let bytes = compile_cmap4(&consecutive_codepoints(0x41, &[9, 3, 4, 5, 6]));
+        let cmap4 = read_cmap4(&bytes);
+        assert_eq!(cmap4.start_code(), &[0x41, 0xFFFF]);
+        assert_eq!(cmap4.glyph_id_array().len(), 5);
+
+        let bytes = compile_cmap4(&consecutive_codepoints(0x41, &[9, 3, 4, 5, 6, 7]));
+        let cmap4 = read_cmap4(&bytes);
+        assert_eq!(cmap4.start_code(), &[0x41, 0x42, 0xFFFF]);
+        // a single glyph is always delta-coded
+        assert_eq!(cmap4.id_delta(), &[9 - 0x41, 3 - 0x42, 1]);
+        assert!(cmap4.glyph_id_array().is_empty());
+    }
+
+    #[test]
+    fn cmap4_large_deltas() {
+        let mappings = [
+            ('\u{1}', GlyphId::new(40000)),
+            ('\u{2}', GlyphId::new(40001)),
+            ('\u{fff0}', GlyphId::new(1)),
+        ];
+        compile_cmap4(&mappings);
+    }
+
+    fn cjk_mappings(count: u16, reversed: bool) -> Vec<(char, GlyphId)> {
+        let gids = (1..=count)
+            .map(|gid| if reversed { count + 1 - gid } else { gid })
+            .collect::<Vec<_>>();
+        consecutive_codepoints(0x3400, &gids)
+    }
+
+    #[test]
+    fn cmap4_many_ordered_mappings() {
+        let bytes = compile_cmap4(&cjk_mappings(38_000, false));
+        let cmap4 = read_cmap4(&bytes);
+        assert_eq!(cmap4.start_code(), &[0x3400, 0xFFFF]);
+    }
+
+    #[test]
+    fn cmap4_many_unordered_mappings() {
+        let bytes = compile_cmap4(&cjk_mappings(20_000, true));
+        let cmap4 = read_cmap4(&bytes);
+        assert_eq!(cmap4.glyph_id_array().len(), 20_000);
+    }
+
+    #[test]
+    fn cmap4_overflow() {
+        let mappings = cjk_mappings(38_000, true);
+        assert!(write::Cmap::try_from_mappings(mappings).is_err());
+    }
+
+    #[test]
+    fn cmap4_overflow_falls_back_to_cmap12() {
+        let mappings = cjk_mappings(38_000, true);
+        let cmap = write::Cmap::from_mappings(mappings.clone());
+        let bytes = dump_table(&cmap).unwrap();
+        let cmap = Cmap::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(
+            encodings(&cmap),
+            [
+                (PlatformId::Unicode, UNICODE_FULL_REPERTOIRE_ENCODING),
+                (PlatformId::Windows, WINDOWS_FULL_REPERTOIRE_ENCODING),
+            ]
+        );
+        for (cp, gid) in mappings {
+            assert_eq!(cmap.map_codepoint(cp), Some(gid), "{cp:?}");
+        }
+    }
+
+    #[test]
+    fn cmap4_excludes_ffff() {
+        let mut mappings = simple_cmap_mappings();
+        mappings.push(('\u{ffff}', GlyphId::new(7)));
+        let cmap = write::Cmap::from_mappings(mappings.clone());
+        let bytes = dump_table(&cmap).unwrap();
+        let cmap4 = read_cmap4(&bytes);
+        // only the final segment covers U+FFFF, and it maps to glyph 0
+        assert_eq!(
+            cmap4
+                .end_code()
+                .iter()
+                .filter(|c| c.get() == 0xFFFF)
+                .count(),
+            1
+        );
+        assert_eq!(cmap4.map_codepoint('\u{ffff}'), Some(GlyphId::NOTDEF));
+
+        // the mapping is kept in a format 12 subtable
+        let cmap = Cmap::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(
+            encodings(&cmap),
+            [
+                (PlatformId::Unicode, UNICODE_BMP_ENCODING),
+                (PlatformId::Unicode, UNICODE_FULL_REPERTOIRE_ENCODING),
+                (PlatformId::Windows, WINDOWS_BMP_ENCODING),
+                (PlatformId::Windows, WINDOWS_FULL_REPERTOIRE_ENCODING),
+            ]
+        );
+        let cmap12 = match cmap.encoding_records()[1].subtable(cmap.offset_data()) {
+            Ok(CmapSubtable::Format12(cmap12)) => cmap12,
+            _ => panic!("expected a cmap12"),
+        };
+        for (cp, gid) in mappings {
+            assert_eq!(cmap12.map_codepoint(cp), Some(gid), "{cp:?}");
+        }
+    }
 }
@@ -0,0 +1,506 @@
+//! The [COLR](https://docs.microsoft.com/en-us/typography/opentype/spec/colr) table
+
+include!("../../generated/generated_colr.rs");
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::variations::{DeltaSetIndexMap, ItemVariationStore};
+
+/// The maximum number of layers a single `PaintColrLayers` can refer to.
+const MAX_PAINT_COLR_LAYERS: usize = u8::MAX as usize;
+
+impl Colr {
+    fn compute_version(&self) -> u16 {
+        let is_v1 = self.base_glyph_list.is_some()
+            || self.layer_list.is_some()
+            || self.clip_list.is_some()
+            || self.var_index_map.is_some()
+            || self.item_variation_store.is_some();
+        is_v1 as u16
+    }
+}
+
+// paints that contain other paints box them, to break the cycle
+impl<'a> FromObjRef<read_fonts::tables::colr::Paint<'a>> for Box<Paint> {
+    fn from_obj_ref(obj: &read_fonts::tables::colr::Paint<'a>, data: FontData) -> Self {
+        Box::new(Paint::from_obj_ref(obj, data))
+    }
+}
+
+impl<'a> FromTableRef<read_fonts::tables::colr::Paint<'a>> for Box<Paint> {}
+
+/// An error if the COLRv0 layer records don't fit in the table.
+///
+/// The number of layer records, and so the index of each base glyph's first
+/// layer, must fit in a `u16`.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct TooManyLayers;
+
+impl std::fmt::Display for TooManyLayers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "More than {} COLRv0 layer records", u16::MAX)
+    }
+}
+
+impl std::error::Error for TooManyLayers {}
+
+/// A builder for the [`Colr`] table.
+///
+/// Glyphs can be added in any order; the records in the final table are
+/// sorted by glyph id.
+///
+/// COLRv1 layers are added with [`add_layers`](Self::add_layers), which
+/// returns the paint that refers to them. Identical runs of layers are only
+/// stored once in the [`LayerList`], and glyphs that share a clip box share
+/// a single [`ClipBox`] subtable.
+///
+/// ```
+/// # use write_fonts::{tables::colr::{ColrBuilder, Paint}, types::{F2Dot14, GlyphId}};
+/// let mut builder = ColrBuilder::new();
+/// let red = Paint::glyph(Paint::solid(0, F2Dot14::ONE), GlyphId::new(2));
+/// let blue = Paint::glyph(Paint::solid(1, F2Dot14::ONE), GlyphId::new(3));
+/// let layers = builder.add_layers(vec![red, blue]);
+/// builder.add_v1_glyph(GlyphId::new(1), layers);
+/// let colr = builder.build().unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ColrBuilder {
+    v0_glyphs: BTreeMap<GlyphId, Vec<Layer>>,
+    v1_glyphs: BTreeMap<GlyphId, Paint>,
+    layers: LayerListBuilder,
+    clips: BTreeMap<GlyphId, ClipBox>,
+    var_index_map: Option<DeltaSetIndexMap>,
+    item_variation_store: Option<ItemVariationStore>,
+}
+
+/// Accumulates the paints of the [`LayerList`], reusing existing layers
+/// where possible.
+#[derive(Clone, Debug, Default)]
+struct LayerListBuilder {
+    layers: Vec<Paint>,
+    // each distinct paint is assigned an id, so that runs of layers can be
+    // looked up without cloning the paints
+    paint_ids: HashMap<Paint, u32>,
+    // every run of two or more layers in the list, and the index of its
+    // first layer
+    runs: HashMap<Vec<u32>, u32>,
+}
+
+impl ColrBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a COLRv0 glyph, made of the given layers from bottom to top.
+    pub fn add_v0_glyph(
+        &mut self,
+        glyph_id: GlyphId,
+        layers: impl IntoIterator<Item = Layer>,
+    ) -> &mut Self {
+        self.v0_glyphs
+            .insert(glyph_id, layers.into_iter().collect());
+        self
+    }
+
+    /// Add a COLRv1 glyph, drawn with the given paint.
+    pub fn add_v1_glyph(&mut self, glyph_id: GlyphId, paint: Paint) -> &mut Self {
+        self.v1_glyphs.insert(glyph_id, paint);
+        self
+    }
+
+    /// Add a list of COLRv1 layers, from bottom to top, to the layer list.
+    ///
+    /// Returns the paint that draws these layers; this is generally a
+    /// [`PaintColrLayers`], but a single layer is returned unchanged.
+    ///
+    /// Runs of layers that have already been added are reused rather than
+    /// added again, and lists too long for a single `PaintColrLayers` are
+    /// split into nested ones.
+    pub fn add_layers(&mut self, layers: Vec<Paint>) -> Paint {
+        self.layers.add(layers)
+    }
+
+    /// Set the clip box of a COLRv1 glyph.
+    pub fn add_clip_box(&mut self, glyph_id: GlyphId, clip_box: ClipBox) -> &mut Self {
+        self.clips.insert(glyph_id, clip_box);
+        self
+    }
+
+    /// Set the variation data used by variable paints.
+    ///
+    /// The `var_index_base` of each variable paint is mapped to a delta set
+    /// in the `item_variation_store` by the `var_index_map`, if present;
+    /// otherwise it is used directly as the delta set index.
+    pub fn set_variations(
+        &mut self,
+        var_index_map: Option<DeltaSetIndexMap>,
+        item_variation_store: ItemVariationStore,
+    ) -> &mut Self {
+        self.var_index_map = var_index_map;
+        self.item_variation_store = Some(item_variation_store);
+        self
+    }
+
+    /// Finish building, returning the `COLR` table.
+    ///
+    /// The table is version 0 unless any COLRv1 data has been added.
+    ///
+    /// Returns an error if the COLRv0 glyphs have more than 65535 layers
+    /// in total.
+    pub fn build(self) -> Result<Colr, TooManyLayers> {
+        let mut base_glyph_records = Vec::with_capacity(self.v0_glyphs.len());
+        let mut layer_records = Vec::new();
+        for (glyph_id, layers) in self.v0_glyphs {
+            let first_layer_index = layer_records.len();
+            layer_records.extend(layers);
+            if layer_records.len() > u16::MAX as usize {
+                return Err(TooManyLayers);
+            }
+            // both fit, since neither is larger than the total
+            base_glyph_records.push(BaseGlyph::new(
+                glyph_id,
+                first_layer_index as u16,
+                (layer_records.len() - first_layer_index) as u16,
+            ));
+        }
+
+        let base_glyph_list = (!self.v1_glyphs.is_empty()).then(|| {
+            BaseGlyphList::new(
+                self.v1_glyphs
+                    .into_iter()
+                    .map(|(glyph_id, paint)| BaseGlyphPaint::new(glyph_id, paint))
+                    .collect(),
+            )
+        });
+        let layer_list =
+            (!self.layers.layers.is_empty()).then(|| LayerList::new(self.layers.layers));
+        let clip_list = (!self.clips.is_empty()).then(|| build_clip_list(self.clips));
+
+        Ok(Colr {
+            base_glyph_records: (!base_glyph_records.is_empty())
+                .then_some(base_glyph_records)
+                .into(),
+            layer_records: (!layer_records.is_empty()).then_some(layer_records).into(),
+            base_glyph_list: base_glyph_list.into(),
+            layer_list: layer_list.into(),
+            clip_list: clip_list.into(),
+            var_index_map: self.var_index_map.into(),
+            item_variation_store: self.item_variation_store.into(),
+        })
+    }
+}
+
+impl LayerListBuilder {
+    fn add(&mut self, layers: Vec<Paint>) -> Paint {
+        let mut layers = self.reuse_runs(layers);
+        while layers.len() > MAX_PAINT_COLR_LAYERS {
+            layers = layers
+                .chunks(MAX_PAINT_COLR_LAYERS)
+                .map(|chunk| self.push(chunk.to_vec()))
+                .collect();
+        }
+        self.push(layers)
+    }
+
+    /// Replace runs of layers that are already in the list with a
+    /// `PaintColrLayers` referring to them, preferring the longest run.
+    fn reuse_runs(&mut self, layers: Vec<Paint>) -> Vec<Paint> {
+        let ids = layers
+            .iter()
+            .map(|paint| self.paint_id(paint))
+            .collect::<Vec<_>>();
+        let mut result = Vec::with_capacity(layers.len());
+        let mut start = 0;
+        while start < layers.len() {
+            let longest_end = ids.len().min(start + MAX_PAINT_COLR_LAYERS);
+            let reused = (start + 2..=longest_end)
+                .rev()
+                .find_map(|end| Some((end, *self.runs.get(&ids[start..end])?)));
+            match reused {
+                Some((end, first_layer_index)) => {
+                    result.push(Paint::colr_layers((end - start) as u8, first_layer_index));
+                    start = end;
+                }
+                None => {
+                    result.push(layers[start].clone());
+                    start += 1;
+                }
+            }
+        }
+        result
+    }
+
+    /// Append layers to the list, returning the paint that refers to them.
+    fn push(&mut self, mut layers: Vec<Paint>) -> Paint {
+        if layers.len() == 1 {
+            return layers.pop().unwrap();
+        }
+        let ids = layers
+            .iter()
+            .map(|paint| self.paint_id(paint))
+            .collect::<Vec<_>>();
+        if let Some(first_layer_index) = self.runs.get(&ids) {
+            return Paint::colr_layers(layers.len() as u8, *first_layer_index);
+        }
+        let first_layer_index = self.layers.len() as u32;
+        for start in 0..ids.len() {
+            for end in start + 2..=ids.len() {
+                self.runs
+                    .entry(ids[start..end].to_vec())
+                    .or_insert(first_layer_index + start as u32);
+            }
+        }
+        let num_layers = layers.len() as u8;
+        self.layers.extend(layers);
+        Paint::colr_layers(num_layers, first_layer_index)
+    }
+
+    fn paint_id(&mut self, paint: &Paint) -> u32 {
+        let next_id = self.paint_ids.len() as u32;
+        *self.paint_ids.entry(paint.clone()).or_insert(next_id)
+    }
+}
+
+/// Group consecutive glyphs with the same clip box into a single [`Clip`].
+///
+/// Identical clip boxes in separate ranges are deduplicated when the table
+/// is compiled, so they are also only written once.
+fn build_clip_list(clips: BTreeMap<GlyphId, ClipBox>) -> ClipList {
+    let mut result: Vec<Clip> = Vec::new();
+    for (glyph_id, clip_box) in clips {
+        match result.last_mut() {
+            Some(prev)
+                if prev.end_glyph_id.to_u16().checked_add(1) == Some(glyph_id.to_u16())
+                    && *prev.clip_box == clip_box =>
+            {
+                prev.end_glyph_id = glyph_id;
+            }
+            _ => result.push(Clip::new(glyph_id, glyph_id, clip_box)),
+        }
+    }
+    ClipList::new(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use read::{tables::colr as read_colr, FontData, FontRead};
+
+    use super::*;
+    use crate::tables::variations::{DeltaSetIndex, VariationRegionList};
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    fn solid(palette_index: u16) -> Paint {
+        Paint::solid(palette_index, F2Dot14::ONE)
+    }
+
+    fn layer(glyph_id: u16, palette_index: u16) -> Paint {
+        Paint::glyph(solid(palette_index), gid(glyph_id))
+    }
+
+    fn colr_layers(paint: &Paint) -> (u8, u32) {
+        match paint {
+            Paint::ColrLayers(layers) => (layers.num_layers, layers.first_layer_index),
+            _ => panic!("expected PaintColrLayers, found {paint:?}"),
+        }
+    }
+
+    #[test]
+    fn v0_glyphs() {
+        let mut builder = ColrBuilder::new();
+        builder
+            .add_v0_glyph(gid(5), [Layer::new(gid(7), 2)])
+            .add_v0_glyph(gid(3), [Layer::new(gid(8), 0), Layer::new(gid(9), 1)]);
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(colr.version(), 0);
+        assert_eq!(colr.num_base_glyph_records(), 2);
+        assert_eq!(colr.num_layer_records(), 3);
+        // version 0 tables have no v1 fields
+        assert_eq!(bytes.len(), 14 + 2 * 6 + 3 * 4);
+
+        assert_eq!(colr.v0_base_glyph(gid(3)).unwrap(), Some(0..2));
+        assert_eq!(colr.v0_base_glyph(gid(5)).unwrap(), Some(2..3));
+        assert_eq!(colr.v0_base_glyph(gid(4)).unwrap(), None);
+        assert_eq!(colr.v0_layer(1).unwrap(), (gid(9), 1));
+        assert_eq!(colr.v0_layer(2).unwrap(), (gid(7), 2));
+    }
+
+    #[test]
+    fn v1_glyphs() {
+        let mut builder = ColrBuilder::new();
+        let layers = builder.add_layers(vec![layer(10, 0), layer(11, 1)]);
+        builder
+            .add_v1_glyph(gid(2), Paint::colr_glyph(gid(1)))
+            .add_v1_glyph(gid(1), layers)
+            .add_v0_glyph(gid(1), [Layer::new(gid(10), 0)]);
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(colr.version(), 1);
+        // v0 records are kept alongside v1 glyphs, for older implementations
+        assert_eq!(colr.v0_base_glyph(gid(1)).unwrap(), Some(0..1));
+        assert!(colr.clip_list().is_none());
+
+        let list = colr.base_glyph_list().unwrap().unwrap();
+        let glyph_ids = list
+            .base_glyph_paint_records()
+            .iter()
+            .map(|record| record.glyph_id())
+            .collect::<Vec<_>>();
+        assert_eq!(glyph_ids, [gid(1), gid(2)]);
+
+        let (paint, _) = colr.v1_base_glyph(gid(1)).unwrap().unwrap();
+        let read_colr::Paint::ColrLayers(layers) = paint else {
+            panic!("expected PaintColrLayers");
+        };
+        assert_eq!((layers.num_layers(), layers.first_layer_index()), (2, 0));
+        let (paint, _) = colr.v1_layer(1).unwrap();
+        let read_colr::Paint::Glyph(glyph) = paint else {
+            panic!("expected PaintGlyph");
+        };
+        assert_eq!(glyph.glyph_id(), gid(11));
+        // PaintGlyph has a 24-bit offset to its paint
+        let read_colr::Paint::Solid(solid) = glyph.paint().unwrap() else {
+            panic!("expected PaintSolid");
+        };
+        assert_eq!(solid.palette_index(), 1);
+    }
+
+    #[test]
+    fn nested_paints_round_trip() {
+        let paint = Paint::translate(layer(4, 3), FWord::new(10), FWord::new(-5));
+        let mut builder = ColrBuilder::new();
+        builder.add_v1_glyph(gid(1), paint.clone());
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        let (read_paint, _) = colr.v1_base_glyph(gid(1)).unwrap().unwrap();
+        let owned: Paint = read_paint.to_owned_table();
+        assert_eq!(owned, paint);
+    }
+
+    #[test]
+    fn too_many_v0_layers() {
+        let layers = |count| vec![Layer::new(gid(1), 0); count];
+        let mut builder = ColrBuilder::new();
+        builder
+            .add_v0_glyph(gid(2), layers(40_000))
+            .add_v0_glyph(gid(3), layers(25_535));
+        let colr = builder.clone().build().unwrap();
+        assert_eq!(
+            colr.layer_records.as_ref().unwrap().len(),
+            u16::MAX as usize
+        );
+
+        builder.add_v0_glyph(gid(4), layers(1));
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn reuse_layers() {
+        let mut builder = ColrBuilder::new();
+        let first = builder.add_layers(vec![layer(1, 0), layer(2, 0), layer(3, 0)]);
+        assert_eq!(colr_layers(&first), (3, 0));
+        // the same list
+        let second = builder.add_layers(vec![layer(1, 0), layer(2, 0), layer(3, 0)]);
+        assert_eq!(first, second);
+        // a run within a previous list
+        let third = builder.add_layers(vec![layer(2, 0), layer(3, 0)]);
+        assert_eq!(colr_layers(&third), (2, 1));
+        // a new list containing a previous one
+        let fourth = builder.add_layers(vec![layer(4, 0), layer(1, 0), layer(2, 0), layer(3, 0)]);
+        assert_eq!(colr_layers(&fourth), (2, 3));
+        assert_eq!(builder.layers.layers.len(), 5);
+        assert_eq!(builder.layers.layers[3], layer(4, 0));
+        assert_eq!(builder.layers.layers[4], first);
+        // a single layer is returned as is
+        assert_eq!(builder.add_layers(vec![layer(5, 0)]), layer(5, 0));
+        assert_eq!(builder.layers.layers.len(), 5);
+    }
+
+    #[test]
+    fn nest_long_layer_lists() {
+        let mut builder = ColrBuilder::new();
+        let layers = (0..300).map(|i| layer(i, 0)).collect::<Vec<_>>();
+        let paint = builder.add_layers(layers);
+        // 255 layers, then the remaining 45, then the two PaintColrLayers
+        // referring to them
+        assert_eq!(colr_layers(&paint), (2, 300));
+        let list = &builder.layers.layers;
+        assert_eq!(list.len(), 302);
+        assert_eq!(colr_layers(&list[300]), (255, 0));
+        assert_eq!(colr_layers(&list[301]), (45, 255));
+
+        builder.add_v1_glyph(gid(1), paint);
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(colr.layer_list().unwrap().unwrap().num_layers(), 302);
+    }
+
+    #[test]
+    fn shared_clip_boxes() {
+        let clip_box = ClipBox::format_1(0.into(), 0.into(), 100.into(), 100.into());
+        let other = ClipBox::format_1(0.into(), 0.into(), 50.into(), 50.into());
+        let mut builder = ColrBuilder::new();
+        builder
+            .add_v1_glyph(gid(1), solid(0))
+            .add_clip_box(gid(1), clip_box.clone())
+            .add_clip_box(gid(2), clip_box.clone())
+            .add_clip_box(gid(3), clip_box.clone())
+            .add_clip_box(gid(4), other)
+            .add_clip_box(gid(5), clip_box.clone())
+            .add_clip_box(gid(7), clip_box);
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        let clip_list = colr.clip_list().unwrap().unwrap();
+        let ranges = clip_list
+            .clips()
+            .iter()
+            .map(|clip| (clip.start_glyph_id().to_u16(), clip.end_glyph_id().to_u16()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 3), (4, 4), (5, 5), (7, 7)]);
+        let offsets = clip_list
+            .clips()
+            .iter()
+            .map(|clip| clip.clip_box_offset())
+            .collect::<Vec<_>>();
+        assert_eq!(offsets[0], offsets[2]);
+        assert_eq!(offsets[0], offsets[3]);
+        assert_ne!(offsets[0], offsets[1]);
+
+        let Some(read_colr::ClipBox::Format1(clip)) = colr.v1_clip_box(gid(2)).unwrap() else {
+            panic!("expected ClipBoxFormat1");
+        };
+        assert_eq!(clip.x_max().to_i16(), 100);
+        assert!(colr.v1_clip_box(gid(6)).unwrap().is_none());
+    }
+
+    #[test]
+    fn variations() {
+        let mut builder = ColrBuilder::new();
+        let var_index_map = DeltaSetIndexMap::from_entries(&[DeltaSetIndex { outer: 0, inner: 1 }]);
+        let store = ItemVariationStore::new(1, VariationRegionList::new(vec![]), vec![]);
+        builder
+            .add_v1_glyph(gid(1), Paint::var_solid(0, F2Dot14::ONE, 0))
+            .set_variations(Some(var_index_map), store);
+        let bytes = crate::dump_table(&builder.build().unwrap()).unwrap();
+        let colr = read_colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(colr.version(), 1);
+        let map = colr.var_index_map().unwrap().unwrap();
+        let index = map.get(0).unwrap();
+        assert_eq!((index.outer, index.inner), (0, 1));
+        assert!(colr.item_variation_store().unwrap().is_ok());
+    }
+
+    #[test]
+    fn v1_only_variations() {
+        // variation data alone is enough to require version 1
+        let mut colr = ColrBuilder::new().build().unwrap();
+        assert_eq!(colr.compute_version(), 0);
+        colr.item_variation_store = Some(ItemVariationStore::default()).into();
+        assert_eq!(colr.compute_version(), 1);
+    }
+}
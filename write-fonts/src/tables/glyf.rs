@@ -8,7 +8,9 @@ use crate::{
 use kurbo::{BezPath, Rect};
 
 use read_fonts::{
-    tables::glyf::{Anchor, CompositeGlyphFlags, CurvePoint, SimpleGlyphFlags, Transform},
+    tables::glyf::{
+        Anchor, CompositeGlyphFlags, CurvePoint, PointFlags, SimpleGlyphFlags, Transform,
+    },
     types::GlyphId,
 };
 
@@ -18,9 +20,29 @@ use crate::{
     FontWrite,
 };
 
-/// A single contour, comprising only line and quadratic bezier segments
+mod glyf_loca_builder;
+
+pub use glyf_loca_builder::GlyfLocaBuilder;
+
+/// The [glyf (Glyph Data)](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf) table
+///
+/// This is the concatenated data of all the glyphs in the font, and is
+/// generally constructed with a [`GlyfLocaBuilder`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Glyf(Vec<u8>);
+
+/// A single contour, comprising line, quadratic and cubic bezier segments
+///
+/// Cubic segments use the `CUBIC` point flag from the
+/// [glyf-cubic](https://github.com/harfbuzz/boring-expansion-spec/blob/main/glyf1-cubicOutlines.md)
+/// proposal: each one is a pair of cubic off-curve points between two
+/// on-curve points.
 #[derive(Clone, Debug, Default)]
-pub struct Contour(Vec<CurvePoint>);
+pub struct Contour {
+    points: Vec<CurvePoint>,
+    // parallel to points; true for off-curve points of cubic segments
+    cubic: Vec<bool>,
+}
 
 /// A Bounding box.
 ///
@@ -51,6 +73,18 @@ pub struct CompositeGlyph {
     _instructions: Vec<u8>,
 }
 
+/// A glyph of any kind.
+#[derive(Clone, Debug, Default)]
+pub enum Glyph {
+    /// A glyph with no outline, such as a space.
+    ///
+    /// This is written as zero bytes.
+    #[default]
+    Empty,
+    Simple(SimpleGlyph),
+    Composite(CompositeGlyph),
+}
+
 /// A single component glyph (part of a [`CompositeGlyph`]).
 #[derive(Clone, Debug)]
 pub struct Component {
@@ -95,7 +129,7 @@ pub enum BadKurbo {
     InconsistentPathElements(usize, Vec<&'static str>),
 }
 
-/// Point with an associated on-curve flag.
+/// Point with associated on-curve and cubic flags.
 ///
 /// Similar to read_fonts::tables::glyf::CurvePoint, but uses kurbo::Point directly
 /// thus it does not require (x, y) coordinates to be rounded to integers.
@@ -103,11 +137,16 @@ pub enum BadKurbo {
 struct ContourPoint {
     point: kurbo::Point,
     on_curve: bool,
+    cubic: bool,
 }
 
 impl ContourPoint {
     fn new(point: kurbo::Point, on_curve: bool) -> Self {
-        Self { point, on_curve }
+        Self {
+            point,
+            on_curve,
+            cubic: false,
+        }
     }
 
     fn on_curve(point: kurbo::Point) -> Self {
@@ -118,6 +157,13 @@ impl ContourPoint {
         Self::new(point, false)
     }
 
+    fn off_curve_cubic(point: kurbo::Point) -> Self {
+        Self {
+            cubic: true,
+            ..Self::off_curve(point)
+        }
+    }
+
     fn distance(&self, other: &Self) -> f64 {
         self.point.distance(other.point)
     }
@@ -169,6 +215,15 @@ impl InterpolatableContourBuilder {
         }
     }
 
+    /// Add a cubic curve segment to all contours
+    fn curve_to(&mut self, pts: &[(kurbo::Point, kurbo::Point, kurbo::Point)]) {
+        for (i, (p0, p1, p2)) in pts.iter().enumerate() {
+            self.0[i].push(ContourPoint::off_curve_cubic(*p0));
+            self.0[i].push(ContourPoint::off_curve_cubic(*p1));
+            self.0[i].push(ContourPoint::on_curve(*p2));
+        }
+    }
+
     /// The total number of points in each interpolatable contour
     fn num_points(&self) -> usize {
         let n = self.0[0].len();
@@ -204,13 +259,16 @@ impl InterpolatableContourBuilder {
         let num_contours = self.len();
         let num_points = self.num_points();
         let mut contours = vec![Contour::default(); num_contours];
-        contours.iter_mut().for_each(|c| c.0.reserve(num_points));
+        contours.iter_mut().for_each(|c| {
+            c.points.reserve(num_points);
+            c.cubic.reserve(num_points);
+        });
         for point_idx in (0..num_points).filter(|point_idx| !self.is_implicit_on_curve(*point_idx))
         {
             for (contour_idx, contour) in contours.iter_mut().enumerate() {
-                contour
-                    .0
-                    .push(CurvePoint::from(self.0[contour_idx][point_idx]));
+                let point = self.0[contour_idx][point_idx];
+                contour.points.push(CurvePoint::from(point));
+                contour.cubic.push(point.cubic);
             }
         }
         contours
@@ -224,7 +282,8 @@ fn is_implicit_on_curve(points: &[ContourPoint], idx: usize) -> bool {
     }
     let p0 = points.wrapping_prev(idx);
     let p2 = points.wrapping_next(idx);
-    if p0.on_curve || p0.on_curve != p2.on_curve {
+    // on-curve points are never implied between cubic off-curves
+    if p0.on_curve || p0.on_curve != p2.on_curve || p0.cubic || p2.cubic {
         return false;
     }
     // if the distance between p1 and p0 is approximately the same as the distance
@@ -250,6 +309,21 @@ fn el_types(elements: &[kurbo::PathEl]) -> Vec<&'static str> {
 }
 
 pub fn simple_glyphs_from_kurbo(paths: &[BezPath]) -> Result<Vec<SimpleGlyph>, BadKurbo> {
+    simple_glyphs_from_kurbo_impl(paths, false)
+}
+
+/// Like [`simple_glyphs_from_kurbo`], but cubic segments are kept instead of
+/// being an error.
+///
+/// See [`SimpleGlyph::from_kurbo_cubic`].
+pub fn simple_glyphs_from_kurbo_cubic(paths: &[BezPath]) -> Result<Vec<SimpleGlyph>, BadKurbo> {
+    simple_glyphs_from_kurbo_impl(paths, true)
+}
+
+fn simple_glyphs_from_kurbo_impl(
+    paths: &[BezPath],
+    allow_cubic: bool,
+) -> Result<Vec<SimpleGlyph>, BadKurbo> {
     // check that all paths have the same number of elements so we can zip them together
     let num_elements: Vec<usize> = paths.iter().map(|path| path.elements().len()).collect();
     if num_elements.iter().any(|n| *n != num_elements[0]) {
@@ -261,6 +335,7 @@ pub fn simple_glyphs_from_kurbo(paths: &[BezPath]) -> Result<Vec<SimpleGlyph>, B
     let num_glyphs = paths.len();
     let mut pts = Vec::with_capacity(num_glyphs);
     let mut quad_pts = Vec::with_capacity(num_glyphs);
+    let mut cubic_pts = Vec::with_capacity(num_glyphs);
     for (i, elements) in path_iters.enumerate() {
         // All i-th path elements are expected to have the same types.
         // elements is never empty (if it were, MultiZip would have stopped), hence the unwrap
@@ -315,7 +390,24 @@ pub fn simple_glyphs_from_kurbo(paths: &[BezPath]) -> Result<Vec<SimpleGlyph>, B
                     .ok_or(BadKurbo::MissingMove)?
                     .quad_to(&quad_pts)
             }
-            kurbo::PathEl::CurveTo(_, _, _) => return Err(BadKurbo::HasCubic),
+            kurbo::PathEl::CurveTo(_, _, _) if !allow_cubic => return Err(BadKurbo::HasCubic),
+            kurbo::PathEl::CurveTo(_, _, _) => {
+                cubic_pts.clear();
+                for el in &elements {
+                    match el {
+                        &kurbo::PathEl::CurveTo(p0, p1, p2) => {
+                            cubic_pts.push((p0, p1, p2));
+                        }
+                        _ => {
+                            return Err(BadKurbo::InconsistentPathElements(i, el_types(&elements)))
+                        }
+                    }
+                }
+                current
+                    .as_mut()
+                    .ok_or(BadKurbo::MissingMove)?
+                    .curve_to(&cubic_pts)
+            }
             kurbo::PathEl::ClosePath => {
                 let contour = current.as_mut().ok_or(BadKurbo::MissingMove)?;
                 // remove last point in closed path if has same coords as the move point
@@ -354,18 +446,85 @@ pub fn simple_glyphs_from_kurbo(paths: &[BezPath]) -> Result<Vec<SimpleGlyph>, B
 }
 
 impl Contour {
+    /// Create a new contour from the given points.
+    ///
+    /// Off-curve points are quadratic; use
+    /// [`set_off_curve_cubic`](Self::set_off_curve_cubic) to mark the control
+    /// points of cubic segments.
+    pub fn new(points: Vec<CurvePoint>) -> Self {
+        let cubic = vec![false; points.len()];
+        Self { points, cubic }
+    }
+
     /// The total number of points in this contour
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.points.len()
     }
 
     /// `true` if this contour is empty
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.points.is_empty()
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &CurvePoint> {
-        self.0.iter()
+        self.points.iter()
+    }
+
+    /// `true` if the point at `idx` is an off-curve point of a cubic segment
+    pub fn is_off_curve_cubic(&self, idx: usize) -> bool {
+        self.cubic.get(idx).copied().unwrap_or(false)
+    }
+
+    /// Mark the off-curve point at `idx` as belonging to a cubic segment.
+    ///
+    /// This has no effect on on-curve points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn set_off_curve_cubic(&mut self, idx: usize, cubic: bool) {
+        self.cubic[idx] = cubic && !self.points[idx].on_curve;
+    }
+
+    /// Iterate over the flags of the points in this contour
+    pub fn flags(&self) -> impl Iterator<Item = PointFlags> + '_ {
+        self.points
+            .iter()
+            .zip(&self.cubic)
+            .map(|(point, cubic)| match (point.on_curve, cubic) {
+                (true, _) => PointFlags::on_curve(),
+                (false, true) => PointFlags::off_curve_cubic(),
+                (false, false) => PointFlags::off_curve_quad(),
+            })
+    }
+
+    /// Returns the index of the first cubic off-curve point that is not part
+    /// of a well-formed cubic segment.
+    ///
+    /// Each cubic segment must be exactly two cubic off-curve points, with an
+    /// on-curve point (wrapping around to the start of the contour) on either
+    /// side. On-curve points are never implied between cubic off-curves.
+    fn first_unpaired_cubic(&self) -> Option<usize> {
+        let len = self.len();
+        let mut idx = 0;
+        while idx < len {
+            if !self.is_off_curve_cubic(idx) {
+                idx += 1;
+                continue;
+            }
+            // the contour cannot start with a cubic off-curve point
+            if idx == 0 || !self.points[idx - 1].on_curve {
+                return Some(idx);
+            }
+            if !self.is_off_curve_cubic(idx + 1) {
+                return Some(idx);
+            }
+            if !self.points[(idx + 2) % len].on_curve {
+                return Some(idx + 1);
+            }
+            idx += 3;
+        }
+        None
     }
 }
 
@@ -374,7 +533,8 @@ impl SimpleGlyph {
     ///
     /// The path may contain only line and quadratic bezier segments. The caller
     /// is responsible for converting any cubic segments to quadratics before
-    /// calling.
+    /// calling, for example with [`path_to_quadratic`](crate::pens::path_to_quadratic),
+    /// or can use [`from_kurbo_cubic`](Self::from_kurbo_cubic) to keep them.
     ///
     /// Returns an error if the input path is malformed; that is, if it is empty,
     /// contains cubic segments, or does not begin with a 'move' instruction.
@@ -395,6 +555,17 @@ impl SimpleGlyph {
             .unwrap())
     }
 
+    /// Attempt to create a simple glyph from a kurbo `BezPath`, keeping any
+    /// cubic segments.
+    ///
+    /// Cubic segments are written as cubic off-curve points, using the `CUBIC`
+    /// point flag. This is not supported by all renderers.
+    pub fn from_kurbo_cubic(path: &BezPath) -> Result<Self, BadKurbo> {
+        Ok(simple_glyphs_from_kurbo_cubic(std::slice::from_ref(path))?
+            .pop()
+            .unwrap())
+    }
+
     /// Compute the flags and deltas for this glyph's points.
     ///
     /// This does not do the final binary encoding, and it also does not handle
@@ -422,9 +593,9 @@ impl SimpleGlyph {
         }
 
         let (mut last_x, mut last_y) = (0, 0);
-        let mut iter = self.contours.iter().flat_map(|c| c.iter());
+        let mut iter = self.contours.iter().flat_map(|c| c.iter().zip(c.flags()));
         std::iter::from_fn(move || {
-            let point = iter.next()?;
+            let (point, point_flags) = iter.next()?;
             let mut flag = SimpleGlyphFlags::empty();
             let d_x = point.x - last_x;
             let d_y = point.y - last_y;
@@ -433,6 +604,8 @@ impl SimpleGlyph {
 
             if point.on_curve {
                 flag |= SimpleGlyphFlags::ON_CURVE_POINT;
+            } else if point_flags.is_off_curve_cubic() {
+                flag |= SimpleGlyphFlags::CUBIC;
             }
             let (x_flag, x_data) = flag_and_delta(
                 d_x,
@@ -463,14 +636,29 @@ impl<'a> FromObjRef<read::tables::glyf::SimpleGlyph<'a>> for SimpleGlyph {
             x_max: from.x_max(),
             y_max: from.y_max(),
         };
-        let mut points = from.points();
+        // the point iterator doesn't expose the cubic flag, so read the
+        // flags separately
+        let num_points = from.num_points();
+        let mut flags = vec![PointFlags::default(); num_points];
+        let mut raw_points = vec![Default::default(); num_points];
+        if from.read_points_fast(&mut raw_points, &mut flags).is_err() {
+            flags
+                .iter_mut()
+                .for_each(|flag| *flag = PointFlags::default());
+        }
+        let mut points = from.points().zip(flags);
         let mut last_end = 0;
         let mut contours = vec![];
         for end_pt in from.end_pts_of_contours() {
             let end = end_pt.get() as usize + 1;
             let count = end - last_end;
             last_end = end;
-            contours.push(Contour(points.by_ref().take(count).collect()));
+            let (points, cubic) = points
+                .by_ref()
+                .take(count)
+                .map(|(point, flag)| (point, !point.on_curve && flag.is_off_curve_cubic()))
+                .unzip();
+            contours.push(Contour { points, cubic });
         }
         Self {
             bbox,
@@ -607,6 +795,13 @@ impl crate::validate::Validate for SimpleGlyph {
         if self._instructions.len() > u16::MAX as usize {
             ctx.report("instructions len overflows");
         }
+        for (i, contour) in self.contours.iter().enumerate() {
+            if let Some(idx) = contour.first_unpaired_cubic() {
+                ctx.report(format!(
+                    "contour {i}: cubic off-curve point {idx} is not part of a cubic segment"
+                ));
+            }
+        }
     }
 }
 
@@ -748,6 +943,59 @@ impl crate::validate::Validate for CompositeGlyph {
     }
 }
 
+impl From<SimpleGlyph> for Glyph {
+    fn from(value: SimpleGlyph) -> Self {
+        Glyph::Simple(value)
+    }
+}
+
+impl From<CompositeGlyph> for Glyph {
+    fn from(value: CompositeGlyph) -> Self {
+        Glyph::Composite(value)
+    }
+}
+
+impl FontWrite for Glyph {
+    fn write_into(&self, writer: &mut crate::TableWriter) {
+        match self {
+            Glyph::Empty => (),
+            Glyph::Simple(glyph) => glyph.write_into(writer),
+            Glyph::Composite(glyph) => glyph.write_into(writer),
+        }
+    }
+}
+
+impl crate::validate::Validate for Glyph {
+    fn validate_impl(&self, ctx: &mut crate::codegen_prelude::ValidationCtx) {
+        match self {
+            Glyph::Empty => (),
+            Glyph::Simple(glyph) => glyph.validate_impl(ctx),
+            Glyph::Composite(glyph) => glyph.validate_impl(ctx),
+        }
+    }
+}
+
+impl Glyf {
+    /// The raw bytes of the table.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl read::TopLevelTable for Glyf {
+    const TAG: types::Tag = types::Tag::new(b"glyf");
+}
+
+impl FontWrite for Glyf {
+    fn write_into(&self, writer: &mut crate::TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl crate::validate::Validate for Glyf {
+    fn validate_impl(&self, _ctx: &mut crate::codegen_prelude::ValidationCtx) {}
+}
+
 impl FontWrite for Anchor {
     fn write_into(&self, writer: &mut crate::TableWriter) {
         let two_bytes = self
@@ -870,6 +1118,127 @@ mod tests {
         assert!(matches!(err, BadKurbo::HasCubic));
     }
 
+    #[test]
+    fn cubic_path_input() {
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.curve_to((10., 10.), (20., 20.), (30., 30.));
+        path.line_to((50., 50.));
+        path.line_to((10., 10.));
+        let glyph = SimpleGlyph::from_kurbo_cubic(&path).unwrap();
+        let contour = &glyph.contours()[0];
+        assert_eq!(
+            contour.flags().collect::<Vec<_>>(),
+            [
+                PointFlags::on_curve(),
+                PointFlags::off_curve_cubic(),
+                PointFlags::off_curve_cubic(),
+                PointFlags::on_curve(),
+                PointFlags::on_curve(),
+                PointFlags::on_curve(),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip_cubic() {
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.curve_to((0., 100.), (100., 200.), (200., 200.));
+        path.line_to((200., 0.));
+        // the closing segment ends at the start point, which is dropped
+        path.curve_to((150., -50.), (50., -50.), (0., 0.));
+        path.close_path();
+
+        let glyph = SimpleGlyph::from_kurbo_cubic(&path).unwrap();
+        assert_eq!(glyph.contours()[0].len(), 7);
+        let bytes = crate::dump_table(&glyph).unwrap();
+        let read = read_glyf::SimpleGlyph::read(FontData::new(&bytes)).unwrap();
+        let mut points = vec![Default::default(); read.num_points()];
+        let mut flags = vec![Default::default(); read.num_points()];
+        read.read_points_fast(&mut points, &mut flags).unwrap();
+        assert_eq!(
+            flags
+                .iter()
+                .map(|f| f.is_off_curve_cubic())
+                .collect::<Vec<_>>(),
+            [false, true, true, false, false, true, true]
+        );
+        assert_eq!(simple_glyph_to_bezpath(&read), path);
+
+        // and back again, via read-fonts
+        let ours = SimpleGlyph::from_table_ref(&read);
+        assert!(ours.contours()[0].is_off_curve_cubic(1));
+        assert!(ours.contours()[0].is_off_curve_cubic(5));
+        assert!(!ours.contours()[0].is_off_curve_cubic(3));
+        assert_eq!(crate::dump_table(&ours).unwrap(), bytes);
+    }
+
+    #[test]
+    fn cubic_on_curves_are_not_implied() {
+        // these cubic off-curves are equidistant from the on-curve between
+        // them, which would be dropped if they were quadratic
+        let mut path = BezPath::new();
+        path.move_to((0., 0.));
+        path.curve_to((0., 50.), (50., 100.), (100., 100.));
+        path.curve_to((150., 100.), (200., 50.), (200., 0.));
+        path.close_path();
+        let glyph = SimpleGlyph::from_kurbo_cubic(&path).unwrap();
+        assert_eq!(glyph.contours()[0].len(), 7);
+        assert!(glyph.contours()[0].iter().nth(3).unwrap().on_curve);
+    }
+
+    #[test]
+    fn validate_cubic_pairing() {
+        fn contour(points: &[(i16, i16, bool)], cubic: &[usize]) -> Contour {
+            let mut contour = Contour::new(
+                points
+                    .iter()
+                    .map(|(x, y, on_curve)| CurvePoint::new(*x, *y, *on_curve))
+                    .collect(),
+            );
+            cubic
+                .iter()
+                .for_each(|idx| contour.set_off_curve_cubic(*idx, true));
+            contour
+        }
+
+        let points = [(0, 0, true), (0, 10, false), (10, 10, false), (10, 0, true)];
+        // a well-formed cubic segment
+        assert_eq!(contour(&points, &[1, 2]).first_unpaired_cubic(), None);
+        // a lone cubic off-curve point
+        assert_eq!(contour(&points, &[1]).first_unpaired_cubic(), Some(1));
+        // a cubic off-curve point after a quadratic one
+        assert_eq!(contour(&points, &[2]).first_unpaired_cubic(), Some(2));
+        // a cubic segment cannot start the contour
+        let wrapped = [(0, 10, false), (10, 10, false), (10, 0, true)];
+        assert_eq!(contour(&wrapped, &[0, 1]).first_unpaired_cubic(), Some(0));
+        // a cubic segment may end at the start of the contour
+        let closing = [(0, 0, true), (10, 0, true), (10, 10, false), (0, 10, false)];
+        assert_eq!(contour(&closing, &[2, 3]).first_unpaired_cubic(), None);
+        // cubic segments need an explicit on-curve point between them
+        let implied = [
+            (0, 0, true),
+            (0, 10, false),
+            (10, 10, false),
+            (20, 10, false),
+            (20, 0, false),
+        ];
+        assert_eq!(
+            contour(&implied, &[1, 2, 3, 4]).first_unpaired_cubic(),
+            Some(2)
+        );
+        // the cubic flag is ignored on on-curve points
+        assert_eq!(contour(&points, &[0]).first_unpaired_cubic(), None);
+
+        let glyph = SimpleGlyph {
+            bbox: Bbox::default(),
+            contours: vec![contour(&points, &[1])],
+            _instructions: Vec::new(),
+        };
+        assert!(crate::dump_table(&glyph).is_err());
+    }
+
     fn simple_glyph_to_bezpath(glyph: &read::tables::glyf::SimpleGlyph) -> BezPath {
         use types::{F26Dot6, Pen};
 
@@ -0,0 +1,165 @@
+//! Building the glyf and loca tables together.
+
+use crate::{
+    error::Error,
+    tables::loca::{Loca, LocaFormat},
+    validate::Validate,
+    FontWrite,
+};
+
+use super::Glyf;
+
+/// A builder for the glyf and loca tables.
+///
+/// Glyphs must be added in glyph id order, including any empty glyphs (which
+/// can be added as [`Glyph::Empty`](super::Glyph::Empty)).
+///
+/// ```
+/// # use write_fonts::tables::glyf::{GlyfLocaBuilder, Glyph};
+/// let mut builder = GlyfLocaBuilder::new();
+/// builder.add_glyph(&Glyph::Empty).unwrap();
+/// let (glyf, loca, loca_format) = builder.build();
+/// // loca_format should be written to `head.index_to_loc_format`
+/// # assert_eq!(loca.format(), loca_format);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlyfLocaBuilder {
+    data: Vec<u8>,
+    offsets: Vec<u32>,
+}
+
+impl GlyfLocaBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        GlyfLocaBuilder {
+            data: Vec::new(),
+            offsets: vec![0],
+        }
+    }
+
+    /// Add the next glyph.
+    ///
+    /// This may be a [`Glyph`](super::Glyph), a [`SimpleGlyph`](super::SimpleGlyph)
+    /// or a [`CompositeGlyph`](super::CompositeGlyph).
+    ///
+    /// Returns an error if the glyph fails validation.
+    pub fn add_glyph(&mut self, glyph: &(impl FontWrite + Validate)) -> Result<&mut Self, Error> {
+        let bytes = crate::dump_table(glyph)?;
+        self.data.extend_from_slice(&bytes);
+        // glyphs are already padded when written, but all offsets must be
+        // even in the short loca format
+        if self.data.len() & 1 != 0 {
+            self.data.push(0);
+        }
+        self.offsets.push(self.data.len() as u32);
+        Ok(self)
+    }
+
+    /// The number of glyphs added so far.
+    pub fn num_glyphs(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Finish building, returning the glyf and loca tables.
+    ///
+    /// The format of the loca table is chosen automatically, and is also
+    /// returned so that it can be used to set `head.index_to_loc_format`.
+    pub fn build(self) -> (Glyf, Loca, LocaFormat) {
+        let loca = Loca::new(self.offsets);
+        let format = loca.format();
+        (Glyf(self.data), loca, format)
+    }
+}
+
+impl Default for GlyfLocaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read::{tables::glyf as read_glyf, types::GlyphId, FontRef, TableProvider, TopLevelTable};
+
+    use super::*;
+    use crate::{
+        from_obj::FromTableRef,
+        tables::glyf::{Glyph, SimpleGlyph},
+    };
+
+    #[test]
+    fn empty() {
+        let builder = GlyfLocaBuilder::new();
+        assert_eq!(builder.num_glyphs(), 0);
+        let (glyf, loca, format) = builder.build();
+        assert!(glyf.as_bytes().is_empty());
+        assert_eq!(loca.offsets(), [0]);
+        assert_eq!(format, LocaFormat::Short);
+    }
+
+    #[test]
+    fn rebuild_simple_glyf() {
+        let font = FontRef::new(font_test_data::SIMPLE_GLYF).unwrap();
+        let orig_loca = font.loca(None).unwrap();
+        let orig_glyf = font.glyf().unwrap();
+
+        let mut builder = GlyfLocaBuilder::new();
+        for gid in 0..orig_loca.len() {
+            let glyph = match orig_loca.get_glyf(GlyphId::new(gid as u16), &orig_glyf) {
+                Ok(None) => Glyph::Empty,
+                Ok(Some(read_glyf::Glyph::Simple(glyph))) => {
+                    SimpleGlyph::from_table_ref(&glyph).into()
+                }
+                _ => panic!("unexpected glyph {gid}"),
+            };
+            builder.add_glyph(&glyph).unwrap();
+        }
+        assert_eq!(builder.num_glyphs(), orig_loca.len());
+        let (glyf, loca, format) = builder.build();
+        assert_eq!(format, LocaFormat::Short);
+
+        let loca_bytes = crate::dump_table(&loca).unwrap();
+        let glyf_bytes = crate::dump_table(&glyf).unwrap();
+        assert_eq!(loca_bytes, font.table_data(Loca::TAG).unwrap().as_ref());
+        assert_eq!(glyf_bytes, orig_glyf.offset_data().as_ref());
+    }
+
+    #[test]
+    fn odd_length_glyphs_are_padded() {
+        struct Odd;
+
+        impl FontWrite for Odd {
+            fn write_into(&self, writer: &mut crate::TableWriter) {
+                writer.write_slice(&[1, 2, 3]);
+            }
+        }
+
+        impl Validate for Odd {
+            fn validate_impl(&self, _ctx: &mut crate::validate::ValidationCtx) {}
+        }
+
+        let mut builder = GlyfLocaBuilder::new();
+        builder
+            .add_glyph(&Odd)
+            .unwrap()
+            .add_glyph(&Glyph::Empty)
+            .unwrap()
+            .add_glyph(&Odd)
+            .unwrap();
+        let (glyf, loca, format) = builder.build();
+        assert_eq!(glyf.as_bytes(), [1, 2, 3, 0, 1, 2, 3, 0]);
+        assert_eq!(loca.offsets(), [0, 4, 4, 8]);
+        assert_eq!(format, LocaFormat::Short);
+    }
+
+    #[test]
+    fn long_format() {
+        let mut builder = GlyfLocaBuilder::new();
+        builder.data = vec![0; 0x20000];
+        builder.offsets.push(0x20000);
+        builder.add_glyph(&Glyph::Empty).unwrap();
+        let (_, loca, format) = builder.build();
+        assert_eq!(format, LocaFormat::Long);
+        assert_eq!(loca.format(), LocaFormat::Long);
+    }
+}
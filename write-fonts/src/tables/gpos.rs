@@ -89,6 +89,10 @@ impl SinglePosFormat2 {
             .map(ValueRecord::format)
             .unwrap_or(ValueFormat::empty())
     }
+
+    fn check_value_formats(&self, ctx: &mut ValidationCtx) {
+        check_value_formats(ctx, self.value_records.iter());
+    }
 }
 
 impl PairPosFormat1 {
@@ -107,6 +111,16 @@ impl PairPosFormat1 {
             .map(|rec| rec.value_record2.format())
             .unwrap_or(ValueFormat::empty())
     }
+
+    fn check_value_formats(&self, ctx: &mut ValidationCtx) {
+        let records = || {
+            self.pair_sets
+                .iter()
+                .flat_map(|pairset| pairset.pair_value_records.iter())
+        };
+        check_value_formats(ctx, records().map(|rec| &rec.value_record1));
+        check_value_formats(ctx, records().map(|rec| &rec.value_record2));
+    }
 }
 
 impl PairPosFormat2 {
@@ -126,6 +140,16 @@ impl PairPosFormat2 {
             .unwrap_or(ValueFormat::empty())
     }
 
+    fn check_value_formats(&self, ctx: &mut ValidationCtx) {
+        let records = || {
+            self.class1_records
+                .iter()
+                .flat_map(|rec| rec.class2_records.iter())
+        };
+        check_value_formats(ctx, records().map(|rec| &rec.value_record1));
+        check_value_formats(ctx, records().map(|rec| &rec.value_record2));
+    }
+
     fn compute_class1_count(&self) -> u16 {
         self.class_def1.class_count()
     }
@@ -163,6 +187,20 @@ impl MarkArray {
     }
 }
 
+/// Value records in a subtable share a single format, so records that omit
+/// fields must be padded with [`ValueRecord::with_explicit_value_format`].
+fn check_value_formats<'a>(
+    ctx: &mut ValidationCtx,
+    mut records: impl Iterator<Item = &'a ValueRecord>,
+) {
+    let Some(format) = records.next().map(ValueRecord::format) else {
+        return;
+    };
+    if records.any(|rec| rec.format() != format) {
+        ctx.report("all value records in a subtable must have the same format");
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -176,13 +214,7 @@ mod tests {
         let cov_one = CoverageTable::format_1(vec![GlyphId::new(2)]);
         let cov_two = CoverageTable::format_1(vec![GlyphId::new(4)]);
         let sub1 = SinglePos::format_1(cov_one, ValueRecord::default());
-        let sub2 = SinglePos::format_1(
-            cov_two,
-            ValueRecord {
-                x_advance: Some(500),
-                ..Default::default()
-            },
-        );
+        let sub2 = SinglePos::format_1(cov_two, ValueRecord::new().with_x_advance(500));
         let lookup = Lookup::new(LookupFlag::default(), vec![sub1, sub2], 0);
         let bytes = crate::dump_table(&lookup).unwrap();
 
@@ -212,4 +244,63 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn value_record_devices() {
+        let var_index = Device::from(super::super::layout::VariationIndex::new(1, 2, 0x8000));
+        let with_device = ValueRecord::new()
+            .with_x_advance(-20)
+            .with_x_advance_device(var_index);
+        let format = with_device.format();
+        assert_eq!(
+            format,
+            ValueFormat::X_ADVANCE | ValueFormat::X_ADVANCE_DEVICE
+        );
+        // records without a device are padded with a null offset
+        let without_device = ValueRecord::new()
+            .with_x_advance(10)
+            .with_explicit_value_format(format);
+        assert_eq!(without_device.format(), format);
+        // the explicit format does not affect equality
+        assert_eq!(without_device, ValueRecord::new().with_x_advance(10));
+        assert_eq!(without_device.encoded_size(), 4);
+
+        let table = SinglePosFormat2::new(
+            CoverageTable::format_1(vec![GlyphId::new(1), GlyphId::new(2)]),
+            vec![with_device, without_device],
+        );
+        let bytes = crate::dump_table(&table).unwrap();
+        let read_table = read_gpos::SinglePosFormat2::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read_table.value_format(), format);
+        let data = read_table.offset_data();
+        let records = read_table
+            .value_records()
+            .iter()
+            .map(|rec| rec.unwrap())
+            .collect::<Vec<_>>();
+        let device = records[0].x_advance_device(data).unwrap().unwrap();
+        let index = device.variation_index().unwrap();
+        assert_eq!((index.outer, index.inner), (1, 2));
+        assert_eq!(records[1].x_advance(), Some(10));
+        assert!(records[1].x_advance_device(data).is_none());
+
+        // the null device offset survives a round trip
+        let owned = SinglePosFormat2::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(owned.value_records[1].format(), format);
+        assert_eq!(crate::dump_table(&owned).unwrap(), bytes);
+    }
+
+    #[test]
+    fn validate_value_formats() {
+        let table = SinglePosFormat2::new(
+            CoverageTable::format_1(vec![GlyphId::new(1), GlyphId::new(2)]),
+            vec![
+                ValueRecord::new().with_x_advance(10),
+                ValueRecord::new()
+                    .with_x_advance(10)
+                    .with_x_advance_device(Device::new(10, 11, &[1, 1])),
+            ],
+        );
+        assert!(table.validate().is_err());
+    }
 }
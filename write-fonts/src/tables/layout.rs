@@ -530,6 +530,19 @@ impl Device {
     }
 }
 
+impl From<VariationIndex> for Device {
+    /// Converts a VariationIndex table to the equivalent [`Device`], which
+    /// shares its layout.
+    fn from(src: VariationIndex) -> Self {
+        Device {
+            start_size: src.delta_set_outer_index,
+            end_size: src.delta_set_inner_index,
+            delta_format: DeltaFormat::VariationIndex,
+            delta_value: vec![],
+        }
+    }
+}
+
 fn encode_delta(format: DeltaFormat, values: &[i8]) -> Vec<u16> {
     let (chunk_size, mask, bits) = match format {
         DeltaFormat::Local2BitDeltas => (8, 0b11, 2),
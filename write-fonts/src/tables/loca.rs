@@ -0,0 +1,123 @@
+//! The [loca (Index to Location)][loca] table
+//!
+//! [loca]: https://docs.microsoft.com/en-us/typography/opentype/spec/loca
+
+use read::TopLevelTable;
+use types::Tag;
+
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The [loca] table.
+///
+/// This is generally constructed along with the glyf table, by a
+/// [`GlyfLocaBuilder`](super::glyf::GlyfLocaBuilder).
+///
+/// [loca]: https://docs.microsoft.com/en-us/typography/opentype/spec/loca
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Loca {
+    offsets: Vec<u32>,
+    format: LocaFormat,
+}
+
+/// The format of the loca table.
+///
+/// This must match the `indexToLocFormat` field of the head table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(i16)]
+pub enum LocaFormat {
+    /// Offsets are stored as `u16` values, divided by two.
+    #[default]
+    Short = 0,
+    /// Offsets are stored as `u32` values.
+    Long = 1,
+}
+
+impl Loca {
+    /// Create a new loca table from the offsets of each glyph in the glyf table.
+    ///
+    /// There should be one more offset than there are glyphs, the last being
+    /// the length of the glyf table. The short format is used if all of the
+    /// offsets can be represented in it.
+    pub fn new(offsets: Vec<u32>) -> Self {
+        let format = LocaFormat::new(&offsets);
+        Loca { offsets, format }
+    }
+
+    /// The format that will be used to write this table.
+    pub fn format(&self) -> LocaFormat {
+        self.format
+    }
+
+    /// The offsets of each glyph, followed by the length of the glyf table.
+    pub fn offsets(&self) -> &[u32] {
+        &self.offsets
+    }
+}
+
+impl LocaFormat {
+    fn new(offsets: &[u32]) -> Self {
+        // in the short format offsets are stored divided by two
+        let fits_short = |offset: &u32| offset & 1 == 0 && offset / 2 <= u16::MAX as u32;
+        if offsets.iter().all(fits_short) {
+            LocaFormat::Short
+        } else {
+            LocaFormat::Long
+        }
+    }
+}
+
+impl From<LocaFormat> for i16 {
+    fn from(value: LocaFormat) -> Self {
+        value as i16
+    }
+}
+
+impl TopLevelTable for Loca {
+    const TAG: Tag = Tag::new(b"loca");
+}
+
+impl FontWrite for Loca {
+    fn write_into(&self, writer: &mut TableWriter) {
+        match self.format {
+            LocaFormat::Short => self
+                .offsets
+                .iter()
+                .for_each(|offset| ((*offset / 2) as u16).write_into(writer)),
+            LocaFormat::Long => self.offsets.write_into(writer),
+        }
+    }
+}
+
+impl Validate for Loca {
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_format() {
+        let loca = Loca::new(vec![0, 4, 4, 0x1FFFE]);
+        assert_eq!(loca.format(), LocaFormat::Short);
+        let bytes = crate::dump_table(&loca).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 2, 0, 2, 0xFF, 0xFF]);
+        let read = read::tables::loca::Loca::read(read::FontData::new(&bytes), false).unwrap();
+        assert_eq!(read.get_raw(3), Some(0x1FFFE));
+    }
+
+    #[test]
+    fn long_format() {
+        // too large for the short format
+        assert_eq!(Loca::new(vec![0, 0x20000]).format(), LocaFormat::Long);
+        // can't be divided by two
+        let loca = Loca::new(vec![0, 3]);
+        assert_eq!(loca.format(), LocaFormat::Long);
+        let bytes = crate::dump_table(&loca).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 3]);
+        assert_eq!(i16::from(loca.format()), 1);
+    }
+}
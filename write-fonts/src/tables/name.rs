@@ -51,6 +51,9 @@ impl NameRecord {
                     }
                 }
             }
+            _ => ctx.report(format!(
+                "Writing strings in the {encoding:?} encoding is not supported"
+            )),
         }
     }
 }
@@ -82,7 +85,7 @@ impl NameStringWriter<'_> {
             Encoding::Utf16Be => self.string.chars().map(|c| c.len_utf16() as u16 * 2).sum(),
             // this will be correct assuming we pass validation
             Encoding::MacRoman => self.string.chars().count().try_into().unwrap(),
-            Encoding::Unknown => 0,
+            _ => 0,
         }
     }
 }
@@ -110,7 +113,8 @@ impl FontWrite for NameStringWriter<'_> {
                         .expect("invalid char for MacRoman")
                         .write_into(writer);
                 }
-                Encoding::Unknown => panic!("unknown encoding"),
+                // other encodings fail validation, and have a length of 0
+                _ => (),
             }
         }
     }
@@ -143,6 +147,18 @@ mod tests {
         assert_eq!(stringthing.compute_length(), 10);
     }
 
+    #[test]
+    fn unsupported_encoding() {
+        let stringthing = NameStringWriter {
+            encoding: Encoding::ShiftJis,
+            string: "hello",
+        };
+        assert_eq!(stringthing.compute_length(), 0);
+        let mut writer = TableWriter::default();
+        stringthing.write_into(&mut writer);
+        assert!(writer.into_data().is_empty());
+    }
+
     #[test]
     fn compute_version() {
         let mut table = Name::default();
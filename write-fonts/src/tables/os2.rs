@@ -3,7 +3,7 @@
 include!("../../generated/generated_os2.rs");
 
 impl Os2 {
-    fn compute_version(&self) -> u16 {
+    pub(crate) fn compute_version(&self) -> u16 {
         if self.us_lower_optical_point_size.is_some() || self.us_upper_optical_point_size.is_some()
         {
             5
@@ -0,0 +1,565 @@
+//! Building blocks shared by the [CFF] and [CFF2] tables.
+//!
+//! [CFF]: https://learn.microsoft.com/en-us/typography/opentype/spec/cff
+//! [CFF2]: https://learn.microsoft.com/en-us/typography/opentype/spec/cff2
+
+mod charstring;
+mod dict;
+mod index;
+mod strings;
+mod subroutinize;
+
+pub(crate) use charstring::{encode_path, CharString};
+pub(crate) use dict::{op, DictWriter};
+pub(crate) use index::write_index;
+pub(crate) use strings::StringTable;
+pub(crate) use subroutinize::{subroutinize, Subroutinized};
+
+/// Hinting values stored in a Private DICT.
+///
+/// Each font (or, in a CID-keyed font, each Font DICT) has its own Private
+/// DICT. Values left empty are omitted, and readers will use the defaults
+/// from the specification.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PrivateDict {
+    /// Pairs of alignment zones, the first being the baseline overshoot.
+    pub blue_values: Vec<f64>,
+    /// Pairs of alignment zones below the baseline.
+    pub other_blues: Vec<f64>,
+    pub family_blues: Vec<f64>,
+    pub family_other_blues: Vec<f64>,
+    pub blue_scale: Option<f64>,
+    pub blue_shift: Option<f64>,
+    pub blue_fuzz: Option<f64>,
+    /// The dominant horizontal stem width.
+    pub std_hw: Option<f64>,
+    /// The dominant vertical stem width.
+    pub std_vw: Option<f64>,
+    pub stem_snap_h: Vec<f64>,
+    pub stem_snap_v: Vec<f64>,
+    /// Only written to CFF tables; this operator was removed in CFF2.
+    pub force_bold: bool,
+    pub language_group: Option<i32>,
+}
+
+/// An error that occurs while building a CFF or CFF2 table.
+#[derive(Debug)]
+pub enum CffError {
+    /// The first glyph of a name-keyed font was not named `.notdef`.
+    MissingNotdef,
+    /// More glyphs were added than a table can hold.
+    TooManyGlyphs,
+    /// A glyph was added with a name to a CID-keyed font, or with a CID to
+    /// a name-keyed font.
+    MixedGlyphKeys { glyph: usize },
+    /// A glyph referred to a Font DICT that does not exist.
+    InvalidFontDict { glyph: usize, font_dict: usize },
+    /// The deltas of a variable glyph do not have the same structure as
+    /// its default outline.
+    IncompatibleDeltas { glyph: usize },
+    /// A variable glyph had a different number of deltas than there are
+    /// regions in the variation store.
+    RegionCountMismatch {
+        glyph: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The variation store could not be compiled.
+    VariationStore(crate::error::Error),
+    /// The compiled variation store is larger than 64KiB, which is too
+    /// large for its length field.
+    VariationStoreTooLarge,
+    /// A value written to a DICT was NaN or infinite.
+    ///
+    /// This includes the font bounding box, so it is also reported for
+    /// glyphs with non-finite coordinates.
+    NonFiniteNumber,
+}
+
+/// The table being written, which determines a few encoding details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Flavor {
+    Cff,
+    Cff2,
+}
+
+impl Flavor {
+    /// The maximum number of operands on the charstring argument stack.
+    pub(crate) fn max_stack(self) -> usize {
+        match self {
+            Flavor::Cff => 48,
+            Flavor::Cff2 => 513,
+        }
+    }
+}
+
+impl PrivateDict {
+    /// Whether all of the values can be written to a DICT.
+    pub(crate) fn is_finite(&self) -> bool {
+        let arrays = [
+            &self.blue_values,
+            &self.other_blues,
+            &self.family_blues,
+            &self.family_other_blues,
+            &self.stem_snap_h,
+            &self.stem_snap_v,
+        ];
+        let numbers = [
+            self.blue_scale,
+            self.blue_shift,
+            self.blue_fuzz,
+            self.std_hw,
+            self.std_vw,
+        ];
+        arrays.into_iter().flatten().all(|value| value.is_finite())
+            && numbers.into_iter().flatten().all(f64::is_finite)
+    }
+
+    /// Encode the dict, with an optional offset to a local subrs INDEX
+    /// immediately following it.
+    ///
+    /// `widths` are the `defaultWidthX` and `nominalWidthX` values, which
+    /// are only present in CFF.
+    pub(crate) fn encode(&self, widths: Option<(i32, i32)>, has_subrs: bool) -> Vec<u8> {
+        let mut dict = DictWriter::default();
+        dict.delta(op::BLUE_VALUES, &self.blue_values)
+            .delta(op::OTHER_BLUES, &self.other_blues)
+            .delta(op::FAMILY_BLUES, &self.family_blues)
+            .delta(op::FAMILY_OTHER_BLUES, &self.family_other_blues)
+            .maybe_number(op::BLUE_SCALE, self.blue_scale)
+            .maybe_number(op::BLUE_SHIFT, self.blue_shift)
+            .maybe_number(op::BLUE_FUZZ, self.blue_fuzz)
+            .maybe_number(op::STD_HW, self.std_hw)
+            .maybe_number(op::STD_VW, self.std_vw)
+            .delta(op::STEM_SNAP_H, &self.stem_snap_h)
+            .delta(op::STEM_SNAP_V, &self.stem_snap_v)
+            .maybe_number(op::LANGUAGE_GROUP, self.language_group.map(f64::from));
+        if let Some((default_width, nominal_width)) = widths {
+            if self.force_bold {
+                dict.int(1).op(op::FORCE_BOLD);
+            }
+            if default_width != 0 {
+                dict.int(default_width).op(op::DEFAULT_WIDTH_X);
+            }
+            if nominal_width != 0 {
+                dict.int(nominal_width).op(op::NOMINAL_WIDTH_X);
+            }
+        }
+        if has_subrs {
+            // the subrs INDEX follows the dict, and its offset is relative
+            // to the start of the dict; offsets have a fixed size, so the
+            // length is known before the offset is written
+            let len = dict.len() + DictWriter::OFFSET_LEN + 1;
+            dict.offset(len).op(op::SUBRS);
+        }
+        dict.into_bytes()
+    }
+}
+
+/// Encode an FDSelect, in whichever of formats 0 and 3 is smallest.
+pub(crate) fn encode_fd_select(font_dicts: &[usize]) -> Vec<u8> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (gid, font_dict) in font_dicts.iter().enumerate() {
+        if ranges.last().map(|(_, fd)| fd) != Some(font_dict) {
+            ranges.push((gid, *font_dict));
+        }
+    }
+    let mut data = Vec::new();
+    if font_dicts.len() <= 4 + ranges.len() * 3 {
+        data.push(0);
+        data.extend(font_dicts.iter().map(|fd| *fd as u8));
+    } else {
+        data.push(3);
+        data.extend((ranges.len() as u16).to_be_bytes());
+        for (first, font_dict) in ranges {
+            data.extend((first as u16).to_be_bytes());
+            data.push(font_dict as u8);
+        }
+        data.extend((font_dicts.len() as u16).to_be_bytes());
+    }
+    data
+}
+
+impl std::fmt::Display for CffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CffError::MissingNotdef => write!(f, "the first glyph must be '.notdef'"),
+            CffError::TooManyGlyphs => write!(f, "too many glyphs"),
+            CffError::MixedGlyphKeys { glyph } => {
+                write!(f, "glyph {glyph} is keyed differently from the font")
+            }
+            CffError::InvalidFontDict { glyph, font_dict } => {
+                write!(f, "glyph {glyph} uses missing Font DICT {font_dict}")
+            }
+            CffError::IncompatibleDeltas { glyph } => {
+                write!(f, "deltas of glyph {glyph} do not match its outline")
+            }
+            CffError::RegionCountMismatch {
+                glyph,
+                expected,
+                found,
+            } => write!(
+                f,
+                "glyph {glyph} has deltas for {found} regions, expected {expected}"
+            ),
+            CffError::VariationStore(err) => write!(f, "invalid variation store: {err}"),
+            CffError::VariationStoreTooLarge => write!(f, "variation store is too large"),
+            CffError::NonFiniteNumber => write!(f, "DICT value is NaN or infinite"),
+        }
+    }
+}
+
+impl std::error::Error for CffError {}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    //! Decoding helpers for checking the output of the CFF writers.
+
+    use std::fmt::Write;
+
+    use kurbo::{BezPath, PathEl};
+    use read::{FontData, ReadError};
+    use ttf_parser::OutlineBuilder;
+    use types::{Fixed, MajorMinor, NameId, Tag};
+
+    use crate::{
+        tables::{
+            fvar::{AxisInstanceArrays, Fvar, VariationAxisRecord},
+            head::Head,
+            hhea::Hhea,
+            maxp::Maxp,
+        },
+        FontBuilder,
+    };
+
+    /// Draw every glyph of a font containing the given CFF or CFF2 table
+    /// with ttf-parser, which has its own, independent, CFF parser.
+    ///
+    /// If `wght` is given, the font has a single 'wght' axis from 0 to 1,
+    /// and the glyphs are drawn at that location.
+    ///
+    /// The outlines are in the format of [`svg`].
+    pub(crate) fn ttf_parser_outlines(
+        tag: Tag,
+        table: &[u8],
+        num_glyphs: u16,
+        wght: Option<f32>,
+    ) -> Vec<String> {
+        let head = Head {
+            units_per_em: 1000,
+            ..Default::default()
+        };
+        let axis = VariationAxisRecord::new(
+            Tag::new(b"wght"),
+            Fixed::ZERO,
+            Fixed::ZERO,
+            Fixed::ONE,
+            0,
+            NameId::new(256),
+        );
+        let fvar = Fvar::new(
+            MajorMinor::VERSION_1_0,
+            AxisInstanceArrays::new(vec![axis], vec![]),
+            1,
+            0,
+        );
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::new(b"head"), crate::dump_table(&head).unwrap())
+            .add_table(
+                Tag::new(b"hhea"),
+                crate::dump_table(&Hhea::default()).unwrap(),
+            )
+            .add_table(
+                Tag::new(b"maxp"),
+                crate::dump_table(&Maxp::new(num_glyphs)).unwrap(),
+            )
+            .add_table(tag, table);
+        if wght.is_some() {
+            builder.add_table(Tag::new(b"fvar"), crate::dump_table(&fvar).unwrap());
+        }
+        let font = builder.build();
+        let mut face = ttf_parser::Face::parse(&font, 0).unwrap();
+        if let Some(wght) = wght {
+            face.set_variation(ttf_parser::Tag::from_bytes(b"wght"), wght)
+                .unwrap();
+        }
+        (0..num_glyphs)
+            .map(|gid| {
+                let mut svg = Svg::default();
+                face.outline_glyph(ttf_parser::GlyphId(gid), &mut svg);
+                // CFF2 charstrings have no endchar, and ttf-parser only
+                // closes a contour when the next one starts
+                if !svg.0.is_empty() && !svg.0.ends_with("Z ") {
+                    svg.close();
+                }
+                svg.0.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// A path as SVG path data, with absolute coordinates.
+    pub(crate) fn svg(path: &BezPath) -> String {
+        let mut svg = Svg::default();
+        for el in path.elements() {
+            match *el {
+                PathEl::MoveTo(p) => svg.move_to(p.x as f32, p.y as f32),
+                PathEl::LineTo(p) => svg.line_to(p.x as f32, p.y as f32),
+                PathEl::QuadTo(..) => panic!("quadratic curves are written as cubics"),
+                PathEl::CurveTo(p1, p2, p) => svg.curve_to(
+                    p1.x as f32,
+                    p1.y as f32,
+                    p2.x as f32,
+                    p2.y as f32,
+                    p.x as f32,
+                    p.y as f32,
+                ),
+                PathEl::ClosePath => svg.close(),
+            }
+        }
+        svg.0.trim_end().to_string()
+    }
+
+    #[derive(Default)]
+    struct Svg(String);
+
+    impl OutlineBuilder for Svg {
+        fn move_to(&mut self, x: f32, y: f32) {
+            write!(self.0, "M{x} {y} ").unwrap();
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            write!(self.0, "L{x} {y} ").unwrap();
+        }
+
+        fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+            write!(self.0, "Q{x1} {y1} {x} {y} ").unwrap();
+        }
+
+        fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+            write!(self.0, "C{x1} {y1} {x2} {y2} {x} {y} ").unwrap();
+        }
+
+        fn close(&mut self) {
+            self.0.push_str("Z ");
+        }
+    }
+
+    /// Read an INDEX at the start of `data`, returning its items and length.
+    pub(crate) fn read_index(data: &[u8], count_size: usize) -> (Vec<Vec<u8>>, usize) {
+        let data = FontData::new(data);
+        let count = match count_size {
+            2 => data.read_at::<u16>(0).unwrap() as usize,
+            _ => data.read_at::<u32>(0).unwrap() as usize,
+        };
+        if count == 0 {
+            return (Vec::new(), count_size);
+        }
+        let off_size = data.read_at::<u8>(count_size).unwrap() as usize;
+        let read_offset = |i: usize| -> Result<usize, ReadError> {
+            let pos = count_size + 1 + i * off_size;
+            let bytes = data
+                .slice(pos..pos + off_size)
+                .ok_or(ReadError::OutOfBounds)?;
+            Ok(bytes
+                .as_ref()
+                .iter()
+                .fold(0usize, |acc, b| acc << 8 | *b as usize))
+        };
+        let data_start = count_size + 1 + (count + 1) * off_size - 1;
+        let items = (0..count)
+            .map(|i| {
+                let start = data_start + read_offset(i).unwrap();
+                let end = data_start + read_offset(i + 1).unwrap();
+                data.as_ref()[start..end].to_vec()
+            })
+            .collect();
+        let end = data_start + read_offset(count).unwrap();
+        (items, end)
+    }
+
+    /// Decode a DICT into its (operator, operands) entries.
+    pub(crate) fn read_dict(data: &[u8]) -> Vec<(u16, Vec<f64>)> {
+        let mut result = Vec::new();
+        let mut operands = Vec::new();
+        let mut i = 0;
+        while i < data.len() {
+            let b0 = data[i];
+            i += 1;
+            match b0 {
+                12 => {
+                    result.push((0x0C00 | data[i] as u16, std::mem::take(&mut operands)));
+                    i += 1;
+                }
+                0..=27 => result.push((b0 as u16, std::mem::take(&mut operands))),
+                28 => {
+                    operands.push(i16::from_be_bytes([data[i], data[i + 1]]) as f64);
+                    i += 2;
+                }
+                29 => {
+                    let bytes = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+                    operands.push(i32::from_be_bytes(bytes) as f64);
+                    i += 4;
+                }
+                30 => {
+                    let mut text = String::new();
+                    'nibbles: loop {
+                        let byte = data[i];
+                        i += 1;
+                        for nibble in [byte >> 4, byte & 0xF] {
+                            match nibble {
+                                0..=9 => text.push((b'0' + nibble) as char),
+                                0xA => text.push('.'),
+                                0xB => text.push('E'),
+                                0xC => text.push_str("E-"),
+                                0xE => text.push('-'),
+                                _ => break 'nibbles,
+                            }
+                        }
+                    }
+                    operands.push(text.parse().unwrap());
+                }
+                32..=246 => operands.push(b0 as f64 - 139.0),
+                247..=250 => {
+                    operands.push(((b0 as f64 - 247.0) * 256.0) + data[i] as f64 + 108.0);
+                    i += 1;
+                }
+                251..=254 => {
+                    operands.push(-((b0 as f64 - 251.0) * 256.0) - data[i] as f64 - 108.0);
+                    i += 1;
+                }
+                _ => panic!("unexpected DICT byte {b0}"),
+            }
+        }
+        result
+    }
+
+    /// The result of running a charstring.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub(crate) struct Outline {
+        pub(crate) width: Option<f64>,
+        /// Each operator that was executed, with its operands, after
+        /// resolving subroutine calls and blends.
+        pub(crate) ops: Vec<(u8, Vec<f64>)>,
+    }
+
+    /// Execute a Type 2 charstring, inlining subroutines.
+    ///
+    /// `scalars` are the region scalars used to apply blends.
+    pub(crate) fn run_charstring(
+        charstring: &[u8],
+        global_subrs: &[Vec<u8>],
+        local_subrs: &[Vec<u8>],
+        scalars: &[f64],
+    ) -> Outline {
+        let mut outline = Outline::default();
+        let mut stack = Vec::new();
+        let mut seen_first_op = false;
+        run(
+            charstring,
+            global_subrs,
+            local_subrs,
+            scalars,
+            &mut stack,
+            &mut outline,
+            &mut seen_first_op,
+        );
+        outline
+    }
+
+    fn bias(count: usize) -> i32 {
+        if count < 1240 {
+            107
+        } else if count < 33900 {
+            1131
+        } else {
+            32768
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn run(
+        data: &[u8],
+        global_subrs: &[Vec<u8>],
+        local_subrs: &[Vec<u8>],
+        scalars: &[f64],
+        stack: &mut Vec<f64>,
+        outline: &mut Outline,
+        seen_first_op: &mut bool,
+    ) {
+        let mut i = 0;
+        while i < data.len() {
+            let b0 = data[i];
+            i += 1;
+            match b0 {
+                10 | 29 => {
+                    let subrs = if b0 == 10 { local_subrs } else { global_subrs };
+                    let index = stack.pop().unwrap() as i32 + bias(subrs.len());
+                    let subr = &subrs[index as usize];
+                    run(
+                        subr,
+                        global_subrs,
+                        local_subrs,
+                        scalars,
+                        stack,
+                        outline,
+                        seen_first_op,
+                    );
+                }
+                11 => return,
+                16 => {
+                    let n = stack.pop().unwrap() as usize;
+                    let k = scalars.len();
+                    let start = stack.len() - n * (k + 1);
+                    let deltas = stack.split_off(start + n);
+                    for (j, value) in stack[start..].iter_mut().enumerate() {
+                        let region_deltas = &deltas[j * k..(j + 1) * k];
+                        *value += region_deltas
+                            .iter()
+                            .zip(scalars)
+                            .map(|(delta, scalar)| delta * scalar)
+                            .sum::<f64>();
+                    }
+                }
+                28 => {
+                    stack.push(i16::from_be_bytes([data[i], data[i + 1]]) as f64);
+                    i += 2;
+                }
+                32..=246 => stack.push(b0 as f64 - 139.0),
+                247..=250 => {
+                    stack.push(((b0 as f64 - 247.0) * 256.0) + data[i] as f64 + 108.0);
+                    i += 1;
+                }
+                251..=254 => {
+                    stack.push(-((b0 as f64 - 251.0) * 256.0) - data[i] as f64 - 108.0);
+                    i += 1;
+                }
+                255 => {
+                    let bytes = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+                    stack.push(i32::from_be_bytes(bytes) as f64 / 65536.0);
+                    i += 4;
+                }
+                _ => {
+                    let expected = match b0 {
+                        4 | 22 => Some(1),
+                        21 => Some(2),
+                        _ => None,
+                    };
+                    if !*seen_first_op {
+                        *seen_first_op = true;
+                        let has_width = match expected {
+                            Some(n) => stack.len() > n,
+                            // rlineto, rrcurveto etc. can't be first; endchar
+                            // takes no operands
+                            None => b0 == 14 && !stack.is_empty(),
+                        };
+                        if has_width {
+                            outline.width = Some(stack.remove(0));
+                        }
+                    }
+                    outline.ops.push((b0, std::mem::take(stack)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,544 @@
+//! Encoding Type 2 charstrings.
+
+use kurbo::{BezPath, PathEl, Point};
+
+use super::{CffError, Flavor};
+
+/// Charstring operators.
+pub(crate) mod op {
+    pub(crate) const VMOVETO: u8 = 4;
+    pub(crate) const RLINETO: u8 = 5;
+    pub(crate) const HLINETO: u8 = 6;
+    pub(crate) const VLINETO: u8 = 7;
+    pub(crate) const RRCURVETO: u8 = 8;
+    pub(crate) const CALLSUBR: u8 = 10;
+    pub(crate) const RETURN: u8 = 11;
+    pub(crate) const ENDCHAR: u8 = 14;
+    pub(crate) const BLEND: u8 = 16;
+    pub(crate) const RMOVETO: u8 = 21;
+    pub(crate) const HMOVETO: u8 = 22;
+    pub(crate) const CALLGSUBR: u8 = 29;
+}
+
+/// A charstring, as a sequence of operators along with their operands.
+///
+/// Each token is the encoding of a single operator, preceded by all of its
+/// operands. This does not include the advance width or the final `endchar`
+/// of a CFF charstring, which are added by [`to_cff_bytes`](Self::to_cff_bytes).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CharString {
+    pub(crate) tokens: Vec<Vec<u8>>,
+}
+
+impl CharString {
+    /// Encode a CFF charstring, with an optional width operand.
+    ///
+    /// The width operand is the difference between the glyph's advance and
+    /// the Private DICT's `nominalWidthX`, and is omitted for glyphs whose
+    /// advance is `defaultWidthX`.
+    pub(crate) fn to_cff_bytes(&self, width: Option<i32>) -> Vec<u8> {
+        let mut data = Vec::new();
+        if let Some(width) = width {
+            encode_number(width as f64, &mut data);
+        }
+        self.tokens.iter().for_each(|token| data.extend(token));
+        data.push(op::ENDCHAR);
+        data
+    }
+
+    /// Encode a CFF2 charstring.
+    pub(crate) fn to_cff2_bytes(&self) -> Vec<u8> {
+        self.tokens.concat()
+    }
+}
+
+/// Encode a charstring number operand.
+///
+/// Integers use the shortest encoding; other values are written as 16.16
+/// fixed point numbers.
+pub(crate) fn encode_number(value: f64, data: &mut Vec<u8>) {
+    if value.fract() != 0.0 || value.abs() > i16::MAX as f64 {
+        data.push(255);
+        let fixed = (value * 65536.0).round() as i32;
+        data.extend(fixed.to_be_bytes());
+        return;
+    }
+    let value = value as i32;
+    match value {
+        -107..=107 => data.push((value + 139) as u8),
+        108..=1131 => {
+            let value = value - 108;
+            data.extend([(value >> 8) as u8 + 247, value as u8]);
+        }
+        -1131..=-108 => {
+            let value = -value - 108;
+            data.extend([(value >> 8) as u8 + 251, value as u8]);
+        }
+        _ => {
+            data.push(28);
+            data.extend((value as i16).to_be_bytes());
+        }
+    }
+}
+
+/// A coordinate (or a difference between coordinates) in each master.
+///
+/// The first value is the default; the rest are the deltas for each
+/// region, used to build `blend` operators in CFF2.
+#[derive(Clone, Debug, PartialEq)]
+struct Value(Vec<f64>);
+
+impl Value {
+    fn default(&self) -> f64 {
+        self.0[0]
+    }
+
+    fn deltas(&self) -> &[f64] {
+        &self.0[1..]
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|v| *v == 0.0)
+    }
+
+    fn is_blended(&self) -> bool {
+        self.deltas().iter().any(|v| *v != 0.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SegmentKind {
+    Move,
+    Line,
+    Curve,
+}
+
+/// A segment with its points relative to the previous point.
+#[derive(Clone, Debug)]
+struct Segment {
+    kind: SegmentKind,
+    /// dx, dy pairs for each point
+    args: Vec<Value>,
+}
+
+impl Segment {
+    fn is_blended(&self) -> bool {
+        self.args.iter().any(Value::is_blended)
+    }
+}
+
+/// A segment with absolute points, for each master.
+#[derive(Clone, Debug)]
+struct AbsSegment {
+    kind: SegmentKind,
+    /// for each point in the segment, its position in each master
+    points: Vec<Vec<Point>>,
+}
+
+/// Encode an outline as a charstring.
+///
+/// `deltas` are the deltas of each point for each variation region, as
+/// paths with the same structure as `default`; they must be empty for CFF.
+/// Quadratic curves are converted to cubic ones, and all coordinates are
+/// rounded to integers.
+pub(crate) fn encode_path(
+    default: &BezPath,
+    deltas: &[BezPath],
+    flavor: Flavor,
+    glyph: usize,
+) -> Result<CharString, CffError> {
+    let segments =
+        absolute_segments(default, deltas).ok_or(CffError::IncompatibleDeltas { glyph })?;
+    let segments = relative_segments(segments, deltas.len() + 1);
+    Ok(CharString {
+        tokens: specialize(&segments, flavor),
+    })
+}
+
+/// Walk all masters in lockstep, collecting their segments.
+///
+/// Returns `None` if the masters are not compatible.
+fn absolute_segments(default: &BezPath, deltas: &[BezPath]) -> Option<Vec<AbsSegment>> {
+    let masters = std::iter::once(default).chain(deltas).collect::<Vec<_>>();
+    let num_elements = default.elements().len();
+    if masters
+        .iter()
+        .any(|path| path.elements().len() != num_elements)
+    {
+        return None;
+    }
+    let num_masters = masters.len();
+    let mut current = vec![Point::ZERO; num_masters];
+    let mut start = vec![Point::ZERO; num_masters];
+    let mut segments: Vec<AbsSegment> = Vec::new();
+    for i in 0..num_elements {
+        let elements = masters
+            .iter()
+            .map(|path| path.elements()[i])
+            .collect::<Vec<_>>();
+        let kind = std::mem::discriminant(&elements[0]);
+        if elements.iter().any(|el| std::mem::discriminant(el) != kind) {
+            return None;
+        }
+        let (kind, points) = match elements[0] {
+            PathEl::MoveTo(_) => (SegmentKind::Move, 1),
+            PathEl::LineTo(_) => (SegmentKind::Line, 1),
+            PathEl::QuadTo(..) | PathEl::CurveTo(..) => (SegmentKind::Curve, 3),
+            PathEl::ClosePath => {
+                let start_points = start.iter().map(round).collect::<Vec<_>>();
+                // the closing line is implied, so drop an explicit one
+                if let Some(last) = segments.last() {
+                    if last.kind == SegmentKind::Line && last.points[0] == start_points {
+                        segments.pop();
+                    }
+                }
+                // drawing continues from the start of the contour, unless
+                // (as is usual) a new contour is started
+                segments.push(AbsSegment {
+                    kind: SegmentKind::Move,
+                    points: vec![start_points],
+                });
+                current.clone_from(&start);
+                continue;
+            }
+        };
+        let mut segment = AbsSegment {
+            kind,
+            points: vec![Vec::with_capacity(num_masters); points],
+        };
+        for (master, el) in elements.iter().enumerate() {
+            let master_points = match *el {
+                PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+                PathEl::QuadTo(q, p) => {
+                    let p0 = current[master];
+                    vec![p0 + (q - p0) * (2.0 / 3.0), p + (q - p) * (2.0 / 3.0), p]
+                }
+                PathEl::CurveTo(c0, c1, p) => vec![c0, c1, p],
+                PathEl::ClosePath => unreachable!(),
+            };
+            current[master] = *master_points.last().unwrap();
+            if kind == SegmentKind::Move {
+                start[master] = current[master];
+            }
+            for (dest, point) in segment.points.iter_mut().zip(master_points) {
+                dest.push(round(&point));
+            }
+        }
+        segments.push(segment);
+    }
+    Some(segments)
+}
+
+fn round(point: &Point) -> Point {
+    Point::new(point.x.round(), point.y.round())
+}
+
+/// Convert to relative coordinates, dropping moves that start empty contours.
+fn relative_segments(segments: Vec<AbsSegment>, num_masters: usize) -> Vec<Segment> {
+    let mut current = vec![Point::ZERO; num_masters];
+    let mut pending_move = None;
+    let mut result = Vec::new();
+    let mut add = |kind, points: &[Vec<Point>], current: &mut Vec<Point>| {
+        let mut args = Vec::with_capacity(points.len() * 2);
+        for point in points {
+            let dx = point.iter().zip(current.iter()).map(|(p, c)| p.x - c.x);
+            let dy = point.iter().zip(current.iter()).map(|(p, c)| p.y - c.y);
+            args.push(Value(dx.collect()));
+            args.push(Value(dy.collect()));
+            current.clone_from(point);
+        }
+        result.push(Segment { kind, args });
+    };
+    for segment in segments {
+        if segment.kind == SegmentKind::Move {
+            pending_move = Some(segment);
+            continue;
+        }
+        if let Some(move_to) = pending_move.take() {
+            add(SegmentKind::Move, &move_to.points, &mut current);
+        }
+        add(segment.kind, &segment.points, &mut current);
+    }
+    result
+}
+
+/// Choose operators for each segment, combining runs of similar segments.
+fn specialize(segments: &[Segment], flavor: Flavor) -> Vec<Vec<u8>> {
+    // leave room for the width, which precedes the first operator in CFF
+    let max_args = match flavor {
+        Flavor::Cff => flavor.max_stack() - 1,
+        Flavor::Cff2 => flavor.max_stack(),
+    };
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < segments.len() {
+        let segment = &segments[i];
+        if segment.kind == SegmentKind::Move {
+            let (dx, dy) = (&segment.args[0], &segment.args[1]);
+            let token = if dy.is_zero() {
+                encode_token(&[dx], op::HMOVETO, max_args)
+            } else if dx.is_zero() {
+                encode_token(&[dy], op::VMOVETO, max_args)
+            } else {
+                encode_token(&[dx, dy], op::RMOVETO, max_args)
+            };
+            tokens.push(token);
+            i += 1;
+            continue;
+        }
+        // blended segments are written individually; other runs of lines
+        // or curves are written with as few operators as possible
+        let run_len = if segment.is_blended() {
+            1
+        } else {
+            segments[i..]
+                .iter()
+                .take_while(|next| next.kind == segment.kind && !next.is_blended())
+                .count()
+        };
+        let run = &segments[i..i + run_len];
+        match segment.kind {
+            SegmentKind::Line => encode_lines(run, max_args, &mut tokens),
+            _ => {
+                let per_op = (max_args / 6).max(1);
+                for chunk in run.chunks(per_op) {
+                    let args = chunk.iter().flat_map(|seg| &seg.args).collect::<Vec<_>>();
+                    tokens.push(encode_token(&args, op::RRCURVETO, max_args));
+                }
+            }
+        }
+        i += run_len;
+    }
+    tokens
+}
+
+/// Encode a run of lines, using `hlineto`/`vlineto` if they alternate
+/// between horizontal and vertical.
+fn encode_lines(run: &[Segment], max_args: usize, tokens: &mut Vec<Vec<u8>>) {
+    let is_horizontal = |seg: &Segment| seg.args[1].is_zero();
+    let is_vertical = |seg: &Segment| seg.args[0].is_zero();
+    let starts_horizontal = is_horizontal(&run[0]);
+    let alternates = run.iter().enumerate().all(|(i, seg)| {
+        if (i & 1 == 0) == starts_horizontal {
+            is_horizontal(seg)
+        } else {
+            is_vertical(seg)
+        }
+    });
+    if !alternates {
+        for chunk in run.chunks((max_args / 2).max(1)) {
+            let args = chunk.iter().flat_map(|seg| &seg.args).collect::<Vec<_>>();
+            tokens.push(encode_token(&args, op::RLINETO, max_args));
+        }
+        return;
+    }
+    let mut horizontal = starts_horizontal;
+    for chunk in run.chunks(max_args.max(1)) {
+        let args = chunk
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| {
+                // chunks have an even length unless they are the last, so the
+                // alternation continues across chunks
+                if (i & 1 == 0) == horizontal {
+                    &seg.args[0]
+                } else {
+                    &seg.args[1]
+                }
+            })
+            .collect::<Vec<_>>();
+        let op = if horizontal { op::HLINETO } else { op::VLINETO };
+        tokens.push(encode_token(&args, op, max_args));
+        if chunk.len() & 1 != 0 {
+            horizontal = !horizontal;
+        }
+    }
+}
+
+/// Encode an operator and its operands, using `blend` for any variation.
+fn encode_token(args: &[&Value], operator: u8, max_args: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    if !args.iter().any(|arg| arg.is_blended()) {
+        args.iter()
+            .for_each(|arg| encode_number(arg.default(), &mut data));
+        data.push(operator);
+        return data;
+    }
+    // each blend consumes n * (k + 1) + 1 operands and leaves n results,
+    // so long argument lists may need more than one
+    let num_regions = args[0].deltas().len();
+    let mut done = 0;
+    while done < args.len() {
+        let available = max_args - done - 1;
+        let n = (available / (num_regions + 1)).clamp(1, args.len() - done);
+        let chunk = &args[done..done + n];
+        chunk
+            .iter()
+            .for_each(|arg| encode_number(arg.default(), &mut data));
+        for arg in chunk {
+            arg.deltas()
+                .iter()
+                .for_each(|delta| encode_number(*delta, &mut data));
+        }
+        encode_number(n as f64, &mut data);
+        data.push(op::BLEND);
+        done += n;
+    }
+    data.push(operator);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::postscript::test_utils::run_charstring;
+
+    fn encode(value: f64) -> Vec<u8> {
+        let mut data = Vec::new();
+        encode_number(value, &mut data);
+        data
+    }
+
+    fn square() -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((10.0, 10.0));
+        path.line_to((110.0, 10.0));
+        path.line_to((110.0, 110.0));
+        path.line_to((10.0, 110.0));
+        path.line_to((10.0, 10.0));
+        path.close_path();
+        path
+    }
+
+    fn ops(charstring: &CharString, flavor: Flavor) -> Vec<(u8, Vec<f64>)> {
+        let bytes = match flavor {
+            Flavor::Cff => charstring.to_cff_bytes(None),
+            Flavor::Cff2 => charstring.to_cff2_bytes(),
+        };
+        run_charstring(&bytes, &[], &[], &[]).ops
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(encode(0.0), [139]);
+        assert_eq!(encode(-107.0), [32]);
+        assert_eq!(encode(108.0), [247, 0]);
+        assert_eq!(encode(1131.0), [250, 255]);
+        assert_eq!(encode(-1131.0), [254, 255]);
+        assert_eq!(encode(1132.0), [28, 0x04, 0x6c]);
+        assert_eq!(encode(-1132.0), [28, 0xfb, 0x94]);
+        assert_eq!(encode(0.5), [255, 0, 0, 0x80, 0]);
+    }
+
+    #[test]
+    fn lines() {
+        let charstring = encode_path(&square(), &[], Flavor::Cff, 0).unwrap();
+        // the final line closing the contour is dropped
+        assert_eq!(
+            ops(&charstring, Flavor::Cff),
+            [
+                (op::RMOVETO, vec![10.0, 10.0]),
+                (op::HLINETO, vec![100.0, 100.0, -100.0]),
+                (op::ENDCHAR, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn diagonal_lines() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 20.0));
+        path.line_to((30.0, 20.0));
+        path.close_path();
+        let charstring = encode_path(&path, &[], Flavor::Cff2, 0).unwrap();
+        // every contour starts with a move, even to the current point
+        assert_eq!(
+            ops(&charstring, Flavor::Cff2),
+            [
+                (op::HMOVETO, vec![0.0]),
+                (op::RLINETO, vec![10.0, 20.0, 20.0, 0.0])
+            ]
+        );
+    }
+
+    #[test]
+    fn curves() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 100.0));
+        path.curve_to((0.0, 155.0), (45.0, 200.0), (100.0, 200.0));
+        path.quad_to((200.0, 200.0), (200.0, 100.0));
+        path.close_path();
+        path.move_to((300.0, 0.0));
+        path.close_path();
+        let charstring = encode_path(&path, &[], Flavor::Cff, 0).unwrap();
+        // the quadratic curve is converted to a cubic, and the empty
+        // contour is dropped
+        assert_eq!(
+            ops(&charstring, Flavor::Cff),
+            [
+                (op::VMOVETO, vec![100.0]),
+                (
+                    op::RRCURVETO,
+                    vec![0.0, 55.0, 45.0, 45.0, 55.0, 0.0, 67.0, 0.0, 33.0, -33.0, 0.0, -67.0]
+                ),
+                (op::ENDCHAR, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_runs_are_split() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        for i in 1..=30 {
+            path.line_to((i as f64 * 10.0, (i & 1) as f64 * 10.0));
+        }
+        let charstring = encode_path(&path, &[], Flavor::Cff, 0).unwrap();
+        let ops = ops(&charstring, Flavor::Cff);
+        // one slot is left for the width, so 23 lines fit in each operator
+        let arg_counts = ops
+            .iter()
+            .map(|(op, args)| (*op, args.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            arg_counts,
+            [
+                (op::HMOVETO, 1),
+                (op::RLINETO, 46),
+                (op::RLINETO, 14),
+                (op::ENDCHAR, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn blends() {
+        let default = square();
+        let mut delta = BezPath::new();
+        delta.move_to((0.0, 0.0));
+        delta.line_to((20.0, 0.0));
+        delta.line_to((20.0, 20.0));
+        delta.line_to((0.0, 20.0));
+        delta.line_to((0.0, 0.0));
+        delta.close_path();
+        let charstring = encode_path(&default, &[delta.clone()], Flavor::Cff2, 0).unwrap();
+        let bytes = charstring.to_cff2_bytes();
+        assert!(bytes.contains(&op::BLEND));
+        // the outline at the default location
+        let outline = run_charstring(&bytes, &[], &[], &[0.0]);
+        assert_eq!(outline.ops[0], (op::RMOVETO, vec![10.0, 10.0]));
+        assert_eq!(outline.ops[1], (op::HLINETO, vec![100.0]));
+        // and at the region's peak, where the square is 120 units wide
+        let outline = run_charstring(&bytes, &[], &[], &[1.0]);
+        assert_eq!(outline.ops[1], (op::HLINETO, vec![120.0]));
+        assert_eq!(outline.ops[2], (op::VLINETO, vec![120.0]));
+        assert_eq!(outline.ops[3], (op::HLINETO, vec![-120.0]));
+
+        delta.move_to((5.0, 5.0));
+        assert!(matches!(
+            encode_path(&default, &[delta], Flavor::Cff2, 3),
+            Err(CffError::IncompatibleDeltas { glyph: 3 })
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! Encoding DICT data.
+
+/// DICT operators.
+///
+/// Two-byte operators (those that begin with the escape byte, 12) are
+/// represented as `0x0C00 | second byte`.
+pub(crate) mod op {
+    // Top DICT
+    pub(crate) const VERSION: u16 = 0;
+    pub(crate) const NOTICE: u16 = 1;
+    pub(crate) const FULL_NAME: u16 = 2;
+    pub(crate) const FAMILY_NAME: u16 = 3;
+    pub(crate) const WEIGHT: u16 = 4;
+    pub(crate) const FONT_BBOX: u16 = 5;
+    pub(crate) const CHARSET: u16 = 15;
+    pub(crate) const CHAR_STRINGS: u16 = 17;
+    pub(crate) const PRIVATE: u16 = 18;
+    pub(crate) const VSTORE: u16 = 24;
+    pub(crate) const COPYRIGHT: u16 = 0x0C00;
+    pub(crate) const IS_FIXED_PITCH: u16 = 0x0C01;
+    pub(crate) const ITALIC_ANGLE: u16 = 0x0C02;
+    pub(crate) const UNDERLINE_POSITION: u16 = 0x0C03;
+    pub(crate) const UNDERLINE_THICKNESS: u16 = 0x0C04;
+    pub(crate) const FONT_MATRIX: u16 = 0x0C07;
+    pub(crate) const ROS: u16 = 0x0C1E;
+    pub(crate) const CID_COUNT: u16 = 0x0C22;
+    pub(crate) const FD_ARRAY: u16 = 0x0C24;
+    pub(crate) const FD_SELECT: u16 = 0x0C25;
+    pub(crate) const FONT_NAME: u16 = 0x0C26;
+
+    // Private DICT
+    pub(crate) const BLUE_VALUES: u16 = 6;
+    pub(crate) const OTHER_BLUES: u16 = 7;
+    pub(crate) const FAMILY_BLUES: u16 = 8;
+    pub(crate) const FAMILY_OTHER_BLUES: u16 = 9;
+    pub(crate) const STD_HW: u16 = 10;
+    pub(crate) const STD_VW: u16 = 11;
+    pub(crate) const SUBRS: u16 = 19;
+    pub(crate) const DEFAULT_WIDTH_X: u16 = 20;
+    pub(crate) const NOMINAL_WIDTH_X: u16 = 21;
+    pub(crate) const BLUE_SCALE: u16 = 0x0C09;
+    pub(crate) const BLUE_SHIFT: u16 = 0x0C0A;
+    pub(crate) const BLUE_FUZZ: u16 = 0x0C0B;
+    pub(crate) const STEM_SNAP_H: u16 = 0x0C0C;
+    pub(crate) const STEM_SNAP_V: u16 = 0x0C0D;
+    pub(crate) const FORCE_BOLD: u16 = 0x0C0E;
+    pub(crate) const LANGUAGE_GROUP: u16 = 0x0C11;
+}
+
+/// Encodes the operands and operators of a DICT.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DictWriter {
+    data: Vec<u8>,
+}
+
+impl DictWriter {
+    /// The length of an operand written with [`offset`](Self::offset).
+    pub(crate) const OFFSET_LEN: usize = 5;
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub(crate) fn op(&mut self, op: u16) -> &mut Self {
+        if op > 0xFF {
+            self.data.push(12);
+        }
+        self.data.push(op as u8);
+        self
+    }
+
+    /// Write an integer operand, in the shortest encoding.
+    pub(crate) fn int(&mut self, value: i32) -> &mut Self {
+        match value {
+            -107..=107 => self.data.push((value + 139) as u8),
+            108..=1131 => {
+                let value = value - 108;
+                self.data.extend([(value >> 8) as u8 + 247, value as u8]);
+            }
+            -1131..=-108 => {
+                let value = -value - 108;
+                self.data.extend([(value >> 8) as u8 + 251, value as u8]);
+            }
+            -32768..=32767 => {
+                self.data.push(28);
+                self.data.extend((value as i16).to_be_bytes());
+            }
+            _ => self.offset_impl(value),
+        }
+        self
+    }
+
+    /// Write an offset operand.
+    ///
+    /// Offsets always use the five byte encoding, so that the size of a
+    /// DICT does not depend on the offsets it contains.
+    pub(crate) fn offset(&mut self, value: usize) -> &mut Self {
+        self.offset_impl(value as i32);
+        self
+    }
+
+    fn offset_impl(&mut self, value: i32) {
+        self.data.push(29);
+        self.data.extend(value.to_be_bytes());
+    }
+
+    /// Write a number, as an integer if possible or otherwise as a real.
+    ///
+    /// The value must be finite; the builders check this before encoding.
+    pub(crate) fn number(&mut self, value: f64) -> &mut Self {
+        debug_assert!(value.is_finite(), "non-finite DICT value {value}");
+        if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 {
+            return self.int(value as i32);
+        }
+        self.data.push(30);
+        let text = value.to_string();
+        let text = text.strip_prefix('0').unwrap_or(&text);
+        let text = text.replace("-0.", "-.");
+        let mut nibbles = text
+            .bytes()
+            .map(|byte| match byte {
+                b'0'..=b'9' => byte - b'0',
+                b'.' => 0xA,
+                _ => 0xE, // b'-'
+            })
+            .collect::<Vec<_>>();
+        nibbles.push(0xF);
+        if nibbles.len() & 1 != 0 {
+            nibbles.push(0xF);
+        }
+        self.data
+            .extend(nibbles.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        self
+    }
+
+    /// Write an entry with a single number, if present.
+    pub(crate) fn maybe_number(&mut self, op: u16, value: Option<f64>) -> &mut Self {
+        if let Some(value) = value {
+            self.number(value).op(op);
+        }
+        self
+    }
+
+    /// Write an entry with a list of numbers.
+    pub(crate) fn array(&mut self, op: u16, values: &[f64]) -> &mut Self {
+        for value in values {
+            self.number(*value);
+        }
+        self.op(op)
+    }
+
+    /// Write a delta-encoded array, if it is not empty.
+    pub(crate) fn delta(&mut self, op: u16, values: &[f64]) -> &mut Self {
+        if values.is_empty() {
+            return self;
+        }
+        let mut prev = 0.0;
+        for value in values {
+            self.number(value - prev);
+            prev = *value;
+        }
+        self.op(op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::postscript::test_utils::read_dict;
+
+    fn encode_int(value: i32) -> Vec<u8> {
+        let mut dict = DictWriter::default();
+        dict.int(value);
+        dict.into_bytes()
+    }
+
+    fn encode_number(value: f64) -> Vec<u8> {
+        let mut dict = DictWriter::default();
+        dict.number(value);
+        dict.into_bytes()
+    }
+
+    #[test]
+    fn integers() {
+        // examples from the CFF spec, table 3
+        assert_eq!(encode_int(0), [0x8b]);
+        assert_eq!(encode_int(100), [0xef]);
+        assert_eq!(encode_int(-100), [0x27]);
+        assert_eq!(encode_int(1000), [0xfa, 0x7c]);
+        assert_eq!(encode_int(-1000), [0xfe, 0x7c]);
+        assert_eq!(encode_int(10000), [0x1c, 0x27, 0x10]);
+        assert_eq!(encode_int(-10000), [0x1c, 0xd8, 0xf0]);
+        assert_eq!(encode_int(100000), [0x1d, 0x00, 0x01, 0x86, 0xa0]);
+        assert_eq!(encode_int(-100000), [0x1d, 0xff, 0xfe, 0x79, 0x60]);
+    }
+
+    #[test]
+    fn reals() {
+        assert_eq!(encode_number(-2.25), [0x1e, 0xe2, 0xa2, 0x5f]);
+        assert_eq!(encode_number(0.039625), [0x1e, 0xa0, 0x39, 0x62, 0x5f]);
+        assert_eq!(encode_number(-0.5), [0x1e, 0xea, 0x5f]);
+        // integral values use the integer encoding
+        assert_eq!(encode_number(3.0), [0x8e]);
+    }
+
+    #[test]
+    fn entries() {
+        let mut dict = DictWriter::default();
+        dict.delta(op::BLUE_VALUES, &[-10.0, 0.0, 500.0, 510.0])
+            .delta(op::OTHER_BLUES, &[])
+            .maybe_number(op::BLUE_SCALE, Some(0.039625))
+            .maybe_number(op::STD_HW, None)
+            .array(op::FONT_BBOX, &[-50.0, -200.0, 1000.0, 800.0])
+            .offset(1234)
+            .op(op::CHARSET);
+        let dict = dict.into_bytes();
+        assert_eq!(
+            read_dict(&dict),
+            [
+                (op::BLUE_VALUES, vec![-10.0, 10.0, 500.0, 10.0]),
+                (op::BLUE_SCALE, vec![0.039625]),
+                (op::FONT_BBOX, vec![-50.0, -200.0, 1000.0, 800.0]),
+                (op::CHARSET, vec![1234.0]),
+            ]
+        );
+    }
+}
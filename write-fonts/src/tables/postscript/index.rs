@@ -0,0 +1,68 @@
+//! Encoding INDEX data.
+
+use super::Flavor;
+
+/// Encode an INDEX containing the given objects.
+///
+/// The count is 16 bits in CFF and 32 bits in CFF2; callers are responsible
+/// for ensuring that it fits.
+pub(crate) fn write_index<T: AsRef<[u8]>>(objects: &[T], flavor: Flavor) -> Vec<u8> {
+    let mut data = match flavor {
+        Flavor::Cff => (objects.len() as u16).to_be_bytes().to_vec(),
+        Flavor::Cff2 => (objects.len() as u32).to_be_bytes().to_vec(),
+    };
+    if objects.is_empty() {
+        return data;
+    }
+    let data_len = objects.iter().map(|obj| obj.as_ref().len()).sum::<usize>();
+    // offsets are one-based
+    let off_size = match data_len + 1 {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFF_FFFF => 3,
+        _ => 4,
+    };
+    data.push(off_size as u8);
+    let mut offset = 1u32;
+    data.extend_from_slice(&offset.to_be_bytes()[4 - off_size..]);
+    for obj in objects {
+        offset += obj.as_ref().len() as u32;
+        data.extend_from_slice(&offset.to_be_bytes()[4 - off_size..]);
+    }
+    for obj in objects {
+        data.extend_from_slice(obj.as_ref());
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::postscript::test_utils::read_index;
+
+    #[test]
+    fn empty() {
+        assert_eq!(write_index::<&[u8]>(&[], Flavor::Cff), [0, 0]);
+        assert_eq!(write_index::<&[u8]>(&[], Flavor::Cff2), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn small() {
+        let index = write_index(&[&b"ab"[..], b"", b"cde"], Flavor::Cff);
+        assert_eq!(index, [0, 3, 1, 1, 3, 3, 6, b'a', b'b', b'c', b'd', b'e']);
+        let (objects, len) = read_index(&index, 2);
+        assert_eq!(objects, [b"ab".to_vec(), vec![], b"cde".to_vec()]);
+        assert_eq!(len, index.len());
+    }
+
+    #[test]
+    fn offset_sizes() {
+        for (len, off_size) in [(0xFE, 1), (0xFF, 2), (0xFFFF, 3), (0xFF_FFFF, 4)] {
+            let index = write_index(&[vec![0u8; len]], Flavor::Cff2);
+            assert_eq!(index[4], off_size, "{len}");
+            let (objects, end) = read_index(&index, 4);
+            assert_eq!(objects[0].len(), len);
+            assert_eq!(end, index.len());
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Extracting repeated parts of charstrings into subroutines.
+//!
+//! This is a greedy approach: every sequence of whole operators (with their
+//! operands) that occurs more than once, and can't be extended without
+//! occurring less often, is a candidate, and candidates are
+//! turned into subroutines in order of their estimated savings, skipping
+//! occurrences that overlap ones already used. Subroutines do not call
+//! other subroutines.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::{
+    charstring::{encode_number, op},
+    CharString, Flavor,
+};
+
+/// The longest sequence of operators considered for a subroutine.
+const MAX_SUBR_TOKENS: usize = 256;
+
+/// The number of subroutines in a (global or local) subrs INDEX is limited
+/// by the range of the biased subroutine numbers.
+const MAX_SUBRS: usize = 65535;
+
+/// The approximate size of a subroutine call: the subroutine number and
+/// the call operator.
+const CALL_SIZE: usize = 2;
+
+/// Charstrings along with the subroutines they call.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Subroutinized {
+    pub(crate) charstrings: Vec<CharString>,
+    /// The encoded global subroutines.
+    pub(crate) global_subrs: Vec<Vec<u8>>,
+    /// The encoded local subroutines of each Font DICT.
+    pub(crate) local_subrs: Vec<Vec<Vec<u8>>>,
+}
+
+/// A sequence of tokens that occurs more than once.
+struct Candidate<'a> {
+    tokens: &'a [u32],
+    /// the glyph and token index of each occurrence
+    occurrences: Vec<(usize, usize)>,
+    /// the encoded size of the sequence
+    size: usize,
+    savings: usize,
+}
+
+/// A chosen subroutine.
+struct Subr<'a> {
+    tokens: &'a [u32],
+    num_uses: usize,
+    font_dicts: BTreeSet<usize>,
+}
+
+/// The number added to a subroutine number to give its index, which
+/// depends on the number of subroutines.
+fn subr_bias(num_subrs: usize) -> i32 {
+    if num_subrs < 1240 {
+        107
+    } else if num_subrs < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
+
+/// Replace repeated sequences of operators with subroutine calls.
+///
+/// `font_dicts` is the index of the Font DICT used by each charstring.
+/// Subroutines used with a single Font DICT are placed in its local subrs,
+/// and the rest in the global subrs.
+pub(crate) fn subroutinize(
+    charstrings: &[CharString],
+    font_dicts: &[usize],
+    num_font_dicts: usize,
+    flavor: Flavor,
+) -> Subroutinized {
+    // operate on token ids, rather than the encoded tokens
+    let mut token_ids: HashMap<&[u8], u32> = HashMap::new();
+    let mut tokens: Vec<&[u8]> = Vec::new();
+    let glyphs = charstrings
+        .iter()
+        .map(|charstring| {
+            charstring
+                .tokens
+                .iter()
+                .map(|token| {
+                    *token_ids.entry(token).or_insert_with(|| {
+                        tokens.push(token);
+                        tokens.len() as u32 - 1
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let token_sizes = tokens.iter().map(|token| token.len()).collect::<Vec<_>>();
+
+    let candidates = find_candidates(&glyphs, &token_sizes, flavor);
+    let (subrs, calls) = choose_subrs(candidates, &glyphs, font_dicts, flavor);
+
+    // number the subroutines, giving the most used the shortest numbers
+    let mut order = (0..subrs.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| std::cmp::Reverse(subrs[*i].num_uses));
+    let mut global_subrs = Vec::new();
+    let mut local_subrs = vec![Vec::new(); num_font_dicts];
+    // for each subroutine, the Font DICT it is local to (if any) and its index
+    let mut locations = vec![(None, 0); subrs.len()];
+    for i in order {
+        let subr = &subrs[i];
+        let mut data = subr
+            .tokens
+            .iter()
+            .flat_map(|id| tokens[*id as usize])
+            .copied()
+            .collect::<Vec<_>>();
+        if flavor == Flavor::Cff {
+            data.push(op::RETURN);
+        }
+        let (font_dict, list) = match (subr.font_dicts.len(), subr.font_dicts.first()) {
+            (1, Some(font_dict)) => (Some(*font_dict), &mut local_subrs[*font_dict]),
+            _ => (None, &mut global_subrs),
+        };
+        locations[i] = (font_dict, list.len());
+        list.push(data);
+    }
+
+    let charstrings = charstrings
+        .iter()
+        .zip(&calls)
+        .zip(font_dicts)
+        .map(|((charstring, calls), font_dict)| {
+            let mut tokens = Vec::new();
+            let mut calls = calls.iter().peekable();
+            let mut i = 0;
+            while i < charstring.tokens.len() {
+                match calls.next_if(|(start, _)| *start == i) {
+                    Some((_, subr)) => {
+                        let (local_to, index) = locations[*subr];
+                        debug_assert!(local_to.is_none() || local_to == Some(*font_dict));
+                        let (num_subrs, call) = match local_to {
+                            Some(_) => (local_subrs[*font_dict].len(), op::CALLSUBR),
+                            None => (global_subrs.len(), op::CALLGSUBR),
+                        };
+                        let mut token = Vec::new();
+                        encode_number((index as i32 - subr_bias(num_subrs)) as f64, &mut token);
+                        token.push(call);
+                        tokens.push(token);
+                        i += subrs[*subr].tokens.len();
+                    }
+                    None => {
+                        tokens.push(charstring.tokens[i].clone());
+                        i += 1;
+                    }
+                }
+            }
+            CharString { tokens }
+        })
+        .collect();
+
+    Subroutinized {
+        charstrings,
+        global_subrs,
+        local_subrs,
+    }
+}
+
+/// Find the sequences of tokens that occur more than once, and would save
+/// space as subroutines.
+///
+/// A sequence is skipped if every occurrence is followed by the same token,
+/// since the longer sequence occurs just as often and saves more. This
+/// keeps the number of candidates linear in the number of tokens.
+fn find_candidates<'a>(
+    glyphs: &'a [Vec<u32>],
+    token_sizes: &[usize],
+    flavor: Flavor,
+) -> Vec<Candidate<'a>> {
+    let mut candidates = Vec::new();
+    let mut add_candidate = |len: usize, mut occurrences: Vec<(usize, usize)>| {
+        let (glyph, start) = occurrences[0];
+        let tokens = &glyphs[glyph][start..start + len];
+        let size = tokens.iter().map(|id| token_sizes[*id as usize]).sum();
+        if let Some(savings) = savings(size, occurrences.len(), flavor) {
+            occurrences.sort_unstable();
+            candidates.push(Candidate {
+                tokens,
+                occurrences,
+                size,
+                savings,
+            });
+        }
+    };
+    // the occurrences of each repeated sequence of the current length,
+    // starting with the empty sequence. A sequence can only repeat if the
+    // sequence one token shorter does, so longer sequences are found by
+    // splitting these groups by the token that follows.
+    let mut groups = vec![glyphs
+        .iter()
+        .enumerate()
+        .flat_map(|(glyph, tokens)| (0..tokens.len()).map(move |i| (glyph, i)))
+        .collect::<Vec<_>>()];
+    for len in 0..MAX_SUBR_TOKENS {
+        let mut next_groups = Vec::new();
+        for occurrences in groups {
+            let mut extended: HashMap<u32, Vec<(usize, usize)>> = HashMap::new();
+            for (glyph, start) in &occurrences {
+                if let Some(token) = glyphs[*glyph].get(start + len) {
+                    extended.entry(*token).or_default().push((*glyph, *start));
+                }
+            }
+            let always_extended =
+                extended.len() == 1 && extended.values().all(|ext| ext.len() == occurrences.len());
+            if len > 0 && !always_extended {
+                add_candidate(len, occurrences);
+            }
+            next_groups.extend(extended.into_values().filter(|ext| ext.len() > 1));
+        }
+        groups = next_groups;
+        if groups.is_empty() {
+            break;
+        }
+    }
+    for occurrences in groups {
+        add_candidate(MAX_SUBR_TOKENS, occurrences);
+    }
+    candidates.sort_by(|a, b| {
+        b.savings
+            .cmp(&a.savings)
+            .then(b.tokens.len().cmp(&a.tokens.len()))
+            .then(a.tokens.cmp(b.tokens))
+    });
+    candidates
+}
+
+/// The estimated number of bytes saved by making a subroutine of a
+/// sequence, if any.
+fn savings(size: usize, num_uses: usize, flavor: Flavor) -> Option<usize> {
+    let return_size = (flavor == Flavor::Cff) as usize;
+    // the subroutine itself, and its offset in the subrs INDEX
+    let cost = size + return_size + 2;
+    let saved = num_uses * size.saturating_sub(CALL_SIZE);
+    saved.checked_sub(cost).filter(|savings| *savings > 0)
+}
+
+/// Greedily choose the subroutines to use, returning them along with the
+/// (start, subroutine) of each call in each glyph.
+#[allow(clippy::type_complexity)]
+fn choose_subrs<'a>(
+    candidates: Vec<Candidate<'a>>,
+    glyphs: &[Vec<u32>],
+    font_dicts: &[usize],
+    flavor: Flavor,
+) -> (Vec<Subr<'a>>, Vec<Vec<(usize, usize)>>) {
+    let mut used = glyphs
+        .iter()
+        .map(|tokens| vec![false; tokens.len()])
+        .collect::<Vec<_>>();
+    let mut calls = vec![Vec::new(); glyphs.len()];
+    let mut subrs = Vec::new();
+    for candidate in candidates {
+        if subrs.len() == MAX_SUBRS {
+            break;
+        }
+        let len = candidate.tokens.len();
+        let mut available: Vec<(usize, usize)> = Vec::new();
+        for (glyph, start) in candidate.occurrences {
+            let overlaps_previous = matches!(
+                available.last(),
+                Some((prev_glyph, prev_start)) if *prev_glyph == glyph && prev_start + len > start
+            );
+            if overlaps_previous || used[glyph][start..start + len].contains(&true) {
+                continue;
+            }
+            available.push((glyph, start));
+        }
+        if savings(candidate.size, available.len(), flavor).is_none() {
+            continue;
+        }
+        for (glyph, start) in &available {
+            used[*glyph][*start..start + len].fill(true);
+            calls[*glyph].push((*start, subrs.len()));
+        }
+        subrs.push(Subr {
+            tokens: candidate.tokens,
+            num_uses: available.len(),
+            font_dicts: available
+                .iter()
+                .map(|(glyph, _)| font_dicts[*glyph])
+                .collect(),
+        });
+    }
+    calls.iter_mut().for_each(|calls| calls.sort_unstable());
+    (subrs, calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::BezPath;
+
+    use super::*;
+    use crate::tables::postscript::{encode_path, test_utils::run_charstring};
+
+    /// A zigzag, starting at the given point.
+    fn zigzag(x: f64, y: f64) -> CharString {
+        let mut path = BezPath::new();
+        path.move_to((x, y));
+        for i in 1..=20 {
+            path.line_to((x + i as f64 * 10.0, y + (i % 3) as f64 * 15.0));
+        }
+        path.close_path();
+        encode_path(&path, &[], Flavor::Cff, 0).unwrap()
+    }
+
+    fn run(
+        charstring: &CharString,
+        global_subrs: &[Vec<u8>],
+        local_subrs: &[Vec<u8>],
+    ) -> Vec<(u8, Vec<f64>)> {
+        run_charstring(
+            &charstring.to_cff_bytes(None),
+            global_subrs,
+            local_subrs,
+            &[],
+        )
+        .ops
+    }
+
+    #[test]
+    fn repeated_outlines() {
+        let charstrings = (0..10)
+            .map(|i| zigzag(i as f64 * 7.0, 50.0))
+            .collect::<Vec<_>>();
+        let result = subroutinize(&charstrings, &[0; 10], 1, Flavor::Cff);
+        assert!(result.global_subrs.is_empty());
+        assert_eq!(result.local_subrs[0].len(), 1);
+        let original_len = charstrings.iter().map(|cs| cs.tokens.concat().len());
+        let new_len = result.charstrings.iter().map(|cs| cs.tokens.concat().len());
+        assert!(new_len.sum::<usize>() + result.local_subrs[0][0].len() < original_len.sum());
+        for (original, new) in charstrings.iter().zip(&result.charstrings) {
+            assert_eq!(
+                run(original, &[], &[]),
+                run(new, &result.global_subrs, &result.local_subrs[0])
+            );
+        }
+    }
+
+    #[test]
+    fn shared_subrs_are_global() {
+        let charstrings = (0..6)
+            .map(|i| zigzag(i as f64 * 7.0, 50.0))
+            .collect::<Vec<_>>();
+        let font_dicts = [0, 1, 0, 1, 0, 1];
+        let result = subroutinize(&charstrings, &font_dicts, 2, Flavor::Cff2);
+        assert_eq!(result.global_subrs.len(), 1);
+        assert!(result.local_subrs.iter().all(Vec::is_empty));
+        // CFF2 subroutines have no return operator
+        assert_ne!(result.global_subrs[0].last(), Some(&op::RETURN));
+        for (original, new) in charstrings.iter().zip(&result.charstrings) {
+            assert_eq!(run(original, &[], &[]), run(new, &result.global_subrs, &[]));
+        }
+    }
+
+    #[test]
+    fn nothing_repeated() {
+        let charstrings = vec![zigzag(0.0, 0.0)];
+        let result = subroutinize(&charstrings, &[0], 1, Flavor::Cff);
+        assert_eq!(result.charstrings, charstrings);
+        assert!(result.global_subrs.is_empty());
+        assert!(result.local_subrs[0].is_empty());
+    }
+
+    #[test]
+    fn candidates_are_bounded() {
+        // every sequence of up to MAX_SUBR_TOKENS tokens is repeated, but
+        // only the longest sequence from each start is a candidate (and the
+        // last two are too short to save anything)
+        let glyphs = vec![(0..300).collect::<Vec<u32>>(); 20];
+        let candidates = find_candidates(&glyphs, &[1; 300], Flavor::Cff);
+        assert_eq!(candidates.len(), 298);
+        assert_eq!(candidates[0].tokens.len(), MAX_SUBR_TOKENS);
+        assert!(candidates.iter().all(|c| c.occurrences.len() == 20));
+    }
+
+    #[test]
+    fn bias() {
+        assert_eq!(subr_bias(0), 107);
+        assert_eq!(subr_bias(1239), 107);
+        assert_eq!(subr_bias(1240), 1131);
+        assert_eq!(subr_bias(33900), 32768);
+    }
+}
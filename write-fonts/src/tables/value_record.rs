@@ -11,7 +11,15 @@ use crate::{
     write::{FontWrite, TableWriter},
 };
 
-#[derive(Clone, Default, PartialEq, Eq, Hash)]
+/// A [ValueRecord](https://learn.microsoft.com/en-us/typography/opentype/spec/gpos#value-record)
+///
+/// Device fields may hold either a [`Device`] table or a VariationIndex
+/// table, represented as a [`Device`] with a delta format of
+/// [`DeltaFormat::VariationIndex`](crate::tables::layout::DeltaFormat::VariationIndex).
+///
+/// Two records are equal if they have the same values, regardless of any
+/// [explicit format](Self::with_explicit_value_format).
+#[derive(Clone, Default)]
 pub struct ValueRecord {
     pub x_placement: Option<i16>,
     pub y_placement: Option<i16>,
@@ -21,9 +29,108 @@ pub struct ValueRecord {
     pub y_placement_device: NullableOffsetMarker<Device>,
     pub x_advance_device: NullableOffsetMarker<Device>,
     pub y_advance_device: NullableOffsetMarker<Device>,
+    // fields to write in addition to those that are set
+    explicit_format: Option<ValueFormat>,
 }
 
 impl ValueRecord {
+    /// Create a new, empty value record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the x placement.
+    pub fn with_x_placement(mut self, val: i16) -> Self {
+        self.x_placement = Some(val);
+        self
+    }
+
+    /// Set the y placement.
+    pub fn with_y_placement(mut self, val: i16) -> Self {
+        self.y_placement = Some(val);
+        self
+    }
+
+    /// Set the x advance.
+    pub fn with_x_advance(mut self, val: i16) -> Self {
+        self.x_advance = Some(val);
+        self
+    }
+
+    /// Set the y advance.
+    pub fn with_y_advance(mut self, val: i16) -> Self {
+        self.y_advance = Some(val);
+        self
+    }
+
+    /// Set the x placement device or VariationIndex table.
+    pub fn with_x_placement_device(mut self, val: impl Into<Device>) -> Self {
+        self.x_placement_device = val.into().into();
+        self
+    }
+
+    /// Set the y placement device or VariationIndex table.
+    pub fn with_y_placement_device(mut self, val: impl Into<Device>) -> Self {
+        self.y_placement_device = val.into().into();
+        self
+    }
+
+    /// Set the x advance device or VariationIndex table.
+    pub fn with_x_advance_device(mut self, val: impl Into<Device>) -> Self {
+        self.x_advance_device = val.into().into();
+        self
+    }
+
+    /// Set the y advance device or VariationIndex table.
+    pub fn with_y_advance_device(mut self, val: impl Into<Device>) -> Self {
+        self.y_advance_device = val.into().into();
+        self
+    }
+
+    /// Returns a copy of this record that is written with at least the fields
+    /// of the given format.
+    ///
+    /// See [`set_explicit_value_format`](Self::set_explicit_value_format).
+    pub fn with_explicit_value_format(mut self, format: ValueFormat) -> Self {
+        self.set_explicit_value_format(format);
+        self
+    }
+
+    /// Write this record with at least the fields of the given format.
+    ///
+    /// Fields that are in the format but not set on the record are written
+    /// as zero, or as a null offset for device fields. This is used to give
+    /// all value records in a subtable the same format.
+    pub fn set_explicit_value_format(&mut self, format: ValueFormat) {
+        self.explicit_format = Some(format);
+    }
+
+    // the fields that make up the value of the record
+    #[allow(clippy::type_complexity)]
+    fn values(
+        &self,
+    ) -> (
+        Option<i16>,
+        Option<i16>,
+        Option<i16>,
+        Option<i16>,
+        &NullableOffsetMarker<Device>,
+        &NullableOffsetMarker<Device>,
+        &NullableOffsetMarker<Device>,
+        &NullableOffsetMarker<Device>,
+    ) {
+        (
+            self.x_placement,
+            self.y_placement,
+            self.x_advance,
+            self.y_advance,
+            &self.x_placement_device,
+            &self.y_placement_device,
+            &self.x_advance_device,
+            &self.y_advance_device,
+        )
+    }
+
     /// The [ValueFormat] of this record.
     pub fn format(&self) -> ValueFormat {
         macro_rules! flag_if_true {
@@ -43,6 +150,7 @@ impl ValueRecord {
             | flag_if_true!(self.y_placement_device, ValueFormat::Y_PLACEMENT_DEVICE)
             | flag_if_true!(self.x_advance_device, ValueFormat::X_ADVANCE_DEVICE)
             | flag_if_true!(self.y_advance_device, ValueFormat::Y_ADVANCE_DEVICE)
+            | self.explicit_format.unwrap_or(ValueFormat::empty())
     }
 
     /// Return the number of bytes required to encode this value record
@@ -53,27 +161,50 @@ impl ValueRecord {
 
 impl FontWrite for ValueRecord {
     fn write_into(&self, writer: &mut TableWriter) {
+        let format = self.format();
         macro_rules! write_field {
-            ($field:expr) => {
-                if let Some(v) = $field {
-                    v.write_into(writer);
+            ($field:expr, $flag:expr) => {
+                if format.contains($flag) {
+                    $field.unwrap_or_default().write_into(writer);
                 }
             };
-            ($field:expr, off) => {
-                if let Some(v) = $field.as_ref() {
-                    writer.write_offset(v, 2);
+            ($field:expr, $flag:expr, off) => {
+                if format.contains($flag) {
+                    $field.write_into(writer);
                 }
             };
         }
 
-        write_field!(self.x_placement);
-        write_field!(self.y_placement);
-        write_field!(self.x_advance);
-        write_field!(self.y_advance);
-        write_field!(self.x_placement_device, off);
-        write_field!(self.y_placement_device, off);
-        write_field!(self.x_advance_device, off);
-        write_field!(self.y_advance_device, off);
+        write_field!(self.x_placement, ValueFormat::X_PLACEMENT);
+        write_field!(self.y_placement, ValueFormat::Y_PLACEMENT);
+        write_field!(self.x_advance, ValueFormat::X_ADVANCE);
+        write_field!(self.y_advance, ValueFormat::Y_ADVANCE);
+        write_field!(
+            self.x_placement_device,
+            ValueFormat::X_PLACEMENT_DEVICE,
+            off
+        );
+        write_field!(
+            self.y_placement_device,
+            ValueFormat::Y_PLACEMENT_DEVICE,
+            off
+        );
+        write_field!(self.x_advance_device, ValueFormat::X_ADVANCE_DEVICE, off);
+        write_field!(self.y_advance_device, ValueFormat::Y_ADVANCE_DEVICE, off);
+    }
+}
+
+impl PartialEq for ValueRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.values() == other.values()
+    }
+}
+
+impl Eq for ValueRecord {}
+
+impl std::hash::Hash for ValueRecord {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.values().hash(state);
     }
 }
 
@@ -106,7 +237,7 @@ impl Validate for ValueRecord {
 
 impl FromObjRef<read_fonts::tables::gpos::ValueRecord> for ValueRecord {
     fn from_obj_ref(from: &read_fonts::tables::gpos::ValueRecord, data: FontData) -> Self {
-        ValueRecord {
+        let mut record = ValueRecord {
             x_placement: from.x_placement(),
             y_placement: from.y_placement(),
             x_advance: from.x_advance(),
@@ -115,6 +246,12 @@ impl FromObjRef<read_fonts::tables::gpos::ValueRecord> for ValueRecord {
             y_placement_device: from.y_placement_device(data).to_owned_obj(data),
             x_advance_device: from.x_advance_device(data).to_owned_obj(data),
             y_advance_device: from.y_advance_device(data).to_owned_obj(data),
+            explicit_format: None,
+        };
+        // preserve device fields with null offsets
+        if record.format() != from.format() {
+            record.explicit_format = Some(from.format());
         }
+        record
     }
 }
@@ -6,7 +6,7 @@ use std::collections::HashSet;
 
 use crate::util::WrappingGet;
 use kurbo::{Point, Vec2};
-pub use read_fonts::tables::variations::{TupleIndex, TupleVariationCount};
+pub use read_fonts::tables::variations::{DeltaSetIndex, TupleIndex, TupleVariationCount};
 
 impl TupleVariationHeader {
     pub fn new(
@@ -70,6 +70,37 @@ impl VariationRegionList {
     }
 }
 
+impl DeltaSetIndexMap {
+    /// Create a map from the delta set index of each item, in item order.
+    ///
+    /// The smallest entry format that can represent every index is chosen,
+    /// and format 1 is used only if there are more than 65535 entries.
+    pub fn from_entries(entries: &[DeltaSetIndex]) -> Self {
+        let ored = entries.iter().fold(0u32, |acc, idx| {
+            acc | (idx.outer as u32) << 16 | idx.inner as u32
+        });
+        let inner_bits = (u32::BITS - (ored & 0xFFFF).leading_zeros()).max(1);
+        let outer_bits = u32::BITS - (ored >> 16).leading_zeros();
+        let entry_size = (inner_bits + outer_bits).div_ceil(8).max(1);
+        let entry_format =
+            EntryFormat::from_bits_truncate(((entry_size - 1) << 4 | (inner_bits - 1)) as u8);
+        let map_data = entries
+            .iter()
+            .flat_map(|idx| {
+                let entry = (idx.outer as u32) << inner_bits | idx.inner as u32;
+                entry
+                    .to_be_bytes()
+                    .into_iter()
+                    .skip(4 - entry_size as usize)
+            })
+            .collect();
+        match u16::try_from(entries.len()) {
+            Ok(map_count) => DeltaSetIndexMap::format_0(entry_format, map_count, map_data),
+            Err(_) => DeltaSetIndexMap::format_1(entry_format, entries.len() as u32, map_data),
+        }
+    }
+}
+
 /// <https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-point-numbers>
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
 pub enum PackedPointNumbers {
@@ -909,6 +940,64 @@ pub fn iup_delta_optimize(
 mod tests {
     use super::*;
 
+    fn read_delta_set_index_map(map: &DeltaSetIndexMap) -> Vec<(u16, u16)> {
+        let bytes = crate::dump_table(map).unwrap();
+        let map =
+            read_fonts::tables::variations::DeltaSetIndexMap::read(FontData::new(&bytes)).unwrap();
+        let map_count = match &map {
+            read_fonts::tables::variations::DeltaSetIndexMap::Format0(map) => {
+                map.map_count() as u32
+            }
+            read_fonts::tables::variations::DeltaSetIndexMap::Format1(map) => map.map_count(),
+        };
+        (0..map_count)
+            .map(|i| map.get(i).unwrap())
+            .map(|idx| (idx.outer, idx.inner))
+            .collect()
+    }
+
+    #[test]
+    fn delta_set_index_map_entry_format() {
+        let entries = |pairs: &[(u16, u16)]| {
+            pairs
+                .iter()
+                .map(|(outer, inner)| DeltaSetIndex {
+                    outer: *outer,
+                    inner: *inner,
+                })
+                .collect::<Vec<_>>()
+        };
+        for (pairs, entry_size, bit_count) in [
+            (vec![(0, 0)], 1, 1),
+            (vec![(0, 1), (1, 3)], 1, 2),
+            (vec![(0, 200), (1, 3)], 2, 8),
+            (vec![(300, 0xFFFF)], 4, 16),
+        ] {
+            let map = DeltaSetIndexMap::from_entries(&entries(&pairs));
+            let DeltaSetIndexMap::Format0(format0) = &map else {
+                panic!("expected format 0");
+            };
+            assert_eq!(format0.entry_format.entry_size(), entry_size, "{pairs:?}");
+            assert_eq!(format0.entry_format.bit_count(), bit_count, "{pairs:?}");
+            assert_eq!(read_delta_set_index_map(&map), pairs);
+        }
+    }
+
+    #[test]
+    fn delta_set_index_map_format_1() {
+        let entries = (0..=u16::MAX as u32)
+            .map(|i| DeltaSetIndex {
+                outer: (i >> 8) as u16,
+                inner: i as u8 as u16,
+            })
+            .collect::<Vec<_>>();
+        let map = DeltaSetIndexMap::from_entries(&entries);
+        assert!(matches!(map, DeltaSetIndexMap::Format1(_)));
+        let read = read_delta_set_index_map(&map);
+        assert_eq!(read.len(), 0x10000);
+        assert_eq!(read[0x1234], (0x12, 0x34));
+    }
+
     #[test]
     fn point_pack_words() {
         let thing = PackedPointNumbers::Some(vec![1002, 2002, 8408, 12228]);
@@ -215,6 +215,12 @@ impl<const N: usize, T: Validate> Validate for NullableOffsetMarker<T, N> {
     }
 }
 
+impl<T: Validate> Validate for Box<T> {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        self.as_ref().validate_impl(ctx)
+    }
+}
+
 impl<T: Validate> Validate for Option<T> {
     fn validate_impl(&self, ctx: &mut ValidationCtx) {
         match self {
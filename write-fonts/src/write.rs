@@ -267,6 +267,16 @@ impl<T: FontWrite> FontWrite for Vec<T> {
     }
 }
 
+impl<T: FontWrite> FontWrite for Box<T> {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.as_ref().write_into(writer)
+    }
+
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+}
+
 impl<T: FontWrite> FontWrite for Option<T> {
     fn write_into(&self, writer: &mut TableWriter) {
         match self {